@@ -0,0 +1,417 @@
+use crate::command;
+use crate::error::{Error, Result};
+use crate::output;
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use rand::{rngs::StdRng, SeedableRng};
+use std::io::{Read, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use tracing::Level;
+
+/// Evolve, inspect, and run neural networks.
+#[derive(Debug, Parser)]
+#[command(name = "brain-farm", version, about)]
+pub struct Cli {
+    #[command(flatten)]
+    pub global: Global,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// The available subcommands.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Measure how many activations per second a network can perform.
+    Bench(command::bench::Args),
+
+    /// Convert a network between serialization formats, guessed from the file extensions.
+    Convert(command::convert::Args),
+
+    /// Breed a child from two genomes.
+    Crossover(command::crossover::Args),
+
+    /// Solve a built-in problem to see the whole pipeline in seconds.
+    Demo(command::demo::Args),
+
+    /// Score a network on a held-out dataset.
+    Evaluate(command::evaluate::Args),
+
+    /// Evolve a network against a dataset.
+    Evolve(command::evolve::Args),
+
+    /// Convert a network to another format, e.g. `--format dot` for Graphviz.
+    Export(command::export::Args),
+
+    /// Generate a network with random weights.
+    Generate(command::generate::Args),
+
+    /// Summarize and validate a network.
+    Inspect(command::inspect::Args),
+
+    /// Write mutated variants of a genome.
+    Mutate(command::mutate::Args),
+
+    /// Run input rows through a network and write its predictions.
+    Predict(command::predict::Args),
+
+    /// Continue an evolution run from a checkpoint.
+    Resume(command::resume::Args),
+
+    /// Serve predictions from a network over HTTP.
+    Serve(command::serve::Args),
+
+    /// Summarize a checkpoint or metrics log.
+    Stats(command::stats::Args),
+}
+
+/// Flags shared by every subcommand.
+#[derive(Debug, clap::Args)]
+pub struct Global {
+    /// Read input from this path instead of stdin; `-` is stdin.
+    #[arg(short, long, global = true)]
+    pub input: Option<PathBuf>,
+
+    /// Write output to this path instead of stdout; `-` is stdout.
+    #[arg(short, long, global = true, visible_alias = "out")]
+    pub output: Option<PathBuf>,
+
+    /// Serialization format for networks and genomes; guessed from the
+    /// output extension if omitted, otherwise JSON.
+    #[arg(short, long, global = true, value_enum)]
+    pub format: Option<Format>,
+
+    /// Format of results such as predictions and summaries; each command
+    /// picks a default if omitted.
+    #[arg(long, global = true, value_enum)]
+    pub output_format: Option<output::Format>,
+
+    /// Seed for the random number generator, for reproducible runs.
+    #[arg(long, global = true)]
+    pub seed: Option<u64>,
+
+    /// The number of threads used to evaluate fitness; defaults to one per CPU.
+    #[arg(long, global = true, value_name = "N")]
+    pub threads: Option<NonZeroUsize>,
+
+    /// Log more details to stderr; repeat for even more.
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// The format of the logs written to stderr.
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Text)]
+    pub log_format: LogFormat,
+}
+
+/// Formats for the logs written to stderr.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum LogFormat {
+    /// One human-readable line per event.
+    Text,
+
+    /// One JSON object per event, for log collectors and scripts.
+    Json,
+}
+
+/// Serialization formats for networks and genomes.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Format {
+    /// JSON, as produced by `Network::to_json`.
+    Json,
+
+    /// Compact binary encoding with `bincode`.
+    Bincode,
+
+    /// `MessagePack`, for tools outside of Rust.
+    #[value(name = "msgpack")]
+    MessagePack,
+
+    /// Protobuf, following `proto/brain_farm.proto`.
+    Protobuf,
+
+    /// Graphviz DOT, for visualizing networks. Cannot be read back.
+    Dot,
+}
+
+impl Format {
+    /// Guess a format from a file extension.
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The path to check.
+    ///
+    /// # Returns
+    ///
+    /// The format, or `None` if the extension is not recognized.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(Self::Json),
+            "bin" | "bincode" => Some(Self::Bincode),
+            "msgpack" | "mpk" => Some(Self::MessagePack),
+            "pb" | "protobuf" => Some(Self::Protobuf),
+            "dot" | "gv" => Some(Self::Dot),
+            _ => None,
+        }
+    }
+
+    /// Check whether the format is human-readable text.
+    ///
+    /// # Returns
+    ///
+    /// True for text formats, false for binary formats.
+    pub fn is_text(self) -> bool {
+        matches!(self, Self::Json | Self::Dot)
+    }
+}
+
+impl Global {
+    /// Choose the format to write networks and genomes in.
+    ///
+    /// # Returns
+    ///
+    /// The `--format` flag, or a guess from the `--output` extension, or JSON.
+    pub fn network_format(&self) -> Format {
+        self.format
+            .or_else(|| self.output_path().and_then(Format::from_path))
+            .unwrap_or(Format::Json)
+    }
+
+    /// Get the path to read input from.
+    ///
+    /// # Returns
+    ///
+    /// The `--input` path, or `None` for stdin.
+    pub fn input_path(&self) -> Option<&Path> {
+        self.input.as_deref().filter(|path| *path != Path::new("-"))
+    }
+
+    /// Get the path to write output to.
+    ///
+    /// # Returns
+    ///
+    /// The `--output` path, or `None` for stdout.
+    pub fn output_path(&self) -> Option<&Path> {
+        self.output
+            .as_deref()
+            .filter(|path| *path != Path::new("-"))
+    }
+
+    /// Read the whole input.
+    ///
+    /// # Returns
+    ///
+    /// The contents of `--input`, or stdin if no input path was given.
+    ///
+    /// # Errors
+    ///
+    /// If the input cannot be read.
+    pub fn read_input(&self) -> Result<String> {
+        let path = self.input_path();
+        let mut contents = String::new();
+        match path {
+            Some(path) => std::fs::File::open(path)
+                .and_then(|mut file| file.read_to_string(&mut contents))
+                .map_err(Error::io(Some(path)))?,
+            None => std::io::stdin()
+                .read_to_string(&mut contents)
+                .map_err(Error::io(None))?,
+        };
+        Ok(contents)
+    }
+
+    /// Read the whole input without decoding it as text.
+    ///
+    /// # Returns
+    ///
+    /// The contents of `--input`, or stdin if no input path was given.
+    ///
+    /// # Errors
+    ///
+    /// If the input cannot be read.
+    pub fn read_input_bytes(&self) -> Result<Vec<u8>> {
+        let path = self.input_path();
+        let mut contents = vec![];
+        match path {
+            Some(path) => std::fs::File::open(path)
+                .and_then(|mut file| file.read_to_end(&mut contents))
+                .map_err(Error::io(Some(path)))?,
+            None => std::io::stdin()
+                .read_to_end(&mut contents)
+                .map_err(Error::io(None))?,
+        };
+        Ok(contents)
+    }
+
+    /// Write the whole output.
+    ///
+    /// # Arguments
+    ///
+    /// - `contents` - The data to write.
+    ///
+    /// # Errors
+    ///
+    /// If the output cannot be written.
+    pub fn write_output(&self, contents: &str) -> Result<()> {
+        let path = self.output_path();
+        match path {
+            Some(path) => std::fs::write(path, contents).map_err(Error::io(Some(path))),
+            None => writeln!(std::io::stdout(), "{contents}").map_err(Error::io(None)),
+        }
+    }
+
+    /// Write the whole output without a trailing newline.
+    ///
+    /// # Arguments
+    ///
+    /// - `contents` - The data to write.
+    ///
+    /// # Errors
+    ///
+    /// If the output cannot be written.
+    pub fn write_output_bytes(&self, contents: &[u8]) -> Result<()> {
+        let path = self.output_path();
+        match path {
+            Some(path) => std::fs::write(path, contents).map_err(Error::io(Some(path))),
+            None => std::io::stdout()
+                .write_all(contents)
+                .map_err(Error::io(None)),
+        }
+    }
+
+    /// Size the thread pool used for parallel work.
+    ///
+    /// # Errors
+    ///
+    /// If the thread pool has already been started.
+    pub fn init_threads(&self) -> Result<()> {
+        let Some(threads) = self.threads else {
+            return Ok(());
+        };
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads.get())
+            .build_global()
+            .map_err(|error| Error::Invalid(format!("cannot start {threads} threads: {error}")))
+    }
+
+    /// Seed the generator that mutation and crossover draw from on this
+    /// thread with `--seed`, if it was given.
+    pub fn init_rng(&self) {
+        if let Some(seed) = self.seed {
+            evo::rng::seed(seed);
+        }
+    }
+
+    /// Create the random number generator for this run.
+    ///
+    /// # Returns
+    ///
+    /// A generator seeded with `--seed`, or from entropy if no seed was given.
+    pub fn rng(&self) -> StdRng {
+        self.seed
+            .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64)
+    }
+
+    /// Send log events to stderr.
+    ///
+    /// Warnings are always logged; `-v` adds progress, `-vv` debugging
+    /// details, and `-vvv` everything.
+    pub fn init_logging(&self) {
+        let level = match self.verbose {
+            0 => Level::WARN,
+            1 => Level::INFO,
+            2 => Level::DEBUG,
+            _ => Level::TRACE,
+        };
+        let logs = tracing_subscriber::fmt()
+            .with_max_level(level)
+            .with_target(false)
+            .with_writer(std::io::stderr);
+
+        // Logging is already set up if this is called twice, e.g. in tests.
+        let _ = match self.log_format {
+            LogFormat::Text => logs.try_init(),
+            LogFormat::Json => logs.json().try_init(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_flags_after_subcommand() {
+        let cli = Cli::try_parse_from([
+            "brain-farm",
+            "generate",
+            "--layers",
+            "2",
+            "--seed",
+            "7",
+            "-vv",
+            "-o",
+            "net.json",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.global.seed, Some(7));
+        assert_eq!(cli.global.verbose, 2);
+        assert_eq!(cli.global.output, Some(PathBuf::from("net.json")));
+        assert_eq!(cli.global.format, None);
+        assert_eq!(cli.global.network_format(), Format::Json);
+    }
+
+    #[test]
+    fn test_output_format_from_extension() {
+        let cli = Cli::try_parse_from(["brain-farm", "export", "-o", "net.MSGPACK"]).unwrap();
+        assert_eq!(cli.global.network_format(), Format::MessagePack);
+
+        let cli =
+            Cli::try_parse_from(["brain-farm", "export", "-o", "net.bin", "-f", "dot"]).unwrap();
+        assert_eq!(cli.global.network_format(), Format::Dot);
+    }
+
+    #[test]
+    fn test_inspect_dot() {
+        let cli = Cli::try_parse_from(["brain-farm", "inspect", "net.json", "--dot"]).unwrap();
+        let Command::Inspect(args) = cli.command else {
+            panic!("expected inspect");
+        };
+
+        assert!(args.dot);
+        assert_eq!(args.network, Some(PathBuf::from("net.json")));
+    }
+
+    #[test]
+    fn test_log_flags() {
+        let cli = Cli::try_parse_from(["brain-farm", "-vv", "demo", "xor", "--log-format", "json"])
+            .unwrap();
+
+        assert_eq!(cli.global.verbose, 2);
+        assert_eq!(cli.global.log_format, LogFormat::Json);
+    }
+
+    #[test]
+    fn test_dash_is_stdin_and_stdout() {
+        let cli = Cli::try_parse_from([
+            "brain-farm",
+            "predict",
+            "-n",
+            "net.json",
+            "--input",
+            "-",
+            "-o",
+            "-",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.global.input_path(), None);
+        assert_eq!(cli.global.output_path(), None);
+    }
+
+    #[test]
+    fn test_requires_subcommand() {
+        assert!(Cli::try_parse_from(["brain-farm"]).is_err());
+    }
+}
@@ -0,0 +1,237 @@
+use crate::command::evolve::{Snapshot, Start, Stats};
+use crate::config::Config;
+use crate::error::{Error, Result};
+use farm::checkpoint::DeltaGeneration;
+use farm::genome::network;
+use farm::organism::Organism;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A saved evolution run that `brain-farm resume` can continue.
+///
+/// The population is stored as deltas against the best genome of the
+/// generation, which keeps checkpoints of large populations small.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Checkpoint {
+    /// The experiment configuration of the run.
+    pub config: Config,
+
+    /// The path of the training data.
+    pub data: PathBuf,
+
+    /// The index of the saved generation, which the resumed run evaluates first.
+    pub generation: usize,
+
+    /// The seed that breeding draws from.
+    ///
+    /// Each generation breeds from its own seed derived from this one and its
+    /// index, so a resumed run follows the same path the original run took.
+    pub seed: u64,
+
+    /// The best fitness and genome seen before the saved generation.
+    pub best: Option<(f64, network::Genome)>,
+
+    /// The saved generation, bred but not yet evaluated.
+    pub population: DeltaGeneration<network::Genome>,
+
+    /// The statistics of every generation before the saved one.
+    #[serde(default)]
+    pub history: Vec<Stats>,
+}
+
+impl Checkpoint {
+    /// Capture the state of a run after a generation has been evaluated,
+    /// saving the generation bred from it.
+    ///
+    /// # Arguments
+    ///
+    /// - `config` - The experiment configuration.
+    /// - `data` - The path of the training data.
+    /// - `snapshot` - The evaluated generation.
+    /// - `seed` - The seed that breeding draws from.
+    ///
+    /// # Returns
+    ///
+    /// The checkpoint.
+    pub fn capture(config: &Config, data: &Path, snapshot: &Snapshot, seed: u64) -> Self {
+        let genomes = snapshot
+            .next
+            .iter()
+            .map(|organism| organism.genome().clone())
+            .collect::<Vec<_>>();
+        let (fitness, best) = snapshot.best;
+
+        Self {
+            config: config.clone(),
+            data: std::fs::canonicalize(data).unwrap_or_else(|_| data.to_path_buf()),
+            generation: snapshot.stats.generation + 1,
+            seed,
+            best: Some((*fitness, best.genome().clone())),
+            population: DeltaGeneration::encode(snapshot.champion.genome().clone(), &genomes),
+            history: snapshot.history.to_vec(),
+        }
+    }
+
+    /// Read a checkpoint from a file.
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The path of the checkpoint.
+    ///
+    /// # Returns
+    ///
+    /// The checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read or is not a checkpoint.
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path).map_err(Error::io(Some(path)))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Write the checkpoint to a file.
+    ///
+    /// The checkpoint is written next to the file and then moved over it, so an
+    /// interrupted write never leaves a truncated checkpoint behind.
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The path of the checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be written.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let partial = path.with_extension("partial");
+        std::fs::write(&partial, serde_json::to_string(self)?)
+            .map_err(Error::io(Some(&partial)))?;
+        std::fs::rename(&partial, path).map_err(Error::io(Some(path)))
+    }
+
+    /// Get the state to continue the run from.
+    ///
+    /// # Returns
+    ///
+    /// The saved generation and the best organism seen so far.
+    pub fn start(&self) -> Start {
+        Start {
+            generation: self.generation,
+            population: self
+                .population
+                .decode()
+                .into_iter()
+                .map(Organism::from)
+                .collect(),
+            best: self
+                .best
+                .clone()
+                .map(|(fitness, genome)| (fitness, Organism::from(genome))),
+            history: self
+                .history
+                .iter()
+                .filter(|stats| stats.generation < self.generation)
+                .copied()
+                .collect(),
+            seed: self.seed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::evolve::evolve;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::ops::ControlFlow;
+
+    #[test]
+    fn test_round_trip() {
+        let config = Config::parse_toml(
+            "[network]\nlayers = [2, 1]\n[evolution]\npopulation = 8\ngenerations = 3\n",
+        )
+        .unwrap();
+        let samples = vec![(vec![0.0], vec![0.0]), (vec![1.0], vec![1.0])];
+        let start = Start::random(&config, 1, &mut StdRng::seed_from_u64(1));
+        let mut original = vec![];
+        let mut saved = None;
+
+        evolve(&config, &samples, start, |snapshot| {
+            original.push(snapshot.stats);
+            if snapshot.stats.generation == 1 {
+                saved = Some(Checkpoint::capture(
+                    &config,
+                    Path::new("data.csv"),
+                    snapshot,
+                    7,
+                ));
+            }
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        let checkpoint = saved.unwrap();
+
+        let path = std::env::temp_dir().join(format!("brain-farm-{}.ckpt", std::process::id()));
+        checkpoint.write(&path).unwrap();
+        let restored = Checkpoint::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(restored, checkpoint);
+        assert_eq!(restored.generation, 2);
+        assert_eq!(restored.population.len(), 8);
+        assert_eq!(restored.history.len(), 2);
+        assert_eq!(restored.start().history.len(), 2);
+
+        let mut resumed = vec![];
+        evolve(&config, &samples, restored.start(), |snapshot| {
+            resumed.push(snapshot.stats);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+        assert_eq!(
+            resumed
+                .iter()
+                .map(|stats| stats.generation)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(resumed[0], original[2]);
+    }
+
+    #[test]
+    fn test_resume_retraces_run() {
+        let config = Config::parse_toml(
+            "[network]\nlayers = [2, 1]\n[evolution]\npopulation = 8\ngenerations = 5\n",
+        )
+        .unwrap();
+        let samples = vec![(vec![0.0], vec![0.0]), (vec![1.0], vec![1.0])];
+        let start = Start::random(&config, 1, &mut StdRng::seed_from_u64(1));
+        let seed = start.seed;
+        let mut original = vec![];
+        let mut saved = None;
+
+        evolve(&config, &samples, start, |snapshot| {
+            original.push(snapshot.stats);
+            if snapshot.stats.generation == 1 {
+                saved = Some(Checkpoint::capture(
+                    &config,
+                    Path::new("data.csv"),
+                    snapshot,
+                    seed,
+                ));
+            }
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+        let mut resumed = vec![];
+        evolve(&config, &samples, saved.unwrap().start(), |snapshot| {
+            resumed.push(snapshot.stats);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+        assert_eq!(resumed, original[2..]);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::args::Global;
+use crate::error::{Error, Result};
+use nnet::Network;
+use rand::Rng;
+use std::fmt;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Arguments for `brain-farm bench`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The serialized network to benchmark.
+    #[arg(short, long)]
+    pub network: PathBuf,
+
+    /// The number of random inputs to activate the network with.
+    #[arg(short, long, default_value_t = 10_000)]
+    pub batch: usize,
+}
+
+/// The results of a benchmark.
+#[derive(Debug)]
+pub struct Report {
+    /// The number of activations performed.
+    pub batch: usize,
+
+    /// The time taken to activate the whole network for every input.
+    pub total: Duration,
+
+    /// The time spent in each layer, measured in a separate pass.
+    pub layers: Vec<Duration>,
+}
+
+/// Measure how quickly a network can be activated.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the network cannot be read, or the report cannot be written.
+pub fn run(args: &Args, global: &Global) -> Result<()> {
+    if args.batch == 0 {
+        return Err(Error::Invalid("the batch must not be empty".into()));
+    }
+
+    let network = super::read_network(&args.network)?;
+    let inputs = random_inputs(&network, args.batch, &mut global.rng());
+    tracing::info!(inputs = inputs.len(), "benchmarking");
+
+    global.write_output(&bench(&network, &inputs).to_string())
+}
+
+/// Create random inputs for a network.
+///
+/// # Arguments
+///
+/// - `network` - The network the inputs are for.
+/// - `batch` - The number of inputs to create.
+/// - `rng` - The random number generator.
+///
+/// # Returns
+///
+/// The inputs.
+pub fn random_inputs(network: &Network, batch: usize, rng: &mut impl Rng) -> Vec<Vec<f64>> {
+    let width = network.input_size().unwrap_or(0);
+    (0..batch)
+        .map(|_| (0..width).map(|_| rng.gen_range(-1.0..=1.0)).collect())
+        .collect()
+}
+
+/// Activate a network with every input, timing the network and each layer.
+///
+/// # Arguments
+///
+/// - `network` - The network to benchmark.
+/// - `inputs` - The inputs to activate the network with.
+///
+/// # Returns
+///
+/// The timings.
+pub fn bench(network: &Network, inputs: &[Vec<f64>]) -> Report {
+    let start = Instant::now();
+    for input in inputs {
+        std::hint::black_box(network.activate(std::hint::black_box(input)));
+    }
+    let total = start.elapsed();
+
+    let mut layers = vec![Duration::ZERO; network.layers().len()];
+    for input in inputs {
+        let mut values = input.clone();
+        for (layer, elapsed) in Iterator::zip(network.layers().iter(), layers.iter_mut()) {
+            let start = Instant::now();
+            values = std::hint::black_box(layer.activate(&values));
+            *elapsed += start.elapsed();
+        }
+    }
+
+    Report {
+        batch: inputs.len(),
+        total,
+        layers,
+    }
+}
+
+impl Report {
+    /// Get the throughput of the network.
+    ///
+    /// # Returns
+    ///
+    /// The number of activations per second.
+    pub fn per_second(&self) -> f64 {
+        #[allow(clippy::cast_precision_loss)]
+        let batch = self.batch as f64;
+        batch / self.total.as_secs_f64().max(f64::MIN_POSITIVE)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[allow(clippy::cast_precision_loss)]
+        let batch = self.batch as f64;
+        let layer_total = self.layers.iter().sum::<Duration>().as_secs_f64();
+
+        writeln!(f, "activations:    {}", self.batch)?;
+        writeln!(f, "total time:     {:?}", self.total)?;
+        writeln!(f, "per second:     {:.0}", self.per_second())?;
+        write!(
+            f,
+            "per activation: {:.3} µs",
+            self.total.as_secs_f64() * 1e6 / batch
+        )?;
+        for (index, elapsed) in self.layers.iter().enumerate() {
+            write!(
+                f,
+                "\n  layer {index}: {:.3} µs ({:.1}%)",
+                elapsed.as_secs_f64() * 1e6 / batch,
+                100.0 * elapsed.as_secs_f64() / layer_total.max(f64::MIN_POSITIVE)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nnet::{BasicNeuron, Layer};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_bench() {
+        let layer = || {
+            Layer::builder()
+                .add_neuron(BasicNeuron::builder().weights(vec![1.0, 1.0]).build())
+                .add_neuron(BasicNeuron::builder().weights(vec![1.0, 1.0]).build())
+                .build()
+        };
+        let network = Network::builder()
+            .add_layer(layer())
+            .add_layer(layer())
+            .build();
+
+        let inputs = random_inputs(&network, 10, &mut StdRng::seed_from_u64(1));
+        let report = bench(&network, &inputs);
+
+        assert_eq!(inputs.len(), 10);
+        assert_eq!(inputs[0].len(), 2);
+        assert_eq!(report.batch, 10);
+        assert_eq!(report.layers.len(), 2);
+        assert!(report.per_second() > 0.0);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::args::{Format, Global};
+use crate::error::{Error, Result};
+use std::path::PathBuf;
+
+/// Arguments for `brain-farm convert`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The serialized network to convert.
+    pub from: PathBuf,
+
+    /// Where to write the converted network.
+    pub to: PathBuf,
+}
+
+/// Convert a network between serialization formats.
+///
+/// The input format is guessed from the extension of `from`. The output
+/// format is `--format`, or guessed from the extension of `to`.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the network cannot be read, or the output format is unknown.
+pub fn run(args: &Args, global: &Global) -> Result<()> {
+    let network = super::read_network(&args.from)?;
+
+    let format = global
+        .format
+        .or_else(|| Format::from_path(&args.to))
+        .ok_or_else(|| {
+            Error::Invalid(format!(
+                "cannot guess the format of {}; pass --format",
+                args.to.display()
+            ))
+        })?;
+    tracing::info!(?format, "converting");
+
+    let bytes = super::encode_network(&network, format)?;
+    std::fs::write(&args.to, bytes).map_err(Error::io(Some(&args.to)))
+}
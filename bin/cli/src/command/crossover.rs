@@ -0,0 +1,99 @@
+use crate::args::Global;
+use crate::error::Result;
+use farm::breed::{Breed, Breeder};
+use farm::genome::network;
+use std::path::PathBuf;
+
+/// Arguments for `brain-farm crossover`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The serialized genome of the first parent.
+    pub left: PathBuf,
+
+    /// The serialized genome of the second parent.
+    pub right: PathBuf,
+
+    /// The probability that each gene of a child mutates after crossover.
+    #[arg(short, long, default_value_t = 0.0)]
+    pub rate: f64,
+
+    /// The maximum size of each mutation.
+    #[arg(short, long, default_value_t = 0.15)]
+    pub size: f64,
+
+    /// The number of children to write.
+    #[arg(short, long, default_value_t = 1)]
+    pub count: usize,
+}
+
+/// Breed children from two genomes and write them.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If a parent cannot be read, the rate is not a probability, or the children
+/// cannot be written.
+pub fn run(args: &Args, global: &Global) -> Result<()> {
+    let breeder = Breeder::new(super::mutate::mutator(args.rate, args.size)?);
+    let left = super::load_genome(Some(&args.left), global)?;
+    let right = super::load_genome(Some(&args.right), global)?;
+    tracing::info!(count = args.count, "breeding");
+
+    super::write_genomes(global, &children(&breeder, (&left, &right), args.count))
+}
+
+/// Breed children from two parents.
+///
+/// # Arguments
+///
+/// - `breeder` - The breeder to cross over and mutate with.
+/// - `parents` - The parents to breed.
+/// - `count` - The number of children.
+///
+/// # Returns
+///
+/// The children, each bred independently from the parents.
+pub fn children(
+    breeder: &Breeder,
+    parents: (&network::Genome, &network::Genome),
+    count: usize,
+) -> Vec<network::Genome> {
+    (0..count)
+        .map(|_| breeder.mutate(breeder.crossover(parents)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_children() {
+        let genome = |seed| {
+            farm::genome::network::Genome::random(2, &[3, 1], &mut StdRng::seed_from_u64(seed))
+        };
+        let (left, right) = (genome(1), genome(2));
+        let breeder = Breeder::new(crate::command::mutate::mutator(0.0, 0.15).unwrap());
+
+        let children = children(&breeder, (&left, &right), 4);
+
+        assert_eq!(children.len(), 4);
+        for child in &children {
+            let weight = child.layers[0].neurons[0].weights[0];
+            let parents = [
+                left.layers[0].neurons[0].weights[0],
+                right.layers[0].neurons[0].weights[0],
+            ];
+            let (low, high) = (parents[0].min(parents[1]), parents[0].max(parents[1]));
+            assert!(
+                (low..=high).contains(&weight),
+                "{weight} not in {low}..={high}"
+            );
+        }
+    }
+}
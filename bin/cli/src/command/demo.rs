@@ -0,0 +1,247 @@
+use super::evolve::{RunArgs, Start};
+use crate::args::{Format, Global};
+use crate::config::{self, Config};
+use crate::data;
+use crate::error::{Error, Result};
+use nnet::Network;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+/// Arguments for `brain-farm demo`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The problem to solve.
+    pub problem: Problem,
+
+    /// Run for this many generations instead of the problem's default.
+    #[arg(long)]
+    pub generations: Option<usize>,
+
+    /// Save the best network to this file.
+    #[arg(long)]
+    pub save: Option<PathBuf>,
+
+    /// Show a live dashboard in the terminal instead of log lines.
+    #[cfg(feature = "tui")]
+    #[arg(long)]
+    pub tui: bool,
+}
+
+/// A built-in problem with a tiny dataset and a configuration that solves it.
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+pub enum Problem {
+    /// Learn the exclusive-or of two bits.
+    Xor,
+
+    /// Fit `sin(πx)` for `x` between -1 and 1.
+    Sine,
+
+    /// Classify iris flowers by the size of their petals and sepals, using 30
+    /// samples of Fisher's dataset scaled to centimeters / 10.
+    Iris,
+}
+
+/// Ten samples of each species from Fisher's iris dataset.
+const IRIS: &str = "\
+sepal_length,sepal_width,petal_length,petal_width,setosa,versicolor,virginica
+5.1,3.5,1.4,0.2,1,0,0
+4.9,3.0,1.4,0.2,1,0,0
+4.7,3.2,1.3,0.2,1,0,0
+4.6,3.1,1.5,0.2,1,0,0
+5.0,3.6,1.4,0.2,1,0,0
+5.4,3.9,1.7,0.4,1,0,0
+4.6,3.4,1.4,0.3,1,0,0
+5.0,3.4,1.5,0.2,1,0,0
+4.4,2.9,1.4,0.2,1,0,0
+4.9,3.1,1.5,0.1,1,0,0
+7.0,3.2,4.7,1.4,0,1,0
+6.4,3.2,4.5,1.5,0,1,0
+6.9,3.1,4.9,1.5,0,1,0
+5.5,2.3,4.0,1.3,0,1,0
+6.5,2.8,4.6,1.5,0,1,0
+5.7,2.8,4.5,1.3,0,1,0
+6.3,3.3,4.7,1.6,0,1,0
+4.9,2.4,3.3,1.0,0,1,0
+6.6,2.9,4.6,1.3,0,1,0
+5.2,2.7,3.9,1.4,0,1,0
+6.3,3.3,6.0,2.5,0,0,1
+5.8,2.7,5.1,1.9,0,0,1
+7.1,3.0,5.9,2.1,0,0,1
+6.3,2.9,5.6,1.8,0,0,1
+6.5,3.0,5.8,2.2,0,0,1
+7.6,3.0,6.6,2.1,0,0,1
+4.9,2.5,4.5,1.7,0,0,1
+7.3,2.9,6.3,1.8,0,0,1
+6.7,2.5,5.8,1.8,0,0,1
+7.2,3.6,6.1,2.5,0,0,1
+";
+
+impl Problem {
+    /// Get the configuration that solves the problem.
+    ///
+    /// # Returns
+    ///
+    /// The experiment configuration.
+    pub fn config(self) -> Config {
+        let (layers, generations, target_fitness) = match self {
+            Self::Xor => (vec![4, 1], 300, 0.01),
+            Self::Sine => (vec![8, 1], 1000, 0.01),
+            Self::Iris => (vec![6, 3], 1000, 0.05),
+        };
+
+        Config {
+            network: config::Network { layers },
+            evolution: config::Evolution {
+                generations,
+                target_fitness: Some(target_fitness),
+                ..config::Evolution::default()
+            },
+            mutation: config::Mutation::default(),
+            pretrained: None,
+        }
+    }
+
+    /// Get the dataset of the problem.
+    ///
+    /// # Returns
+    ///
+    /// The rows of the dataset; the trailing columns are the expected outputs.
+    pub fn rows(self) -> Vec<Vec<f64>> {
+        match self {
+            Self::Xor => vec![
+                vec![0.0, 0.0, 0.0],
+                vec![0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0],
+                vec![1.0, 1.0, 0.0],
+            ],
+            Self::Sine => (0..=20)
+                .map(|step| {
+                    let x = f64::from(step) / 10.0 - 1.0;
+                    vec![x, (std::f64::consts::PI * x).sin()]
+                })
+                .collect(),
+            Self::Iris => data::parse_rows(IRIS, data::Format::Csv)
+                .expect("the iris dataset is valid")
+                .into_iter()
+                .map(|mut row| {
+                    row[..4].iter_mut().for_each(|value| *value /= 10.0);
+                    row
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Evolve a network for a built-in problem and show how well it does.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the run fails, or the results cannot be written.
+pub fn run(args: &Args, global: &Global) -> Result<()> {
+    let mut config = args.problem.config();
+    if let Some(generations) = args.generations {
+        config.evolution.generations = generations;
+    }
+    let samples = super::evolve::load_samples(&config, &args.problem.rows())?;
+    let inputs = samples.first().map_or(0, |(input, _)| input.len());
+
+    let run = RunArgs {
+        metrics: None,
+        websocket: None,
+        #[cfg(feature = "tui")]
+        tui: args.tui,
+        checkpoint: None,
+        checkpoint_every: 0,
+        #[cfg(feature = "plot")]
+        plot: None,
+    };
+    let mut rng = global.rng();
+    let start = Start::random(&config, inputs, &mut rng);
+    let best = super::evolve::follow(
+        &run,
+        &config,
+        Path::new(""),
+        &samples,
+        start,
+        &AtomicBool::new(false),
+    )?;
+
+    if let Some(path) = &args.save {
+        let format = Format::from_path(path).unwrap_or(Format::Json);
+        let bytes = super::encode_network(best.network(), format)?;
+        std::fs::write(path, bytes).map_err(Error::io(Some(path)))?;
+    }
+
+    global.write_output(&report(best.network(), &samples))
+}
+
+/// Compare the predictions of a network with the expected outputs.
+///
+/// # Arguments
+///
+/// - `network` - The network to predict with.
+/// - `samples` - The input and expected output values of each sample.
+///
+/// # Returns
+///
+/// A table with the inputs, expected outputs, and predictions of each sample.
+pub fn report(network: &Network, samples: &[(Vec<f64>, Vec<f64>)]) -> String {
+    let columns = |values: &[f64]| {
+        values
+            .iter()
+            .map(|value| format!("{value:>7.3}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let mut table = String::new();
+    let mut error = 0.0;
+    let mut values = 0;
+    for (input, expected) in samples {
+        let predicted = network.activate(input);
+        error += Iterator::zip(expected.iter(), predicted.iter())
+            .map(|(expected, predicted)| (expected - predicted).powi(2))
+            .sum::<f64>();
+        values += expected.len();
+        // Writing to a `String` cannot fail.
+        let _ = writeln!(
+            table,
+            "{}  | expected {}  | predicted {}",
+            columns(input),
+            columns(expected),
+            columns(&predicted)
+        );
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let count = values.max(1) as f64;
+    let _ = write!(table, "mean squared error: {:.6}", error / count);
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn test_problems_fit_their_configs() {
+        for problem in Problem::value_variants() {
+            let config = problem.config();
+            let samples = super::super::evolve::load_samples(&config, &problem.rows()).unwrap();
+
+            assert!(!samples.is_empty(), "{problem:?}");
+            assert_eq!(
+                samples[0].1.len(),
+                *config.network.layers.last().unwrap(),
+                "{problem:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use crate::args::Global;
+use crate::data;
+use crate::error::{Error, Result};
+use crate::output;
+use nnet::Network;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Arguments for `brain-farm evaluate`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The serialized network to evaluate.
+    #[arg(short, long)]
+    pub network: PathBuf,
+
+    /// The held-out data. The trailing columns are the expected outputs, one
+    /// for each neuron in the last layer.
+    #[arg(short, long)]
+    pub data: PathBuf,
+
+    /// How to score each record.
+    #[arg(short, long, value_enum, default_value_t = Metric::Mse)]
+    pub metric: Metric,
+}
+
+/// Ways to score a prediction against the expected outputs.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Metric {
+    /// Mean squared error; lower is better.
+    Mse,
+
+    /// Mean absolute error; lower is better.
+    Mae,
+
+    /// 1 if the prediction picks the right class, otherwise 0; higher is
+    /// better. A single output is a yes/no class split at 0.5, several
+    /// outputs are one class each and the largest wins.
+    Accuracy,
+}
+
+/// The scores of a network on a dataset.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Evaluation {
+    /// The metric the records were scored with.
+    pub metric: Metric,
+
+    /// The mean score over every record.
+    pub score: f64,
+
+    /// The score of each record.
+    pub records: Vec<Record>,
+}
+
+/// The score of a single record.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Record {
+    /// The expected outputs.
+    pub expected: Vec<f64>,
+
+    /// The outputs of the network.
+    pub predicted: Vec<f64>,
+
+    /// The score of the prediction.
+    pub score: f64,
+}
+
+/// Score a network on a dataset.
+///
+/// The scores are a table unless `--output-format` says otherwise.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the network or data cannot be read, the data does not fit the network,
+/// or the scores cannot be written.
+pub fn run(args: &Args, global: &Global) -> Result<()> {
+    let network = super::read_network(&args.network)?;
+    let outputs = network.output_size().unwrap_or(0);
+    let samples = data::split_rows(data::read_rows(&args.data)?, outputs)?;
+    tracing::info!(records = samples.len(), "evaluating");
+
+    let evaluation = evaluate(&network, &samples, args.metric)?;
+    let contents = match global.output_format.unwrap_or(output::Format::Table) {
+        output::Format::Json => serde_json::to_string(&evaluation)?,
+        output::Format::Csv => {
+            let (header, rows) = evaluation.cells();
+            output::csv(&header, &rows)?
+        }
+        output::Format::Table => {
+            let (header, rows) = evaluation.cells();
+            output::table(&header, &rows)
+        }
+    };
+    global.write_output(&contents)
+}
+
+/// Score a network on every sample.
+///
+/// # Arguments
+///
+/// - `network` - The network to evaluate.
+/// - `samples` - The input and expected output values of each sample.
+/// - `metric` - How to score each sample.
+///
+/// # Returns
+///
+/// The score of each sample and their mean.
+///
+/// # Errors
+///
+/// If there are no samples, or a sample does not fit the network.
+pub fn evaluate(
+    network: &Network,
+    samples: &[(Vec<f64>, Vec<f64>)],
+    metric: Metric,
+) -> Result<Evaluation> {
+    if samples.is_empty() {
+        return Err(Error::Invalid("the data has no records".into()));
+    }
+
+    let inputs = samples
+        .iter()
+        .map(|(input, _)| input.clone())
+        .collect::<Vec<_>>();
+    let predictions = super::predict::predict(network, &inputs)?;
+    let records = Iterator::zip(samples.iter(), predictions)
+        .map(|((_, expected), predicted)| Record {
+            score: metric.score(expected, &predicted),
+            expected: expected.clone(),
+            predicted,
+        })
+        .collect::<Vec<_>>();
+
+    #[allow(clippy::cast_precision_loss)]
+    let score = records.iter().map(|record| record.score).sum::<f64>() / records.len() as f64;
+    Ok(Evaluation {
+        metric,
+        score,
+        records,
+    })
+}
+
+impl Metric {
+    /// Score a prediction.
+    ///
+    /// # Arguments
+    ///
+    /// - `expected` - The expected outputs.
+    /// - `predicted` - The outputs of the network.
+    ///
+    /// # Returns
+    ///
+    /// The score.
+    pub fn score(self, expected: &[f64], predicted: &[f64]) -> f64 {
+        let errors = Iterator::zip(expected.iter(), predicted.iter())
+            .map(|(expected, predicted)| expected - predicted);
+        #[allow(clippy::cast_precision_loss)]
+        let count = expected.len().max(1) as f64;
+
+        match self {
+            Self::Mse => errors.map(|error| error.powi(2)).sum::<f64>() / count,
+            Self::Mae => errors.map(f64::abs).sum::<f64>() / count,
+            Self::Accuracy => {
+                let correct = match (expected, predicted) {
+                    ([expected], [predicted]) => (*expected >= 0.5) == (*predicted >= 0.5),
+                    _ => argmax(expected) == argmax(predicted),
+                };
+                f64::from(u8::from(correct))
+            }
+        }
+    }
+}
+
+/// Find the index of the largest value.
+///
+/// # Arguments
+///
+/// - `values` - The values to search.
+///
+/// # Returns
+///
+/// The index, or `None` if there are no values.
+fn argmax(values: &[f64]) -> Option<usize> {
+    values
+        .iter()
+        .enumerate()
+        .max_by(|left, right| left.1.total_cmp(right.1))
+        .map(|(index, _)| index)
+}
+
+impl Evaluation {
+    /// Lay out the scores as rows of cells.
+    ///
+    /// Each record is a row with its index, expected outputs, predicted
+    /// outputs, and score, followed by a `mean` row with the overall score.
+    ///
+    /// # Returns
+    ///
+    /// The header and the rows.
+    pub fn cells(&self) -> (Vec<String>, Vec<Vec<String>>) {
+        let width = self
+            .records
+            .first()
+            .map_or(0, |record| record.expected.len());
+        let header = std::iter::once("record".to_string())
+            .chain((0..width).map(|index| format!("expected_{index}")))
+            .chain((0..width).map(|index| format!("predicted_{index}")))
+            .chain(std::iter::once(format!("{:?}", self.metric).to_lowercase()))
+            .collect();
+
+        let mut rows = self
+            .records
+            .iter()
+            .enumerate()
+            .map(|(index, record)| {
+                std::iter::once(index.to_string())
+                    .chain(record.expected.iter().map(f64::to_string))
+                    .chain(record.predicted.iter().map(f64::to_string))
+                    .chain(std::iter::once(record.score.to_string()))
+                    .collect()
+            })
+            .collect::<Vec<Vec<String>>>();
+        rows.push(
+            std::iter::once("mean".to_string())
+                .chain(std::iter::repeat_n(String::new(), 2 * width))
+                .chain(std::iter::once(self.score.to_string()))
+                .collect(),
+        );
+
+        (header, rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nnet::{ActivationFunction, BasicNeuron, Layer};
+
+    fn network() -> Network {
+        let neuron = BasicNeuron::builder()
+            .weights(vec![1.0])
+            .activation(ActivationFunction::linear())
+            .build();
+        Network::builder()
+            .add_layer(Layer::builder().add_neuron(neuron).build())
+            .build()
+    }
+
+    #[test]
+    fn test_metrics() {
+        assert!((Metric::Mse.score(&[1.0, 0.0], &[0.0, 2.0]) - 2.5).abs() < f64::EPSILON);
+        assert!((Metric::Mae.score(&[1.0, 0.0], &[0.0, 2.0]) - 1.5).abs() < f64::EPSILON);
+        assert!((Metric::Accuracy.score(&[1.0], &[0.7]) - 1.0).abs() < f64::EPSILON);
+        assert!(Metric::Accuracy.score(&[0.0, 1.0], &[0.9, 0.1]).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let samples = vec![(vec![1.0], vec![1.0]), (vec![0.2], vec![1.0])];
+
+        let evaluation = evaluate(&network(), &samples, Metric::Accuracy).unwrap();
+
+        assert!((evaluation.score - 0.5).abs() < f64::EPSILON);
+        assert_eq!(evaluation.records[1].predicted, vec![0.2]);
+
+        let (header, rows) = evaluation.cells();
+        assert_eq!(header, ["record", "expected_0", "predicted_0", "accuracy"]);
+        assert_eq!(rows[2], ["mean", "", "", "0.5"]);
+    }
+
+    #[test]
+    fn test_evaluate_rejects_empty_data() {
+        assert!(evaluate(&network(), &[], Metric::Mse).is_err());
+    }
+}
@@ -0,0 +1,1178 @@
+use crate::args::Global;
+use crate::checkpoint::Checkpoint;
+use crate::config::{Config, Pretrained};
+use crate::data;
+use crate::error::{Error, Result};
+use crate::output;
+use crate::stream::Broadcaster;
+use evo::{EvoAlgorithm, FitnessCalc, TrainingRecord};
+use farm::genome::{network, Extract};
+use farm::mutate::Target;
+use farm::{breed::Breeder, mutate::Mutator, organism::Organism};
+use notify::{EventKind, RecursiveMode, Watcher};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::num::NonZeroUsize;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+/// Arguments for `brain-farm evolve`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The TOML experiment configuration.
+    #[arg(short, long)]
+    pub config: PathBuf,
+
+    /// The training data. The trailing columns are the expected outputs, one
+    /// for each neuron in the last layer.
+    #[arg(short, long)]
+    pub data: PathBuf,
+
+    /// Start over whenever the configuration or data changes, seeding the new
+    /// run with the best genome of the last one if it still fits.
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Evolve this many independent runs, each from its own seed, and report
+    /// how their best fitness varies. Metrics and checkpoints are numbered by
+    /// run.
+    #[arg(long, default_value = "1", value_name = "N")]
+    pub runs: NonZeroUsize,
+
+    /// Evolve the runs at the same time instead of one after another.
+    #[arg(long, requires = "runs")]
+    pub parallel: bool,
+
+    /// Write the report of a multi-run experiment to this file, in
+    /// `--output-format` (a table by default).
+    #[arg(long, requires = "runs")]
+    pub report: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub run: RunArgs,
+}
+
+/// Flags for following and saving a run, shared by `evolve` and `resume`.
+#[derive(Debug, clap::Args)]
+pub struct RunArgs {
+    /// Write per-generation fitness statistics to this CSV file.
+    #[arg(long)]
+    pub metrics: Option<PathBuf>,
+
+    /// Stream per-generation statistics and the best genome as JSON to
+    /// WebSocket clients on this address, e.g. `127.0.0.1:9001`.
+    #[arg(long, value_name = "ADDRESS")]
+    pub websocket: Option<String>,
+
+    /// Show a live dashboard in the terminal instead of log lines.
+    #[cfg(feature = "tui")]
+    #[arg(long)]
+    pub tui: bool,
+
+    /// Save the run to this file so it can be continued with `brain-farm resume`.
+    #[arg(long)]
+    pub checkpoint: Option<PathBuf>,
+
+    /// The number of generations between checkpoints.
+    #[arg(long, default_value_t = 10, value_name = "GENERATIONS")]
+    pub checkpoint_every: usize,
+
+    /// Plot the fitness and diversity of each generation to this PNG or SVG
+    /// file at the end of the run.
+    #[cfg(feature = "plot")]
+    #[arg(long)]
+    pub plot: Option<PathBuf>,
+}
+
+impl RunArgs {
+    /// Get the flags for one run of a multi-run experiment, with its own
+    /// numbered files and nothing that runs would share.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the run.
+    ///
+    /// # Returns
+    ///
+    /// The flags for the run.
+    fn numbered(&self, index: usize) -> Self {
+        let numbered =
+            |path: &Option<PathBuf>| path.as_deref().map(|path| super::numbered(path, index));
+        Self {
+            metrics: numbered(&self.metrics),
+            websocket: None,
+            #[cfg(feature = "tui")]
+            tui: false,
+            checkpoint: numbered(&self.checkpoint),
+            checkpoint_every: self.checkpoint_every,
+            #[cfg(feature = "plot")]
+            plot: numbered(&self.plot),
+        }
+    }
+}
+
+/// Fitness statistics for a single generation.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Stats {
+    /// The index of the generation, starting from 0 for the initial population.
+    pub generation: usize,
+
+    /// The best (lowest) fitness in the generation.
+    pub best: f64,
+
+    /// The mean fitness of the generation.
+    pub mean: f64,
+
+    /// The mean standard deviation of each weight and bias across the generation.
+    pub diversity: f64,
+}
+
+/// The state a run starts from: either a fresh population or a checkpoint.
+#[derive(Clone, Debug)]
+pub struct Start {
+    /// The index of the first generation to evaluate.
+    pub generation: usize,
+
+    /// The organisms of the first generation.
+    pub population: Vec<Organism>,
+
+    /// The best fitness and organism seen before the first generation.
+    pub best: Option<(f64, Organism)>,
+
+    /// The statistics of every generation before the first one.
+    pub history: Vec<Stats>,
+
+    /// The seed that breeding draws from, so the run can be repeated.
+    pub seed: u64,
+}
+
+/// Everything known about a generation once it has been evaluated.
+pub struct Snapshot<'a> {
+    /// The fitness statistics of the generation.
+    pub stats: Stats,
+
+    /// The best organism of the generation.
+    pub champion: &'a Organism,
+
+    /// The next generation, bred from this one but not yet evaluated.
+    pub next: &'a [Organism],
+
+    /// The statistics of every generation so far, ending with this one.
+    pub history: &'a [Stats],
+
+    /// The best fitness and organism seen in any generation so far.
+    pub best: &'a (f64, Organism),
+}
+
+/// The best fitness reached by each run of a multi-run experiment.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Aggregate {
+    /// The mean of the best fitness of each run.
+    pub mean: f64,
+
+    /// The sample standard deviation of the best fitness of each run.
+    pub std: f64,
+
+    /// The best fitness of the best run.
+    pub best: f64,
+
+    /// The best fitness of the worst run.
+    pub worst: f64,
+
+    /// The outcome of each run.
+    pub runs: Vec<Outcome>,
+}
+
+/// The outcome of a single run of a multi-run experiment.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Outcome {
+    /// The index of the run, starting from 0.
+    pub run: usize,
+
+    /// The seed of the run's random number generator.
+    pub seed: u64,
+
+    /// The best fitness the run reached.
+    pub fitness: f64,
+}
+
+/// A progress update streamed to WebSocket clients.
+#[derive(Serialize)]
+struct Progress<'a> {
+    #[serde(flatten)]
+    stats: &'a Stats,
+
+    /// The best genome of the generation.
+    genome: &'a farm::genome::network::Genome,
+}
+
+/// Evolve a network against a dataset and write the best network found.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the configuration or data cannot be loaded, or the results cannot be written.
+pub fn run(args: &Args, global: &Global) -> Result<()> {
+    if args.runs.get() > 1 {
+        return repeat(args, global);
+    }
+    if args.watch {
+        return watch(args, global);
+    }
+
+    let config = Config::read(&args.config)?;
+    let samples = load_samples(&config, &data::read_rows(&args.data)?)?;
+    let inputs = samples.first().map_or(0, |(input, _)| input.len());
+
+    let mut rng = global.rng();
+    let start = Start::initial(&config, inputs, &mut rng)?;
+    let best = follow(
+        &args.run,
+        &config,
+        &args.data,
+        &samples,
+        start,
+        &AtomicBool::new(false),
+    )?;
+
+    super::write_network(global, best.network())
+}
+
+/// Evolve several independent runs, write the best network of the best run,
+/// and report how the best fitness varies between runs.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the configuration or data cannot be loaded, a run fails, or the results
+/// cannot be written.
+fn repeat(args: &Args, global: &Global) -> Result<()> {
+    if args.watch {
+        return Err(Error::Invalid(
+            "--watch cannot be combined with --runs".into(),
+        ));
+    }
+    #[cfg(feature = "tui")]
+    if args.run.tui {
+        return Err(Error::Invalid(
+            "--tui cannot be combined with --runs".into(),
+        ));
+    }
+    if args.run.websocket.is_some() {
+        return Err(Error::Invalid(
+            "--websocket cannot be combined with --runs".into(),
+        ));
+    }
+
+    let config = Config::read(&args.config)?;
+    let samples = load_samples(&config, &data::read_rows(&args.data)?)?;
+    let inputs = samples.first().map_or(0, |(input, _)| input.len());
+    let calc = fitness_calc(&samples);
+
+    let mut rng = global.rng();
+    let seeds = (0..args.runs.get())
+        .map(|_| rng.gen())
+        .collect::<Vec<u64>>();
+    let evolve_run = |(index, seed): (usize, u64)| -> Result<(Organism, Outcome)> {
+        let _span = tracing::info_span!("run", run = index).entered();
+        let run = args.run.numbered(index);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let start = Start::initial(&config, inputs, &mut rng)?;
+        let best = follow(
+            &run,
+            &config,
+            &args.data,
+            &samples,
+            start,
+            &AtomicBool::new(false),
+        )?;
+
+        let fitness = calc
+            .check(&best)
+            .map_err(|error| Error::Invalid(error.to_string()))?;
+        tracing::info!(fitness, "finished a run");
+        Ok((
+            best,
+            Outcome {
+                run: index,
+                seed,
+                fitness,
+            },
+        ))
+    };
+    let results = if args.parallel {
+        seeds
+            .into_par_iter()
+            .enumerate()
+            .map(&evolve_run)
+            .collect::<Result<Vec<_>>>()?
+    } else {
+        seeds
+            .into_iter()
+            .enumerate()
+            .map(&evolve_run)
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let (organisms, outcomes): (Vec<_>, Vec<_>) = results.into_iter().unzip();
+    let aggregate = Aggregate::new(outcomes);
+    tracing::info!(
+        mean = aggregate.mean,
+        std = aggregate.std,
+        best = aggregate.best,
+        worst = aggregate.worst,
+        "finished every run"
+    );
+
+    if let Some(path) = &args.report {
+        let contents = aggregate.render(global.output_format.unwrap_or(output::Format::Table))?;
+        std::fs::write(path, contents).map_err(Error::io(Some(path)))?;
+    }
+
+    let best = aggregate
+        .runs
+        .iter()
+        .min_by(|left, right| left.fitness.total_cmp(&right.fitness))
+        .map_or(0, |outcome| outcome.run);
+    super::write_network(global, organisms[best].network())
+}
+
+/// Evolve, then evolve again every time the configuration or data changes,
+/// writing the best network at the end of each run.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the files cannot be watched, or the results cannot be written.
+fn watch(args: &Args, global: &Global) -> Result<()> {
+    #[cfg(feature = "tui")]
+    if args.run.tui {
+        return Err(Error::Invalid(
+            "--watch cannot be combined with --tui".into(),
+        ));
+    }
+
+    let files = [&args.config, &args.data]
+        .map(|path| std::fs::canonicalize(path).map_err(Error::io(Some(path))));
+    let files = files.into_iter().collect::<Result<Vec<_>>>()?;
+    let changed = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = mpsc::channel();
+
+    let flag = Arc::clone(&changed);
+    let targets = files.clone();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        let relevant = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+            && event.paths.iter().any(|path| targets.contains(path));
+        if relevant {
+            flag.store(true, Ordering::Relaxed);
+            let _ = sender.send(());
+        }
+    })
+    .map_err(|error| Error::Invalid(format!("cannot watch for changes: {error}")))?;
+    // Editors often replace files instead of writing to them, so watch the
+    // directories that contain them.
+    for directory in files.iter().filter_map(|path| path.parent()) {
+        watcher
+            .watch(directory, RecursiveMode::NonRecursive)
+            .map_err(|error| {
+                Error::Invalid(format!("cannot watch {}: {error}", directory.display()))
+            })?;
+    }
+
+    let mut rng = global.rng();
+    let mut previous: Option<Organism> = None;
+    loop {
+        changed.store(false, Ordering::Relaxed);
+        let loaded = Config::read(&args.config).and_then(|config| {
+            let samples = load_samples(&config, &data::read_rows(&args.data)?)?;
+            Ok((config, samples))
+        });
+        let outcome = loaded.and_then(|(config, samples)| {
+            let inputs = samples.first().map_or(0, |(input, _)| input.len());
+            let mut start = Start::initial(&config, inputs, &mut rng)?;
+            if let Some(best) = &previous {
+                if start.warm(best, &config, inputs) {
+                    tracing::info!("seeding the run with the best genome of the last one");
+                }
+            }
+            follow(&args.run, &config, &args.data, &samples, start, &changed)
+        });
+        match outcome {
+            Ok(best) => {
+                super::write_network(global, best.network())?;
+                previous = Some(best);
+            }
+            Err(error) => tracing::warn!(%error, "the run failed"),
+        }
+
+        if !changed.load(Ordering::Relaxed) {
+            tracing::info!("waiting for changes");
+            if receiver.recv().is_err() {
+                return Ok(());
+            }
+        }
+        // Wait for a burst of writes to settle before reading the files again.
+        std::thread::sleep(Duration::from_millis(200));
+        while receiver.try_recv().is_ok() {}
+        tracing::info!("restarting after a change");
+    }
+}
+
+/// Run evolution while reporting progress and saving checkpoints.
+///
+/// # Arguments
+///
+/// - `run` - The flags for following and saving the run.
+/// - `config` - The experiment configuration.
+/// - `data` - The path of the training data, recorded in checkpoints.
+/// - `samples` - The input and expected output values of each sample.
+/// - `start` - The state to start from.
+/// - `interrupt` - Stops the run after the current generation once set.
+///
+/// # Returns
+///
+/// The best organism seen in any generation.
+///
+/// # Errors
+///
+/// If the run fails, or a checkpoint or the metrics cannot be written.
+pub fn follow(
+    run: &RunArgs,
+    config: &Config,
+    data: &Path,
+    samples: &[(Vec<f64>, Vec<f64>)],
+    start: Start,
+    interrupt: &AtomicBool,
+) -> Result<Organism> {
+    let broadcaster = run
+        .websocket
+        .as_deref()
+        .map(Broadcaster::bind)
+        .transpose()?;
+    if let Some(broadcaster) = &broadcaster {
+        tracing::info!(address = %broadcaster.address(), "streaming progress");
+    }
+
+    #[cfg(feature = "tui")]
+    let mut dashboard = run
+        .tui
+        .then(|| crate::tui::Dashboard::new(config))
+        .transpose()?;
+
+    let seed = start.seed;
+    let mut history = start.history.clone();
+    let mut failed = None;
+    let best = evolve(config, samples, start, |snapshot| {
+        let stats = &snapshot.stats;
+        history.push(*stats);
+        if let Some(broadcaster) = &broadcaster {
+            let progress = Progress {
+                stats,
+                genome: snapshot.champion.genome(),
+            };
+            if let Ok(message) = serde_json::to_string(&progress) {
+                broadcaster.send(&message);
+            }
+        }
+
+        if let Some(path) = &run.checkpoint {
+            if run.checkpoint_every > 0 && stats.generation % run.checkpoint_every == 0 {
+                let checkpoint = Checkpoint::capture(config, data, snapshot, seed);
+                if let Err(error) = checkpoint.write(path) {
+                    failed = Some(error);
+                    return ControlFlow::Break(());
+                }
+                tracing::info!(
+                    checkpoint = %path.display(),
+                    generation = stats.generation,
+                    "saved a checkpoint"
+                );
+            }
+        }
+
+        if interrupt.load(Ordering::Relaxed) {
+            return ControlFlow::Break(());
+        }
+        #[cfg(feature = "tui")]
+        if let Some(dashboard) = &mut dashboard {
+            return dashboard.update(stats, snapshot.champion);
+        }
+        tracing::info!(
+            generation = stats.generation,
+            best = stats.best,
+            mean = stats.mean,
+            diversity = stats.diversity,
+            "evaluated a generation"
+        );
+        ControlFlow::Continue(())
+    });
+
+    #[cfg(feature = "tui")]
+    if let Some(dashboard) = dashboard {
+        dashboard.finish();
+    }
+    if let Some(error) = failed {
+        return Err(error);
+    }
+    let best = best?;
+
+    if let Some(path) = &run.metrics {
+        let mut metrics = String::from("generation,best,mean,diversity\n");
+        for stats in &history {
+            // Writing to a `String` cannot fail.
+            let _ = writeln!(
+                metrics,
+                "{},{},{},{}",
+                stats.generation, stats.best, stats.mean, stats.diversity
+            );
+        }
+        std::fs::write(path, metrics).map_err(Error::io(Some(path)))?;
+    }
+    #[cfg(feature = "plot")]
+    if let Some(path) = &run.plot {
+        crate::plot::render(path, &history)?;
+    }
+
+    Ok(best)
+}
+
+/// Split the rows of a dataset to match the configured network.
+///
+/// # Arguments
+///
+/// - `config` - The experiment configuration.
+/// - `rows` - The rows of the dataset.
+///
+/// # Returns
+///
+/// The input and expected output values of each row.
+///
+/// # Errors
+///
+/// If the configuration is invalid, or the rows do not fit the network.
+pub fn load_samples(config: &Config, rows: &[Vec<f64>]) -> Result<Vec<(Vec<f64>, Vec<f64>)>> {
+    config.validate()?;
+    config.check_columns(rows.first().map_or(0, Vec::len))?;
+
+    let outputs = config.network.layers.last().copied().unwrap_or(0);
+    data::split_rows(rows.to_vec(), outputs)
+}
+
+/// Create a fitness calculator that scores genomes against the samples.
+///
+/// # Arguments
+///
+/// - `samples` - The input and expected output values of each sample.
+///
+/// # Returns
+///
+/// The fitness calculator.
+pub fn fitness_calc(samples: &[(Vec<f64>, Vec<f64>)]) -> FitnessCalc {
+    samples
+        .iter()
+        .fold(FitnessCalc::builder(), |builder, (input, output)| {
+            builder.add_training_record(TrainingRecord::new(input.clone(), output.clone()))
+        })
+        .build()
+}
+
+impl Start {
+    /// Create a random initial population.
+    ///
+    /// Each organism is generated in parallel from its own seed, drawn from
+    /// `rng` in order, so the population is the same however many threads
+    /// generate it.
+    ///
+    /// # Arguments
+    ///
+    /// - `config` - The experiment configuration.
+    /// - `inputs` - The number of inputs to each network.
+    /// - `rng` - The random number generator.
+    ///
+    /// # Returns
+    ///
+    /// A start from generation 0.
+    pub fn random(config: &Config, inputs: usize, rng: &mut impl Rng) -> Self {
+        let seeds = (0..config.evolution.population)
+            .map(|_| rng.gen())
+            .collect::<Vec<u64>>();
+        let population = seeds
+            .into_par_iter()
+            .map(|seed| {
+                Organism::from(network::Genome::random(
+                    inputs,
+                    &config.network.layers,
+                    &mut StdRng::seed_from_u64(seed),
+                ))
+            })
+            .collect();
+
+        Self {
+            generation: 0,
+            population,
+            best: None,
+            history: vec![],
+            seed: rng.gen(),
+        }
+    }
+
+    /// Create the initial population, seeded with the pretrained model if the
+    /// configuration names one.
+    ///
+    /// # Arguments
+    ///
+    /// - `config` - The experiment configuration.
+    /// - `inputs` - The number of inputs to each network.
+    /// - `rng` - The random number generator.
+    ///
+    /// # Returns
+    ///
+    /// A start from generation 0.
+    ///
+    /// # Errors
+    ///
+    /// If the pretrained model cannot be read or does not fit.
+    pub fn initial(config: &Config, inputs: usize, rng: &mut impl Rng) -> Result<Self> {
+        let mut start = Self::random(config, inputs, rng);
+        if let Some(pretrained) = &config.pretrained {
+            let network = super::read_network(&pretrained.model)?;
+            let seeded = start.pretrain(&network, pretrained, config, inputs)?;
+            tracing::info!(seeded, "seeded the population with the pretrained model");
+        }
+        Ok(start)
+    }
+
+    /// Seed the population with copies of a pretrained network.
+    ///
+    /// The first copy is exact and the rest are mutated. Frozen layers are
+    /// copied into every organism, so evolution only adapts the later layers.
+    ///
+    /// # Arguments
+    ///
+    /// - `network` - The pretrained network.
+    /// - `pretrained` - How to seed the population with it.
+    /// - `config` - The experiment configuration.
+    /// - `inputs` - The number of inputs to each network.
+    ///
+    /// # Returns
+    ///
+    /// The number of organisms replaced with copies of the network.
+    ///
+    /// # Errors
+    ///
+    /// If the network's shape does not match the configuration and the data.
+    pub fn pretrain(
+        &mut self,
+        network: &nnet::Network,
+        pretrained: &Pretrained,
+        config: &Config,
+        inputs: usize,
+    ) -> Result<usize> {
+        let fits = network.input_size() == Some(inputs)
+            && Iterator::eq(
+                network.layers().iter().map(|layer| layer.neurons().len()),
+                config.network.layers.iter().copied(),
+            );
+        if !fits {
+            return Err(Error::Invalid(format!(
+                "the pretrained model {} does not match `network.layers` and {inputs} inputs",
+                pretrained.model.display()
+            )));
+        }
+
+        let mut genome = network.genome();
+        genome.freeze_layers(pretrained.freeze);
+        if pretrained.freeze > 0 {
+            for organism in &mut self.population {
+                let mut frozen = organism.genome().clone();
+                frozen.layers[..pretrained.freeze]
+                    .clone_from_slice(&genome.layers[..pretrained.freeze]);
+                frozen.frozen.clone_from(&genome.frozen);
+                *organism = Organism::from(frozen);
+            }
+        }
+
+        let mutator = Mutator::builder()
+            .mutation_rate(config.mutation.rate)
+            .mutation_size(pretrained.perturbation)
+            .build();
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_precision_loss,
+            clippy::cast_sign_loss
+        )]
+        let count = ((pretrained.fraction * self.population.len() as f64).round() as usize)
+            .min(self.population.len());
+        for (index, organism) in self.population.iter_mut().take(count).enumerate() {
+            let copy = genome.clone();
+            *organism = Organism::from(if index == 0 {
+                copy
+            } else {
+                copy.mutate(&mutator)
+            });
+        }
+        Ok(count)
+    }
+
+    /// Replace the first organism with an organism from an earlier run.
+    ///
+    /// # Arguments
+    ///
+    /// - `organism` - The organism to carry over.
+    /// - `config` - The experiment configuration.
+    /// - `inputs` - The number of inputs to each network.
+    ///
+    /// # Returns
+    ///
+    /// True if the organism was carried over, false if its shape no longer
+    /// fits the configuration.
+    pub fn warm(&mut self, organism: &Organism, config: &Config, inputs: usize) -> bool {
+        let layers = &organism.genome().layers;
+        let fits = organism.network().input_size() == Some(inputs)
+            && Iterator::eq(
+                layers.iter().map(|layer| layer.neurons.len()),
+                config.network.layers.iter().copied(),
+            );
+        match self.population.first_mut() {
+            Some(first) if fits => {
+                *first = organism.clone();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Run the evolutionary algorithm.
+///
+/// # Arguments
+///
+/// - `config` - The experiment configuration.
+/// - `samples` - The input and expected output values of each sample.
+/// - `start` - The state to start from.
+/// - `on_generation` - Called with a snapshot of every generation; returning
+///   [`ControlFlow::Break`] stops the run early.
+///
+/// # Returns
+///
+/// The best organism seen in any generation.
+///
+/// # Errors
+///
+/// If no organism in a generation could be scored.
+pub fn evolve(
+    config: &Config,
+    samples: &[(Vec<f64>, Vec<f64>)],
+    start: Start,
+    mut on_generation: impl FnMut(&Snapshot) -> ControlFlow<()>,
+) -> Result<Organism> {
+    let evolution = &config.evolution;
+
+    let mutator = Mutator::builder()
+        .mutation_rate(config.mutation.rate)
+        .mutation_size(config.mutation.size)
+        .build();
+    let algorithm = EvoAlgorithm::builder()
+        .breeder(Breeder::new(mutator))
+        .fitness_calc(fitness_calc(samples))
+        .elitism(evolution.elitism)
+        .tournament_size(evolution.tournament_size)
+        .tournament_win_probability(evolution.tournament_win_probability)
+        .seed(start.seed)
+        .first_generation(start.generation)
+        .build()
+        .map_err(|error| Error::Invalid(error.to_string()))?;
+
+    let Start {
+        generation: first,
+        mut population,
+        mut best,
+        mut history,
+        ..
+    } = start;
+    for index in first..=evolution.generations {
+        // The algorithm scores the generation once, to rank it and to breed
+        // the next one, which is what checkpoints save.
+        let spread = diversity(&population);
+        let outcome = algorithm.run(population);
+        let (Some(champion), Some(generation)) = (outcome.best, outcome.stats) else {
+            return Err(Error::Invalid(format!(
+                "generation {index} has no valid genomes"
+            )));
+        };
+        if outcome.discarded > 0 {
+            tracing::warn!(
+                generation = index,
+                discarded = outcome.discarded,
+                "discarded genomes that could not be scored"
+            );
+        }
+
+        let fittest = champion.fitness;
+        let best_ever = match best.take() {
+            Some(record) if record.0 <= fittest => best.insert(record),
+            _ => best.insert((fittest, champion.predict.clone())),
+        };
+        let stats = Stats {
+            generation: index,
+            best: generation.best,
+            mean: generation.mean,
+            diversity: spread,
+        };
+        history.push(stats);
+        let flow = on_generation(&Snapshot {
+            stats,
+            champion: &champion.predict,
+            next: &outcome.generation,
+            best: best_ever,
+            history: &history,
+        });
+
+        let target_reached = evolution
+            .target_fitness
+            .is_some_and(|target| fittest <= target);
+        if flow.is_break() || target_reached || index >= evolution.generations {
+            break;
+        }
+        population = outcome.generation;
+    }
+
+    best.map(|(_, organism)| organism)
+        .ok_or_else(|| Error::Invalid("the population is empty".into()))
+}
+
+impl Aggregate {
+    /// Summarize the outcomes of a multi-run experiment.
+    ///
+    /// # Arguments
+    ///
+    /// - `runs` - The outcome of each run.
+    ///
+    /// # Returns
+    ///
+    /// The mean, spread, and extremes of the best fitness of each run.
+    pub fn new(runs: Vec<Outcome>) -> Self {
+        let fitness = runs.iter().map(|outcome| outcome.fitness);
+        #[allow(clippy::cast_precision_loss)]
+        let count = runs.len() as f64;
+        let mean = fitness.clone().sum::<f64>() / count;
+        let variance = fitness
+            .clone()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / (count - 1.0).max(1.0);
+
+        Self {
+            mean,
+            std: variance.sqrt(),
+            best: fitness.clone().fold(f64::INFINITY, f64::min),
+            worst: fitness.fold(f64::NEG_INFINITY, f64::max),
+            runs,
+        }
+    }
+
+    /// Lay out the outcome of each run as rows of cells.
+    ///
+    /// # Returns
+    ///
+    /// The header and one row per run.
+    pub fn cells(&self) -> (Vec<String>, Vec<Vec<String>>) {
+        let header = ["run", "seed", "fitness"].map(str::to_string).to_vec();
+        let rows = self
+            .runs
+            .iter()
+            .map(|outcome| {
+                vec![
+                    outcome.run.to_string(),
+                    outcome.seed.to_string(),
+                    outcome.fitness.to_string(),
+                ]
+            })
+            .collect();
+        (header, rows)
+    }
+
+    /// Write the report in a results format.
+    ///
+    /// # Arguments
+    ///
+    /// - `format` - The format to write.
+    ///
+    /// # Returns
+    ///
+    /// The report.
+    ///
+    /// # Errors
+    ///
+    /// If the report cannot be serialized.
+    pub fn render(&self, format: output::Format) -> Result<String> {
+        Ok(match format {
+            output::Format::Json => serde_json::to_string(self)?,
+            output::Format::Csv => {
+                let (header, rows) = self.cells();
+                output::csv(&header, &rows)?
+            }
+            output::Format::Table => self.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for Aggregate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "runs:  {}", self.runs.len())?;
+        writeln!(f, "mean:  {:.6}", self.mean)?;
+        writeln!(f, "std:   {:.6}", self.std)?;
+        writeln!(f, "best:  {:.6}", self.best)?;
+        writeln!(f, "worst: {:.6}", self.worst)?;
+
+        let (header, rows) = self.cells();
+        write!(f, "\n{}", output::table(&header, &rows))
+    }
+}
+
+/// Measure how different the organisms in a generation are from each other.
+///
+/// # Arguments
+///
+/// - `generation` - The organisms to compare.
+///
+/// # Returns
+///
+/// The standard deviation of each weight and bias across the generation,
+/// averaged over every weight and bias.
+pub fn diversity(generation: &[Organism]) -> f64 {
+    let parameters = generation
+        .iter()
+        .map(|organism| {
+            organism
+                .genome()
+                .layers
+                .iter()
+                .flat_map(|layer| &layer.neurons)
+                .flat_map(|neuron| Iterator::chain(neuron.weights.iter(), [&neuron.bias]))
+                .copied()
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let width = parameters.iter().map(Vec::len).min().unwrap_or(0);
+    if width == 0 {
+        return 0.0;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let count = parameters.len() as f64;
+    let total = (0..width)
+        .map(|index| {
+            let mean = parameters.iter().map(|p| p[index]).sum::<f64>() / count;
+            let variance = parameters
+                .iter()
+                .map(|p| (p[index] - mean).powi(2))
+                .sum::<f64>()
+                / count;
+            variance.sqrt()
+        })
+        .sum::<f64>();
+
+    #[allow(clippy::cast_precision_loss)]
+    let width = width as f64;
+    total / width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(generations: usize) -> Config {
+        Config::parse_toml(&format!(
+            "[network]\nlayers = [1]\n[evolution]\npopulation = 20\ngenerations = {generations}\n"
+        ))
+        .unwrap()
+    }
+
+    fn start(config: &Config) -> Start {
+        Start::random(config, 1, &mut StdRng::seed_from_u64(1))
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let runs = [0.5, 1.0, 1.5]
+            .into_iter()
+            .enumerate()
+            .map(|(run, fitness)| Outcome {
+                run,
+                seed: 0,
+                fitness,
+            })
+            .collect();
+
+        let aggregate = Aggregate::new(runs);
+
+        assert!((aggregate.mean - 1.0).abs() < f64::EPSILON);
+        assert!((aggregate.std - 0.5).abs() < f64::EPSILON);
+        assert!((aggregate.best - 0.5).abs() < f64::EPSILON);
+        assert!((aggregate.worst - 1.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_load_samples() {
+        let samples = load_samples(&config(1), &[vec![1.0, 2.0, 3.0]]).unwrap();
+
+        assert_eq!(samples, vec![(vec![1.0, 2.0], vec![3.0])]);
+    }
+
+    #[test]
+    fn test_evolve_reports_every_generation() {
+        let samples = vec![(vec![0.0], vec![0.0]), (vec![1.0], vec![1.0])];
+        let mut history = vec![];
+
+        let best = evolve(&config(5), &samples, start(&config(5)), |snapshot| {
+            history.push(snapshot.stats);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+        assert_eq!(history.len(), 6);
+        let best_fitness = fitness_calc(&samples).check(&best).unwrap();
+        assert!(history.iter().all(|stats| best_fitness <= stats.best));
+    }
+
+    #[test]
+    fn test_evolve_stops_at_target_fitness() {
+        let mut config = config(50);
+        config.evolution.target_fitness = Some(f64::INFINITY);
+        let samples = vec![(vec![0.0], vec![0.0])];
+        let mut generations = 0;
+
+        evolve(&config, &samples, start(&config), |_| {
+            generations += 1;
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+        assert_eq!(generations, 1);
+    }
+
+    #[test]
+    fn test_evolve_stops_on_break() {
+        let samples = vec![(vec![0.0], vec![0.0])];
+        let mut generations = 0;
+
+        evolve(&config(50), &samples, start(&config(50)), |_| {
+            generations += 1;
+            ControlFlow::Break(())
+        })
+        .unwrap();
+
+        assert_eq!(generations, 1);
+    }
+
+    #[test]
+    fn test_evolve_is_reproducible() {
+        let samples = vec![(vec![0.0], vec![0.0]), (vec![1.0], vec![1.0])];
+        let history = || {
+            let mut history = vec![];
+            evolve(&config(5), &samples, start(&config(5)), |snapshot| {
+                history.push(snapshot.stats);
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+            history
+        };
+
+        assert_eq!(history(), history());
+    }
+
+    #[test]
+    fn test_evolve_continues_from_start() {
+        let samples = vec![(vec![0.0], vec![0.0])];
+        let mut first = start(&config(5));
+        first.generation = 3;
+        let mut generations = vec![];
+
+        evolve(&config(5), &samples, first, |snapshot| {
+            generations.push(snapshot.stats.generation);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+        assert_eq!(generations, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_warm_start() {
+        let best = start(&config(1)).population.remove(0);
+        let mut warm = Start::random(&config(1), 1, &mut StdRng::seed_from_u64(2));
+
+        assert!(warm.warm(&best, &config(1), 1));
+        assert_eq!(warm.population[0], best);
+
+        let mut wider = Start::random(&config(1), 2, &mut StdRng::seed_from_u64(2));
+        assert!(!wider.warm(&best, &config(1), 2));
+        assert_ne!(wider.population[0], best);
+    }
+
+    #[test]
+    fn test_pretrain() {
+        let mut config = config(1);
+        config.network.layers = vec![2, 1];
+        let model = start(&Config {
+            network: config.network.clone(),
+            ..config.clone()
+        });
+        let model = model.population[0].network();
+        let pretrained = Pretrained {
+            model: PathBuf::from("pretrained.json"),
+            fraction: 0.25,
+            perturbation: 0.1,
+            freeze: 1,
+        };
+
+        let mut seeded = start(&config);
+        let count = seeded.pretrain(model, &pretrained, &config, 1).unwrap();
+
+        assert_eq!(count, 5);
+        assert_eq!(seeded.population[0].network(), model);
+        assert!(seeded.population.iter().all(|organism| {
+            organism.genome().frozen == [true, false]
+                && organism.network().layers()[0] == model.layers()[0]
+        }));
+
+        let mut wider = Start::random(&config, 2, &mut StdRng::seed_from_u64(2));
+        assert!(wider.pretrain(model, &pretrained, &config, 2).is_err());
+    }
+
+    #[test]
+    fn test_diversity() {
+        let organism = |weight| {
+            Organism::from(network::Genome::random(
+                1,
+                &[1],
+                &mut StdRng::seed_from_u64(weight),
+            ))
+        };
+
+        assert!(diversity(&[organism(1), organism(1)]).abs() < f64::EPSILON);
+        assert!(diversity(&[organism(1), organism(2)]) > 0.0);
+        assert!(diversity(&[]).abs() < f64::EPSILON);
+    }
+}
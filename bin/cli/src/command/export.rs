@@ -0,0 +1,26 @@
+use crate::args::Global;
+use crate::error::Result;
+use std::path::PathBuf;
+
+/// Arguments for `brain-farm export`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The serialized network to export; reads `--input` or stdin if omitted.
+    pub network: Option<PathBuf>,
+}
+
+/// Write a network in the format selected with `--format`.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the network cannot be read or written.
+pub fn run(args: &Args, global: &Global) -> Result<()> {
+    let network = super::load_network(args.network.as_deref(), global)?;
+
+    super::write_network(global, &network)
+}
@@ -0,0 +1,47 @@
+use crate::args::Global;
+use crate::error::{Error, Result};
+use farm::genome::{network, Create};
+use nnet::Network;
+
+/// Arguments for `brain-farm generate`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Number of inputs the network accepts.
+    #[arg(long, default_value_t = 1)]
+    pub inputs: usize,
+
+    /// Comma-separated number of neurons in each layer, e.g. `4,4,1`.
+    #[arg(long, value_delimiter = ',', required = true)]
+    pub layers: Vec<usize>,
+
+    /// Write the genome instead of the network it creates, e.g. for `mutate`.
+    #[arg(long)]
+    pub genome: bool,
+}
+
+/// Generate a network with random weights, biases, and activation functions.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the network cannot be serialized or written.
+pub fn run(args: &Args, global: &Global) -> Result<()> {
+    if args.layers.contains(&0) {
+        return Err(Error::Invalid(
+            "layers must have at least one neuron".into(),
+        ));
+    }
+
+    let genome = network::Genome::random(args.inputs, &args.layers, &mut global.rng());
+    if args.genome {
+        return super::write_genomes(global, &[genome]);
+    }
+    let network: Network = genome.create();
+    tracing::info!(layers = network.layers().len(), "generated a network");
+
+    super::write_network(global, &network)
+}
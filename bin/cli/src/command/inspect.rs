@@ -0,0 +1,411 @@
+use crate::args::Global;
+use crate::error::Result;
+use crate::output;
+use nnet::{ActivationFunction, InputSource, Network};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Arguments for `brain-farm inspect`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The serialized network to inspect; reads `--input` or stdin if omitted.
+    pub network: Option<PathBuf>,
+
+    /// Print the network as a Graphviz graph instead of a summary.
+    #[arg(long)]
+    pub dot: bool,
+}
+
+/// A human-readable summary of a network.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Summary {
+    /// The number of inputs the network expects, if it has any neurons.
+    pub inputs: Option<usize>,
+
+    /// The summary of each layer.
+    pub layers: Vec<LayerSummary>,
+
+    /// The total number of weights and biases.
+    pub parameters: usize,
+
+    /// Statistics over every weight in the network.
+    pub weights: WeightStats,
+
+    /// Problems that would stop the network from working as expected.
+    pub issues: Vec<String>,
+}
+
+/// A summary of a single layer.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct LayerSummary {
+    /// The number of neurons in the layer.
+    pub neurons: usize,
+
+    /// The number of neurons using each activation function.
+    pub activations: BTreeMap<&'static str, usize>,
+}
+
+/// Statistics over a set of weights.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct WeightStats {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+/// Print a summary of a network.
+///
+/// The summary is a table unless `--output-format` says otherwise. With
+/// `--dot`, the network is printed as a Graphviz graph instead.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the network cannot be read or the summary cannot be written.
+pub fn run(args: &Args, global: &Global) -> Result<()> {
+    let network = super::load_network(args.network.as_deref(), global)?;
+    if args.dot {
+        return global.write_output(&network.to_dot());
+    }
+
+    let summary = summarize(&network);
+    let contents = match global.output_format.unwrap_or(output::Format::Table) {
+        output::Format::Json => serde_json::to_string(&summary)?,
+        output::Format::Csv => summary.to_csv()?,
+        output::Format::Table => summary.to_string(),
+    };
+    global.write_output(&contents)
+}
+
+/// Get the name of an activation function.
+///
+/// # Arguments
+///
+/// - `function` - The activation function.
+///
+/// # Returns
+///
+/// The name.
+pub fn activation_name(function: &ActivationFunction) -> &'static str {
+    match function {
+        ActivationFunction::Linear(_) => "linear",
+        ActivationFunction::Sigmoid(_) => "sigmoid",
+        ActivationFunction::LeakyRelu(_) => "leaky_relu",
+        ActivationFunction::Tanh(_) => "tanh",
+    }
+}
+
+impl Summary {
+    /// Serialize the layers of the summary as comma-separated values.
+    ///
+    /// Each row is a layer, with its index, its number of neurons, and the
+    /// number of neurons using each activation function.
+    ///
+    /// # Returns
+    ///
+    /// The header and one row per layer.
+    ///
+    /// # Errors
+    ///
+    /// If the rows cannot be serialized.
+    pub fn to_csv(&self) -> Result<String> {
+        let activations = self
+            .layers
+            .iter()
+            .flat_map(|layer| layer.activations.keys().copied())
+            .collect::<BTreeSet<_>>();
+        let header = ["layer", "neurons"]
+            .into_iter()
+            .chain(activations.iter().copied())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let rows = self
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| {
+                [index, layer.neurons]
+                    .into_iter()
+                    .chain(
+                        activations
+                            .iter()
+                            .map(|name| layer.activations.get(name).copied().unwrap_or(0)),
+                    )
+                    .map(|count| count.to_string())
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+
+        output::csv(&header, &rows)
+    }
+}
+
+impl WeightStats {
+    /// Calculate statistics over a set of weights.
+    ///
+    /// # Arguments
+    ///
+    /// - `weights` - The weights.
+    ///
+    /// # Returns
+    ///
+    /// The statistics, or all zeros if there are no weights.
+    pub fn new(weights: &[f64]) -> Self {
+        if weights.is_empty() {
+            return Self::default();
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let count = weights.len() as f64;
+        let mean = weights.iter().sum::<f64>() / count;
+        let variance = weights.iter().map(|w| (w - mean).powi(2)).sum::<f64>() / count;
+
+        Self {
+            count: weights.len(),
+            min: weights.iter().copied().fold(f64::INFINITY, f64::min),
+            max: weights.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            mean,
+            std_dev: variance.sqrt(),
+        }
+    }
+}
+
+/// Summarize a network.
+///
+/// # Arguments
+///
+/// - `network` - The network to summarize.
+///
+/// # Returns
+///
+/// The summary.
+pub fn summarize(network: &Network) -> Summary {
+    let inputs = network.input_size();
+    let mut issues = vec![];
+    let mut weights = vec![];
+    let mut parameters = 0;
+
+    if network.layers().is_empty() {
+        issues.push("the network has no layers".to_string());
+    }
+
+    let mut sizes: Vec<usize> = vec![];
+    let layers = network
+        .layers()
+        .iter()
+        .enumerate()
+        .map(|(l_index, layer)| {
+            let neurons = layer.neurons();
+            if neurons.is_empty() {
+                issues.push(format!("layer {l_index} has no neurons"));
+            }
+            let expected_weights = match network.input_source(l_index) {
+                InputSource::Previous => sizes.last().copied().or(inputs),
+                InputSource::Layers(sources) => {
+                    let found: Option<usize> =
+                        sources.iter().map(|source| sizes.get(*source)).sum();
+                    if found.is_none() {
+                        issues.push(format!(
+                            "layer {l_index} takes inputs from a layer that does not come before it"
+                        ));
+                    }
+                    found
+                }
+            };
+
+            let mut activations = BTreeMap::new();
+            for (n_index, neuron) in neurons.iter().enumerate() {
+                *activations
+                    .entry(activation_name(neuron.activator()))
+                    .or_default() += 1;
+
+                let location = format!("layer {l_index}, neuron {n_index}");
+                if let Some(expected) = expected_weights {
+                    if neuron.weights().len() != expected {
+                        issues.push(format!(
+                            "{location} has {} weights, expected {expected}",
+                            neuron.weights().len()
+                        ));
+                    }
+                }
+                if !neuron.weights().iter().all(|w| w.is_finite()) {
+                    issues.push(format!("{location} has a non-finite weight"));
+                }
+                if !neuron.bias().is_finite() {
+                    issues.push(format!("{location} has a non-finite bias"));
+                }
+
+                parameters += neuron.weights().len() + 1;
+                weights.extend_from_slice(neuron.weights());
+            }
+
+            sizes.push(neurons.len());
+            LayerSummary {
+                neurons: neurons.len(),
+                activations,
+            }
+        })
+        .collect();
+
+    Summary {
+        inputs,
+        layers,
+        parameters,
+        weights: WeightStats::new(&weights),
+        issues,
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inputs {
+            Some(inputs) => writeln!(f, "inputs:     {inputs}")?,
+            None => writeln!(f, "inputs:     unknown")?,
+        }
+        writeln!(f, "layers:     {}", self.layers.len())?;
+        for (index, layer) in self.layers.iter().enumerate() {
+            let activations = layer
+                .activations
+                .iter()
+                .map(|(name, count)| format!("{count} {name}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(f, "  {index}: {} neurons ({activations})", layer.neurons)?;
+        }
+        writeln!(f, "parameters: {}", self.parameters)?;
+        writeln!(
+            f,
+            "weights:    min {:.4}, max {:.4}, mean {:.4}, std dev {:.4}",
+            self.weights.min, self.weights.max, self.weights.mean, self.weights.std_dev
+        )?;
+
+        if self.issues.is_empty() {
+            write!(f, "validation: ok")
+        } else {
+            write!(f, "validation: {} issues", self.issues.len())?;
+            self.issues
+                .iter()
+                .try_for_each(|issue| write!(f, "\n  - {issue}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nnet::{BasicNeuron, Layer};
+
+    fn neuron(weights: Vec<f64>, activation: ActivationFunction) -> BasicNeuron {
+        BasicNeuron::builder()
+            .weights(weights)
+            .bias(0.5)
+            .activation(activation)
+            .build()
+    }
+
+    #[test]
+    fn test_summarize() {
+        let network = Network::builder()
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(neuron(vec![1.0, 2.0], ActivationFunction::linear()))
+                    .add_neuron(neuron(vec![3.0, 4.0], ActivationFunction::sigmoid()))
+                    .build(),
+            )
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(neuron(vec![5.0, 6.0], ActivationFunction::sigmoid()))
+                    .build(),
+            )
+            .build();
+
+        let summary = summarize(&network);
+
+        assert_eq!(summary.inputs, Some(2));
+        assert_eq!(summary.layers[0].neurons, 2);
+        assert_eq!(summary.layers[0].activations["linear"], 1);
+        assert_eq!(summary.layers[1].activations["sigmoid"], 1);
+        assert_eq!(summary.parameters, 9);
+        assert_eq!(summary.weights.count, 6);
+        assert!((summary.weights.mean - 3.5).abs() < f64::EPSILON);
+        assert!(summary.issues.is_empty());
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let network = Network::builder()
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(neuron(vec![1.0], ActivationFunction::linear()))
+                    .add_neuron(neuron(vec![1.0], ActivationFunction::sigmoid()))
+                    .build(),
+            )
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(neuron(vec![1.0, 1.0], ActivationFunction::sigmoid()))
+                    .build(),
+            )
+            .build();
+
+        let csv = summarize(&network).to_csv().unwrap();
+
+        assert_eq!(csv, "layer,neurons,linear,sigmoid\n0,2,1,1\n1,1,0,1");
+    }
+
+    #[test]
+    fn test_summarize_reports_issues() {
+        let network = Network::builder()
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(neuron(vec![1.0], ActivationFunction::linear()))
+                    .build(),
+            )
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(neuron(vec![1.0, f64::NAN], ActivationFunction::linear()))
+                    .build(),
+            )
+            .build();
+
+        let summary = summarize(&network);
+
+        assert_eq!(
+            summary.issues,
+            vec![
+                "layer 1, neuron 0 has 2 weights, expected 1".to_string(),
+                "layer 1, neuron 0 has a non-finite weight".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summarize_skip_connections() {
+        let layer = |weights: usize| {
+            Layer::builder()
+                .add_neuron(neuron(vec![1.0; weights], ActivationFunction::linear()))
+                .add_neuron(neuron(vec![1.0; weights], ActivationFunction::linear()))
+                .build()
+        };
+        let network = Network::builder()
+            .add_layer(layer(3))
+            .add_layer(layer(2))
+            .add_layer_with_input(layer(4), InputSource::Layers(vec![0, 1]))
+            .add_layer_with_input(layer(2), InputSource::Layers(vec![4]))
+            .build();
+
+        let summary = summarize(&network);
+
+        assert_eq!(
+            summary.issues,
+            vec!["layer 3 takes inputs from a layer that does not come before it".to_string()]
+        );
+    }
+}
@@ -0,0 +1,385 @@
+pub mod bench;
+pub mod convert;
+pub mod crossover;
+pub mod demo;
+pub mod evaluate;
+pub mod evolve;
+pub mod export;
+pub mod generate;
+pub mod inspect;
+pub mod mutate;
+pub mod predict;
+pub mod resume;
+pub mod serve;
+pub mod stats;
+
+use crate::args::{Cli, Command, Format, Global};
+use crate::error::{Error, Result};
+use crate::proto;
+use farm::genome::{network, Create};
+use nnet::Network;
+use std::path::{Path, PathBuf};
+
+/// Run the subcommand selected on the command line.
+///
+/// # Arguments
+///
+/// - `cli` - The parsed command line.
+///
+/// # Errors
+///
+/// If the thread pool cannot be sized, or the subcommand fails.
+pub fn run(cli: &Cli) -> Result<()> {
+    cli.global.init_logging();
+    cli.global.init_threads()?;
+    cli.global.init_rng();
+
+    match &cli.command {
+        Command::Bench(args) => bench::run(args, &cli.global),
+        Command::Convert(args) => convert::run(args, &cli.global),
+        Command::Crossover(args) => crossover::run(args, &cli.global),
+        Command::Demo(args) => demo::run(args, &cli.global),
+        Command::Evaluate(args) => evaluate::run(args, &cli.global),
+        Command::Evolve(args) => evolve::run(args, &cli.global),
+        Command::Export(args) => export::run(args, &cli.global),
+        Command::Generate(args) => generate::run(args, &cli.global),
+        Command::Inspect(args) => inspect::run(args, &cli.global),
+        Command::Mutate(args) => mutate::run(args, &cli.global),
+        Command::Predict(args) => predict::run(args, &cli.global),
+        Command::Resume(args) => resume::run(args, &cli.global),
+        Command::Serve(args) => serve::run(args, &cli.global),
+        Command::Stats(args) => stats::run(args, &cli.global),
+    }
+}
+
+/// Load a serialized network, guessing its format from the file extension.
+///
+/// # Arguments
+///
+/// - `path` - The path to the serialized network.
+///
+/// # Returns
+///
+/// The network.
+///
+/// # Errors
+///
+/// If the file cannot be read or does not contain a network.
+pub fn read_network(path: &Path) -> Result<Network> {
+    let bytes = std::fs::read(path).map_err(Error::io(Some(path)))?;
+    decode_network(&bytes, Format::from_path(path).unwrap_or(Format::Json))
+}
+
+/// Load a serialized network from a path, or from the input if no path was given.
+///
+/// # Arguments
+///
+/// - `path` - The path to the serialized network, if any.
+/// - `global` - The shared command-line flags.
+///
+/// # Returns
+///
+/// The network.
+///
+/// # Errors
+///
+/// If the network cannot be read or decoded.
+pub fn load_network(path: Option<&Path>, global: &Global) -> Result<Network> {
+    if let Some(path) = path {
+        return read_network(path);
+    }
+
+    let format = global
+        .input
+        .as_deref()
+        .and_then(Format::from_path)
+        .unwrap_or(Format::Json);
+    decode_network(&global.read_input_bytes()?, format)
+}
+
+/// Write a network in the format selected with `--format`.
+///
+/// # Arguments
+///
+/// - `global` - The shared command-line flags.
+/// - `network` - The network to write.
+///
+/// # Errors
+///
+/// If the network cannot be serialized or written.
+pub fn write_network(global: &Global, network: &Network) -> Result<()> {
+    let format = global.network_format();
+    let bytes = encode_network(network, format)?;
+    if format.is_text() {
+        global.write_output(&String::from_utf8_lossy(&bytes))
+    } else {
+        global.write_output_bytes(&bytes)
+    }
+}
+
+/// Serialize a network.
+///
+/// # Arguments
+///
+/// - `network` - The network to serialize.
+/// - `format` - The format to serialize to.
+///
+/// # Returns
+///
+/// The serialized network.
+///
+/// # Errors
+///
+/// If the network cannot be serialized.
+pub fn encode_network(network: &Network, format: Format) -> Result<Vec<u8>> {
+    Ok(match format {
+        Format::Json => network.to_json()?.into_bytes(),
+        Format::Bincode => bincode::serialize(network)?,
+        Format::MessagePack => rmp_serde::to_vec(network)?,
+        Format::Protobuf => proto::encode_network(network),
+        Format::Dot => network.to_dot().into_bytes(),
+    })
+}
+
+/// Deserialize a network.
+///
+/// # Arguments
+///
+/// - `bytes` - The serialized network.
+/// - `format` - The format to deserialize from.
+///
+/// # Returns
+///
+/// The network.
+///
+/// # Errors
+///
+/// If the bytes do not contain a network, or the format cannot be read back.
+pub fn decode_network(bytes: &[u8], format: Format) -> Result<Network> {
+    Ok(match format {
+        Format::Json => serde_json::from_slice(bytes)?,
+        Format::Bincode => bincode::deserialize(bytes)?,
+        Format::MessagePack => rmp_serde::from_slice(bytes)?,
+        Format::Protobuf => proto::decode_network(bytes)?,
+        Format::Dot => {
+            return Err(Error::Invalid("networks cannot be read from DOT".into()));
+        }
+    })
+}
+
+/// Load a serialized genome from a path, or from the input if no path was given.
+///
+/// # Arguments
+///
+/// - `path` - The path to the serialized genome, if any.
+/// - `global` - The shared command-line flags.
+///
+/// # Returns
+///
+/// The genome.
+///
+/// # Errors
+///
+/// If the genome cannot be read or decoded.
+pub fn load_genome(path: Option<&Path>, global: &Global) -> Result<network::Genome> {
+    let (bytes, format) = match path {
+        Some(path) => (
+            std::fs::read(path).map_err(Error::io(Some(path)))?,
+            Format::from_path(path),
+        ),
+        None => (
+            global.read_input_bytes()?,
+            global.input_path().and_then(Format::from_path),
+        ),
+    };
+
+    decode_genome(&bytes, format.unwrap_or(Format::Json))
+}
+
+/// Deserialize a genome.
+///
+/// # Arguments
+///
+/// - `bytes` - The serialized genome.
+/// - `format` - The format to deserialize from.
+///
+/// # Returns
+///
+/// The genome.
+///
+/// # Errors
+///
+/// If the bytes do not contain a genome, or the format cannot be read back.
+pub fn decode_genome(bytes: &[u8], format: Format) -> Result<network::Genome> {
+    Ok(match format {
+        Format::Json => serde_json::from_slice(bytes)?,
+        Format::Bincode => bincode::deserialize(bytes)?,
+        Format::MessagePack => rmp_serde::from_slice(bytes)?,
+        Format::Protobuf => proto::decode_genome(bytes)?,
+        Format::Dot => {
+            return Err(Error::Invalid("genomes cannot be read from DOT".into()));
+        }
+    })
+}
+
+/// Serialize a genome.
+///
+/// DOT renders the network the genome creates.
+///
+/// # Arguments
+///
+/// - `genome` - The genome to serialize.
+/// - `format` - The format to serialize to.
+///
+/// # Returns
+///
+/// The serialized genome.
+///
+/// # Errors
+///
+/// If the genome cannot be serialized.
+pub fn encode_genome(genome: &network::Genome, format: Format) -> Result<Vec<u8>> {
+    Ok(match format {
+        Format::Json => serde_json::to_vec(genome)?,
+        Format::Bincode => bincode::serialize(genome)?,
+        Format::MessagePack => rmp_serde::to_vec(genome)?,
+        Format::Protobuf => proto::encode_genome(genome),
+        Format::Dot => genome.create().to_dot().into_bytes(),
+    })
+}
+
+/// Write genomes in the format selected with `--format`.
+///
+/// A single genome is written to the output. Several genomes are numbered
+/// after the output path, e.g. `variant-0.json`, `variant-1.json`, or
+/// written to stdout one per line.
+///
+/// # Arguments
+///
+/// - `global` - The shared command-line flags.
+/// - `genomes` - The genomes to write.
+///
+/// # Errors
+///
+/// If a genome cannot be serialized or written, or several binary genomes
+/// would be written to stdout.
+pub fn write_genomes(global: &Global, genomes: &[network::Genome]) -> Result<()> {
+    let format = global.network_format();
+    let encoded = genomes
+        .iter()
+        .map(|genome| encode_genome(genome, format))
+        .collect::<Result<Vec<_>>>()?;
+
+    match (global.output_path(), encoded.as_slice()) {
+        (_, [bytes]) if format.is_text() => global.write_output(&String::from_utf8_lossy(bytes)),
+        (_, [bytes]) => global.write_output_bytes(bytes),
+        (Some(path), _) => encoded.iter().enumerate().try_for_each(|(index, bytes)| {
+            let path = numbered(path, index);
+            std::fs::write(&path, bytes).map_err(Error::io(Some(&path)))
+        }),
+        (None, _) if format.is_text() => global.write_output(
+            &encoded
+                .iter()
+                .map(|bytes| String::from_utf8_lossy(bytes))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+        (None, _) => Err(Error::Invalid(
+            "several binary genomes need an --output path to be numbered after".into(),
+        )),
+    }
+}
+
+/// Number a path by appending an index to its file name.
+///
+/// # Arguments
+///
+/// - `path` - The path to number.
+/// - `index` - The number to append.
+///
+/// # Returns
+///
+/// The path with `-index` before its extension.
+fn numbered(path: &Path, index: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{stem}-{index}.{}", extension.to_string_lossy()),
+        None => format!("{stem}-{index}"),
+    };
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use farm::genome::Extract;
+    use nnet::{ActivationFunction, BasicNeuron, Layer};
+
+    #[test]
+    fn test_encode_round_trip() {
+        let neuron = BasicNeuron::builder()
+            .weights(vec![0.25, -1.5])
+            .bias(0.5)
+            .activation(ActivationFunction::sigmoid())
+            .build();
+        let network = Network::builder()
+            .add_layer(Layer::builder().add_neuron(neuron).build())
+            .build();
+
+        for format in [Format::Json, Format::Bincode, Format::MessagePack] {
+            let bytes = encode_network(&network, format).unwrap();
+            assert_eq!(decode_network(&bytes, format).unwrap(), network);
+        }
+        assert!(decode_network(b"digraph {}", Format::Dot).is_err());
+    }
+
+    #[test]
+    fn test_encode_genome_round_trip() {
+        let neuron = BasicNeuron::builder()
+            .weights(vec![0.25, -1.5])
+            .bias(0.5)
+            .activation(ActivationFunction::sigmoid())
+            .build();
+        let genome = Network::builder()
+            .add_layer(Layer::builder().add_neuron(neuron).build())
+            .build()
+            .genome();
+
+        for format in [
+            Format::Json,
+            Format::Bincode,
+            Format::MessagePack,
+            Format::Protobuf,
+        ] {
+            let bytes = encode_genome(&genome, format).unwrap();
+            assert_eq!(decode_genome(&bytes, format).unwrap(), genome, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn test_read_network() {
+        let network = Network::builder()
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(BasicNeuron::builder().build())
+                    .build(),
+            )
+            .build();
+        let path = std::env::temp_dir().join(format!("brain-farm-{}.bin", std::process::id()));
+        std::fs::write(&path, encode_network(&network, Format::Bincode).unwrap()).unwrap();
+
+        let read = read_network(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(read.unwrap(), network);
+    }
+
+    #[test]
+    fn test_numbered() {
+        assert_eq!(
+            numbered(Path::new("out/variant.json"), 3),
+            Path::new("out/variant-3.json")
+        );
+        assert_eq!(numbered(Path::new("variant"), 0), Path::new("variant-0"));
+    }
+}
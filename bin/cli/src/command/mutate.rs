@@ -0,0 +1,106 @@
+use crate::args::Global;
+use crate::error::{Error, Result};
+use farm::genome::network;
+use farm::mutate::{Mutator, Target};
+use std::path::PathBuf;
+
+/// Arguments for `brain-farm mutate`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The serialized genome to mutate; reads `--input` or stdin if omitted.
+    pub genome: Option<PathBuf>,
+
+    /// The probability that each gene mutates.
+    #[arg(short, long, default_value_t = 0.15)]
+    pub rate: f64,
+
+    /// The maximum size of each mutation.
+    #[arg(short, long, default_value_t = 0.15)]
+    pub size: f64,
+
+    /// The number of variants to write.
+    #[arg(short, long, default_value_t = 1)]
+    pub count: usize,
+}
+
+/// Mutate a genome several times and write every variant.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the genome cannot be read, the rate is not a probability, or the
+/// variants cannot be written.
+pub fn run(args: &Args, global: &Global) -> Result<()> {
+    let mutator = mutator(args.rate, args.size)?;
+    let genome = super::load_genome(args.genome.as_deref(), global)?;
+    tracing::info!(count = args.count, "mutating");
+
+    super::write_genomes(global, &variants(&genome, &mutator, args.count))
+}
+
+/// Create a mutator from command-line settings.
+///
+/// # Arguments
+///
+/// - `rate` - The probability that each gene mutates.
+/// - `size` - The maximum size of each mutation.
+///
+/// # Returns
+///
+/// The mutator.
+///
+/// # Errors
+///
+/// If the rate is not a probability.
+pub fn mutator(rate: f64, size: f64) -> Result<Mutator> {
+    if !(0.0..=1.0).contains(&rate) {
+        return Err(Error::Invalid(format!(
+            "the mutation rate must be between 0 and 1, found {rate}"
+        )));
+    }
+
+    Ok(Mutator::builder()
+        .mutation_rate(rate)
+        .mutation_size(size)
+        .build())
+}
+
+/// Mutate copies of a genome.
+///
+/// # Arguments
+///
+/// - `genome` - The genome to mutate.
+/// - `mutator` - The mutator to apply to each copy.
+/// - `count` - The number of variants.
+///
+/// # Returns
+///
+/// The variants, each mutated independently from the original genome.
+pub fn variants(genome: &network::Genome, mutator: &Mutator, count: usize) -> Vec<network::Genome> {
+    (0..count).map(|_| genome.clone().mutate(mutator)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_variants() {
+        let genome =
+            farm::genome::network::Genome::random(2, &[3, 1], &mut StdRng::seed_from_u64(1));
+        let mutator = |rate| mutator(rate, 0.5).unwrap();
+
+        let unchanged = variants(&genome, &mutator(0.0), 3);
+        let changed = variants(&genome, &mutator(1.0), 3);
+
+        assert_eq!(unchanged, vec![genome.clone(); 3]);
+        assert_eq!(changed.len(), 3);
+        assert!(changed.iter().all(|variant| *variant != genome));
+        assert!(super::mutator(1.5, 0.5).is_err());
+    }
+}
@@ -0,0 +1,347 @@
+use crate::args::Global;
+use crate::data;
+use crate::error::{Error, Result};
+use crate::output;
+use nnet::Network;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// Arguments for `brain-farm predict`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The serialized network to run.
+    #[arg(short, long)]
+    pub network: PathBuf,
+
+    /// Format of the input rows; guessed from the input if omitted.
+    #[arg(long, value_enum)]
+    pub input_format: Option<data::Format>,
+
+    /// Answer each input line as soon as it arrives instead of reading the
+    /// whole input first. Lines may be comma-separated values or JSON arrays,
+    /// and JSON predictions are written one array per line. Tables cannot be
+    /// streamed. Implied by `--input -`, unless the output is a table.
+    #[arg(long)]
+    pub stream: bool,
+}
+
+impl Args {
+    /// Check whether input lines should be answered as they arrive.
+    ///
+    /// # Arguments
+    ///
+    /// - `global` - The shared command-line flags.
+    ///
+    /// # Returns
+    ///
+    /// True if `--stream` is set, or the input is explicitly stdin and the
+    /// output is not a table.
+    pub fn streams(&self, global: &Global) -> bool {
+        let piped = global.input.as_deref() == Some(Path::new("-"));
+        self.stream || (piped && global.output_format != Some(output::Format::Table))
+    }
+}
+
+/// Run every input row through a network and write one row of outputs per input row.
+///
+/// Predictions are written as CSV unless `--output-format` says otherwise.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the network or input cannot be read, or a row has the wrong number of inputs.
+pub fn run(args: &Args, global: &Global) -> Result<()> {
+    let network = super::read_network(&args.network)?;
+    if args.streams(global) {
+        let input = global.input_path();
+        let reader: Box<dyn BufRead> = match input {
+            Some(path) => Box::new(BufReader::new(
+                std::fs::File::open(path).map_err(Error::io(Some(path)))?,
+            )),
+            None => Box::new(std::io::stdin().lock()),
+        };
+        let output = global.output_path();
+        let writer: Box<dyn Write> = match output {
+            Some(path) => Box::new(std::fs::File::create(path).map_err(Error::io(Some(path)))?),
+            None => Box::new(std::io::stdout().lock()),
+        };
+
+        let count = stream(&network, args, global, reader, writer)?;
+        tracing::info!(rows = count, "predicted a stream");
+        return Ok(());
+    }
+
+    let contents = global.read_input()?;
+    let format = args
+        .input_format
+        .unwrap_or_else(|| data::Format::detect(global.input_path(), &contents));
+    let rows = data::parse_rows(&contents, format)?;
+    tracing::info!(rows = rows.len(), "predicting");
+
+    let outputs = predict(&network, &rows)?;
+    let format = global.output_format.unwrap_or(output::Format::Csv);
+    global.write_output(&write_predictions(&outputs, format)?)
+}
+
+/// Serialize predictions.
+///
+/// JSON predictions are an array with one array of outputs per row. CSV and
+/// tables have an `output_N` column for each output.
+///
+/// # Arguments
+///
+/// - `outputs` - The outputs for each row.
+/// - `format` - The format to serialize to.
+///
+/// # Returns
+///
+/// The serialized predictions.
+///
+/// # Errors
+///
+/// If the predictions cannot be serialized.
+pub fn write_predictions(outputs: &[Vec<f64>], format: output::Format) -> Result<String> {
+    let header = header(outputs.first().map_or(0, Vec::len));
+    let cells = || {
+        outputs
+            .iter()
+            .map(|row| row.iter().map(f64::to_string).collect())
+            .collect::<Vec<_>>()
+    };
+
+    match format {
+        output::Format::Json => data::write_rows(outputs, data::Format::Json),
+        output::Format::Csv => output::csv(&header, &cells()),
+        output::Format::Table => Ok(output::table(&header, &cells())),
+    }
+}
+
+/// Name the output columns of a prediction.
+///
+/// # Arguments
+///
+/// - `width` - The number of outputs.
+///
+/// # Returns
+///
+/// The column names.
+fn header(width: usize) -> Vec<String> {
+    (0..width).map(|index| format!("output_{index}")).collect()
+}
+
+/// Answer input rows one line at a time, flushing each prediction as it is made.
+///
+/// A first line that is not numeric is skipped as a CSV header. The stream
+/// ends quietly if the reader of the output goes away, e.g. `| head`.
+///
+/// # Arguments
+///
+/// - `network` - The network to activate.
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+/// - `reader` - The input lines.
+/// - `writer` - The destination for the predictions.
+///
+/// # Returns
+///
+/// The number of rows predicted.
+///
+/// # Errors
+///
+/// If the input cannot be read, a line is not a valid row, or the output
+/// cannot be written.
+pub fn stream(
+    network: &Network,
+    args: &Args,
+    global: &Global,
+    reader: impl BufRead,
+    mut writer: impl Write,
+) -> Result<usize> {
+    let expected = network.input_size();
+    let output_format = match global.output_format.unwrap_or(output::Format::Csv) {
+        output::Format::Json => data::Format::Json,
+        output::Format::Csv => data::Format::Csv,
+        output::Format::Table => {
+            return Err(Error::Invalid(
+                "tables cannot be streamed; use csv or json".into(),
+            ))
+        }
+    };
+    if output_format == data::Format::Csv {
+        let outputs = network.output_size().unwrap_or(0);
+        writeln!(writer, "{}", header(outputs).join(","))
+            .map_err(Error::io(global.output_path()))?;
+    }
+    let mut count = 0;
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(Error::io(global.input_path()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let input_format = args
+            .input_format
+            .unwrap_or_else(|| data::Format::detect(None, &line));
+        let row = match data::parse_row(&line, input_format) {
+            Ok(row) => row,
+            Err(_) if index == 0 => continue,
+            Err(error) => return Err(Error::Invalid(format!("row {}: {error}", index + 1))),
+        };
+        if let Some(expected) = expected.filter(|expected| *expected != row.len()) {
+            return Err(Error::Invalid(format!(
+                "row {}: expected {expected} inputs, found {}",
+                index + 1,
+                row.len()
+            )));
+        }
+
+        let prediction = data::write_row(&network.activate(&row), output_format)?;
+        match writeln!(writer, "{prediction}").and_then(|()| writer.flush()) {
+            Ok(()) => count += 1,
+            Err(error) if error.kind() == ErrorKind::BrokenPipe => break,
+            Err(error) => return Err(Error::io(global.output_path())(error)),
+        }
+    }
+
+    Ok(count)
+}
+
+/// Run every input row through a network.
+///
+/// # Arguments
+///
+/// - `network` - The network to activate.
+/// - `rows` - The input rows.
+///
+/// # Returns
+///
+/// The outputs for each row.
+///
+/// # Errors
+///
+/// If a row does not have as many values as the network has inputs.
+pub fn predict(network: &Network, rows: &[Vec<f64>]) -> Result<Vec<Vec<f64>>> {
+    let expected = network.input_size();
+
+    rows.iter()
+        .enumerate()
+        .map(|(index, row)| match expected {
+            Some(expected) if row.len() != expected => Err(Error::Invalid(format!(
+                "row {}: expected {expected} inputs, found {}",
+                index + 1,
+                row.len()
+            ))),
+            _ => Ok(network.activate(row)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::{Cli, Command};
+    use clap::Parser;
+    use nnet::{ActivationFunction, BasicNeuron, Layer};
+
+    fn network() -> Network {
+        let neuron = BasicNeuron::builder()
+            .weights(vec![1.0, 2.0])
+            .bias(0.5)
+            .activation(ActivationFunction::linear())
+            .build();
+        let layer = Layer::builder().add_neuron(neuron).build();
+        Network::builder().add_layer(layer).build()
+    }
+
+    #[test]
+    fn test_predict() {
+        let outputs = predict(&network(), &[vec![1.0, 1.0], vec![0.0, -1.0]]).unwrap();
+
+        assert_eq!(outputs, vec![vec![3.5], vec![-1.5]]);
+    }
+
+    #[test]
+    fn test_predict_rejects_wrong_width() {
+        assert!(predict(&network(), &[vec![1.0]]).is_err());
+    }
+
+    #[test]
+    fn test_write_predictions() {
+        let outputs = vec![vec![3.5, 1.0], vec![-1.5, 10.0]];
+
+        assert_eq!(
+            write_predictions(&outputs, output::Format::Json).unwrap(),
+            "[[3.5,1.0],[-1.5,10.0]]"
+        );
+        assert_eq!(
+            write_predictions(&outputs, output::Format::Csv).unwrap(),
+            "output_0,output_1\n3.5,1\n-1.5,10"
+        );
+        assert_eq!(
+            write_predictions(&outputs, output::Format::Table).unwrap(),
+            "output_0  output_1\n--------  --------\n     3.5         1\n    -1.5        10"
+        );
+    }
+
+    #[test]
+    fn test_stream() {
+        let cli = Cli::try_parse_from([
+            "brain-farm",
+            "predict",
+            "-n",
+            "net.json",
+            "--stream",
+            "--output-format",
+            "json",
+        ])
+        .unwrap();
+        let Command::Predict(args) = &cli.command else {
+            panic!("expected the predict subcommand");
+        };
+        let input = "x, y\n1, 1\n\n[0, -1]\n";
+        let mut output = vec![];
+
+        let count = stream(&network(), args, &cli.global, input.as_bytes(), &mut output).unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(String::from_utf8(output).unwrap(), "[3.5]\n[-1.5]\n");
+        assert!(stream(&network(), args, &cli.global, "1\n".as_bytes(), vec![]).is_err());
+        assert!(args.streams(&cli.global));
+
+        // Piped input streams without the flag.
+        let cli = Cli::try_parse_from(["brain-farm", "predict", "-n", "net.json", "--input", "-"])
+            .unwrap();
+        let Command::Predict(args) = &cli.command else {
+            panic!("expected the predict subcommand");
+        };
+        let mut output = vec![];
+
+        let count = stream(&network(), args, &cli.global, input.as_bytes(), &mut output).unwrap();
+
+        assert!(args.streams(&cli.global));
+        assert_eq!(count, 2);
+        assert_eq!(String::from_utf8(output).unwrap(), "output_0\n3.5\n-1.5\n");
+
+        let parse = |extra: &[&str]| {
+            let cli = Cli::try_parse_from(
+                ["brain-farm", "predict", "-n", "net.json"]
+                    .iter()
+                    .chain(extra),
+            )
+            .unwrap();
+            let Command::Predict(args) = &cli.command else {
+                panic!("expected the predict subcommand");
+            };
+            args.streams(&cli.global)
+        };
+        assert!(parse(&["--input", "-", "--output-format", "json"]));
+        assert!(!parse(&["--input", "-", "--output-format", "table"]));
+        assert!(!parse(&["--input", "rows.csv"]));
+        assert!(!parse(&[]));
+    }
+}
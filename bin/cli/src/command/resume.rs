@@ -0,0 +1,64 @@
+use super::evolve::RunArgs;
+use crate::args::Global;
+use crate::checkpoint::Checkpoint;
+use crate::data;
+use crate::error::{Error, Result};
+use std::sync::atomic::AtomicBool;
+
+/// Arguments for `brain-farm resume`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Run until this generation instead of the configured number.
+    #[arg(long)]
+    pub generations: Option<usize>,
+
+    #[command(flatten)]
+    pub run: RunArgs,
+}
+
+/// Continue an evolution run from the checkpoint given with `--checkpoint`.
+///
+/// The checkpoint keeps being updated as the run continues.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the checkpoint or its data cannot be loaded, or the results cannot be written.
+pub fn run(args: &Args, global: &Global) -> Result<()> {
+    let path = args
+        .run
+        .checkpoint
+        .as_deref()
+        .ok_or_else(|| Error::Invalid("resuming needs a --checkpoint".into()))?;
+    let checkpoint = Checkpoint::read(path)?;
+
+    let mut config = checkpoint.config.clone();
+    if let Some(generations) = args.generations {
+        config.evolution.generations = generations;
+    }
+    let samples = super::evolve::load_samples(&config, &data::read_rows(&checkpoint.data)?)?;
+    tracing::info!(
+        checkpoint = %path.display(),
+        generation = checkpoint.generation,
+        "resuming a run"
+    );
+
+    let mut start = checkpoint.start();
+    if let Some(seed) = global.seed {
+        start.seed = seed;
+    }
+    let best = super::evolve::follow(
+        &args.run,
+        &config,
+        &checkpoint.data,
+        &samples,
+        start,
+        &AtomicBool::new(false),
+    )?;
+
+    super::write_network(global, best.network())
+}
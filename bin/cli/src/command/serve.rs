@@ -0,0 +1,190 @@
+use crate::args::Global;
+use crate::error::{Error, Result};
+use nnet::Network;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// Arguments for `brain-farm serve`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The serialized network to serve.
+    #[arg(short, long)]
+    pub network: PathBuf,
+
+    /// The address to listen on.
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+
+    /// The port to listen on.
+    #[arg(short, long, default_value_t = 8080)]
+    pub port: u16,
+}
+
+/// The body of a `POST /predict` request.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PredictRequest {
+    /// Several rows of inputs, answered with one row of outputs each.
+    Batch(Vec<Vec<f64>>),
+
+    /// A single row of inputs, answered with a single row of outputs.
+    Single(Vec<f64>),
+}
+
+/// A response to an HTTP request.
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn json(status: u16, body: String) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: impl std::fmt::Display) -> Self {
+        Self::json(
+            status,
+            serde_json::json!({ "error": message.to_string() }).to_string(),
+        )
+    }
+}
+
+/// Serve predictions from a network over HTTP until the process is stopped.
+///
+/// - `GET /health` answers `{"status":"ok"}`.
+/// - `POST /predict` takes a JSON array of inputs, or an array of such
+///   arrays, and answers with the outputs in the same shape.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the network cannot be read or the server cannot listen on the address.
+pub fn run(args: &Args, _global: &Global) -> Result<()> {
+    let network = super::read_network(&args.network)?;
+    let address = format!("{}:{}", args.host, args.port);
+    let server = tiny_http::Server::http(&address)
+        .map_err(|error| Error::Invalid(format!("cannot listen on {address}: {error}")))?;
+    tracing::info!(address, "listening");
+
+    for mut request in server.incoming_requests() {
+        let mut body = String::new();
+        let response = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => handle(&network, request.method().as_str(), request.url(), &body),
+            Err(error) => Response::error(400, error),
+        };
+        tracing::info!(
+            method = %request.method(),
+            url = request.url(),
+            status = response.status,
+            "answered a request"
+        );
+
+        let content_type = tiny_http::Header::from_bytes("Content-Type", "application/json")
+            .expect("the content type header is valid");
+        let reply = tiny_http::Response::from_string(response.body)
+            .with_status_code(response.status)
+            .with_header(content_type);
+        if let Err(error) = request.respond(reply) {
+            tracing::warn!(%error, "failed to respond");
+        }
+    }
+
+    Ok(())
+}
+
+/// Answer a single HTTP request.
+///
+/// # Arguments
+///
+/// - `network` - The network to predict with.
+/// - `method` - The HTTP method of the request.
+/// - `url` - The requested URL.
+/// - `body` - The body of the request.
+///
+/// # Returns
+///
+/// The response.
+pub fn handle(network: &Network, method: &str, url: &str, body: &str) -> Response {
+    let path = url.split('?').next().unwrap_or_default();
+    match (method, path) {
+        ("GET", "/health") => Response::json(200, r#"{"status":"ok"}"#.into()),
+        ("POST", "/predict") => predict(network, body),
+        (_, "/health" | "/predict") => Response::error(405, "method not allowed"),
+        _ => Response::error(404, "not found"),
+    }
+}
+
+/// Answer a `POST /predict` request.
+///
+/// # Arguments
+///
+/// - `network` - The network to predict with.
+/// - `body` - The body of the request.
+///
+/// # Returns
+///
+/// The response.
+fn predict(network: &Network, body: &str) -> Response {
+    let request = match serde_json::from_str::<PredictRequest>(body) {
+        Ok(request) => request,
+        Err(error) => {
+            return Response::error(
+                400,
+                format!("expected an array of inputs, or an array of arrays: {error}"),
+            )
+        }
+    };
+
+    let outputs = match request {
+        PredictRequest::Batch(rows) => {
+            super::predict::predict(network, &rows).map(|outputs| serde_json::json!(outputs))
+        }
+        PredictRequest::Single(row) => super::predict::predict(network, &[row])
+            .map(|mut outputs| serde_json::json!(outputs.remove(0))),
+    };
+
+    match outputs {
+        Ok(outputs) => Response::json(200, outputs.to_string()),
+        Err(error) => Response::error(422, error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nnet::{ActivationFunction, BasicNeuron, Layer};
+
+    fn network() -> Network {
+        let neuron = BasicNeuron::builder()
+            .weights(vec![1.0, 2.0])
+            .activation(ActivationFunction::linear())
+            .build();
+        Network::builder()
+            .add_layer(Layer::builder().add_neuron(neuron).build())
+            .build()
+    }
+
+    #[test]
+    fn test_predict_single_and_batch() {
+        let single = handle(&network(), "POST", "/predict", "[1, 1]");
+        assert_eq!(single, Response::json(200, "[3.0]".into()));
+
+        let batch = handle(&network(), "POST", "/predict", "[[1, 1], [0, 1]]");
+        assert_eq!(batch, Response::json(200, "[[3.0],[2.0]]".into()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(handle(&network(), "POST", "/predict", "{}").status, 400);
+        assert_eq!(handle(&network(), "POST", "/predict", "[1]").status, 422);
+        assert_eq!(handle(&network(), "GET", "/predict", "").status, 405);
+        assert_eq!(handle(&network(), "GET", "/nope", "").status, 404);
+        assert_eq!(handle(&network(), "GET", "/health", "").status, 200);
+    }
+}
@@ -0,0 +1,333 @@
+use super::evolve::Stats;
+use crate::args::Global;
+use crate::checkpoint::Checkpoint;
+use crate::data;
+use crate::error::{Error, Result};
+use crate::output;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Arguments for `brain-farm stats`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The checkpoint or metrics CSV to summarize; reads `--input` or stdin if
+    /// omitted.
+    pub run: Option<PathBuf>,
+
+    /// The number of generations to show in the trajectory table.
+    #[arg(long, default_value_t = 10)]
+    pub samples: usize,
+}
+
+/// A summary of an evolution run.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Report {
+    /// The number of generations recorded.
+    pub generations: usize,
+
+    /// The generation with the best fitness.
+    pub best: Option<Stats>,
+
+    /// The last recorded generation.
+    pub last: Option<Stats>,
+
+    /// The saved population, for checkpoints.
+    pub population: Option<Population>,
+
+    /// The statistics of every recorded generation.
+    pub history: Vec<Stats>,
+
+    /// The number of generations shown in the trajectory table.
+    #[serde(skip)]
+    samples: usize,
+}
+
+/// A summary of the population saved in a checkpoint.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Population {
+    /// The number of genomes.
+    pub size: usize,
+
+    /// The number of genomes that differ from every other genome.
+    pub distinct: usize,
+
+    /// The best fitness seen up to the saved generation.
+    pub best_fitness: Option<f64>,
+}
+
+/// Summarize a checkpoint or a metrics log.
+///
+/// The summary is a table unless `--output-format` says otherwise; its CSV is
+/// the statistics of every generation, in the same columns as `--metrics`.
+///
+/// # Arguments
+///
+/// - `args` - The subcommand arguments.
+/// - `global` - The shared command-line flags.
+///
+/// # Errors
+///
+/// If the run cannot be read, or the summary cannot be written.
+pub fn run(args: &Args, global: &Global) -> Result<()> {
+    let contents = match &args.run {
+        Some(path) => std::fs::read_to_string(path).map_err(Error::io(Some(path)))?,
+        None => global.read_input()?,
+    };
+    let report = parse(&contents)?.with_samples(args.samples);
+
+    let contents = match global.output_format.unwrap_or(output::Format::Table) {
+        output::Format::Json => serde_json::to_string(&report)?,
+        output::Format::Csv => output::csv(&history_header(), &history_cells(&report.history))?,
+        output::Format::Table => report.to_string(),
+    };
+    global.write_output(&contents)
+}
+
+/// Summarize a checkpoint or a metrics log.
+///
+/// # Arguments
+///
+/// - `contents` - A JSON checkpoint, or CSV rows of `generation,best,mean,diversity`.
+///
+/// # Returns
+///
+/// The summary.
+///
+/// # Errors
+///
+/// If the contents are neither a checkpoint nor a metrics log.
+pub fn parse(contents: &str) -> Result<Report> {
+    if contents.trim_start().starts_with('{') {
+        let checkpoint = serde_json::from_str::<Checkpoint>(contents)?;
+        return Ok(Report::from_checkpoint(&checkpoint));
+    }
+
+    let history = data::parse_rows(contents, data::Format::Csv)?
+        .into_iter()
+        .enumerate()
+        .map(|(index, row)| match row[..] {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            [generation, best, mean, diversity] => Ok(Stats {
+                generation: generation as usize,
+                best,
+                mean,
+                diversity,
+            }),
+            _ => Err(Error::Invalid(format!(
+                "row {}: expected generation, best, mean, and diversity",
+                index + 1
+            ))),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Report::new(history, None))
+}
+
+impl Report {
+    /// Summarize the statistics of a run.
+    ///
+    /// # Arguments
+    ///
+    /// - `history` - The statistics of every recorded generation.
+    /// - `population` - The saved population, if any.
+    ///
+    /// # Returns
+    ///
+    /// The summary.
+    pub fn new(history: Vec<Stats>, population: Option<Population>) -> Self {
+        Self {
+            generations: history.len(),
+            best: history
+                .iter()
+                .min_by(|left, right| left.best.total_cmp(&right.best))
+                .copied(),
+            last: history.last().copied(),
+            population,
+            history,
+            samples: 10,
+        }
+    }
+
+    /// Summarize a checkpoint.
+    ///
+    /// # Arguments
+    ///
+    /// - `checkpoint` - The checkpoint.
+    ///
+    /// # Returns
+    ///
+    /// The summary.
+    pub fn from_checkpoint(checkpoint: &Checkpoint) -> Self {
+        let genomes = checkpoint.population.decode();
+        let distinct = genomes
+            .iter()
+            .filter_map(|genome| serde_json::to_string(genome).ok())
+            .collect::<HashSet<_>>()
+            .len();
+
+        Self::new(
+            checkpoint.history.clone(),
+            Some(Population {
+                size: genomes.len(),
+                distinct,
+                best_fitness: checkpoint.best.as_ref().map(|(fitness, _)| *fitness),
+            }),
+        )
+    }
+
+    /// Set how many generations the trajectory table shows.
+    ///
+    /// # Arguments
+    ///
+    /// - `samples` - The number of generations.
+    ///
+    /// # Returns
+    ///
+    /// The summary.
+    #[must_use]
+    pub fn with_samples(self, samples: usize) -> Self {
+        Self { samples, ..self }
+    }
+
+    /// Pick evenly spaced generations, always including the first and last.
+    ///
+    /// # Returns
+    ///
+    /// The statistics of up to `samples` generations.
+    pub fn trajectory(&self) -> Vec<Stats> {
+        let count = self.history.len();
+        if count <= self.samples {
+            return self.history.clone();
+        }
+        if self.samples < 2 {
+            return self.history.last().copied().into_iter().collect();
+        }
+
+        let mut picked = (0..self.samples)
+            .map(|index| index * (count - 1) / (self.samples - 1))
+            .collect::<Vec<_>>();
+        picked.dedup();
+        picked
+            .into_iter()
+            .map(|index| self.history[index])
+            .collect()
+    }
+}
+
+/// Name the columns of the statistics of a generation.
+///
+/// # Returns
+///
+/// The column names.
+fn history_header() -> Vec<String> {
+    ["generation", "best", "mean", "diversity"]
+        .map(str::to_string)
+        .to_vec()
+}
+
+/// Lay out the statistics of each generation as rows of cells.
+///
+/// # Arguments
+///
+/// - `history` - The statistics of each generation.
+///
+/// # Returns
+///
+/// One row per generation.
+fn history_cells(history: &[Stats]) -> Vec<Vec<String>> {
+    history
+        .iter()
+        .map(|stats| {
+            vec![
+                stats.generation.to_string(),
+                stats.best.to_string(),
+                stats.mean.to_string(),
+                stats.diversity.to_string(),
+            ]
+        })
+        .collect()
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "generations: {}", self.generations)?;
+        if let Some(best) = &self.best {
+            writeln!(
+                f,
+                "best:        {:.6} at generation {}",
+                best.best, best.generation
+            )?;
+        }
+        if let Some(last) = &self.last {
+            writeln!(
+                f,
+                "last:        generation {}, best {:.6}, mean {:.6}, diversity {:.6}",
+                last.generation, last.best, last.mean, last.diversity
+            )?;
+        }
+        if let Some(population) = &self.population {
+            writeln!(
+                f,
+                "population:  {} genomes, {} distinct",
+                population.size, population.distinct
+            )?;
+        }
+
+        let rows = self
+            .trajectory()
+            .iter()
+            .map(|stats| {
+                vec![
+                    stats.generation.to_string(),
+                    format!("{:.6}", stats.best),
+                    format!("{:.6}", stats.mean),
+                    format!("{:.6}", stats.diversity),
+                ]
+            })
+            .collect::<Vec<_>>();
+        write!(f, "\n{}", output::table(&history_header(), &rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METRICS: &str = "generation,best,mean,diversity\n\
+        0,0.5,1.0,0.3\n\
+        1,0.25,0.75,0.2\n\
+        2,0.3,0.5,0.1\n";
+
+    #[test]
+    fn test_parse_metrics() {
+        let report = parse(METRICS).unwrap();
+
+        assert_eq!(report.generations, 3);
+        assert_eq!(report.best.unwrap().generation, 1);
+        assert_eq!(report.last.unwrap().generation, 2);
+        assert_eq!(report.population, None);
+        assert!(parse("1,2\n").is_err());
+    }
+
+    #[test]
+    fn test_trajectory() {
+        let history = (0..100)
+            .map(|generation| Stats {
+                generation,
+                best: 0.0,
+                mean: 0.0,
+                diversity: 0.0,
+            })
+            .collect();
+        let report = Report::new(history, None).with_samples(5);
+
+        let generations = report
+            .trajectory()
+            .iter()
+            .map(|stats| stats.generation)
+            .collect::<Vec<_>>();
+
+        assert_eq!(generations, vec![0, 24, 49, 74, 99]);
+    }
+}
@@ -0,0 +1,568 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// An experiment configuration, as loaded from a TOML file.
+///
+/// ```toml
+/// [network]
+/// layers = [4, 1]
+///
+/// [evolution]
+/// population = 100
+/// generations = 200
+/// target_fitness = 0.001
+///
+/// [mutation]
+/// rate = 0.15
+/// size = 0.15
+///
+/// [pretrained]
+/// model = "pretrained.json"
+/// fraction = 0.5
+/// ```
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Config {
+    pub network: Network,
+
+    #[serde(default)]
+    pub evolution: Evolution,
+
+    #[serde(default)]
+    pub mutation: Mutation,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pretrained: Option<Pretrained>,
+}
+
+/// The shape of the evolved networks.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Network {
+    /// The number of neurons in each layer. The last layer must have one
+    /// neuron for each output column in the dataset.
+    pub layers: Vec<usize>,
+}
+
+/// Settings for the evolutionary algorithm.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Evolution {
+    /// The number of genomes in each generation.
+    pub population: usize,
+
+    /// The maximum number of generations to run.
+    pub generations: usize,
+
+    /// The number of best genomes carried over unchanged to the next generation.
+    pub elitism: usize,
+
+    /// The number of genomes competing in each parent selection.
+    pub tournament_size: usize,
+
+    /// The probability that the best genome in a tournament wins it. Below 1,
+    /// weaker genomes sometimes win, which keeps small populations diverse.
+    pub tournament_win_probability: f64,
+
+    /// Stop early once the best fitness is at or below this value.
+    pub target_fitness: Option<f64>,
+}
+
+impl Default for Evolution {
+    fn default() -> Self {
+        Self {
+            population: 100,
+            generations: 100,
+            elitism: 1,
+            tournament_size: 10,
+            tournament_win_probability: 1.0,
+            target_fitness: None,
+        }
+    }
+}
+
+/// Settings for the mutator.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Mutation {
+    /// The probability that each gene mutates.
+    pub rate: f64,
+
+    /// The maximum size of each mutation.
+    pub size: f64,
+}
+
+impl Default for Mutation {
+    fn default() -> Self {
+        Self {
+            rate: 0.15,
+            size: 0.15,
+        }
+    }
+}
+
+/// A pretrained network to seed the initial population with.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Pretrained {
+    /// The serialized network, in any format `brain-farm` reads. Its shape
+    /// must match `network.layers` and the data.
+    pub model: PathBuf,
+
+    /// The share of the initial population seeded with copies of the model.
+    /// The first copy is exact, and the rest are mutated.
+    #[serde(default = "Pretrained::default_fraction")]
+    pub fraction: f64,
+
+    /// The mutation size applied to each copy after the first.
+    #[serde(default = "Pretrained::default_perturbation")]
+    pub perturbation: f64,
+
+    /// The number of layers, from the input side, that keep the model's
+    /// weights in every genome and are never mutated or crossed over.
+    #[serde(default)]
+    pub freeze: usize,
+}
+
+impl Pretrained {
+    fn default_fraction() -> f64 {
+        0.5
+    }
+
+    fn default_perturbation() -> f64 {
+        0.1
+    }
+
+    /// Check that every value is in range.
+    ///
+    /// # Arguments
+    ///
+    /// - `layers` - The number of layers in the network.
+    ///
+    /// # Returns
+    ///
+    /// Every issue found.
+    fn issues(&self, layers: usize) -> Vec<Issue> {
+        let mut issues = vec![];
+        if !(0.0..=1.0).contains(&self.fraction) {
+            issues.push(Issue::new(
+                "pretrained.fraction",
+                format!("{} is not a share of the population", self.fraction),
+                "set it between 0 and 1, e.g. `fraction = 0.5`",
+            ));
+        }
+        if !self.perturbation.is_finite() || self.perturbation < 0.0 {
+            issues.push(Issue::new(
+                "pretrained.perturbation",
+                format!("{} is not a mutation size", self.perturbation),
+                "set it to 0 or more, e.g. `perturbation = 0.1`",
+            ));
+        }
+        if self.freeze > layers {
+            issues.push(Issue::new(
+                "pretrained.freeze",
+                format!(
+                    "{} layers cannot be frozen in a network of {layers}",
+                    self.freeze
+                ),
+                "freeze at most as many layers as `network.layers` lists",
+            ));
+        }
+        issues
+    }
+}
+
+/// The sections of a configuration file and the keys each one accepts.
+const SCHEMA: &[(&str, &[&str])] = &[
+    ("network", &["layers"]),
+    (
+        "evolution",
+        &[
+            "population",
+            "generations",
+            "elitism",
+            "tournament_size",
+            "tournament_win_probability",
+            "target_fitness",
+        ],
+    ),
+    ("mutation", &["rate", "size"]),
+    (
+        "pretrained",
+        &["model", "fraction", "perturbation", "freeze"],
+    ),
+];
+
+/// A problem with a configuration, and how to fix it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Issue {
+    /// The dotted path of the offending key, such as `mutation.rate`.
+    pub path: String,
+
+    /// What is wrong with the key.
+    pub problem: String,
+
+    /// How to fix it.
+    pub fix: String,
+}
+
+impl Issue {
+    fn new(path: impl Into<String>, problem: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            problem: problem.into(),
+            fix: fix.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}; {}", self.path, self.problem, self.fix)
+    }
+}
+
+impl Config {
+    /// Parse a configuration from TOML.
+    ///
+    /// Unknown keys and out-of-range values are reported together, each with
+    /// the path of the offending key and a suggested fix.
+    ///
+    /// # Arguments
+    ///
+    /// - `toml` - The TOML to parse.
+    ///
+    /// # Returns
+    ///
+    /// The configuration.
+    ///
+    /// # Errors
+    ///
+    /// If the TOML is malformed, has unknown keys, or does not describe a
+    /// valid configuration.
+    pub fn parse_toml(toml: &str) -> Result<Self> {
+        let issues = unknown_keys(&toml.parse::<toml::Table>()?);
+        if !issues.is_empty() {
+            return Err(Error::Schema(issues));
+        }
+
+        let config: Self = toml::from_str(toml)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Load a configuration from a TOML file.
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The path to the configuration file.
+    ///
+    /// # Returns
+    ///
+    /// The configuration.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read or parsed.
+    pub fn read(path: &Path) -> Result<Self> {
+        let toml = std::fs::read_to_string(path).map_err(Error::io(Some(path)))?;
+        Self::parse_toml(&toml)
+    }
+
+    /// Check that every value is in range and consistent with the others.
+    ///
+    /// # Errors
+    ///
+    /// If any value is out of range, with every issue found.
+    pub fn validate(&self) -> Result<()> {
+        let mut issues = vec![];
+
+        if self.network.layers.is_empty() {
+            issues.push(Issue::new(
+                "network.layers",
+                "the network needs at least one layer",
+                "list the neurons in each layer, e.g. `layers = [4, 1]`",
+            ));
+        }
+        for (index, _) in self
+            .network
+            .layers
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| **n == 0)
+        {
+            issues.push(Issue::new(
+                format!("network.layers[{index}]"),
+                "a layer needs at least one neuron",
+                "remove the layer or give it 1 or more neurons",
+            ));
+        }
+
+        let evolution = &self.evolution;
+        if evolution.population == 0 {
+            issues.push(Issue::new(
+                "evolution.population",
+                "the population is empty",
+                "set it to 1 or more; 100 is a good start",
+            ));
+        }
+        if evolution.elitism > evolution.population {
+            issues.push(Issue::new(
+                "evolution.elitism",
+                format!(
+                    "{} elites do not fit in a population of {}",
+                    evolution.elitism, evolution.population
+                ),
+                "lower it below the population, or raise the population",
+            ));
+        }
+        if evolution.tournament_size == 0 {
+            issues.push(Issue::new(
+                "evolution.tournament_size",
+                "a tournament needs at least one genome",
+                "set it to 1 or more; larger tournaments select more greedily",
+            ));
+        }
+        if !(0.0..=1.0).contains(&evolution.tournament_win_probability) {
+            issues.push(Issue::new(
+                "evolution.tournament_win_probability",
+                format!(
+                    "{} is not a probability",
+                    evolution.tournament_win_probability
+                ),
+                "set it between 0 and 1; 1 always picks the best",
+            ));
+        }
+        if evolution
+            .target_fitness
+            .is_some_and(|target| !target.is_finite() || target < 0.0)
+        {
+            issues.push(Issue::new(
+                "evolution.target_fitness",
+                "fitness is a non-negative error, so this target is never reached",
+                "set it to 0 or more, or remove it to run every generation",
+            ));
+        }
+
+        if !(0.0..=1.0).contains(&self.mutation.rate) {
+            issues.push(Issue::new(
+                "mutation.rate",
+                format!("{} is not a probability", self.mutation.rate),
+                "set it between 0 and 1, e.g. `rate = 0.15`",
+            ));
+        }
+        if !self.mutation.size.is_finite() || self.mutation.size < 0.0 {
+            issues.push(Issue::new(
+                "mutation.size",
+                format!("{} is not a mutation size", self.mutation.size),
+                "set it to 0 or more, e.g. `size = 0.15`",
+            ));
+        }
+
+        if let Some(pretrained) = &self.pretrained {
+            issues.extend(pretrained.issues(self.network.layers.len()));
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Schema(issues))
+        }
+    }
+
+    /// Check that the network fits a dataset.
+    ///
+    /// # Arguments
+    ///
+    /// - `columns` - The number of columns in each row of the dataset.
+    ///
+    /// # Errors
+    ///
+    /// If the last layer leaves no columns for the inputs.
+    pub fn check_columns(&self, columns: usize) -> Result<()> {
+        let outputs = self.network.layers.last().copied().unwrap_or(0);
+        if outputs < columns {
+            return Ok(());
+        }
+
+        Err(Error::Schema(vec![Issue::new(
+            format!("network.layers[{}]", self.network.layers.len().saturating_sub(1)),
+            format!(
+                "the last layer has {outputs} outputs, but the data has only {columns} columns"
+            ),
+            "give the last layer one neuron for each trailing output column, leaving at least one input column",
+        )]))
+    }
+}
+
+/// Find the keys of a configuration file that no setting reads.
+///
+/// # Arguments
+///
+/// - `table` - The parsed configuration file.
+///
+/// # Returns
+///
+/// An issue for each unknown section or key, suggesting the nearest known one.
+fn unknown_keys(table: &toml::Table) -> Vec<Issue> {
+    let sections = SCHEMA.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+
+    let mut issues = vec![];
+    for (name, value) in table {
+        let Some((_, keys)) = SCHEMA.iter().find(|(section, _)| section == name) else {
+            issues.push(Issue::new(
+                name.clone(),
+                "unknown section",
+                suggest(name, &sections),
+            ));
+            continue;
+        };
+        let Some(value) = value.as_table() else {
+            continue;
+        };
+        for key in value.keys().filter(|key| !keys.contains(&key.as_str())) {
+            issues.push(Issue::new(
+                format!("{name}.{key}"),
+                "unknown key",
+                suggest(key, keys),
+            ));
+        }
+    }
+    issues
+}
+
+/// Suggest the known name closest to a misspelt one.
+///
+/// # Arguments
+///
+/// - `name` - The unknown name.
+/// - `known` - The names that are allowed.
+///
+/// # Returns
+///
+/// A "did you mean" hint if a known name is a typo away, otherwise the list of
+/// known names.
+fn suggest(name: &str, known: &[&str]) -> String {
+    let closest = known
+        .iter()
+        .map(|candidate| (distance(name, candidate), candidate))
+        .min_by_key(|(distance, _)| *distance);
+
+    match closest {
+        Some((distance, candidate)) if distance <= 2 => format!("did you mean `{candidate}`?"),
+        _ => format!(
+            "expected one of {}",
+            known
+                .iter()
+                .map(|name| format!("`{name}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Count the single-character edits that turn one string into another.
+///
+/// # Arguments
+///
+/// - `left` - The first string.
+/// - `right` - The second string.
+///
+/// # Returns
+///
+/// The Levenshtein distance between the strings.
+fn distance(left: &str, right: &str) -> usize {
+    let right = right.chars().collect::<Vec<_>>();
+    let mut previous = (0..=right.len()).collect::<Vec<_>>();
+    for (row, left) in left.chars().enumerate() {
+        let mut current = vec![row + 1];
+        for (column, right) in right.iter().enumerate() {
+            let substitution = previous[column] + usize::from(left != *right);
+            current.push(
+                substitution
+                    .min(previous[column + 1] + 1)
+                    .min(current[column] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[right.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let config = Config::parse_toml("[network]\nlayers = [2, 1]\n").unwrap();
+
+        assert_eq!(config.network.layers, vec![2, 1]);
+        assert_eq!(config.evolution, Evolution::default());
+        assert_eq!(config.mutation, Mutation::default());
+    }
+
+    #[test]
+    fn test_partial_sections() {
+        let config = Config::parse_toml(
+            "[network]\nlayers = [1]\n[evolution]\ngenerations = 5\ntarget_fitness = 0.5\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.evolution.generations, 5);
+        assert_eq!(config.evolution.target_fitness, Some(0.5));
+        assert_eq!(config.evolution.population, 100);
+    }
+
+    #[test]
+    fn test_requires_network() {
+        assert!(Config::parse_toml("[evolution]\ngenerations = 5\n").is_err());
+    }
+
+    fn issues(toml: &str) -> Vec<String> {
+        match Config::parse_toml(toml) {
+            Err(Error::Schema(issues)) => issues.iter().map(ToString::to_string).collect(),
+            result => panic!("expected schema issues, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_unknown_keys() {
+        let issues = issues("[network]\nlayers = [1]\n[evolution]\npopulaton = 5\n[logging]\n");
+
+        assert_eq!(
+            issues,
+            [
+                "evolution.populaton: unknown key; did you mean `population`?",
+                "logging: unknown section; expected one of `network`, `evolution`, `mutation`, `pretrained`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let issues = issues(
+            "[network]\nlayers = [2, 0]\n[evolution]\npopulation = 2\nelitism = 3\ntournament_win_probability = 1.5\n[mutation]\nrate = 1.5\n",
+        );
+
+        let paths = issues
+            .iter()
+            .map(|issue| issue.split(':').next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                "network.layers[1]",
+                "evolution.elitism",
+                "evolution.tournament_win_probability",
+                "mutation.rate"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_columns() {
+        let config = Config::parse_toml("[network]\nlayers = [2, 3]\n").unwrap();
+
+        assert!(config.check_columns(4).is_ok());
+        assert!(config.check_columns(3).is_err());
+    }
+}
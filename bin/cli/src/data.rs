@@ -0,0 +1,299 @@
+use crate::error::{Error, Result};
+use std::path::Path;
+
+/// Formats for tabular data such as datasets and predictions.
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+pub enum Format {
+    /// Comma-separated values, one row per line.
+    Csv,
+
+    /// A JSON array of rows, where each row is an array of numbers.
+    Json,
+}
+
+impl Format {
+    /// Guess the format of some data.
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The path the data was read from, if any.
+    /// - `contents` - The data.
+    ///
+    /// # Returns
+    ///
+    /// JSON if the path ends in `.json` or the data starts with `[`, otherwise CSV.
+    pub fn detect(path: Option<&Path>, contents: &str) -> Self {
+        let is_json_path = path
+            .and_then(Path::extension)
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+
+        if is_json_path || contents.trim_start().starts_with('[') {
+            Self::Json
+        } else {
+            Self::Csv
+        }
+    }
+}
+
+/// Parse rows of numbers.
+///
+/// A CSV header row is skipped if the first row is not numeric.
+///
+/// # Arguments
+///
+/// - `contents` - The data to parse.
+/// - `format` - The format of the data.
+///
+/// # Returns
+///
+/// The rows.
+///
+/// # Errors
+///
+/// If the data is malformed or contains a value that is not a number.
+pub fn parse_rows(contents: &str, format: Format) -> Result<Vec<Vec<f64>>> {
+    match format {
+        Format::Json => Ok(serde_json::from_str(contents)?),
+        Format::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .has_headers(false)
+                .trim(csv::Trim::All)
+                .from_reader(contents.as_bytes());
+
+            let mut rows = vec![];
+            for (index, record) in reader.records().enumerate() {
+                let record = record?;
+                let row: std::result::Result<Vec<f64>, _> =
+                    record.iter().map(str::parse::<f64>).collect();
+                match row {
+                    Ok(row) => rows.push(row),
+                    Err(_) if index == 0 => {}
+                    Err(error) => {
+                        return Err(Error::Invalid(format!("row {}: {error}", index + 1)));
+                    }
+                }
+            }
+            Ok(rows)
+        }
+    }
+}
+
+/// Parse a single row of numbers, such as one line of a stream.
+///
+/// # Arguments
+///
+/// - `line` - The row to parse, either comma-separated values or a JSON array.
+/// - `format` - The format of the row.
+///
+/// # Returns
+///
+/// The values in the row.
+///
+/// # Errors
+///
+/// If the row is malformed or contains a value that is not a number.
+pub fn parse_row(line: &str, format: Format) -> Result<Vec<f64>> {
+    match format {
+        Format::Json => Ok(serde_json::from_str(line)?),
+        Format::Csv => line
+            .split(',')
+            .map(|value| {
+                value.trim().parse::<f64>().map_err(|error| {
+                    Error::Invalid(format!("{:?} is not a number: {error}", value.trim()))
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Read rows of numbers from a file, guessing its format.
+///
+/// With the `npy` feature, `.npy` files are read as a 1- or 2-dimensional
+/// array, and `.npz` archives as their `inputs` and `outputs` arrays side by
+/// side.
+///
+/// # Arguments
+///
+/// - `path` - The path to the file.
+///
+/// # Returns
+///
+/// The rows.
+///
+/// # Errors
+///
+/// If the file cannot be read or parsed.
+pub fn read_rows(path: &Path) -> Result<Vec<Vec<f64>>> {
+    #[cfg(feature = "npy")]
+    let npy_error = |source| Error::Npy {
+        path: path.display().to_string(),
+        source,
+    };
+    #[cfg(feature = "npy")]
+    match path.extension().and_then(std::ffi::OsStr::to_str) {
+        Some("npy") => return evo::read_npy(path).map_err(npy_error),
+        Some("npz") => {
+            let records = evo::load_npz(path, "inputs", "outputs").map_err(npy_error)?;
+            return Ok(records
+                .into_iter()
+                .map(|mut record| {
+                    record.input.append(&mut record.output);
+                    record.input
+                })
+                .collect());
+        }
+        _ => {}
+    }
+
+    let contents = std::fs::read_to_string(path).map_err(Error::io(Some(path)))?;
+    parse_rows(&contents, Format::detect(Some(path), &contents))
+}
+
+/// Split each row into input and expected output columns.
+///
+/// # Arguments
+///
+/// - `rows` - The rows to split.
+/// - `outputs` - The number of output columns at the end of each row.
+///
+/// # Returns
+///
+/// The input and output values of each row.
+///
+/// # Errors
+///
+/// If the rows have different widths, or no room for any inputs.
+pub fn split_rows(rows: Vec<Vec<f64>>, outputs: usize) -> Result<Vec<(Vec<f64>, Vec<f64>)>> {
+    let width = rows.first().map_or(0, Vec::len);
+    if width <= outputs {
+        return Err(Error::Invalid(format!(
+            "rows need more than {outputs} columns, found {width}"
+        )));
+    }
+
+    rows.into_iter()
+        .enumerate()
+        .map(|(index, mut row)| {
+            if row.len() != width {
+                return Err(Error::Invalid(format!(
+                    "row {}: expected {width} columns, found {}",
+                    index + 1,
+                    row.len()
+                )));
+            }
+            let output = row.split_off(width - outputs);
+            Ok((row, output))
+        })
+        .collect()
+}
+
+/// Serialize rows of numbers.
+///
+/// # Arguments
+///
+/// - `rows` - The rows to serialize.
+/// - `format` - The format to serialize to.
+///
+/// # Returns
+///
+/// The serialized rows.
+///
+/// # Errors
+///
+/// If the rows cannot be serialized.
+pub fn write_rows(rows: &[Vec<f64>], format: Format) -> Result<String> {
+    match format {
+        Format::Json => Ok(serde_json::to_string(rows)?),
+        Format::Csv => Ok(rows
+            .iter()
+            .map(|row| write_row(row, format))
+            .collect::<Result<Vec<_>>>()?
+            .join("\n")),
+    }
+}
+
+/// Serialize a single row of numbers, such as one line of a stream.
+///
+/// # Arguments
+///
+/// - `row` - The row to serialize.
+/// - `format` - The format to serialize to; JSON rows are written as arrays.
+///
+/// # Returns
+///
+/// The serialized row, without a trailing newline.
+///
+/// # Errors
+///
+/// If the row cannot be serialized.
+pub fn write_row(row: &[f64], format: Format) -> Result<String> {
+    match format {
+        Format::Json => Ok(serde_json::to_string(row)?),
+        Format::Csv => Ok(row.iter().map(f64::to_string).collect::<Vec<_>>().join(",")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            Format::detect(Some(Path::new("data.JSON")), ""),
+            Format::Json
+        );
+        assert_eq!(Format::detect(None, "  [[1, 2]]"), Format::Json);
+        assert_eq!(
+            Format::detect(Some(Path::new("data.csv")), "1,2"),
+            Format::Csv
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_skips_header() {
+        let rows = parse_rows("a, b\n1, 2\n3.5, -4\n", Format::Csv).unwrap();
+
+        assert_eq!(rows, vec![vec![1.0, 2.0], vec![3.5, -4.0]]);
+    }
+
+    #[test]
+    fn test_parse_csv_rejects_bad_values() {
+        assert!(parse_rows("1,2\n3,x\n", Format::Csv).is_err());
+    }
+
+    #[test]
+    fn test_parse_and_write_row() {
+        assert_eq!(parse_row("1, -2.5", Format::Csv).unwrap(), vec![1.0, -2.5]);
+        assert_eq!(
+            parse_row("[1, -2.5]", Format::Json).unwrap(),
+            vec![1.0, -2.5]
+        );
+        assert!(parse_row("1, x", Format::Csv).is_err());
+
+        assert_eq!(write_row(&[1.0, -2.5], Format::Csv).unwrap(), "1,-2.5");
+        assert_eq!(write_row(&[1.0, -2.5], Format::Json).unwrap(), "[1.0,-2.5]");
+    }
+
+    #[test]
+    fn test_split_rows() {
+        let split = split_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]], 1).unwrap();
+
+        assert_eq!(
+            split,
+            vec![(vec![1.0, 2.0], vec![3.0]), (vec![4.0, 5.0], vec![6.0])]
+        );
+        assert!(split_rows(vec![vec![1.0, 2.0], vec![1.0]], 1).is_err());
+        assert!(split_rows(vec![vec![1.0]], 1).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let rows = vec![vec![0.5, 1.0], vec![-2.0, 3.25]];
+
+        for format in [Format::Csv, Format::Json] {
+            let written = write_rows(&rows, format).unwrap();
+            assert_eq!(parse_rows(&written, format).unwrap(), rows);
+        }
+    }
+}
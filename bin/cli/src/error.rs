@@ -0,0 +1,61 @@
+/// An error that can occur when running a CLI command.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("invalid config: {0}")]
+    Config(#[from] toml::de::Error),
+
+    #[error("invalid config:\n  {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n  "))]
+    Schema(Vec<crate::config::Issue>),
+
+    #[error("invalid bincode: {0}")]
+    Bincode(#[from] bincode::Error),
+
+    #[error("invalid MessagePack: {0}")]
+    MessagePackEncode(#[from] rmp_serde::encode::Error),
+
+    #[error("invalid MessagePack: {0}")]
+    MessagePackDecode(#[from] rmp_serde::decode::Error),
+
+    #[error("invalid protobuf: {0}")]
+    Protobuf(#[from] prost::DecodeError),
+
+    #[error("invalid CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[cfg(feature = "npy")]
+    #[error("{path}: invalid NumPy data: {source}")]
+    Npy { path: String, source: evo::NpyError },
+
+    #[error("{0}")]
+    Invalid(String),
+}
+
+impl Error {
+    /// Attach the path being read or written to an I/O error.
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The path that caused the error, or `None` for stdin/stdout.
+    ///
+    /// # Returns
+    ///
+    /// A function that wraps an I/O error.
+    pub fn io(path: Option<&std::path::Path>) -> impl FnOnce(std::io::Error) -> Self + '_ {
+        move |source| Self::Io {
+            path: path.map_or_else(|| "<stdio>".to_string(), |p| p.display().to_string()),
+            source,
+        }
+    }
+}
+
+/// A result that can occur when running a CLI command.
+pub type Result<T> = std::result::Result<T, Error>;
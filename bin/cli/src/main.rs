@@ -7,52 +7,31 @@
     clippy::pedantic
 )]
 
-use nnet::Network;
+mod args;
+mod checkpoint;
+mod command;
+mod config;
+mod data;
+mod error;
+mod output;
+#[cfg(feature = "plot")]
+mod plot;
+mod proto;
+mod stream;
+#[cfg(feature = "tui")]
+mod tui;
 
-const NETWORK_JSON: &str = r#"
-{
-    "layers": [
-        {
-            "neurons": [
-                {
-                    "Basic": {
-                        "bias": 0.0,
-                        "weights": [
-                            1.0
-                        ],
-                        "activation": {
-                            "Sigmoid": null
-                        }
-                    }
-                }
-            ]
-        },
-        {
-            "neurons": [
-                {
-                    "Basic": {
-                        "bias": 0.0,
-                        "weights": [
-                            0.5
-                        ],
-                        "activation": {
-                            "Sigmoid": null
-                        }
-                    }
-                }
-            ]
-        }
-    ]
-}
-"#;
-
-fn main() {
-    farm::run();
+use clap::Parser;
+use std::process::ExitCode;
 
-    let network = Network::parse_json(NETWORK_JSON).unwrap();
+fn main() -> ExitCode {
+    let cli = args::Cli::parse();
 
-    let input = vec![1.0, 1.0];
-    let output = network.activate(&input);
-
-    println!("{network:?} took {input:?} and produced {output:?}");
+    match command::run(&cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,107 @@
+use crate::error::{Error, Result};
+
+/// Formats for results such as predictions, summaries, and statistics.
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+pub enum Format {
+    /// JSON, for scripts.
+    Json,
+
+    /// Comma-separated values with a header row, for spreadsheets and scripts.
+    Csv,
+
+    /// Aligned columns, for people.
+    Table,
+}
+
+/// Write rows of cells as comma-separated values.
+///
+/// # Arguments
+///
+/// - `header` - The name of each column.
+/// - `rows` - The cells of each row.
+///
+/// # Returns
+///
+/// The header and rows, one per line.
+///
+/// # Errors
+///
+/// If the rows cannot be serialized.
+pub fn csv(header: &[String], rows: &[Vec<String>]) -> Result<String> {
+    let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(vec![]);
+    writer.write_record(header)?;
+    for row in rows {
+        writer.write_record(row)?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|error| Error::Invalid(error.to_string()))?;
+    Ok(String::from_utf8_lossy(&bytes).trim_end().to_string())
+}
+
+/// Lay out rows of cells in aligned columns.
+///
+/// # Arguments
+///
+/// - `header` - The name of each column.
+/// - `rows` - The cells of each row.
+///
+/// # Returns
+///
+/// The header, a rule, and the rows, with each column padded to its widest cell.
+pub fn table(header: &[String], rows: &[Vec<String>]) -> String {
+    let mut widths = header
+        .iter()
+        .map(|cell| cell.chars().count())
+        .collect::<Vec<_>>();
+    for row in rows {
+        for (index, cell) in row.iter().enumerate() {
+            match widths.get_mut(index) {
+                Some(width) => *width = (*width).max(cell.chars().count()),
+                None => widths.push(cell.chars().count()),
+            }
+        }
+    }
+
+    let line = |cells: &[String]| {
+        Iterator::zip(cells.iter(), widths.iter())
+            .map(|(cell, width)| format!("{cell:>width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
+    let rule = widths
+        .iter()
+        .map(|width| "-".repeat(*width))
+        .collect::<Vec<_>>()
+        .join("  ");
+
+    [line(header), rule]
+        .into_iter()
+        .chain(rows.iter().map(|row| line(row)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = csv(&cells(&["a", "b"]), &[cells(&["1", "x,y"])]).unwrap();
+
+        assert_eq!(csv, "a,b\n1,\"x,y\"");
+    }
+
+    #[test]
+    fn test_table() {
+        let table = table(&cells(&["name", "n"]), &[cells(&["a", "10"])]);
+
+        assert_eq!(table, "name   n\n----  --\n   a  10");
+    }
+}
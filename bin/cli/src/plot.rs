@@ -0,0 +1,165 @@
+use crate::command::evolve::Stats;
+use crate::error::{Error, Result};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::path::Path;
+
+/// The size of a rendered plot, in pixels.
+const SIZE: (u32, u32) = (1024, 768);
+
+/// Render the fitness and diversity curves of a run to an image.
+///
+/// The top chart shows the best and mean fitness of each generation, and the
+/// bottom chart shows its diversity.
+///
+/// # Arguments
+///
+/// - `path` - The image to write; `.svg` files are vector images, anything
+///   else is a bitmap in the format of its extension, such as `.png`.
+/// - `history` - The statistics of each generation.
+///
+/// # Errors
+///
+/// If there is nothing to plot, or the image cannot be drawn or written.
+pub fn render(path: &Path, history: &[Stats]) -> Result<()> {
+    if history.is_empty() {
+        return Err(Error::Invalid("there are no generations to plot".into()));
+    }
+
+    let is_svg = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("svg"));
+    // The backends have different error types, so only their messages are kept.
+    let drawn = if is_svg {
+        draw(&SVGBackend::new(path, SIZE).into_drawing_area(), history)
+            .map_err(|error| error.to_string())
+    } else {
+        draw(&BitMapBackend::new(path, SIZE).into_drawing_area(), history)
+            .map_err(|error| error.to_string())
+    };
+    drawn.map_err(|error| Error::Invalid(format!("{}: cannot plot: {error}", path.display())))
+}
+
+/// Draw the fitness and diversity charts.
+///
+/// # Arguments
+///
+/// - `root` - The area to draw on.
+/// - `history` - The statistics of each generation; must not be empty.
+///
+/// # Errors
+///
+/// If the backend fails to draw or save the image.
+fn draw<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    history: &[Stats],
+) -> std::result::Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    root.fill(&WHITE)?;
+    let (top, bottom) = root.split_vertically(SIZE.1 * 2 / 3);
+
+    #[allow(clippy::cast_precision_loss)]
+    let generations = {
+        let first = history[0].generation as f64;
+        let last = history[history.len() - 1].generation as f64;
+        first..last.max(first + 1.0)
+    };
+    #[allow(clippy::cast_precision_loss)]
+    let points = |value: fn(&Stats) -> f64| {
+        history
+            .iter()
+            .map(move |stats| (stats.generation as f64, value(stats)))
+    };
+
+    let fitness = range(history.iter().flat_map(|stats| [stats.best, stats.mean]));
+    let mut chart = ChartBuilder::on(&top)
+        .caption("Fitness", ("sans-serif", 24))
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(60)
+        .build_cartesian_2d(generations.clone(), fitness)?;
+    chart
+        .configure_mesh()
+        .x_desc("generation")
+        .x_label_formatter(&|generation| format!("{generation:.0}"))
+        .y_desc("fitness")
+        .draw()?;
+    chart
+        .draw_series(LineSeries::new(points(|stats| stats.best), &BLUE))?
+        .label("best")
+        .legend(|(x, y)| PathElement::new([(x, y), (x + 20, y)], BLUE));
+    chart
+        .draw_series(LineSeries::new(points(|stats| stats.mean), &RED))?
+        .label("mean")
+        .legend(|(x, y)| PathElement::new([(x, y), (x + 20, y)], RED));
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+
+    let diversity = range(history.iter().map(|stats| stats.diversity));
+    let mut chart = ChartBuilder::on(&bottom)
+        .caption("Diversity", ("sans-serif", 24))
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(60)
+        .build_cartesian_2d(generations, diversity)?;
+    chart
+        .configure_mesh()
+        .x_desc("generation")
+        .x_label_formatter(&|generation| format!("{generation:.0}"))
+        .y_desc("diversity")
+        .draw()?;
+    chart.draw_series(LineSeries::new(points(|stats| stats.diversity), &GREEN))?;
+
+    root.present()
+}
+
+/// Find the range of a chart axis, ignoring values that cannot be plotted.
+///
+/// # Arguments
+///
+/// - `values` - The values on the axis.
+///
+/// # Returns
+///
+/// The range from the smallest to the largest finite value, widened so it is
+/// never empty.
+fn range(values: impl Iterator<Item = f64>) -> std::ops::Range<f64> {
+    let (low, high) = values
+        .filter(|value| value.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), value| {
+            (low.min(value), high.max(value))
+        });
+    if low > high {
+        return 0.0..1.0;
+    }
+    let margin = ((high - low) * 0.05).max(f64::EPSILON);
+    low - margin..high + margin
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_svg() {
+        let history = (1..=5u8)
+            .map(|count| Stats {
+                generation: usize::from(count - 1),
+                best: 1.0 / f64::from(count),
+                mean: 2.0 / f64::from(count),
+                diversity: 0.1,
+            })
+            .collect::<Vec<_>>();
+        let path = std::env::temp_dir().join(format!("brain-farm-plot-{}.svg", std::process::id()));
+
+        render(&path, &history).unwrap();
+        let svg = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(svg.contains("Fitness"));
+        assert!(svg.contains("Diversity"));
+        assert!(render(&path, &[]).is_err());
+    }
+}
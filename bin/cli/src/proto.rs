@@ -0,0 +1,418 @@
+//! Protobuf messages for networks and genomes.
+//!
+//! These mirror `proto/brain_farm.proto`; keep the field tags in sync with it.
+//! `Genome` has the same fields as `Network` plus a freeze mask, so both are
+//! encoded with [`Network`], leaving the mask empty for networks.
+
+use crate::error::{Error, Result};
+use farm::genome::{activator, layer, network, neuron, Create, Extract};
+use nnet::InputSource;
+use prost::Message;
+
+/// The activation function of a neuron.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, prost::Enumeration)]
+#[repr(i32)]
+pub enum Activation {
+    Linear = 0,
+    Sigmoid = 1,
+    LeakyRelu = 2,
+    Tanh = 3,
+}
+
+/// A neuron: one weight per input, a bias, and an activation function.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Neuron {
+    #[prost(double, repeated, tag = "1")]
+    pub weights: Vec<f64>,
+
+    #[prost(double, tag = "2")]
+    pub bias: f64,
+
+    #[prost(enumeration = "Activation", tag = "3")]
+    pub activation: i32,
+
+    /// The parameter of the activation function, if it is not the default.
+    #[prost(double, optional, tag = "4")]
+    pub parameter: Option<f64>,
+
+    /// The gates of an LSTM neuron; unset for basic neurons.
+    #[prost(message, optional, tag = "5")]
+    pub gates: Option<Gates>,
+}
+
+/// The gates of an LSTM neuron.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Gates {
+    #[prost(double, tag = "1")]
+    pub recurrent: f64,
+
+    #[prost(message, optional, tag = "2")]
+    pub input: Option<Gate>,
+
+    #[prost(message, optional, tag = "3")]
+    pub forget: Option<Gate>,
+
+    #[prost(message, optional, tag = "4")]
+    pub output: Option<Gate>,
+}
+
+/// A gate of an LSTM neuron.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Gate {
+    #[prost(double, repeated, tag = "1")]
+    pub weights: Vec<f64>,
+
+    #[prost(double, tag = "2")]
+    pub recurrent: f64,
+
+    #[prost(double, tag = "3")]
+    pub bias: f64,
+}
+
+/// A layer of neurons.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Layer {
+    #[prost(message, repeated, tag = "1")]
+    pub neurons: Vec<Neuron>,
+}
+
+/// A feed-forward network, or the genome of one.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Network {
+    #[prost(message, repeated, tag = "1")]
+    pub layers: Vec<Layer>,
+
+    /// Which layers of a genome are frozen; always empty for networks.
+    #[prost(bool, repeated, tag = "2")]
+    pub frozen: Vec<bool>,
+
+    /// Where each layer takes its inputs from; empty unless some layer has a
+    /// skip connection.
+    #[prost(message, repeated, tag = "3")]
+    pub sources: Vec<Source>,
+}
+
+/// Where a layer takes its inputs from.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Source {
+    /// The layers whose outputs are concatenated; empty for the previous
+    /// layer.
+    #[prost(uint64, repeated, tag = "1")]
+    pub layers: Vec<u64>,
+}
+
+/// Serialize a network.
+///
+/// # Arguments
+///
+/// - `network` - The network to serialize.
+///
+/// # Returns
+///
+/// The encoded `Network` message.
+pub fn encode_network(network: &nnet::Network) -> Vec<u8> {
+    encode_genome(&network.genome())
+}
+
+/// Deserialize a network.
+///
+/// # Arguments
+///
+/// - `bytes` - An encoded `Network` message.
+///
+/// # Returns
+///
+/// The network.
+///
+/// # Errors
+///
+/// If the bytes are not a `Network` message, or name an unknown activation.
+pub fn decode_network(bytes: &[u8]) -> Result<nnet::Network> {
+    Ok(decode_genome(bytes)?.create())
+}
+
+/// Serialize a genome.
+///
+/// # Arguments
+///
+/// - `genome` - The genome to serialize.
+///
+/// # Returns
+///
+/// The encoded `Genome` message.
+pub fn encode_genome(genome: &network::Genome) -> Vec<u8> {
+    let layers = genome
+        .layers
+        .iter()
+        .map(|layer| Layer {
+            neurons: layer
+                .neurons
+                .iter()
+                .map(|neuron| Neuron {
+                    weights: neuron.weights.clone(),
+                    bias: neuron.bias,
+                    activation: encode_activation(&neuron.activator.activator) as i32,
+                    parameter: neuron.activator.parameter,
+                    gates: neuron.gates.as_ref().map(|gates| Gates {
+                        recurrent: gates.recurrent,
+                        input: Some(encode_gate(&gates.input)),
+                        forget: Some(encode_gate(&gates.forget)),
+                        output: Some(encode_gate(&gates.output)),
+                    }),
+                })
+                .collect(),
+        })
+        .collect();
+    Network {
+        layers,
+        frozen: genome.frozen.clone(),
+        sources: genome
+            .sources
+            .iter()
+            .map(|source| Source {
+                layers: match source {
+                    InputSource::Previous => vec![],
+                    InputSource::Layers(layers) => layers.iter().map(|&l| l as u64).collect(),
+                },
+            })
+            .collect(),
+    }
+    .encode_to_vec()
+}
+
+/// Deserialize a genome.
+///
+/// # Arguments
+///
+/// - `bytes` - An encoded `Genome` message.
+///
+/// # Returns
+///
+/// The genome.
+///
+/// # Errors
+///
+/// If the bytes are not a `Genome` message, or name an unknown activation.
+pub fn decode_genome(bytes: &[u8]) -> Result<network::Genome> {
+    let message = Network::decode(bytes)?;
+    let layers = message
+        .layers
+        .into_iter()
+        .map(|layer| {
+            let neurons = layer
+                .neurons
+                .into_iter()
+                .map(|neuron| {
+                    Ok(neuron::Genome {
+                        activator: decode_activator(neuron.activation, neuron.parameter)?,
+                        weights: neuron.weights,
+                        bias: neuron.bias,
+                        gates: neuron.gates.map(|gates| neuron::Gates {
+                            recurrent: gates.recurrent,
+                            input: decode_gate(gates.input),
+                            forget: decode_gate(gates.forget),
+                            output: decode_gate(gates.output),
+                        }),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(layer::Genome { neurons })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(network::Genome {
+        layers,
+        frozen: message.frozen,
+        sources: message
+            .sources
+            .into_iter()
+            .map(|source| {
+                if source.layers.is_empty() {
+                    return Ok(InputSource::Previous);
+                }
+                source
+                    .layers
+                    .into_iter()
+                    .map(|layer| {
+                        usize::try_from(layer)
+                            .map_err(|_| Error::Invalid(format!("unknown source layer {layer}")))
+                    })
+                    .collect::<Result<_>>()
+                    .map(InputSource::Layers)
+            })
+            .collect::<Result<_>>()?,
+    })
+}
+
+/// Get the protobuf activation of an activation gene.
+///
+/// # Arguments
+///
+/// - `gene` - The activation gene.
+///
+/// # Returns
+///
+/// The activation.
+fn encode_activation(gene: &activator::Gene) -> Activation {
+    match gene {
+        activator::Gene::Linear => Activation::Linear,
+        activator::Gene::Sigmoid => Activation::Sigmoid,
+        activator::Gene::LeakyRelu => Activation::LeakyRelu,
+        activator::Gene::Tanh => Activation::Tanh,
+    }
+}
+
+/// Convert an LSTM gate genome to its message.
+///
+/// # Arguments
+///
+/// - `gate` - The gate genome.
+///
+/// # Returns
+///
+/// The message.
+fn encode_gate(gate: &neuron::Gate) -> Gate {
+    Gate {
+        weights: gate.weights.clone(),
+        recurrent: gate.recurrent,
+        bias: gate.bias,
+    }
+}
+
+/// Convert an LSTM gate message to its genome.
+///
+/// # Arguments
+///
+/// - `gate` - The message, or `None` for a gate that was not written.
+///
+/// # Returns
+///
+/// The gate genome; a missing gate has no weights.
+fn decode_gate(gate: Option<Gate>) -> neuron::Gate {
+    let gate = gate.unwrap_or_default();
+    neuron::Gate {
+        weights: gate.weights,
+        recurrent: gate.recurrent,
+        bias: gate.bias,
+    }
+}
+
+/// Look up an activation by its protobuf value.
+///
+/// # Arguments
+///
+/// - `value` - The value of the `activation` field.
+/// - `parameter` - The value of the `parameter` field.
+///
+/// # Returns
+///
+/// The activator genome.
+///
+/// # Errors
+///
+/// If the value is not a known activation.
+fn decode_activator(value: i32, parameter: Option<f64>) -> Result<activator::Genome> {
+    let activator = match Activation::try_from(value) {
+        Ok(Activation::Linear) => activator::Gene::Linear,
+        Ok(Activation::Sigmoid) => activator::Gene::Sigmoid,
+        Ok(Activation::LeakyRelu) => activator::Gene::LeakyRelu,
+        Ok(Activation::Tanh) => activator::Gene::Tanh,
+        Err(_) => {
+            return Err(Error::Invalid(format!(
+                "unknown activation function {value}"
+            )))
+        }
+    };
+    Ok(activator::Genome {
+        activator,
+        parameter,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use farm::genome::Create;
+
+    fn genome() -> network::Genome {
+        let neuron = |activator, weights: &[f64], bias| neuron::Genome {
+            activator: activator::Genome {
+                parameter: (activator == activator::Gene::LeakyRelu).then_some(0.2),
+                activator,
+            },
+            weights: weights.to_vec(),
+            bias,
+            gates: None,
+        };
+        let gate = |bias| neuron::Gate {
+            weights: vec![0.5],
+            recurrent: -0.25,
+            bias,
+        };
+        network::Genome {
+            layers: vec![
+                layer::Genome {
+                    neurons: vec![
+                        neuron(activator::Gene::Sigmoid, &[0.5, -1.0], 0.25),
+                        neuron(activator::Gene::Linear, &[2.0, 0.0], -0.5),
+                        neuron(activator::Gene::LeakyRelu, &[0.0, 1.0], 0.0),
+                    ],
+                },
+                layer::Genome {
+                    neurons: vec![neuron(activator::Gene::Linear, &[1.0, 1.0, 1.0], 0.0)],
+                },
+                layer::Genome {
+                    neurons: vec![neuron::Genome {
+                        gates: Some(neuron::Gates {
+                            recurrent: 0.75,
+                            input: gate(0.0),
+                            forget: gate(1.0),
+                            output: gate(-1.0),
+                        }),
+                        ..neuron(activator::Gene::Tanh, &[1.5], 0.1)
+                    }],
+                },
+            ],
+            frozen: vec![true, false, false],
+            sources: vec![
+                InputSource::Previous,
+                InputSource::Previous,
+                InputSource::Layers(vec![1]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_genome_round_trip() {
+        let genome = genome();
+
+        assert_eq!(decode_genome(&encode_genome(&genome)).unwrap(), genome);
+    }
+
+    #[test]
+    fn test_network_round_trip() {
+        let network = genome().create();
+
+        assert_eq!(decode_network(&encode_network(&network)).unwrap(), network);
+    }
+
+    #[test]
+    fn test_rejects_unknown_activation() {
+        let bytes = Network {
+            layers: vec![Layer {
+                neurons: vec![Neuron {
+                    weights: vec![1.0],
+                    bias: 0.0,
+                    activation: 7,
+                    parameter: None,
+                    gates: None,
+                }],
+            }],
+            frozen: vec![],
+            sources: vec![],
+        }
+        .encode_to_vec();
+
+        assert!(decode_network(&bytes).is_err());
+        assert!(decode_genome(&bytes).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use crate::error::{Error, Result};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, PoisonError};
+use tungstenite::{Message, WebSocket};
+
+/// Broadcasts messages to every connected WebSocket client.
+///
+/// Clients that connect late receive the most recent message first, so a
+/// dashboard can show the current state without waiting for the next update.
+pub struct Broadcaster {
+    address: SocketAddr,
+    shared: Arc<Mutex<Shared>>,
+}
+
+#[derive(Default)]
+struct Shared {
+    clients: Vec<WebSocket<TcpStream>>,
+    latest: Option<String>,
+}
+
+impl Broadcaster {
+    /// Listen for WebSocket clients in a background thread.
+    ///
+    /// # Arguments
+    ///
+    /// - `address` - The address to listen on, e.g. `127.0.0.1:9001`.
+    ///
+    /// # Returns
+    ///
+    /// The broadcaster.
+    ///
+    /// # Errors
+    ///
+    /// If the address cannot be bound.
+    pub fn bind(address: &str) -> Result<Self> {
+        let listener = TcpListener::bind(address)
+            .map_err(|error| Error::Invalid(format!("cannot listen on {address}: {error}")))?;
+        let address = listener
+            .local_addr()
+            .map_err(|error| Error::Invalid(format!("cannot listen on {address}: {error}")))?;
+        let shared = Arc::new(Mutex::new(Shared::default()));
+
+        let accepted = Arc::clone(&shared);
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let Ok(mut client) = tungstenite::accept(stream) else {
+                    continue;
+                };
+                let mut shared = accepted.lock().unwrap_or_else(PoisonError::into_inner);
+                let welcome = match shared.latest.clone() {
+                    Some(latest) => client.send(Message::Text(latest)).is_ok(),
+                    None => true,
+                };
+                if welcome {
+                    shared.clients.push(client);
+                }
+            }
+        });
+
+        Ok(Self { address, shared })
+    }
+
+    /// Get the address the broadcaster is listening on.
+    ///
+    /// # Returns
+    ///
+    /// The address.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Send a message to every connected client, dropping clients that have
+    /// disconnected.
+    ///
+    /// # Arguments
+    ///
+    /// - `message` - The message to send.
+    pub fn send(&self, message: &str) {
+        let mut shared = self.shared.lock().unwrap_or_else(PoisonError::into_inner);
+        shared
+            .clients
+            .retain_mut(|client| client.send(Message::Text(message.to_string())).is_ok());
+        shared.latest = Some(message.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_late_clients_receive_latest_message() {
+        let broadcaster = Broadcaster::bind("127.0.0.1:0").unwrap();
+        broadcaster.send("first");
+        broadcaster.send("second");
+
+        let url = format!("ws://{}", broadcaster.address());
+        let (mut client, _) = tungstenite::connect(url).unwrap();
+
+        assert_eq!(client.read().unwrap(), Message::Text("second".into()));
+    }
+}
@@ -0,0 +1,327 @@
+use crate::command::evolve::Stats;
+use crate::config::Config;
+use crate::error::{Error, Result};
+use farm::organism::Organism;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::symbols::Marker;
+use ratatui::text::Line;
+use ratatui::widgets::{Axis, Block, Chart, Dataset, GraphType, Paragraph, Sparkline};
+use ratatui::{DefaultTerminal, Frame};
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+
+/// A live terminal dashboard for an evolution run.
+pub struct Dashboard {
+    terminal: DefaultTerminal,
+    state: State,
+}
+
+/// Everything the dashboard shows.
+pub struct State {
+    started: Instant,
+    population: usize,
+    generations: usize,
+    mutation_rate: f64,
+    mutation_size: f64,
+    history: Vec<Stats>,
+    best: Option<(f64, String)>,
+    finished: bool,
+}
+
+impl Dashboard {
+    /// Take over the terminal to show the dashboard.
+    ///
+    /// # Arguments
+    ///
+    /// - `config` - The experiment configuration.
+    ///
+    /// # Returns
+    ///
+    /// The dashboard.
+    ///
+    /// # Errors
+    ///
+    /// If the terminal cannot be initialized.
+    pub fn new(config: &Config) -> Result<Self> {
+        let terminal = ratatui::try_init().map_err(Error::io(None))?;
+        Ok(Self {
+            terminal,
+            state: State::new(config),
+        })
+    }
+
+    /// Show the statistics of a new generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `stats` - The statistics of the generation.
+    /// - `champion` - The best organism of the generation.
+    ///
+    /// # Returns
+    ///
+    /// Whether to keep evolving; pressing `q` stops the run early.
+    pub fn update(&mut self, stats: &Stats, champion: &Organism) -> ControlFlow<()> {
+        self.state.record(stats, champion);
+        let _ = self.terminal.draw(|frame| render(frame, &self.state));
+
+        while event::poll(Duration::ZERO).unwrap_or(false) {
+            if let Ok(Event::Key(key)) = event::read() {
+                if key.kind == KeyEventKind::Press && key.code == KeyCode::Char('q') {
+                    return ControlFlow::Break(());
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Show the final state until a key is pressed, then restore the terminal.
+    pub fn finish(mut self) {
+        self.state.finished = true;
+        let _ = self.terminal.draw(|frame| render(frame, &self.state));
+
+        loop {
+            match event::read() {
+                Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => break,
+                Ok(_) => {}
+                Err(_) => break,
+            }
+        }
+        ratatui::restore();
+    }
+}
+
+impl State {
+    /// Create an empty dashboard state.
+    ///
+    /// # Arguments
+    ///
+    /// - `config` - The experiment configuration.
+    ///
+    /// # Returns
+    ///
+    /// The state.
+    pub fn new(config: &Config) -> Self {
+        Self {
+            started: Instant::now(),
+            population: config.evolution.population,
+            generations: config.evolution.generations,
+            mutation_rate: config.mutation.rate,
+            mutation_size: config.mutation.size,
+            history: vec![],
+            best: None,
+            finished: false,
+        }
+    }
+
+    /// Record the statistics of a new generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `stats` - The statistics of the generation.
+    /// - `champion` - The best organism of the generation.
+    pub fn record(&mut self, stats: &Stats, champion: &Organism) {
+        self.history.push(*stats);
+        if self
+            .best
+            .as_ref()
+            .is_none_or(|(best, _)| stats.best < *best)
+        {
+            let layers = champion
+                .genome()
+                .layers
+                .iter()
+                .map(|layer| layer.neurons.len().to_string())
+                .collect::<Vec<_>>()
+                .join(" → ");
+            self.best = Some((stats.best, layers));
+        }
+    }
+}
+
+/// Draw the dashboard.
+///
+/// # Arguments
+///
+/// - `frame` - The frame to draw on.
+/// - `state` - The state to show.
+pub fn render(frame: &mut Frame, state: &State) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(8),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [charts, summary] =
+        Layout::horizontal([Constraint::Min(40), Constraint::Length(34)]).areas(body);
+    let [fitness, diversity] =
+        Layout::vertical([Constraint::Min(6), Constraint::Length(5)]).areas(charts);
+
+    let generation = state.history.last().map_or(0, |stats| stats.generation);
+    frame.render_widget(
+        Paragraph::new(format!(
+            "generation {generation} of {}    elapsed {:.1?}",
+            state.generations,
+            state.started.elapsed()
+        ))
+        .block(Block::bordered().title(" brain-farm evolve ")),
+        header,
+    );
+
+    render_fitness(frame, fitness, state);
+
+    let scale = state
+        .history
+        .iter()
+        .map(|stats| stats.diversity)
+        .fold(f64::MIN_POSITIVE, f64::max);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let bars = state
+        .history
+        .iter()
+        .map(|stats| (100.0 * stats.diversity / scale) as u64)
+        .collect::<Vec<_>>();
+    let visible = bars
+        .len()
+        .saturating_sub(diversity.width.saturating_sub(2).into());
+    frame.render_widget(
+        Sparkline::default()
+            .block(Block::bordered().title(" diversity "))
+            .data(&bars[visible..])
+            .max(100)
+            .style(Style::default().fg(Color::Cyan)),
+        diversity,
+    );
+
+    let mut lines = vec![
+        Line::from(format!("population:    {}", state.population)),
+        Line::from(format!("mutation rate: {}", state.mutation_rate)),
+        Line::from(format!("mutation size: {}", state.mutation_size)),
+        Line::from(""),
+    ];
+    if let Some(stats) = state.history.last() {
+        lines.push(Line::from(format!("best:          {:.6}", stats.best)));
+        lines.push(Line::from(format!("mean:          {:.6}", stats.mean)));
+        lines.push(Line::from(format!("diversity:     {:.6}", stats.diversity)));
+    }
+    if let Some((fitness, layers)) = &state.best {
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!("best ever:     {fitness:.6}")));
+        lines.push(Line::from(format!("layers:        {layers}")));
+    }
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(" summary ")),
+        summary,
+    );
+
+    let help = if state.finished {
+        "finished — press any key to exit"
+    } else {
+        "press q to stop early"
+    };
+    frame.render_widget(Paragraph::new(help), footer);
+}
+
+/// Draw the best and mean fitness of every generation.
+///
+/// # Arguments
+///
+/// - `frame` - The frame to draw on.
+/// - `area` - The area to draw in.
+/// - `state` - The state to show.
+fn render_fitness(frame: &mut Frame, area: Rect, state: &State) {
+    #[allow(clippy::cast_precision_loss)]
+    let points = |value: fn(&Stats) -> f64| {
+        state
+            .history
+            .iter()
+            .map(|stats| (stats.generation as f64, value(stats)))
+            .filter(|(_, y)| y.is_finite())
+            .collect::<Vec<_>>()
+    };
+    let best = points(|stats| stats.best);
+    let mean = points(|stats| stats.mean);
+
+    let max_x = best.last().map_or(1.0, |(x, _)| x.max(1.0));
+    let max_y = Iterator::chain(best.iter(), mean.iter())
+        .map(|(_, y)| *y)
+        .fold(f64::MIN_POSITIVE, f64::max);
+
+    let chart = Chart::new(vec![
+        Dataset::default()
+            .name("best")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Green))
+            .data(&best),
+        Dataset::default()
+            .name("mean")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Yellow))
+            .data(&mean),
+    ])
+    .block(Block::bordered().title(" fitness (lower is better) "))
+    .x_axis(
+        Axis::default()
+            .bounds([0.0, max_x])
+            .labels(["0".to_string(), format!("{max_x:.0}")]),
+    )
+    .y_axis(
+        Axis::default()
+            .bounds([0.0, max_y])
+            .labels(["0".to_string(), format!("{max_y:.3}")]),
+    );
+    frame.render_widget(chart, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use farm::genome::{activator, layer, network, neuron};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_render() {
+        let config = Config::parse_toml("[network]\nlayers = [1]\n").unwrap();
+        let champion = Organism::from(network::Genome {
+            layers: vec![layer::Genome {
+                neurons: vec![neuron::Genome {
+                    activator: activator::Genome {
+                        activator: activator::Gene::Linear,
+                        parameter: None,
+                    },
+                    weights: vec![1.0],
+                    bias: 0.0,
+                    gates: None,
+                }],
+            }],
+            frozen: vec![],
+            sources: vec![],
+        });
+
+        let mut state = State::new(&config);
+        for generation in 0..3 {
+            #[allow(clippy::cast_precision_loss)]
+            let best = 1.0 / (generation + 1) as f64;
+            state.record(
+                &Stats {
+                    generation,
+                    best,
+                    mean: 2.0 * best,
+                    diversity: 0.5,
+                },
+                &champion,
+            );
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+        terminal.draw(|frame| render(frame, &state)).unwrap();
+        let screen = format!("{:?}", terminal.backend().buffer());
+
+        assert!(screen.contains("generation 2 of 100"));
+        assert!(screen.contains("best ever:     0.333333"));
+        assert!(screen.contains("press q to stop early"));
+    }
+}
@@ -0,0 +1,142 @@
+//! Adaptive mutation: each generation's diversity is measured, and the
+//! breeder is told to mutate harder when it falls below a target.
+//!
+//! A population that has converged on one solution can no longer find
+//! better ones by crossover alone. Mutating more brings variety back until
+//! the population spreads out again.
+
+use crate::{CompareRecord, Distance, Predict};
+
+/// The most genomes whose distances are measured each generation, since
+/// every pair of them is compared.
+const SAMPLE: usize = 32;
+
+/// How the mutation pressure follows a generation's diversity.
+pub(crate) struct AdaptiveMutation<TGenome> {
+    /// Measures the distance between genomes.
+    pub distance: Box<dyn Distance<TGenome> + Send + Sync>,
+
+    /// The diversity at or above which the pressure is 1.
+    pub target: f64,
+
+    /// The greatest pressure, reached when every genome is the same.
+    pub max_pressure: f64,
+}
+
+impl<TGenome> AdaptiveMutation<TGenome>
+where
+    TGenome: Predict + PartialOrd,
+{
+    /// Measure the diversity of a scored generation: the mean distance
+    /// between its genomes.
+    ///
+    /// Large generations are measured on a sample of genomes spread evenly
+    /// over the generation, in its order.
+    ///
+    /// # Arguments
+    ///
+    /// - `ranked` is the scored generation.
+    ///
+    /// # Returns
+    ///
+    /// The diversity, which is zero for fewer than two genomes.
+    pub fn diversity(&self, ranked: &[CompareRecord<TGenome>]) -> f64 {
+        let count = ranked.len().min(SAMPLE);
+        let sample = (0..count)
+            .map(|index| &ranked[index * ranked.len() / count].predict)
+            .collect::<Vec<_>>();
+
+        let mut total = 0.0;
+        let mut pairs = 0_u32;
+        for (index, left) in sample.iter().enumerate() {
+            for right in &sample[index + 1..] {
+                total += self.distance.distance((left, right));
+                pairs += 1;
+            }
+        }
+        if pairs == 0 {
+            0.0
+        } else {
+            total / f64::from(pairs)
+        }
+    }
+
+    /// Get the mutation pressure for a diversity.
+    ///
+    /// # Arguments
+    ///
+    /// - `diversity` is the generation's diversity.
+    ///
+    /// # Returns
+    ///
+    /// The target over the diversity, from 1 up to the greatest pressure.
+    pub fn pressure(&self, diversity: f64) -> f64 {
+        let max_pressure = self.max_pressure.max(1.0);
+        if diversity > 0.0 {
+            (self.target / diversity).clamp(1.0, max_pressure)
+        } else {
+            max_pressure
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, PartialOrd)]
+    struct Genome(f64);
+
+    impl Predict for Genome {
+        fn predict(&self, _input: &[f64]) -> Vec<f64> {
+            vec![self.0]
+        }
+    }
+
+    struct Difference;
+
+    impl Distance<Genome> for Difference {
+        fn distance(&self, pair: (&Genome, &Genome)) -> f64 {
+            (pair.0 .0 - pair.1 .0).abs()
+        }
+    }
+
+    fn ranked(values: &[f64]) -> Vec<CompareRecord<Genome>> {
+        values
+            .iter()
+            .map(|&value| CompareRecord {
+                fitness: 0.0,
+                predict: Genome(value),
+            })
+            .collect()
+    }
+
+    fn adaptive() -> AdaptiveMutation<Genome> {
+        AdaptiveMutation {
+            distance: Box::new(Difference),
+            target: 2.0,
+            max_pressure: 5.0,
+        }
+    }
+
+    #[test]
+    fn test_diversity() {
+        let adaptive = adaptive();
+
+        // Distances of 1, 3, and 2.
+        assert!((adaptive.diversity(&ranked(&[0.0, 1.0, 3.0])) - 2.0).abs() < f64::EPSILON);
+        assert!(adaptive.diversity(&ranked(&[1.0])).abs() < f64::EPSILON);
+        assert!(adaptive.diversity(&ranked(&[1.0; 100])).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_pressure() {
+        let adaptive = adaptive();
+
+        assert!((adaptive.pressure(4.0) - 1.0).abs() < f64::EPSILON);
+        assert!((adaptive.pressure(1.0) - 2.0).abs() < f64::EPSILON);
+        assert!((adaptive.pressure(0.1) - 5.0).abs() < f64::EPSILON);
+        assert!((adaptive.pressure(0.0) - 5.0).abs() < f64::EPSILON);
+        assert!((adaptive.pressure(f64::NAN) - 5.0).abs() < f64::EPSILON);
+    }
+}
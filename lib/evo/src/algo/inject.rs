@@ -1,6 +1,26 @@
-use crate::{Generation, Predict};
+use crate::{rng, CompareRecord, Generation, NanSafeCompare, Predict};
+use rand::seq::SliceRandom;
 use rand::Rng;
 
+/// How the elite genomes of a generation are put into the next one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Policy {
+    /// Overwrite random offspring. An elite genome can overwrite another, or
+    /// the best offspring. This is the default.
+    #[default]
+    ReplaceRandom,
+
+    /// Overwrite the least fit offspring.
+    ReplaceWorst,
+
+    /// Add the elite genomes to the offspring, then drop the least fit
+    /// genomes until the generation is back to its size.
+    AppendThenTruncate,
+
+    /// Overwrite distinct random offspring, other than the fittest.
+    ReplaceRandomExcludingBest,
+}
+
 /// Replace a portion of the current generation with elite genomes,
 /// preserving the rest of the generation.
 ///
@@ -21,12 +41,81 @@ where
 {
     let generation_size = generation.len();
     for genome in elite {
-        let generation_index = rand::thread_rng().gen_range(0..generation_size);
+        let generation_index = rng::handle().gen_range(0..generation_size);
         generation[generation_index] = genome;
     }
     generation
 }
 
+/// Put elite genomes into a scored generation of offspring, as a policy
+/// says.
+///
+/// # Parameters
+///
+/// - `policy`: How to put the elite genomes in.
+/// - `offspring`: The scored offspring.
+/// - `elite`: The scored elite genomes, fittest first.
+///
+/// # Returns
+///
+/// The next generation, the same size as the offspring, unless there are no
+/// offspring, in which case it is the elite genomes.
+pub fn ranked<TGenome>(
+    policy: Policy,
+    mut offspring: Vec<CompareRecord<TGenome>>,
+    elite: Vec<CompareRecord<TGenome>>,
+) -> Generation<TGenome>
+where
+    TGenome: Predict + PartialOrd,
+{
+    let unrank = |records: Vec<CompareRecord<TGenome>>| {
+        records
+            .into_iter()
+            .map(|record| record.predict)
+            .collect::<Vec<_>>()
+    };
+    if offspring.is_empty() {
+        return unrank(elite);
+    }
+
+    let by_fitness = |offspring: &[CompareRecord<TGenome>]| {
+        let mut order = (0..offspring.len()).collect::<Vec<_>>();
+        order.sort_by(|&left, &right| {
+            NanSafeCompare::fitness(offspring[left].fitness, offspring[right].fitness)
+        });
+        order
+    };
+    match policy {
+        Policy::ReplaceRandom => genomes(unrank(offspring), unrank(elite)),
+        Policy::ReplaceWorst => {
+            let worst = by_fitness(&offspring).into_iter().rev();
+            for (index, genome) in worst.zip(elite) {
+                offspring[index] = genome;
+            }
+            unrank(offspring)
+        }
+        Policy::AppendThenTruncate => {
+            let size = offspring.len();
+            offspring.extend(elite);
+            let mut keep = vec![false; offspring.len()];
+            for index in by_fitness(&offspring).into_iter().take(size) {
+                keep[index] = true;
+            }
+            let mut keep = keep.into_iter();
+            offspring.retain(|_| keep.next().unwrap_or(false));
+            unrank(offspring)
+        }
+        Policy::ReplaceRandomExcludingBest => {
+            let mut slots = by_fitness(&offspring).split_off(1);
+            slots.shuffle(&mut rng::handle());
+            for (index, genome) in slots.into_iter().zip(elite) {
+                offspring[index] = genome;
+            }
+            unrank(offspring)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,4 +153,63 @@ mod tests {
             None
         );
     }
+
+    fn ranked_records(values: &[f64]) -> Vec<CompareRecord<Predictor>> {
+        values
+            .iter()
+            .map(|&value| CompareRecord {
+                fitness: value,
+                predict: Predictor { value },
+            })
+            .collect()
+    }
+
+    fn values(generation: &[Predictor]) -> Vec<f64> {
+        generation.iter().map(|predictor| predictor.value).collect()
+    }
+
+    #[test]
+    fn test_replace_worst() {
+        let result = ranked(
+            Policy::ReplaceWorst,
+            ranked_records(&[3.0, 1.0, 4.0, 2.0]),
+            ranked_records(&[0.0, 0.5]),
+        );
+
+        assert_eq!(values(&result), vec![0.5, 1.0, 0.0, 2.0]);
+    }
+
+    #[test]
+    fn test_append_then_truncate() {
+        let result = ranked(
+            Policy::AppendThenTruncate,
+            ranked_records(&[3.0, 1.0, 4.0, 2.0]),
+            ranked_records(&[0.0, 5.0]),
+        );
+
+        assert_eq!(values(&result), vec![3.0, 1.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_replace_random_excluding_best() {
+        for _ in 0..20 {
+            let result = ranked(
+                Policy::ReplaceRandomExcludingBest,
+                ranked_records(&[3.0, 1.0, 4.0]),
+                ranked_records(&[0.0, 0.5, 0.7]),
+            );
+
+            let mut result = values(&result);
+            assert!((result[1] - 1.0).abs() < f64::EPSILON);
+            result.sort_by(f64::total_cmp);
+            assert_eq!(result, vec![0.0, 0.5, 1.0]);
+        }
+    }
+
+    #[test]
+    fn test_ranked_without_offspring() {
+        let result = ranked(Policy::ReplaceWorst, vec![], ranked_records(&[1.0]));
+
+        assert_eq!(values(&result), vec![1.0]);
+    }
 }
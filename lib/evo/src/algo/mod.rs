@@ -1,11 +1,17 @@
 mod inject;
 mod run;
+mod selection;
 mod sort;
+mod termination;
 mod tournament;
 mod unrank;
 
 pub use self::{
-    inject::genomes as inject_genomes, sort::generation as sort_generation, tournament::Tournament,
+    inject::{ranked as inject_ranked, Policy as InjectionPolicy},
+    sort::partition as partition_generation,
+    tournament::Tournament,
     unrank::generation as unrank_generation,
 };
-pub use crate::algo::run::Run as Algorithm;
+pub use crate::algo::run::{CheckpointError, Outcome as RunOutcome, Run as Algorithm};
+pub use crate::algo::selection::Selection;
+pub use crate::algo::termination::{Builder as TerminationBuilder, Evolution, Stop, Termination};
@@ -1,22 +1,220 @@
-use super::{inject_genomes, sort_generation, unrank_generation, Tournament};
-use crate::{Breed, BreedManager, CompareRecord, FitnessCalc, Generation, Predict};
+mod checkpoint;
+
+pub use checkpoint::Error as CheckpointError;
+
+use super::selection::{Boltzmann, Lexicase, Selector};
+use super::{
+    inject_ranked, partition_generation, unrank_generation, Evolution, InjectionPolicy, Selection,
+    Stop, Termination, Tournament,
+};
+use crate::adaptive::AdaptiveMutation;
+use crate::executor::{self, Execute, Sequential};
+use crate::speciation::{Speciation, Species};
+use crate::stats::{GenerationStats, History};
+use crate::{
+    rng, Breed, BreedManager, CompareRecord, Distance, Error as FitnessError, Evaluate,
+    FitnessCalc, Generation, NanSafeCompare, Predict, Result, Schedule, Stock,
+};
+use rand::Rng;
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+/// Called after each generation is scored.
+type Observer = Box<dyn FnMut(&GenerationStats) -> ControlFlow<()> + Send>;
+
+/// Measures the diversity of a generation.
+type Diversity<TGenome> = Box<dyn Fn(&[&TGenome]) -> f64 + Send + Sync>;
+
+/// A pair of parents selected to breed one offspring.
+struct Mating<'x, TGenome> {
+    /// The parents.
+    parents: (&'x TGenome, &'x TGenome),
+
+    /// A retired genome to reuse for the offspring, if one is left.
+    spare: Mutex<Option<TGenome>>,
+
+    /// Seeds the random number generator the offspring is bred with.
+    seed: u64,
+}
+
+/// The fitness checks one evolution may make, and the genomes left waiting
+/// for one when they run out.
+struct Budget<TGenome> {
+    /// The number of genomes that may still be scored.
+    remaining: usize,
+
+    /// The number of genomes scored so far.
+    spent: usize,
+
+    /// Genomes left unscored when the budget ran out, in order, to be scored
+    /// when evolution resumes.
+    pending: Vec<TGenome>,
+}
+
+impl<TGenome> Budget<TGenome> {
+    /// Creates a budget.
+    ///
+    /// # Arguments
+    ///
+    /// - `max_evaluations`: The greatest number of genomes to score.
+    ///
+    /// # Returns
+    ///
+    /// The budget, with nothing spent.
+    fn new(max_evaluations: usize) -> Self {
+        Self {
+            remaining: max_evaluations,
+            spent: 0,
+            pending: vec![],
+        }
+    }
+
+    /// Creates a budget that never runs out.
+    ///
+    /// # Returns
+    ///
+    /// The budget, with nothing spent.
+    fn unlimited() -> Self {
+        Self::new(usize::MAX)
+    }
+
+    /// Checks whether the budget is spent.
+    ///
+    /// # Returns
+    ///
+    /// True if no more genomes may be scored.
+    fn exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Counts genomes about to be scored, as many as the budget allows.
+    ///
+    /// # Arguments
+    ///
+    /// - `count`: The number of genomes to score.
+    ///
+    /// # Returns
+    ///
+    /// The number of genomes that may be scored, from the first.
+    fn reserve(&mut self, count: usize) -> usize {
+        let count = count.min(self.remaining);
+        self.remaining -= count;
+        self.spent += count;
+        count
+    }
+
+    /// Pairs genomes with their fitness, keeping those over the budget in
+    /// the queue and dropping those that could not be scored.
+    ///
+    /// # Arguments
+    ///
+    /// - `genomes`: The genomes.
+    /// - `fitness`: The fitness of each genome.
+    ///
+    /// # Returns
+    ///
+    /// The scored genomes, in order.
+    fn rank(
+        &mut self,
+        genomes: Vec<TGenome>,
+        fitness: Vec<Result<f64>>,
+    ) -> Vec<CompareRecord<TGenome>>
+    where
+        TGenome: Predict + PartialOrd,
+    {
+        Iterator::zip(genomes.into_iter(), fitness)
+            .filter_map(|(predict, fitness)| match fitness {
+                Ok(fitness) => Some(CompareRecord { fitness, predict }),
+                Err(FitnessError::OverBudget) => {
+                    self.pending.push(predict);
+                    None
+                }
+                Err(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// Generates genomes to replace duplicate offspring.
+type Stocker<TGenome> = Box<dyn Stock<TGenome> + Send + Sync>;
+
+/// The result of running one generation.
+pub struct Outcome<TGenome>
+where
+    TGenome: Predict + PartialOrd,
+{
+    /// The next generation, bred from the one that was run.
+    pub generation: Generation<TGenome>,
+
+    /// The elite genomes of the generation that was run, fittest first, with
+    /// their fitness. They were also put into the next generation.
+    pub elite: Vec<CompareRecord<TGenome>>,
+
+    /// The fittest genome of the generation that was run, with its fitness,
+    /// or `None` if no genome could be scored.
+    pub best: Option<CompareRecord<TGenome>>,
+
+    /// The statistics of the generation that was run, or `None` if no
+    /// genome could be scored.
+    pub stats: Option<GenerationStats>,
+
+    /// The number of genomes that could not be scored, and were left out of
+    /// breeding.
+    pub discarded: usize,
+}
 
 /// Runs the genetic algorithm.
 pub struct Run<TGenome, TBreeder>
 where
-    TGenome: Predict + PartialOrd,
-    TBreeder: Breed<TGenome>,
+    TGenome: Predict + PartialOrd + Send + Sync,
+    TBreeder: Breed<TGenome> + Sync,
 {
     breeder: BreedManager<TGenome, TBreeder>,
     fitness_calc: FitnessCalc,
+    evaluator: Option<Box<dyn Evaluate<TGenome> + Send + Sync>>,
+    executor: Box<dyn Execute + Send + Sync>,
     elitism: usize,
+    elitism_schedule: Option<Schedule>,
     tournament_size: usize,
+    tournament_size_schedule: Option<Schedule>,
+    tournament_win_probability: f64,
+    selection: Selection,
+    temperature: Schedule,
+    mate_attempts: usize,
+    seed: Option<u64>,
+    observers: Mutex<Vec<Observer>>,
+    diversity: Option<Diversity<TGenome>>,
+    speciation: Option<Speciation<TGenome>>,
+    adaptive_mutation: Option<AdaptiveMutation<TGenome>>,
+    dedupe: Option<Stocker<TGenome>>,
+    injection: InjectionPolicy,
+
+    /// Genomes retired from the last generation, whose allocations are reused
+    /// for the offspring of the next one.
+    spares: Mutex<Vec<TGenome>>,
+
+    /// The number of generations run so far.
+    generation: AtomicUsize,
+
+    /// When the first generation started.
+    started: OnceLock<Instant>,
+
+    /// When the last generation was scored.
+    scored: Mutex<Option<Instant>>,
+
+    /// The number of genomes scored so far.
+    evaluations: AtomicUsize,
+
+    /// The statistics of every generation so far.
+    history: Mutex<History>,
 }
 
 impl<TGenome, TBreeder> Run<TGenome, TBreeder>
 where
-    TGenome: Predict + PartialOrd,
-    TBreeder: Breed<TGenome>,
+    TGenome: Predict + PartialOrd + Send + Sync,
+    TBreeder: Breed<TGenome> + Sync,
 {
     /// Creates a new builder.
     ///
@@ -24,7 +222,7 @@ where
     ///
     /// ```
     /// use rand::Rng;
-    /// use evo::{Breed, Compare, CompareRecord, FitnessCalc, Predict, EvoAlgorithm, TrainingRecord};
+    /// use evo::{Breed, FitnessCalc, Predict, EvoAlgorithm, TrainingRecord};
     ///
     /// #[derive(Debug, PartialEq, PartialOrd, Clone)]
     /// struct Genome {
@@ -37,18 +235,6 @@ where
     ///     }
     /// }
     ///
-    /// struct Comparator;
-    ///
-    /// impl Compare<Genome> for Comparator {
-    ///     fn compare(
-    ///         &self,
-    ///         left: &CompareRecord<&Genome>,
-    ///         right: &CompareRecord<&Genome>,
-    ///     ) -> std::cmp::Ordering {
-    ///         left.fitness.partial_cmp(&right.fitness).unwrap()
-    ///     }
-    /// }
-    ///
     /// struct Breeder {
     ///     mut_range: f64,
     /// }
@@ -61,7 +247,7 @@ where
     ///     }
     ///
     ///     fn mutate(&self, mut genome: Genome) -> Genome {
-    ///         genome.value += rand::thread_rng().gen_range(-1.0..=1.0) * self.mut_range;
+    ///         genome.value += evo::rng::handle().gen_range(-1.0..=1.0) * self.mut_range;
     ///         genome
     ///     }
     /// }
@@ -70,11 +256,11 @@ where
     ///     .add_training_record(TrainingRecord {
     ///         input: vec![0.0, 0.0],
     ///         output: vec![0.0],
+    ///         weight: 1.0,
     ///     })
     ///     .build();
     ///
     /// let breeder = Breeder { mut_range: 0.1 };
-    /// let comparator = Comparator;
     /// let algo = EvoAlgorithm::builder()
     ///     .breeder(breeder)
     ///     .fitness_calc(fitness_calc)
@@ -87,11 +273,17 @@ where
 
     /// Runs the genetic algorithm.
     ///
+    /// Training data changes staged through [`FitnessCalc::updates`] are
+    /// applied first, so the whole generation is scored against the same data.
+    /// The fitness calculator is also told which generation this is, so it can
+    /// follow its curriculum. If a seed is set, every random choice made while
+    /// breeding is drawn from a generator seeded for this generation.
+    ///
     /// # Examples
     ///
     /// ```
     /// use rand::Rng;
-    /// use evo::{Breed, Compare, CompareRecord, FitnessCalc, Predict, EvoAlgorithm, TrainingRecord};
+    /// use evo::{Breed, FitnessCalc, Predict, EvoAlgorithm, TrainingRecord};
     ///
     /// #[derive(Debug, PartialEq, PartialOrd, Clone)]
     /// struct Genome {
@@ -104,18 +296,6 @@ where
     ///     }
     /// }
     ///
-    /// struct Comparator;
-    ///
-    /// impl Compare<Genome> for Comparator {
-    ///     fn compare(
-    ///         &self,
-    ///         left: &CompareRecord<&Genome>,
-    ///         right: &CompareRecord<&Genome>,
-    ///     ) -> std::cmp::Ordering {
-    ///         left.fitness.partial_cmp(&right.fitness).unwrap()
-    ///     }
-    /// }
-    ///
     /// struct Breeder {
     ///     mut_range: f64,
     /// }
@@ -128,7 +308,7 @@ where
     ///     }
     ///
     ///     fn mutate(&self, mut genome: Genome) -> Genome {
-    ///         genome.value += rand::thread_rng().gen_range(-1.0..=1.0) * self.mut_range;
+    ///         genome.value += evo::rng::handle().gen_range(-1.0..=1.0) * self.mut_range;
     ///         genome
     ///     }
     /// }
@@ -137,11 +317,11 @@ where
     ///     .add_training_record(TrainingRecord {
     ///         input: vec![0.0, 0.0],
     ///         output: vec![0.0],
+    ///         weight: 1.0,
     ///     })
     ///     .build();
     ///
     /// let breeder = Breeder { mut_range: 0.1 };
-    /// let comparator = Comparator;
     /// let algo = EvoAlgorithm::builder()
     ///     .breeder(breeder)
     ///     .fitness_calc(fitness_calc)
@@ -154,50 +334,534 @@ where
     ///     Genome { value: 3.0 },
     /// ];
     ///
-    /// let actual = algo.run(generation.clone());
+    /// let outcome = algo.run(generation.clone());
     ///
-    /// assert_eq!(actual.len(), generation.len());
-    /// assert_ne!(actual, generation);
+    /// assert_eq!(outcome.generation.len(), generation.len());
+    /// assert_ne!(outcome.generation, generation);
+    /// assert_eq!(outcome.best.unwrap().predict, Genome { value: 1.0 });
+    /// assert_eq!(outcome.elite.len(), 1);
     /// ```
-    pub fn run(&self, generation: Generation<TGenome>) -> Generation<TGenome> {
+    pub fn run(&self, generation: Generation<TGenome>) -> Outcome<TGenome>
+    where
+        TGenome: Clone,
+    {
+        let clone = |record: &CompareRecord<TGenome>| CompareRecord {
+            fitness: record.fitness,
+            predict: record.predict.clone(),
+        };
+
+        let index = self.begin_generation();
+        let size = generation.len();
         let ranked_generation = self.rank_generation(generation);
+        let discarded = size - ranked_generation.len();
+        let (stats, _) = self.record(index, &ranked_generation);
+        let best = ranked_generation
+            .iter()
+            .min_by(|left, right| NanSafeCompare::fitness(left.fitness, right.fitness))
+            .map(clone);
+        let mut elite = vec![];
+        let generation = self.breed_generation_with(
+            index,
+            ranked_generation,
+            &mut Budget::unlimited(),
+            |fittest| {
+                elite = fittest.iter().map(clone).collect();
+            },
+        );
+        Outcome {
+            generation,
+            elite,
+            best,
+            stats,
+            discarded,
+        }
+    }
+
+    /// Runs the genetic algorithm until a termination criterion is met.
+    ///
+    /// Each generation is scored once, both to check the criteria and to
+    /// breed the next generation from.
+    ///
+    /// # Arguments
+    ///
+    /// - `initial`: The generation to start from.
+    /// - `termination`: When to stop.
+    ///
+    /// # Returns
+    ///
+    /// The last generation, the fittest genome seen in any generation, and
+    /// the best fitness of each generation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{Breed, EvoAlgorithm, FitnessCalc, Predict, Stop, Termination, TrainingRecord};
+    ///
+    /// #[derive(Clone, Debug, PartialEq, PartialOrd)]
+    /// struct Genome {
+    ///     value: f64,
+    /// }
+    ///
+    /// impl Predict for Genome {
+    ///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+    ///         vec![self.value]
+    ///     }
+    /// }
+    ///
+    /// struct Breeder;
+    ///
+    /// impl Breed<Genome> for Breeder {
+    ///     fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+    ///         Genome {
+    ///             value: (pair.0.value + pair.1.value) / 2.0,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let fitness_calc = FitnessCalc::builder()
+    ///     .add_training_record(TrainingRecord {
+    ///         input: vec![0.0],
+    ///         output: vec![1.0],
+    ///         weight: 1.0,
+    ///     })
+    ///     .build();
+    /// let algo = EvoAlgorithm::builder()
+    ///     .breeder(Breeder)
+    ///     .fitness_calc(fitness_calc)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let initial = (0..10).map(|value| Genome { value: f64::from(value) }).collect();
+    /// let termination = Termination::builder().generations(20).stagnation(5).build();
+    /// let evolution = algo.evolve(initial, termination);
+    ///
+    /// assert_eq!(evolution.best.unwrap().predict, Genome { value: 1.0 });
+    /// assert_eq!(evolution.stop, Stop::Stagnation);
+    /// ```
+    pub fn evolve(
+        &self,
+        initial: Generation<TGenome>,
+        termination: Termination,
+    ) -> Evolution<TGenome>
+    where
+        TGenome: Clone,
+    {
+        self.evolve_within(initial, termination, Budget::unlimited())
+    }
+
+    /// Runs the genetic algorithm until a termination criterion is met, or
+    /// until a number of genomes have been scored.
+    ///
+    /// Every fitness check counts against the budget, including those of
+    /// offspring while breeding and those lexicase selection makes, so
+    /// algorithms can be compared by the work they do rather than by their
+    /// generations. Lexicase selection gives way to tournament selection
+    /// when the budget cannot cover a generation's checks. The budget is never
+    /// overspent: once it runs out, the remaining genomes go unscored and
+    /// evolution stops with [`Stop::Budget`]. Unscored genomes are kept in
+    /// the last generation, so evolving it with another budget resumes the
+    /// run without losing them.
+    ///
+    /// The budget belongs to this call alone, so runs sharing the algorithm
+    /// on other threads neither spend it nor are limited by it.
+    ///
+    /// # Arguments
+    ///
+    /// - `initial`: The generation to start from.
+    /// - `termination`: When to stop, if the budget lasts.
+    /// - `max_evaluations`: The greatest number of genomes to score.
+    ///
+    /// # Returns
+    ///
+    /// The same as [`Run::evolve`], with the number of genomes scored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{Breed, EvoAlgorithm, FitnessCalc, Predict, Stop, Termination, TrainingRecord};
+    ///
+    /// #[derive(Clone, Debug, PartialEq, PartialOrd)]
+    /// struct Genome {
+    ///     value: f64,
+    /// }
+    ///
+    /// impl Predict for Genome {
+    ///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+    ///         vec![self.value]
+    ///     }
+    /// }
+    ///
+    /// struct Breeder;
+    ///
+    /// impl Breed<Genome> for Breeder {
+    ///     fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+    ///         Genome {
+    ///             value: (pair.0.value + pair.1.value) / 2.0,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let fitness_calc = FitnessCalc::builder()
+    ///     .add_training_record(TrainingRecord {
+    ///         input: vec![0.0],
+    ///         output: vec![1.0],
+    ///         weight: 1.0,
+    ///     })
+    ///     .build();
+    /// let algo = EvoAlgorithm::builder()
+    ///     .breeder(Breeder)
+    ///     .fitness_calc(fitness_calc)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let initial = (0..10).map(|value| Genome { value: f64::from(value) }).collect();
+    /// let evolution = algo.evolve_with_budget(initial, Termination::default(), 100);
+    ///
+    /// assert_eq!(evolution.stop, Stop::Budget);
+    /// assert_eq!(evolution.evaluations, 100);
+    /// ```
+    pub fn evolve_with_budget(
+        &self,
+        initial: Generation<TGenome>,
+        termination: Termination,
+        max_evaluations: usize,
+    ) -> Evolution<TGenome>
+    where
+        TGenome: Clone,
+    {
+        self.evolve_within(initial, termination, Budget::new(max_evaluations))
+    }
+
+    /// Runs the genetic algorithm until a termination criterion is met, or
+    /// until a budget is spent.
+    ///
+    /// # Arguments
+    ///
+    /// - `initial`: The generation to start from.
+    /// - `termination`: When to stop, if the budget lasts.
+    /// - `budget`: The fitness checks evolution may make.
+    ///
+    /// # Returns
+    ///
+    /// The last generation, with any genomes left unscored, the fittest
+    /// genome seen in any generation, and the best fitness of each
+    /// generation.
+    fn evolve_within(
+        &self,
+        initial: Generation<TGenome>,
+        termination: Termination,
+        mut budget: Budget<TGenome>,
+    ) -> Evolution<TGenome>
+    where
+        TGenome: Clone,
+    {
+        let started = Instant::now();
+        let mut generation = initial;
+        let mut best: Option<CompareRecord<TGenome>> = None;
+        let mut history = History::default();
+        let mut stagnant = 0;
+        let mut best_validation: Option<f64> = None;
+        let mut validation_stagnant = 0;
+        loop {
+            if budget.exhausted() {
+                return Evolution {
+                    generation,
+                    best,
+                    history,
+                    stop: Stop::Budget,
+                    evaluations: budget.spent,
+                };
+            }
+
+            let index = self.begin_generation();
+            let fitness = self.evaluate(&generation, &mut budget);
+            let ranked_generation = budget.rank(generation, fitness);
+            let (fittest, (stats, observed)) = (
+                ranked_generation
+                    .iter()
+                    .min_by(|left, right| NanSafeCompare::fitness(left.fitness, right.fitness)),
+                self.record(index, &ranked_generation),
+            );
+            let (Some(fittest), Some(stats)) = (fittest, stats) else {
+                let mut generation = unrank_generation(ranked_generation);
+                generation.append(&mut budget.pending);
+                return Evolution {
+                    generation,
+                    best,
+                    history,
+                    stop: Stop::Extinct,
+                    evaluations: budget.spent,
+                };
+            };
+
+            history.push(stats);
+            if best
+                .as_ref()
+                .is_none_or(|best| fittest.fitness < best.fitness)
+            {
+                best = Some(CompareRecord {
+                    fitness: fittest.fitness,
+                    predict: fittest.predict.clone(),
+                });
+                stagnant = 0;
+            } else {
+                stagnant += 1;
+            }
+            if let Some(validation) = stats.validation {
+                if best_validation.is_none_or(|best| validation < best) {
+                    best_validation = Some(validation);
+                    validation_stagnant = 0;
+                } else {
+                    validation_stagnant += 1;
+                }
+            }
+
+            let stop = match observed {
+                ControlFlow::Break(()) => Some(Stop::Observer),
+                ControlFlow::Continue(()) => termination
+                    .check(
+                        stats.best,
+                        history.len(),
+                        stagnant,
+                        validation_stagnant,
+                        started.elapsed(),
+                    )
+                    .or_else(|| budget.exhausted().then_some(Stop::Budget)),
+            };
+            if let Some(stop) = stop {
+                // Genomes the budget ran out before are kept, so evolving
+                // the generation again carries on where this stopped.
+                let mut generation = unrank_generation(ranked_generation);
+                generation.append(&mut budget.pending);
+                return Evolution {
+                    generation,
+                    best,
+                    history,
+                    stop,
+                    evaluations: budget.spent,
+                };
+            }
+            generation = self.breed_generation_with(index, ranked_generation, &mut budget, |_| {});
+        }
+    }
+
+    /// Gets the number of genomes scored so far.
+    ///
+    /// # Returns
+    ///
+    /// The number of fitness checks.
+    pub(crate) fn evaluations(&self) -> usize {
+        self.evaluations.load(Ordering::Relaxed)
+    }
+
+    /// Gets the statistics of every generation run so far, e.g. to plot a
+    /// training curve with [`History::to_csv`].
+    ///
+    /// # Returns
+    ///
+    /// The statistics, in order.
+    pub fn history(&self) -> History {
+        self.history
+            .lock()
+            .map(|history| history.clone())
+            .unwrap_or_default()
+    }
+
+    /// Prepares the fitness calculator for the next generation.
+    ///
+    /// # Returns
+    ///
+    /// The number of generations run before this one.
+    pub(crate) fn begin_generation(&self) -> usize {
+        self.started.get_or_init(Instant::now);
+        self.fitness_calc.apply_updates();
+        let index = self.generation.fetch_add(1, Ordering::Relaxed);
+        self.fitness_calc.set_generation(index);
+        index
+    }
+
+    /// Records the statistics of a scored generation in the history, and
+    /// reports them to the observers.
+    ///
+    /// # Arguments
+    ///
+    /// - `index`: The number of generations run before this one.
+    /// - `ranked_generation`: The scored generation.
+    ///
+    /// # Returns
+    ///
+    /// The statistics, or `None` if no genome was scored, and a break if any
+    /// observer asked to stop.
+    pub(crate) fn record(
+        &self,
+        index: usize,
+        ranked_generation: &[CompareRecord<TGenome>],
+    ) -> (Option<GenerationStats>, ControlFlow<()>) {
+        let now = Instant::now();
+        let started = *self.started.get_or_init(|| now);
+        let previous = self
+            .scored
+            .lock()
+            .ok()
+            .and_then(|mut scored| scored.replace(now));
+        let Some(mut stats) = GenerationStats::new(index, ranked_generation) else {
+            return (None, ControlFlow::Continue(()));
+        };
+        stats.validation = ranked_generation
+            .iter()
+            .min_by(|left, right| NanSafeCompare::fitness(left.fitness, right.fitness))
+            .and_then(|fittest| self.fitness_calc.validate(&fittest.predict)?.ok());
+        stats.diversity = self.diversity.as_ref().map(|measure| {
+            let genomes = ranked_generation
+                .iter()
+                .map(|record| &record.predict)
+                .collect::<Vec<_>>();
+            measure(&genomes)
+        });
+        stats.evaluations = self.evaluations.load(Ordering::Relaxed);
+        stats.duration = now.duration_since(previous.unwrap_or(started));
+        stats.elapsed = now.duration_since(started);
+        if let Ok(mut history) = self.history.lock() {
+            history.push(stats);
+        }
 
-        let next_generation = self.breed_generation(&ranked_generation);
-        let elite = self.partition_elite(ranked_generation);
+        // Every observer sees the generation, even after one asks to stop.
+        let mut flow = ControlFlow::Continue(());
+        if let Ok(mut observers) = self.observers.lock() {
+            for observer in observers.iter_mut() {
+                if observer(&stats).is_break() {
+                    flow = ControlFlow::Break(());
+                }
+            }
+        }
+        (Some(stats), flow)
+    }
 
-        inject_genomes(next_generation, elite)
+    /// Breeds the next generation from a ranked one, keeping its elite.
+    ///
+    /// # Arguments
+    ///
+    /// - `index`: The number of generations run before this one.
+    /// - `ranked_generation`: The ranked generation to breed.
+    ///
+    /// # Returns
+    ///
+    /// The next generation.
+    pub(crate) fn breed_generation(
+        &self,
+        index: usize,
+        ranked_generation: Vec<CompareRecord<TGenome>>,
+    ) -> Generation<TGenome> {
+        self.breed_generation_with(index, ranked_generation, &mut Budget::unlimited(), |_| {})
     }
 
-    /// Breeds a new generation of genomes.
+    /// Breeds the next generation from a ranked one, keeping its elite, and
+    /// shows the elite to a callback before they join the next generation.
     ///
     /// # Arguments
     ///
-    /// - `parent_generation`: The parent generation to breed.
+    /// - `index`: The number of generations run before this one.
+    /// - `ranked_generation`: The ranked generation to breed.
+    /// - `budget`: The fitness checks left for scoring offspring.
+    /// - `on_elite`: Receives the elite genomes, fittest first.
     ///
     /// # Returns
     ///
-    /// A new generation of genomes.
-    fn breed_generation(&self, parent_generation: &[CompareRecord<TGenome>]) -> Vec<TGenome> {
-        let next_generation = self.new_generation(parent_generation);
-        unrank_generation(next_generation)
+    /// The next generation, ending with any offspring the budget ran out
+    /// before.
+    fn breed_generation_with(
+        &self,
+        index: usize,
+        ranked_generation: Vec<CompareRecord<TGenome>>,
+        budget: &mut Budget<TGenome>,
+        on_elite: impl FnOnce(&[CompareRecord<TGenome>]),
+    ) -> Generation<TGenome> {
+        let breed = || {
+            self.breeder.set_generation(index);
+            self.adapt_mutation(&ranked_generation);
+            let size = ranked_generation.len();
+            let (ranked_generation, species) = self.speciate(ranked_generation);
+            let next_generation = self.new_generation(index, &ranked_generation, &species, budget);
+            let elite = self.partition_elite(index, ranked_generation);
+            on_elite(&elite);
+
+            let mut next_generation = inject_ranked(self.injection, next_generation, elite);
+            if !budget.pending.is_empty() {
+                next_generation.append(&mut budget.pending);
+                next_generation.truncate(size);
+            }
+            next_generation
+        };
+        match self.seed {
+            Some(seed) => rng::seeded(rng::derive(seed, index as u64), breed),
+            None => breed(),
+        }
     }
 
-    /// Partitions the elite genomes from the generation.
+    /// Sets the breeder's mutation pressure from the diversity of a ranked
+    /// generation, if adaptive mutation is enabled.
     ///
     /// # Arguments
     ///
-    /// - `ranked_generation`: The ranked generation to partition.
+    /// - `ranked_generation`: The ranked generation about to breed.
+    fn adapt_mutation(&self, ranked_generation: &[CompareRecord<TGenome>]) {
+        if let Some(adaptive) = &self.adaptive_mutation {
+            let diversity = adaptive.diversity(ranked_generation);
+            self.breeder.set_pressure(adaptive.pressure(diversity));
+        }
+    }
+
+    /// Sorts a ranked generation into species, if speciation is enabled, and
+    /// shares out the offspring of the next generation between them.
+    ///
+    /// # Arguments
+    ///
+    /// - `ranked_generation`: The ranked generation to sort.
     ///
     /// # Returns
     ///
-    /// The elite genomes.
-    fn partition_elite(&self, ranked_generation: Vec<CompareRecord<TGenome>>) -> Vec<TGenome> {
-        let sorted_generation = sort_generation(ranked_generation);
-        let sorted_generation = unrank_generation(sorted_generation);
+    /// The generation, with each species' members next to each other, and the
+    /// species. Without speciation, the whole generation is one species.
+    fn speciate(
+        &self,
+        ranked_generation: Vec<CompareRecord<TGenome>>,
+    ) -> (Vec<CompareRecord<TGenome>>, Vec<Species>) {
+        let gen_size = ranked_generation.len();
+        match &self.speciation {
+            Some(speciation) => speciation.speciate(ranked_generation, gen_size),
+            None => (
+                ranked_generation,
+                vec![Species {
+                    members: 0..gen_size,
+                    offspring: gen_size,
+                }],
+            ),
+        }
+    }
 
-        let elitism = std::cmp::min(self.elitism, sorted_generation.len());
-        let mut elite = sorted_generation;
-        elite.truncate(elitism);
+    /// Partitions the elite genomes from the generation. The rest are kept as
+    /// spares for breeding the next generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `index`: The number of generations run before this one.
+    /// - `ranked_generation`: The ranked generation to partition.
+    ///
+    /// # Returns
+    ///
+    /// The elite genomes, fittest first.
+    fn partition_elite(
+        &self,
+        index: usize,
+        ranked_generation: Vec<CompareRecord<TGenome>>,
+    ) -> Vec<CompareRecord<TGenome>> {
+        let elitism = self
+            .elitism_schedule
+            .map_or(self.elitism, |schedule| schedule.count(index));
+        let (elite, retired) = partition_generation(ranked_generation, elitism);
+        if let Ok(mut spares) = self.spares.lock() {
+            spares.extend(retired.into_iter().map(|record| record.predict));
+        }
         elite
     }
 
@@ -210,45 +874,248 @@ where
     /// # Returns
     ///
     /// The ranked generation.
-    fn rank_generation(&self, generation: Generation<TGenome>) -> Vec<CompareRecord<TGenome>> {
-        let fitness_calc = &self.fitness_calc;
+    pub(crate) fn rank_generation(
+        &self,
+        generation: Generation<TGenome>,
+    ) -> Vec<CompareRecord<TGenome>> {
+        let mut budget = Budget::unlimited();
+        let fitness = self.evaluate(&generation, &mut budget);
+        budget.rank(generation, fitness)
+    }
 
-        generation
-            .into_iter()
-            .filter_map(|predict| fitness_calc.create_compare_record(predict).ok())
-            .collect::<Vec<_>>()
+    /// Calculates the fitness of every genome in a generation, with the
+    /// evaluator if one is set, or else the fitness calculator. Fitness checks
+    /// are scheduled by the executor.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation`: The genomes to score.
+    /// - `budget`: The fitness checks left.
+    ///
+    /// # Returns
+    ///
+    /// One fitness per genome. Genomes beyond the budget are not scored.
+    fn evaluate(&self, generation: &[TGenome], budget: &mut Budget<TGenome>) -> Vec<Result<f64>> {
+        let (scored, unscored) = generation.split_at(budget.reserve(generation.len()));
+        self.evaluations.fetch_add(scored.len(), Ordering::Relaxed);
+        let fitness_calc = &self.fitness_calc;
+        let mut fitness = match &self.evaluator {
+            Some(evaluator) => evaluator.evaluate(scored),
+            None => executor::map(&*self.executor, scored, |genome| fitness_calc.check(genome))
+                .into_iter()
+                .map(|fitness| fitness.unwrap_or(Err(FitnessError::Unscored)))
+                .collect(),
+        };
+        fitness.extend(unscored.iter().map(|_| Err(FitnessError::OverBudget)));
+        fitness
     }
 
     /// Creates a new generation of genomes.
     ///
+    /// Each species breeds its share of the offspring from its own members.
+    /// Offspring are scored in batches, and those whose fitness cannot be
+    /// calculated are discarded, so every genome in the new generation can be
+    /// ranked. Breeding stops when the budget runs out, and offspring left
+    /// unscored wait in its queue. A species that breeds no offspring that
+    /// can be scored in a whole batch is skipped, so the new generation can
+    /// be smaller than the parent generation.
+    ///
     /// # Arguments
     ///
+    /// - `index`: The number of generations run before the parent generation.
     /// - `generation`: The parent generation to breed.
+    /// - `species`: The species of the parent generation.
+    /// - `budget`: The fitness checks left for scoring offspring.
     ///
     /// # Returns
     ///
-    /// A new generation of genomes.
-    fn new_generation(&self, generation: &[CompareRecord<TGenome>]) -> Vec<CompareRecord<TGenome>> {
-        let gen_size = generation.len();
-        let mut next_generation = Vec::with_capacity(gen_size);
-        let tournament = Tournament::new(self.tournament_size);
+    /// A new generation of scored genomes.
+    fn new_generation(
+        &self,
+        index: usize,
+        generation: &[CompareRecord<TGenome>],
+        species: &[Species],
+        budget: &mut Budget<TGenome>,
+    ) -> Vec<CompareRecord<TGenome>> {
+        let mut next_generation = Vec::with_capacity(generation.len());
+        let mut spares = self
+            .spares
+            .lock()
+            .map(|mut spares| std::mem::take(&mut *spares))
+            .unwrap_or_default();
 
-        while next_generation.len() < gen_size {
-            let left = tournament.select(generation);
-            let right = tournament.select(generation);
-            let (Some(left), Some(right)) = (left, right) else {
+        for species in species {
+            let members = &generation[species.members.clone()];
+            if members.is_empty() {
                 continue;
-            };
-
-            let child = self.breeder.breed(&left.predict, &right.predict);
-            if let Ok(fitness) = self.fitness_calc.check(&child) {
-                next_generation.push(CompareRecord {
-                    fitness,
-                    predict: child,
-                });
             }
-        }
-        next_generation
+            let selector = self.selector(index, members, budget);
+            let size = next_generation.len() + species.offspring;
+            while next_generation.len() < size && !budget.exhausted() {
+                let bred = next_generation.len();
+                // Parents are selected here, so the random draws happen in the
+                // same order whichever executor breeds them.
+                let mut matings = Vec::with_capacity(size - next_generation.len());
+                for _ in next_generation.len()..size {
+                    let Some(left) = selector.select(members) else {
+                        continue;
+                    };
+                    let Some(right) = self.select_mate(&selector, members, left) else {
+                        continue;
+                    };
+
+                    matings.push(Mating {
+                        parents: (&left.predict, &right.predict),
+                        spare: Mutex::new(spares.pop()),
+                        seed: rng::handle().gen(),
+                    });
+                }
+
+                let offspring = self.dedupe(&next_generation, self.breed(&matings));
+                let fitness = self.evaluate(&offspring, budget);
+                next_generation.extend(budget.rank(offspring, fitness));
+
+                // Give up on the species if a whole batch adds nothing, e.g.
+                // when no parents can be selected or no offspring is fit,
+                // rather than breed forever.
+                if next_generation.len() == bred {
+                    break;
+                }
+            }
+        }
+        next_generation
+    }
+
+    /// Breeds offspring from selected parents. Breeding is scheduled by the
+    /// executor, and each mating draws from its own seeded random number
+    /// generator, so the offspring are the same whichever executor runs it.
+    ///
+    /// # Arguments
+    ///
+    /// - `matings`: The parents to breed.
+    ///
+    /// # Returns
+    ///
+    /// The offspring, in order, without any the executor did not breed.
+    fn breed(&self, matings: &[Mating<TGenome>]) -> Vec<TGenome> {
+        let breeder = &self.breeder;
+        executor::map(&*self.executor, matings, |mating| {
+            let (left, right) = mating.parents;
+            let spare = mating.spare.lock().ok().and_then(|mut spare| spare.take());
+            rng::seeded(mating.seed, || match spare {
+                Some(spare) => breeder.breed_into(left, right, spare),
+                None => breeder.breed(left, right),
+            })
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Replaces offspring that duplicate another offspring of the same
+    /// generation with freshly stocked genomes, if deduplication is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// - `bred`: The offspring already in the next generation.
+    /// - `offspring`: The offspring to check.
+    ///
+    /// # Returns
+    ///
+    /// The offspring, with each duplicate replaced.
+    fn dedupe(&self, bred: &[CompareRecord<TGenome>], offspring: Vec<TGenome>) -> Vec<TGenome> {
+        let Some(stock) = &self.dedupe else {
+            return offspring;
+        };
+
+        let mut unique: Vec<TGenome> = Vec::with_capacity(offspring.len());
+        for child in offspring {
+            let duplicate = bred
+                .iter()
+                .map(|record| &record.predict)
+                .chain(&unique)
+                .any(|genome| *genome == child);
+            unique.push(if duplicate { stock.generate() } else { child });
+        }
+        unique
+    }
+
+    /// Prepares to select parents from a generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `index`: The number of generations run before the parent generation.
+    /// - `generation`: The parent generation to select from.
+    /// - `budget`: The fitness checks left. Lexicase selection checks every
+    ///   parent again, and falls back to tournament selection if the budget
+    ///   cannot cover that.
+    ///
+    /// # Returns
+    ///
+    /// The selector for the configured selection.
+    fn selector(
+        &self,
+        index: usize,
+        generation: &[CompareRecord<TGenome>],
+        budget: &mut Budget<TGenome>,
+    ) -> Selector {
+        match self.selection {
+            Selection::Lexicase if budget.remaining >= generation.len() => {
+                budget.reserve(generation.len());
+                self.evaluations
+                    .fetch_add(generation.len(), Ordering::Relaxed);
+                let fitness_calc = &self.fitness_calc;
+                let errors = executor::map(&*self.executor, generation, |record| {
+                    fitness_calc.errors(&record.predict).ok()
+                });
+                Selector::Lexicase(Lexicase::new(
+                    errors.into_iter().map(Option::flatten).collect(),
+                ))
+            }
+            Selection::Tournament | Selection::Lexicase => Selector::Tournament(
+                Tournament::new(
+                    self.tournament_size_schedule
+                        .map_or(self.tournament_size, |schedule| schedule.count(index)),
+                )
+                .with_win_probability(self.tournament_win_probability),
+            ),
+            Selection::Boltzmann => {
+                Selector::Boltzmann(Boltzmann::new(generation, self.temperature.value(index)))
+            }
+        }
+    }
+
+    /// Selects a mate for a genome, preferring mates the breeder accepts.
+    ///
+    /// # Arguments
+    ///
+    /// - `selector`: The selector to select with.
+    /// - `generation`: The parent generation to select from.
+    /// - `partner`: The genome that needs a mate.
+    ///
+    /// # Returns
+    ///
+    /// The first compatible mate, or the last candidate if no compatible mate
+    /// was found within the allowed number of attempts.
+    fn select_mate<'x>(
+        &self,
+        selector: &Selector,
+        generation: &'x [CompareRecord<TGenome>],
+        partner: &CompareRecord<TGenome>,
+    ) -> Option<&'x CompareRecord<TGenome>> {
+        let mut mate = None;
+        for _ in 0..usize::max(self.mate_attempts, 1) {
+            mate = selector.select(generation);
+            if let Some(candidate) = mate {
+                if self
+                    .breeder
+                    .compatible(&partner.predict, &candidate.predict)
+                {
+                    break;
+                }
+            }
+        }
+        mate
     }
 }
 
@@ -258,7 +1125,7 @@ where
 ///
 /// ```
 /// use rand::Rng;
-/// use evo::{Breed, Compare, CompareRecord, FitnessCalc, Predict, EvoAlgorithm, TrainingRecord};
+/// use evo::{Breed, FitnessCalc, Predict, EvoAlgorithm, TrainingRecord};
 ///
 /// #[derive(Debug, PartialEq, PartialOrd, Clone)]
 /// struct Genome {
@@ -271,18 +1138,6 @@ where
 ///     }
 /// }
 ///
-/// struct Comparator;
-///
-/// impl Compare<Genome> for Comparator {
-///     fn compare(
-///         &self,
-///         left: &CompareRecord<&Genome>,
-///         right: &CompareRecord<&Genome>,
-///     ) -> std::cmp::Ordering {
-///         left.fitness.partial_cmp(&right.fitness).unwrap()
-///     }
-/// }
-///
 /// struct Breeder {
 ///     mut_range: f64,
 /// }
@@ -295,7 +1150,7 @@ where
 ///     }
 ///
 ///     fn mutate(&self, mut genome: Genome) -> Genome {
-///         genome.value += rand::thread_rng().gen_range(-1.0..=1.0) * self.mut_range;
+///         genome.value += evo::rng::handle().gen_range(-1.0..=1.0) * self.mut_range;
 ///         genome
 ///     }
 /// }
@@ -304,11 +1159,11 @@ where
 ///     .add_training_record(TrainingRecord {
 ///         input: vec![0.0, 0.0],
 ///         output: vec![0.0],
+///         weight: 1.0,
 ///     })
 ///     .build();
 ///
 /// let breeder = Breeder { mut_range: 0.1 };
-/// let comparator = Comparator;
 /// let algo = EvoAlgorithm::builder()
 ///     .breeder(breeder)
 ///     .fitness_calc(fitness_calc)
@@ -317,26 +1172,60 @@ where
 /// ```
 pub struct Builder<TGenome, TBreeder>
 where
-    TGenome: Predict + PartialOrd,
-    TBreeder: Breed<TGenome>,
+    TGenome: Predict + PartialOrd + Send + Sync,
+    TBreeder: Breed<TGenome> + Sync,
 {
     elitism: usize,
+    elitism_schedule: Option<Schedule>,
     tournament_size: usize,
+    tournament_size_schedule: Option<Schedule>,
+    tournament_win_probability: f64,
+    selection: Selection,
+    temperature: Schedule,
+    mate_attempts: usize,
+    seed: Option<u64>,
+    first_generation: usize,
+    observers: Vec<Observer>,
+    diversity: Option<Diversity<TGenome>>,
+    speciation: Option<Speciation<TGenome>>,
+    adaptive_mutation: Option<AdaptiveMutation<TGenome>>,
+    dedupe: bool,
+    stock: Option<Stocker<TGenome>>,
+    injection: InjectionPolicy,
     breeder: Option<BreedManager<TGenome, TBreeder>>,
     fitness_calc: Option<FitnessCalc>,
+    evaluator: Option<Box<dyn Evaluate<TGenome> + Send + Sync>>,
+    executor: Box<dyn Execute + Send + Sync>,
 }
 
 impl<TGenome, TBreeder> Default for Builder<TGenome, TBreeder>
 where
-    TBreeder: Breed<TGenome>,
-    TGenome: Predict + PartialOrd,
+    TBreeder: Breed<TGenome> + Sync,
+    TGenome: Predict + PartialOrd + Send + Sync,
 {
     fn default() -> Self {
         Self {
             elitism: 1,
+            elitism_schedule: None,
             tournament_size: 10,
+            tournament_size_schedule: None,
+            tournament_win_probability: 1.0,
+            selection: Selection::Tournament,
+            temperature: Schedule::Constant(1.0),
+            mate_attempts: 5,
+            seed: None,
+            first_generation: 0,
+            observers: vec![],
+            diversity: None,
+            speciation: None,
+            adaptive_mutation: None,
+            dedupe: false,
+            stock: None,
+            injection: InjectionPolicy::default(),
             breeder: None,
             fitness_calc: None,
+            evaluator: None,
+            executor: Box::new(Sequential),
         }
     }
 }
@@ -349,12 +1238,15 @@ pub enum Error {
 
     #[error("fitness calc not set")]
     FitnessCalcNotSet,
+
+    #[error("stock not set, but needed to replace duplicates")]
+    DedupeWithoutStock,
 }
 
 impl<TGenome, TBreeder> Builder<TGenome, TBreeder>
 where
-    TGenome: Predict + PartialOrd,
-    TBreeder: Breed<TGenome>,
+    TGenome: Predict + PartialOrd + Send + Sync,
+    TBreeder: Breed<TGenome> + Sync,
 {
     /// Builds the genetic algorithm.
     ///
@@ -365,13 +1257,48 @@ where
     /// # Errors
     ///
     /// - [`Error::BreederNotSet`] if the breeder is not set.
-    /// - [`Error::FitnessCalcNotSet`] if the fitness calculator is not set.
-    pub fn build(self) -> Result<Run<TGenome, TBreeder>, Error> {
+    /// - [`Error::FitnessCalcNotSet`] if neither the fitness calculator nor an
+    ///   evaluator is set.
+    /// - [`Error::DedupeWithoutStock`] if duplicates are replaced, but no stock is
+    ///   set to replace them from.
+    pub fn build(self) -> std::result::Result<Run<TGenome, TBreeder>, Error> {
+        let fitness_calc = match (self.fitness_calc, &self.evaluator) {
+            (Some(fitness_calc), _) => fitness_calc,
+            (None, Some(_)) => FitnessCalc::builder().build(),
+            (None, None) => return Err(Error::FitnessCalcNotSet),
+        };
+        let dedupe = match (self.dedupe, self.stock) {
+            (true, Some(stock)) => Some(stock),
+            (true, None) => return Err(Error::DedupeWithoutStock),
+            (false, _) => None,
+        };
+
         Ok(Run {
             breeder: self.breeder.ok_or(Error::BreederNotSet)?,
-            fitness_calc: self.fitness_calc.ok_or(Error::FitnessCalcNotSet)?,
+            fitness_calc,
+            evaluator: self.evaluator,
+            executor: self.executor,
             elitism: self.elitism,
+            elitism_schedule: self.elitism_schedule,
             tournament_size: self.tournament_size,
+            tournament_size_schedule: self.tournament_size_schedule,
+            tournament_win_probability: self.tournament_win_probability,
+            selection: self.selection,
+            temperature: self.temperature,
+            mate_attempts: self.mate_attempts,
+            seed: self.seed,
+            observers: Mutex::new(self.observers),
+            diversity: self.diversity,
+            speciation: self.speciation,
+            adaptive_mutation: self.adaptive_mutation,
+            dedupe,
+            injection: self.injection,
+            spares: Mutex::default(),
+            generation: AtomicUsize::new(self.first_generation),
+            started: OnceLock::new(),
+            scored: Mutex::default(),
+            evaluations: AtomicUsize::default(),
+            history: Mutex::default(),
         })
     }
 
@@ -390,6 +1317,39 @@ where
         self
     }
 
+    /// Sets the elitism in each generation, overriding [`Builder::elitism`],
+    /// e.g. to keep few elite genomes while exploring early and more once the
+    /// run settles.
+    ///
+    /// # Arguments
+    ///
+    /// - `schedule`: The elitism schedule, rounded to whole genomes.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn elitism_schedule(mut self, schedule: Schedule) -> Self {
+        self.elitism_schedule = Some(schedule);
+        self
+    }
+
+    /// Sets how the elite genomes are put into the next generation. Defaults
+    /// to [`InjectionPolicy::ReplaceRandom`].
+    ///
+    /// # Arguments
+    ///
+    /// - `injection`: The injection policy.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn injection(mut self, injection: InjectionPolicy) -> Self {
+        self.injection = injection;
+        self
+    }
+
     /// Sets the tournament size.
     ///
     /// # Arguments
@@ -405,114 +1365,1712 @@ where
         self
     }
 
-    /// Sets the breeder.
+    /// Sets the tournament size in each generation, overriding
+    /// [`Builder::tournament_size`]. Growing tournaments select ever more
+    /// strongly for the fittest genomes as the run goes on.
     ///
     /// # Arguments
     ///
-    /// - `breeder`: The breeder to use.
+    /// - `schedule`: The tournament size schedule, rounded to whole genomes.
     ///
     /// # Returns
     ///
     /// The builder.
     #[must_use]
-    pub fn breeder(mut self, breeder: TBreeder) -> Self {
-        self.breeder = Some(BreedManager::new(breeder));
+    pub fn tournament_size_schedule(mut self, schedule: Schedule) -> Self {
+        self.tournament_size_schedule = Some(schedule);
         self
     }
 
-    /// Sets the breeder manager.
+    /// Sets the probability that the best genome in a tournament wins it.
+    ///
+    /// The best wins with probability `p`, the second best with `p(1 - p)`,
+    /// and so on. Defaults to 1, where the best always wins; lower values
+    /// keep diversity in small populations.
     ///
     /// # Arguments
     ///
-    /// - `breeder`: The breeder manager to use.
+    /// - `win_probability`: The probability, from 0 to 1.
     ///
     /// # Returns
     ///
     /// The builder.
     #[must_use]
-    pub fn breeder_manager(mut self, breeder: BreedManager<TGenome, TBreeder>) -> Self {
-        self.breeder = Some(breeder);
+    pub fn tournament_win_probability(mut self, win_probability: f64) -> Self {
+        self.tournament_win_probability = win_probability;
         self
     }
 
-    /// Sets the fitness calculator.
+    /// Sets how parents are selected for breeding. Defaults to
+    /// [`Selection::Tournament`].
     ///
     /// # Arguments
     ///
-    /// - `fitness_calc`: The fitness calculator to use.
+    /// - `selection`: The selection to use.
     ///
     /// # Returns
     ///
     /// The builder.
     #[must_use]
-    pub fn fitness_calc(mut self, fitness_calc: FitnessCalc) -> Self {
-        self.fitness_calc = Some(fitness_calc);
+    pub fn selection(mut self, selection: Selection) -> Self {
+        self.selection = selection;
         self
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{Breed, Compare, TrainingRecord};
-
-    #[test]
-    fn test_run() {
-        #[derive(Debug, PartialEq, PartialOrd, Clone)]
-        struct Genome {
-            value: f64,
-        }
 
-        impl Predict for Genome {
-            fn predict(&self, input: &[f64]) -> Vec<f64> {
-                input.iter().map(|x| x * self.value).collect()
-            }
-        }
+    /// Sets the temperature of [`Selection::Boltzmann`] in each generation.
+    /// Defaults to a constant temperature of 1.
+    ///
+    /// Temperatures are in units of fitness: a genome whose fitness is worse
+    /// than the best by the temperature is picked `e` times less often than
+    /// the best. A decaying schedule anneals the run, exploring early and
+    /// exploiting late.
+    ///
+    /// # Arguments
+    ///
+    /// - `temperature`: The temperature schedule.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn temperature(mut self, temperature: Schedule) -> Self {
+        self.temperature = temperature;
+        self
+    }
 
-        struct Comparator;
+    /// Sets the number of attempts made to find a mate the breeder accepts.
+    ///
+    /// Breeders reject pairs through [`Breed::compatible`], e.g. to avoid
+    /// inbreeding. Once the attempts run out, the last candidate is used.
+    ///
+    /// # Arguments
+    ///
+    /// - `mate_attempts`: The maximum number of mates to select for each offspring.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn mate_attempts(mut self, mate_attempts: usize) -> Self {
+        self.mate_attempts = mate_attempts;
+        self
+    }
 
-        impl Compare<Genome> for Comparator {
-            fn compare(
-                &self,
-                left: &CompareRecord<&Genome>,
-                right: &CompareRecord<&Genome>,
-            ) -> std::cmp::Ordering {
-                left.fitness.partial_cmp(&right.fitness).unwrap()
-            }
-        }
+    /// Seeds the random choices made while breeding, so that runs starting
+    /// from the same generation produce the same genomes.
+    ///
+    /// Each generation is bred with its own seed, derived from this one and
+    /// the number of generations run so far. Breeders and generators should
+    /// draw from [`crate::rng`] for their choices to be covered.
+    ///
+    /// # Arguments
+    ///
+    /// - `seed`: The seed.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
 
-        struct Breeder;
-        impl Breed<Genome> for Breeder {
-            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
-                Genome {
-                    value: (pair.0.value + pair.1.value) / 2.0,
-                }
-            }
-        }
+    /// Sets the number of generations already run, to continue a run that
+    /// was saved some other way than with [`Run::checkpoint`]. Seeds and
+    /// schedules then carry on from that generation, so a seeded run breeds
+    /// the same genomes as the run it continues.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation`: The number of generations already run.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn first_generation(mut self, generation: usize) -> Self {
+        self.first_generation = generation;
+        self
+    }
 
-        let fitness_calc = FitnessCalc::builder()
-            .add_training_record(TrainingRecord {
-                input: vec![0.0, 0.0],
-                output: vec![0.0],
-            })
-            .build();
+    /// Enables speciation, as in NEAT: each generation is sorted into species
+    /// of similar genomes, which select parents only among themselves and
+    /// breed a share of the next generation earned by their members' mean
+    /// fitness.
+    ///
+    /// This protects new structures, which usually score worse than the
+    /// genomes around them until their weights are tuned, from being bred
+    /// out straight away.
+    ///
+    /// # Arguments
+    ///
+    /// - `distance`: Measures how different two genomes are.
+    /// - `threshold`: The greatest distance from a species' fittest member at
+    ///   which a genome joins the species.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn speciation(
+        mut self,
+        distance: impl Distance<TGenome> + Send + Sync + 'static,
+        threshold: f64,
+    ) -> Self {
+        self.speciation = Some(Speciation {
+            distance: Box::new(distance),
+            threshold,
+        });
+        self
+    }
 
-        let run = Run {
-            breeder: Breeder.to_manager(),
-            tournament_size: 2,
-            fitness_calc,
-            elitism: 1,
-        };
+    /// Enables adaptive mutation: before each generation breeds, its
+    /// diversity, the mean distance between its genomes, is measured, and
+    /// the breeder's mutation pressure is set through [`Breed::set_pressure`].
+    ///
+    /// The pressure is 1 while the diversity is at or above the target, and
+    /// rises as the diversity falls below it, in proportion, up to the
+    /// greatest pressure. This brings variety back when the population
+    /// collapses onto one solution.
+    ///
+    /// # Arguments
+    ///
+    /// - `distance`: Measures how different two genomes are.
+    /// - `target`: The diversity to keep up.
+    /// - `max_pressure`: The greatest pressure, at which a generation of
+    ///   identical genomes breeds.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn adaptive_mutation(
+        mut self,
+        distance: impl Distance<TGenome> + Send + Sync + 'static,
+        target: f64,
+        max_pressure: f64,
+    ) -> Self {
+        self.adaptive_mutation = Some(AdaptiveMutation {
+            distance: Box::new(distance),
+            target,
+            max_pressure,
+        });
+        self
+    }
 
-        let generation = vec![
-            Genome { value: 1.0 },
-            Genome { value: 2.0 },
-            Genome { value: 3.0 },
-        ];
+    /// Sets whether offspring that equal another offspring of the same
+    /// generation are replaced with genomes from the stock set with
+    /// [`Builder::stock`]. Defaults to false.
+    ///
+    /// This keeps a population from collapsing into clones of one genome.
+    /// Genomes are compared with `PartialEq`, pair by pair, so each
+    /// generation takes time in proportion to the square of its size. Stocked
+    /// genomes are not checked again.
+    ///
+    /// # Arguments
+    ///
+    /// - `dedupe`: Whether to replace duplicates.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn dedupe(mut self, dedupe: bool) -> Self {
+        self.dedupe = dedupe;
+        self
+    }
 
-        let actual = run.run(generation.clone());
+    /// Sets the stock that fresh genomes are drawn from, e.g. to replace
+    /// duplicates.
+    ///
+    /// # Arguments
+    ///
+    /// - `stock`: Generates fresh genomes.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn stock(mut self, stock: impl Stock<TGenome> + Send + Sync + 'static) -> Self {
+        self.stock = Some(Box::new(stock));
+        self
+    }
 
-        assert_eq!(actual.len(), generation.len());
-        assert_ne!(actual, generation);
+    /// Adds an observer, called with the statistics of each generation once
+    /// it is scored, e.g. to log or plot progress.
+    ///
+    /// Observers can stop [`Run::evolve`] early by returning
+    /// [`ControlFlow::Break`]; [`Run::run`] only runs one generation, so it
+    /// ignores the request.
+    ///
+    /// # Arguments
+    ///
+    /// - `observer`: The observer.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{stats::GenerationStats, Breed, EvoAlgorithm, FitnessCalc, Predict};
+    /// use std::ops::ControlFlow;
+    ///
+    /// #[derive(Clone, PartialEq, PartialOrd)]
+    /// struct Genome;
+    ///
+    /// impl Predict for Genome {
+    ///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+    ///         vec![]
+    ///     }
+    /// }
+    ///
+    /// struct Breeder;
+    ///
+    /// impl Breed<Genome> for Breeder {
+    ///     fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+    ///         pair.0.clone()
+    ///     }
+    /// }
+    ///
+    /// let algo = EvoAlgorithm::builder()
+    ///     .breeder(Breeder)
+    ///     .fitness_calc(FitnessCalc::builder().build())
+    ///     .on_generation(|stats: &GenerationStats| {
+    ///         println!("generation {}: {}", stats.generation, stats.best);
+    ///         ControlFlow::Continue(())
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[must_use]
+    pub fn on_generation(
+        mut self,
+        observer: impl FnMut(&GenerationStats) -> ControlFlow<()> + Send + 'static,
+    ) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Sets how to measure the diversity of a generation, which observers
+    /// receive in [`GenerationStats::diversity`].
+    ///
+    /// # Arguments
+    ///
+    /// - `diversity`: Measures the diversity of the scored genomes, e.g. their
+    ///   mean distance from each other.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn diversity(
+        mut self,
+        diversity: impl Fn(&[&TGenome]) -> f64 + Send + Sync + 'static,
+    ) -> Self {
+        self.diversity = Some(Box::new(diversity));
+        self
+    }
+
+    /// Sets the breeder.
+    ///
+    /// # Arguments
+    ///
+    /// - `breeder`: The breeder to use.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn breeder(mut self, breeder: TBreeder) -> Self {
+        self.breeder = Some(BreedManager::new(breeder));
+        self
+    }
+
+    /// Sets the breeder manager.
+    ///
+    /// # Arguments
+    ///
+    /// - `breeder`: The breeder manager to use.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn breeder_manager(mut self, breeder: BreedManager<TGenome, TBreeder>) -> Self {
+        self.breeder = Some(breeder);
+        self
+    }
+
+    /// Sets the fitness calculator.
+    ///
+    /// # Arguments
+    ///
+    /// - `fitness_calc`: The fitness calculator to use.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn fitness_calc(mut self, fitness_calc: FitnessCalc) -> Self {
+        self.fitness_calc = Some(fitness_calc);
+        self
+    }
+
+    /// Sets an evaluator that scores each generation in one batch, in place
+    /// of checking genomes one at a time with the fitness calculator.
+    ///
+    /// # Arguments
+    ///
+    /// - `evaluator`: The evaluator to use, such as a GPU-backed one.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn evaluator(mut self, evaluator: impl Evaluate<TGenome> + Send + Sync + 'static) -> Self {
+        self.evaluator = Some(Box::new(evaluator));
+        self
+    }
+
+    /// Sets whether fitness checks and breeding run concurrently on rayon's
+    /// global thread pool, which pays off when each check scores a large
+    /// training set, or when crossover and mutation are expensive.
+    ///
+    /// This is shorthand for setting the executor to [`crate::Parallel`] or
+    /// [`Sequential`].
+    ///
+    /// # Arguments
+    ///
+    /// - `parallel`: Whether to check genomes concurrently.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn parallel(self, parallel: bool) -> Self {
+        if parallel {
+            self.executor(executor::Parallel)
+        } else {
+            self.executor(Sequential)
+        }
+    }
+
+    /// Sets the executor that schedules fitness checks and breeding, e.g.
+    /// [`crate::Parallel`] to check and breed genomes concurrently. Defaults to
+    /// [`Sequential`].
+    ///
+    /// An evaluator set with [`Builder::evaluator`] schedules its own work.
+    /// Parents are always selected on the calling thread, and each offspring
+    /// is bred with its own seed, so a seeded run breeds the same genomes
+    /// with any executor.
+    ///
+    /// # Arguments
+    ///
+    /// - `executor`: The executor to use.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn executor(mut self, executor: impl Execute + Send + Sync + 'static) -> Self {
+        self.executor = Box::new(executor);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Breed, Compare, TrainingRecord};
+
+    #[test]
+    fn test_run() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, input: &[f64]) -> Vec<f64> {
+                input.iter().map(|x| x * self.value).collect()
+            }
+        }
+
+        #[allow(dead_code)]
+        struct Comparator;
+
+        impl Compare<Genome> for Comparator {
+            fn compare(
+                &self,
+                left: &CompareRecord<&Genome>,
+                right: &CompareRecord<&Genome>,
+            ) -> std::cmp::Ordering {
+                left.fitness.partial_cmp(&right.fitness).unwrap()
+            }
+        }
+
+        struct Breeder;
+        impl Breed<Genome> for Breeder {
+            #[allow(clippy::manual_midpoint)]
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                Genome {
+                    value: (pair.0.value + pair.1.value) / 2.0,
+                }
+            }
+        }
+
+        let fitness_calc = FitnessCalc::builder()
+            .add_training_record(TrainingRecord {
+                input: vec![0.0, 0.0],
+                output: vec![0.0],
+                weight: 1.0,
+            })
+            .build();
+
+        let run = Run {
+            breeder: Breeder.to_manager(),
+            tournament_size: 2,
+            tournament_size_schedule: None,
+            tournament_win_probability: 1.0,
+            selection: Selection::Tournament,
+            temperature: Schedule::Constant(1.0),
+            fitness_calc,
+            evaluator: None,
+            executor: Box::new(Sequential),
+            elitism: 1,
+            elitism_schedule: None,
+            mate_attempts: 1,
+            seed: None,
+            observers: Mutex::default(),
+            diversity: None,
+            speciation: None,
+            adaptive_mutation: None,
+            dedupe: None,
+            injection: InjectionPolicy::default(),
+            spares: Mutex::default(),
+            generation: AtomicUsize::default(),
+            started: OnceLock::new(),
+            scored: Mutex::default(),
+            evaluations: AtomicUsize::default(),
+            history: Mutex::default(),
+        };
+
+        let generation = vec![
+            Genome { value: 1.0 },
+            Genome { value: 2.0 },
+            Genome { value: 3.0 },
+        ];
+
+        let actual = run.run(generation.clone()).generation;
+
+        assert_eq!(actual.len(), generation.len());
+        assert_ne!(actual, generation);
+    }
+
+    #[test]
+    fn test_mate_attempts() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, input: &[f64]) -> Vec<f64> {
+                input.iter().map(|x| x * self.value).collect()
+            }
+        }
+
+        struct Breeder {
+            checks: std::sync::Arc<AtomicUsize>,
+        }
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                pair.0.clone()
+            }
+
+            fn compatible(&self, _pair: (&Genome, &Genome)) -> bool {
+                self.checks.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+        }
+
+        let fitness_calc = FitnessCalc::builder()
+            .add_training_record(TrainingRecord {
+                input: vec![0.0, 0.0],
+                output: vec![0.0],
+                weight: 1.0,
+            })
+            .build();
+
+        let checks = std::sync::Arc::new(AtomicUsize::new(0));
+        let run = Run::builder()
+            .breeder(Breeder {
+                checks: checks.clone(),
+            })
+            .fitness_calc(fitness_calc)
+            .mate_attempts(3)
+            .build()
+            .unwrap();
+
+        let generation = vec![Genome { value: 1.0 }, Genome { value: 2.0 }];
+        let actual = run.run(generation).generation;
+
+        assert_eq!(actual.len(), 2);
+        assert_eq!(checks.load(Ordering::Relaxed), 6);
+    }
+
+    #[test]
+    fn test_reuses_retired_genomes() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, input: &[f64]) -> Vec<f64> {
+                input.iter().map(|x| x * self.value).collect()
+            }
+        }
+
+        struct Breeder {
+            reused: std::sync::Arc<AtomicUsize>,
+        }
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                pair.0.clone()
+            }
+
+            fn crossover_into(&self, pair: (&Genome, &Genome), offspring: &mut Genome) {
+                self.reused.fetch_add(1, Ordering::Relaxed);
+                offspring.clone_from(pair.0);
+            }
+        }
+
+        let fitness_calc = FitnessCalc::builder()
+            .add_training_record(TrainingRecord {
+                input: vec![1.0],
+                output: vec![0.0],
+                weight: 1.0,
+            })
+            .build();
+
+        let reused = std::sync::Arc::new(AtomicUsize::new(0));
+        let run = Run::builder()
+            .breeder(Breeder {
+                reused: reused.clone(),
+            })
+            .fitness_calc(fitness_calc)
+            .elitism(1)
+            .build()
+            .unwrap();
+
+        let generation = vec![Genome { value: 1.0 }, Genome { value: 2.0 }];
+        let generation = run.run(generation).generation;
+        assert_eq!(reused.load(Ordering::Relaxed), 0);
+
+        let generation = run.run(generation).generation;
+        assert_eq!(generation.len(), 2);
+        assert_eq!(reused.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_evaluator_scores_batches() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                pair.1.clone()
+            }
+        }
+
+        struct Evaluator {
+            batches: std::sync::Arc<Mutex<Vec<usize>>>,
+        }
+
+        impl Evaluate<Genome> for Evaluator {
+            fn evaluate(&self, generation: &[Genome]) -> Vec<Result<f64>> {
+                self.batches.lock().unwrap().push(generation.len());
+                generation.iter().map(|genome| Ok(genome.value)).collect()
+            }
+        }
+
+        let batches = std::sync::Arc::default();
+        let run = Run::builder()
+            .breeder(Breeder)
+            .evaluator(Evaluator {
+                batches: std::sync::Arc::clone(&batches),
+            })
+            .elitism(1)
+            .build()
+            .unwrap();
+
+        let generation = run
+            .run(vec![
+                Genome { value: 3.0 },
+                Genome { value: 1.0 },
+                Genome { value: 2.0 },
+            ])
+            .generation;
+
+        assert_eq!(generation.len(), 3);
+        assert_eq!(*batches.lock().unwrap(), vec![3, 3]);
+    }
+
+    #[test]
+    fn test_executor_schedules_fitness_checks() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                pair.0.clone()
+            }
+        }
+
+        struct Counting {
+            tasks: std::sync::Arc<AtomicUsize>,
+        }
+
+        impl Execute for Counting {
+            fn execute(&self, tasks: usize, task: &(dyn Fn(usize) + Sync)) {
+                self.tasks.fetch_add(tasks, Ordering::Relaxed);
+                crate::Parallel.execute(tasks, task);
+            }
+        }
+
+        let tasks = std::sync::Arc::default();
+        let run = Run::builder()
+            .breeder(Breeder)
+            .fitness_calc(
+                FitnessCalc::builder()
+                    .add_training_record(TrainingRecord {
+                        input: vec![0.0],
+                        output: vec![0.0],
+                        weight: 1.0,
+                    })
+                    .build(),
+            )
+            .executor(Counting {
+                tasks: std::sync::Arc::clone(&tasks),
+            })
+            .build()
+            .unwrap();
+
+        let generation = run
+            .run(vec![Genome { value: 1.0 }, Genome { value: 2.0 }])
+            .generation;
+
+        // Two checks of the parents, two offspring bred, and two checks of
+        // the offspring.
+        assert_eq!(generation.len(), 2);
+        assert_eq!(tasks.load(Ordering::Relaxed), 6);
+    }
+
+    #[test]
+    fn test_seed() {
+        use rand::Rng;
+
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                pair.0.clone()
+            }
+
+            fn mutate(&self, genome: Genome) -> Genome {
+                Genome {
+                    value: genome.value + rng::handle().gen_range(-1.0..1.0),
+                }
+            }
+        }
+
+        let evolve = |seed| {
+            let run = Run::builder()
+                .breeder(Breeder)
+                .fitness_calc(
+                    FitnessCalc::builder()
+                        .add_training_record(TrainingRecord {
+                            input: vec![0.0],
+                            output: vec![0.0],
+                            weight: 1.0,
+                        })
+                        .build(),
+                )
+                .tournament_size(2)
+                .seed(seed)
+                .build()
+                .unwrap();
+            let generation = (0..10).map(|value| Genome {
+                value: f64::from(value),
+            });
+            (0..5).fold(generation.collect(), |generation, _| {
+                run.run(generation).generation
+            })
+        };
+
+        assert_eq!(evolve(1), evolve(1));
+        assert_ne!(evolve(1), evolve(2));
+    }
+
+    #[test]
+    fn test_parallel() {
+        use rand::Rng;
+
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                pair.0.clone()
+            }
+
+            fn mutate(&self, genome: Genome) -> Genome {
+                Genome {
+                    value: genome.value + rng::handle().gen_range(-1.0..1.0),
+                }
+            }
+        }
+
+        let evolve = |parallel| {
+            let run = Run::builder()
+                .breeder(Breeder)
+                .fitness_calc(
+                    FitnessCalc::builder()
+                        .add_training_record(TrainingRecord {
+                            input: vec![0.0],
+                            output: vec![0.0],
+                            weight: 1.0,
+                        })
+                        .build(),
+                )
+                .parallel(parallel)
+                .seed(3)
+                .build()
+                .unwrap();
+            let generation = (0..20).map(|value| Genome {
+                value: f64::from(value),
+            });
+            (0..5).fold(generation.collect(), |generation, _| {
+                run.run(generation).generation
+            })
+        };
+
+        assert_eq!(evolve(true), evolve(false));
+    }
+
+    #[test]
+    fn test_evolve() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                Genome {
+                    value: f64::midpoint(pair.0.value, pair.1.value),
+                }
+            }
+        }
+
+        struct Evaluator;
+
+        impl Evaluate<Genome> for Evaluator {
+            fn evaluate(&self, generation: &[Genome]) -> Vec<Result<f64>> {
+                generation
+                    .iter()
+                    .map(|genome| match genome.value {
+                        value if value < 0.0 => Err(FitnessError::Unscored),
+                        value => Ok(value),
+                    })
+                    .collect()
+            }
+        }
+
+        let run = Run::builder()
+            .breeder(Breeder)
+            .evaluator(Evaluator)
+            .build()
+            .unwrap();
+        let initial = || {
+            (1..=4)
+                .map(|value| Genome {
+                    value: f64::from(value),
+                })
+                .collect()
+        };
+
+        let evolution = run.evolve(initial(), Termination::builder().generations(3).build());
+        assert_eq!(evolution.stop, Stop::Generations);
+        assert_eq!(evolution.history.len(), 4);
+        assert_eq!(evolution.generation.len(), 4);
+        assert!(evolution
+            .best
+            .is_some_and(|best| best.predict == Genome { value: 1.0 }));
+
+        let evolution = run.evolve(
+            initial(),
+            Termination::builder().target_fitness(2.0).build(),
+        );
+        assert_eq!(evolution.stop, Stop::TargetFitness);
+        assert_eq!(evolution.history.best().collect::<Vec<_>>(), vec![1.0]);
+
+        let extinct = vec![Genome { value: -1.0 }];
+        let evolution = run.evolve(extinct, Termination::default());
+        assert_eq!(evolution.stop, Stop::Extinct);
+        assert!(evolution.best.is_none());
+        assert!(evolution.history.is_empty());
+    }
+
+    #[test]
+    fn test_on_generation() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                pair.0.clone()
+            }
+        }
+
+        struct Evaluator;
+
+        impl Evaluate<Genome> for Evaluator {
+            fn evaluate(&self, generation: &[Genome]) -> Vec<Result<f64>> {
+                generation.iter().map(|genome| Ok(genome.value)).collect()
+            }
+        }
+
+        let seen = std::sync::Arc::new(Mutex::new(vec![]));
+        let run = Run::builder()
+            .breeder(Breeder)
+            .evaluator(Evaluator)
+            .diversity(|genomes: &[&Genome]| {
+                #[allow(clippy::cast_precision_loss)]
+                let count = genomes.len() as f64;
+                count
+            })
+            .on_generation({
+                let seen = std::sync::Arc::clone(&seen);
+                move |stats| {
+                    seen.lock().unwrap().push(*stats);
+                    if stats.generation < 2 {
+                        ControlFlow::Continue(())
+                    } else {
+                        ControlFlow::Break(())
+                    }
+                }
+            })
+            .build()
+            .unwrap();
+        let initial = (1..=3)
+            .map(|value| Genome {
+                value: f64::from(value),
+            })
+            .collect();
+
+        let evolution = run.evolve(initial, Termination::builder().generations(10).build());
+
+        assert_eq!(evolution.stop, Stop::Observer);
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 3);
+        let first = seen[0];
+        assert_eq!(first.generation, 0);
+        assert!((first.best - 1.0).abs() < f64::EPSILON);
+        assert!((first.mean - 2.0).abs() < f64::EPSILON);
+        assert!((first.worst - 3.0).abs() < f64::EPSILON);
+        assert_eq!(first.diversity, Some(3.0));
+        assert!(seen
+            .windows(2)
+            .all(|pair| pair[0].elapsed <= pair[1].elapsed));
+    }
+
+    #[test]
+    fn test_lexicase_selection() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                pair.0.clone()
+            }
+        }
+
+        let record = |output| TrainingRecord {
+            input: vec![0.0],
+            output: vec![output],
+            weight: 1.0,
+        };
+        let run = Run::builder()
+            .breeder(Breeder)
+            .fitness_calc(
+                FitnessCalc::builder()
+                    .add_training_records(vec![record(0.0), record(10.0)])
+                    .build(),
+            )
+            .selection(Selection::Lexicase)
+            .elitism(0)
+            .build()
+            .unwrap();
+
+        // The middle genome has the best mean error, but is never best on a
+        // single record, so only the specialists breed.
+        let generation = vec![
+            Genome { value: 0.0 },
+            Genome { value: 5.0 },
+            Genome { value: 10.0 },
+        ];
+        let generation = run.run(generation).generation;
+
+        assert_eq!(generation.len(), 3);
+        assert!(generation
+            .iter()
+            .all(|genome| genome.value.abs() < f64::EPSILON
+                || (genome.value - 10.0).abs() < f64::EPSILON));
+    }
+
+    #[test]
+    fn test_boltzmann_selection() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                pair.0.clone()
+            }
+        }
+
+        let run = Run::builder()
+            .breeder(Breeder)
+            .fitness_calc(
+                FitnessCalc::builder()
+                    .add_training_record(TrainingRecord {
+                        input: vec![0.0],
+                        output: vec![0.0],
+                        weight: 1.0,
+                    })
+                    .build(),
+            )
+            .selection(Selection::Boltzmann)
+            .temperature(Schedule::Linear {
+                start: 1000.0,
+                end: 0.0,
+                generations: 1,
+            })
+            .elitism(0)
+            .build()
+            .unwrap();
+
+        // The first generation is bred hot, so the offspring stay mixed; the
+        // next is bred cold, so only the fittest parent is picked.
+        let initial = (0..20)
+            .map(|value| Genome {
+                value: f64::from(value % 2),
+            })
+            .collect();
+        let hot = run.run(initial).generation;
+        assert!(hot.iter().any(|genome| genome.value > 0.5));
+        assert!(hot.iter().any(|genome| genome.value < 0.5));
+
+        let cold = run.run(hot).generation;
+        assert!(cold.iter().all(|genome| genome.value < 0.5));
+    }
+
+    #[test]
+    fn test_speciation() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                pair.0.clone()
+            }
+        }
+
+        struct Difference;
+
+        impl Distance<Genome> for Difference {
+            fn distance(&self, pair: (&Genome, &Genome)) -> f64 {
+                (pair.0.value - pair.1.value).abs()
+            }
+        }
+
+        let run = |speciation: bool| {
+            let builder = Run::builder()
+                .breeder(Breeder)
+                .fitness_calc(
+                    FitnessCalc::builder()
+                        .add_training_record(TrainingRecord {
+                            input: vec![0.0],
+                            output: vec![0.0],
+                            weight: 1.0,
+                        })
+                        .build(),
+                )
+                .elitism(0);
+            let builder = if speciation {
+                builder.speciation(Difference, 0.5)
+            } else {
+                builder
+            };
+            let initial = (0..10)
+                .map(|value| Genome {
+                    value: f64::from(value % 2),
+                })
+                .collect();
+            let next = builder.build().unwrap().run(initial).generation;
+            next.iter().filter(|genome| genome.value > 0.5).count()
+        };
+
+        // Every tournament holds the whole generation, so the weaker genomes
+        // only breed in a species of their own, which earns a third of the
+        // offspring.
+        assert_eq!(run(false), 0);
+        assert_eq!(run(true), 3);
+    }
+
+    #[test]
+    fn test_adaptive_mutation() {
+        use std::sync::atomic::AtomicU64;
+        use std::sync::Arc;
+
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder {
+            pressure: Arc<AtomicU64>,
+        }
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                pair.0.clone()
+            }
+
+            fn set_pressure(&self, pressure: f64) {
+                self.pressure.store(pressure.to_bits(), Ordering::Relaxed);
+            }
+        }
+
+        struct Difference;
+
+        impl Distance<Genome> for Difference {
+            fn distance(&self, pair: (&Genome, &Genome)) -> f64 {
+                (pair.0.value - pair.1.value).abs()
+            }
+        }
+
+        let pressure = Arc::new(AtomicU64::new(0));
+        let run = Run::builder()
+            .breeder(Breeder {
+                pressure: Arc::clone(&pressure),
+            })
+            .fitness_calc(
+                FitnessCalc::builder()
+                    .add_training_record(TrainingRecord {
+                        input: vec![0.0],
+                        output: vec![0.0],
+                        weight: 1.0,
+                    })
+                    .build(),
+            )
+            .adaptive_mutation(Difference, 1.0, 4.0)
+            .build()
+            .unwrap();
+        let pressure = || f64::from_bits(pressure.load(Ordering::Relaxed));
+
+        let diverse = (0..4).map(|value| Genome {
+            value: f64::from(value),
+        });
+        run.run(diverse.collect());
+        assert!((pressure() - 1.0).abs() < f64::EPSILON);
+
+        run.run(vec![Genome { value: 1.0 }; 4]);
+        assert!((pressure() - 4.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_schedules() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder {
+            generation: std::sync::Arc<AtomicUsize>,
+        }
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, _pair: (&Genome, &Genome)) -> Genome {
+                Genome { value: 9.0 }
+            }
+
+            fn set_generation(&self, generation: usize) {
+                self.generation.store(generation, Ordering::Relaxed);
+            }
+        }
+
+        let generation = std::sync::Arc::new(AtomicUsize::new(usize::MAX));
+        let run = Run::builder()
+            .breeder(Breeder {
+                generation: std::sync::Arc::clone(&generation),
+            })
+            .fitness_calc(
+                FitnessCalc::builder()
+                    .add_training_record(TrainingRecord {
+                        input: vec![0.0],
+                        output: vec![0.0],
+                        weight: 1.0,
+                    })
+                    .build(),
+            )
+            .elitism(4)
+            .elitism_schedule(Schedule::Linear {
+                start: 0.0,
+                end: 2.0,
+                generations: 1,
+            })
+            .build()
+            .unwrap();
+        let initial = || {
+            (1..=4)
+                .map(|value| Genome {
+                    value: f64::from(value),
+                })
+                .collect()
+        };
+        let elite = |generation: &[Genome]| {
+            generation
+                .iter()
+                .filter(|genome| genome.value < 9.0)
+                .count()
+        };
+
+        // No elite genomes in the first generation, and two after.
+        assert_eq!(elite(&run.run(initial()).generation), 0);
+        assert_eq!(generation.load(Ordering::Relaxed), 0);
+        assert!(elite(&run.run(initial()).generation) > 0);
+        assert_eq!(generation.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_dedupe() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                pair.0.clone()
+            }
+        }
+
+        struct Stocker;
+
+        impl Stock<Genome> for Stocker {
+            fn generate(&self) -> Genome {
+                Genome { value: 5.0 }
+            }
+        }
+
+        let run = |dedupe: bool| {
+            Run::builder()
+                .breeder(Breeder)
+                .fitness_calc(
+                    FitnessCalc::builder()
+                        .add_training_record(TrainingRecord {
+                            input: vec![0.0],
+                            output: vec![0.0],
+                            weight: 1.0,
+                        })
+                        .build(),
+                )
+                .elitism(0)
+                .dedupe(dedupe)
+                .stock(Stocker)
+                .build()
+                .unwrap()
+                .run(vec![Genome { value: 1.0 }; 4])
+                .generation
+        };
+
+        assert_eq!(run(false), vec![Genome { value: 1.0 }; 4]);
+        let deduped = run(true);
+        assert_eq!(deduped[0], Genome { value: 1.0 });
+        assert_eq!(deduped[1..], vec![Genome { value: 5.0 }; 3]);
+
+        let unstocked = Run::<Genome, _>::builder()
+            .breeder(Breeder)
+            .fitness_calc(FitnessCalc::builder().build())
+            .dedupe(true)
+            .build();
+        assert!(matches!(unstocked, Err(Error::DedupeWithoutStock)));
+    }
+
+    #[test]
+    fn test_injection() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, _pair: (&Genome, &Genome)) -> Genome {
+                Genome { value: 9.0 }
+            }
+        }
+
+        let run = Run::builder()
+            .breeder(Breeder)
+            .fitness_calc(
+                FitnessCalc::builder()
+                    .add_training_record(TrainingRecord {
+                        input: vec![0.0],
+                        output: vec![0.0],
+                        weight: 1.0,
+                    })
+                    .build(),
+            )
+            .elitism(2)
+            .injection(InjectionPolicy::ReplaceWorst)
+            .build()
+            .unwrap();
+        let initial = (1..=4).map(|value| Genome {
+            value: f64::from(value),
+        });
+
+        let mut values = run
+            .run(initial.collect())
+            .generation
+            .iter()
+            .map(|genome| genome.value)
+            .collect::<Vec<_>>();
+        values.sort_by(f64::total_cmp);
+
+        // Both elite genomes survive, instead of possibly overwriting each
+        // other.
+        assert_eq!(values, vec![1.0, 2.0, 9.0, 9.0]);
+    }
+
+    #[test]
+    fn test_outcome() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, _pair: (&Genome, &Genome)) -> Genome {
+                Genome { value: 9.0 }
+            }
+        }
+
+        let run = Run::builder()
+            .breeder(Breeder)
+            .fitness_calc(
+                FitnessCalc::builder()
+                    .add_training_record(TrainingRecord {
+                        input: vec![0.0],
+                        output: vec![0.0],
+                        weight: 1.0,
+                    })
+                    .build(),
+            )
+            .elitism(2)
+            .build()
+            .unwrap();
+        let initial = [3.0, 1.0, 4.0, 2.0].map(|value| Genome { value });
+
+        let outcome = run.run(initial.to_vec());
+
+        assert_eq!(outcome.generation.len(), 4);
+        let best = outcome.best.unwrap();
+        assert_eq!(best.predict, Genome { value: 1.0 });
+        assert!((best.fitness - 1.0).abs() < f64::EPSILON);
+        let elite = outcome
+            .elite
+            .iter()
+            .map(|record| record.predict.value)
+            .collect::<Vec<_>>();
+        assert_eq!(elite, vec![1.0, 2.0]);
+        let stats = outcome.stats.unwrap();
+        assert_eq!(stats.generation, 0);
+        assert!((stats.best - 1.0).abs() < f64::EPSILON);
+        assert_eq!(outcome.discarded, 0);
+    }
+
+    #[test]
+    fn test_evolve_with_budget() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                Genome {
+                    value: f64::midpoint(pair.0.value, pair.1.value),
+                }
+            }
+        }
+
+        let run = Run::builder()
+            .breeder(Breeder)
+            .fitness_calc(
+                FitnessCalc::builder()
+                    .add_training_record(TrainingRecord {
+                        input: vec![0.0],
+                        output: vec![0.0],
+                        weight: 1.0,
+                    })
+                    .build(),
+            )
+            .elitism(1)
+            .build()
+            .unwrap();
+        let initial = || {
+            (1..=4).map(|value| Genome {
+                value: f64::from(value),
+            })
+        };
+
+        // Four parents and four offspring are scored, then only one genome
+        // of the next generation; the other three wait for another budget.
+        let evolution = run.evolve_with_budget(initial().collect(), Termination::default(), 9);
+
+        assert_eq!(evolution.stop, Stop::Budget);
+        assert_eq!(evolution.evaluations, 9);
+        assert_eq!(evolution.generation.len(), 4);
+        assert_eq!(evolution.history.len(), 2);
+
+        let resumed = run.evolve_with_budget(
+            evolution.generation,
+            Termination::builder().generations(0).build(),
+            usize::MAX,
+        );
+
+        assert_eq!(resumed.stop, Stop::Generations);
+        assert_eq!(resumed.evaluations, 4);
+
+        // Four parents and two offspring are scored; the other two offspring
+        // are kept unscored.
+        let evolution = run.evolve_with_budget(initial().collect(), Termination::default(), 6);
+
+        assert_eq!(evolution.stop, Stop::Budget);
+        assert_eq!(evolution.evaluations, 6);
+        assert_eq!(evolution.generation.len(), 4);
+        assert_eq!(evolution.history.len(), 1);
+
+        // The budget only lasts for the one evolution, even while another
+        // evolution runs.
+        let (budgeted, unbudgeted) = std::thread::scope(|scope| {
+            let budgeted = scope
+                .spawn(|| run.evolve_with_budget(initial().collect(), Termination::default(), 9));
+            let unbudgeted = run.evolve(
+                initial().collect(),
+                Termination::builder().generations(50).build(),
+            );
+            (budgeted.join().unwrap(), unbudgeted)
+        });
+
+        assert_eq!(budgeted.evaluations, 9);
+        assert_eq!(unbudgeted.stop, Stop::Generations);
+        assert_eq!(unbudgeted.evaluations, 51 * 4 + 50 * 4);
+        assert_eq!(run.run(initial().collect()).generation.len(), 4);
+    }
+
+    #[test]
+    fn test_evolve_with_budget_lexicase() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                Genome {
+                    value: f64::midpoint(pair.0.value, pair.1.value),
+                }
+            }
+        }
+
+        let run = Run::builder()
+            .breeder(Breeder)
+            .fitness_calc(
+                FitnessCalc::builder()
+                    .add_training_record(TrainingRecord::new(vec![0.0], vec![0.0]))
+                    .build(),
+            )
+            .selection(Selection::Lexicase)
+            .elitism(1)
+            .build()
+            .unwrap();
+        let initial = || {
+            (1..=4)
+                .map(|value| Genome {
+                    value: f64::from(value),
+                })
+                .collect()
+        };
+
+        // Four parents are scored, checked again for lexicase selection, and
+        // then only one offspring is scored.
+        let evolution = run.evolve_with_budget(initial(), Termination::default(), 9);
+
+        assert_eq!(evolution.stop, Stop::Budget);
+        assert_eq!(evolution.evaluations, 9);
+        assert_eq!(evolution.history.len(), 1);
+
+        // Lexicase selection cannot check the four parents with two checks
+        // left, so tournament selection picks the parents of two offspring.
+        let evolution = run.evolve_with_budget(initial(), Termination::default(), 6);
+
+        assert_eq!(evolution.stop, Stop::Budget);
+        assert_eq!(evolution.evaluations, 6);
+        assert_eq!(evolution.generation.len(), 4);
+    }
+
+    #[test]
+    fn test_run_with_unfit_offspring() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        // Every offspring predicts NaN, so none can be scored.
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, _pair: (&Genome, &Genome)) -> Genome {
+                Genome { value: f64::NAN }
+            }
+        }
+
+        let run = Run::builder()
+            .breeder(Breeder)
+            .fitness_calc(
+                FitnessCalc::builder()
+                    .add_training_record(TrainingRecord::new(vec![0.0], vec![0.0]))
+                    .build(),
+            )
+            .elitism(1)
+            .build()
+            .unwrap();
+        let initial = (1..=4)
+            .map(|value| Genome {
+                value: f64::from(value),
+            })
+            .collect();
+
+        let outcome = run.run(initial);
+
+        assert_eq!(outcome.generation, vec![Genome { value: 1.0 }]);
+    }
+
+    #[test]
+    fn test_validation_stagnation() {
+        #[derive(Debug, PartialEq, PartialOrd, Clone)]
+        struct Genome {
+            value: f64,
+        }
+
+        impl Predict for Genome {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                vec![self.value]
+            }
+        }
+
+        struct Breeder;
+
+        impl Breed<Genome> for Breeder {
+            fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+                pair.0.clone()
+            }
+        }
+
+        let record = |output| TrainingRecord {
+            input: vec![0.0],
+            output: vec![output],
+            weight: 1.0,
+        };
+        let run = Run::builder()
+            .breeder(Breeder)
+            .fitness_calc(
+                FitnessCalc::builder()
+                    .add_training_record(record(0.0))
+                    .add_validation_records(vec![record(1.0)])
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let initial = vec![Genome { value: 0.0 }, Genome { value: 2.0 }];
+
+        let evolution = run.evolve(
+            initial,
+            Termination::builder()
+                .generations(10)
+                .validation_stagnation(2)
+                .build(),
+        );
+
+        assert_eq!(evolution.stop, Stop::ValidationStagnation);
+        assert_eq!(evolution.history.len(), 3);
+        let validation = evolution.history.generations()[0].validation.unwrap();
+        assert!((validation - 1.0).abs() < f64::EPSILON);
     }
 }
@@ -0,0 +1,271 @@
+//! Saving a run to resume it later, e.g. after a crash during a long run.
+//!
+//! A checkpoint holds a generation, the number of generations run so far,
+//! and the algorithm's settings. The breeder, fitness calculator, and
+//! observers are code, not data, so a run is resumed by building it as
+//! before and then reading the checkpoint into it.
+//!
+//! Seeded runs derive each generation's random numbers from the seed and the
+//! generation counter, so a resumed seeded run continues exactly as the
+//! original would have.
+
+use super::Run;
+use crate::{Breed, Generation, Predict};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::sync::atomic::Ordering;
+
+/// An error saving or resuming a run.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error(
+        "the checkpoint has version {found}, which is newer than the supported version {supported}"
+    )]
+    UnsupportedVersion { found: u32, supported: u32 },
+}
+
+/// The settings of a run that can be saved.
+#[derive(Deserialize, Serialize)]
+struct Settings {
+    elitism: usize,
+    tournament_size: usize,
+    #[serde(default = "Settings::default_tournament_win_probability")]
+    tournament_win_probability: f64,
+    mate_attempts: usize,
+    seed: Option<u64>,
+}
+
+impl Settings {
+    /// The win probability of checkpoints saved before it could be set.
+    fn default_tournament_win_probability() -> f64 {
+        1.0
+    }
+}
+
+/// A saved run, holding its population as `P`.
+#[derive(Deserialize, Serialize)]
+struct Checkpoint<P> {
+    version: u32,
+    generation: usize,
+    settings: Settings,
+    population: P,
+}
+
+/// The version of the checkpoints written by this crate.
+const VERSION: u32 = 1;
+
+impl<TGenome, TBreeder> Run<TGenome, TBreeder>
+where
+    TGenome: Predict + PartialOrd + Send + Sync,
+    TBreeder: Breed<TGenome> + Sync,
+{
+    /// Saves the run, so it can be resumed from a generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation`: The generation to resume from, usually the one
+    ///   [`Run::run`] returned last.
+    /// - `writer`: Receives the checkpoint, as JSON.
+    ///
+    /// # Errors
+    ///
+    /// If a genome cannot be serialized, or the writer fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{Breed, EvoAlgorithm, FitnessCalc, Predict};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Clone, Debug, Deserialize, PartialEq, PartialOrd, Serialize)]
+    /// struct Genome {
+    ///     value: f64,
+    /// }
+    ///
+    /// impl Predict for Genome {
+    ///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+    ///         vec![self.value]
+    ///     }
+    /// }
+    ///
+    /// struct Breeder;
+    ///
+    /// impl Breed<Genome> for Breeder {
+    ///     fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+    ///         pair.0.clone()
+    ///     }
+    /// }
+    ///
+    /// let algo = || {
+    ///     EvoAlgorithm::builder()
+    ///         .breeder(Breeder)
+    ///         .fitness_calc(FitnessCalc::builder().build())
+    ///         .build()
+    ///         .unwrap()
+    /// };
+    ///
+    /// let generation = vec![Genome { value: 1.0 }, Genome { value: 2.0 }];
+    /// let mut saved = vec![];
+    /// algo().checkpoint(&generation, &mut saved).unwrap();
+    ///
+    /// let mut resumed = algo();
+    /// assert_eq!(resumed.resume(saved.as_slice()).unwrap(), generation);
+    /// ```
+    pub fn checkpoint(
+        &self,
+        generation: &[TGenome],
+        writer: impl Write,
+    ) -> std::result::Result<(), Error>
+    where
+        TGenome: Serialize,
+    {
+        let checkpoint = Checkpoint {
+            version: VERSION,
+            generation: self.generation.load(Ordering::Relaxed),
+            settings: Settings {
+                elitism: self.elitism,
+                tournament_size: self.tournament_size,
+                tournament_win_probability: self.tournament_win_probability,
+                mate_attempts: self.mate_attempts,
+                seed: self.seed,
+            },
+            population: generation,
+        };
+        serde_json::to_writer(writer, &checkpoint)?;
+        Ok(())
+    }
+
+    /// Resumes a saved run, restoring its settings and generation counter.
+    ///
+    /// # Arguments
+    ///
+    /// - `reader`: Supplies the checkpoint written by [`Run::checkpoint`].
+    ///
+    /// # Returns
+    ///
+    /// The generation to continue from.
+    ///
+    /// # Errors
+    ///
+    /// If the checkpoint cannot be read, holds genomes of another type, or was
+    /// written by a newer version of this crate.
+    pub fn resume(&mut self, reader: impl Read) -> std::result::Result<Generation<TGenome>, Error>
+    where
+        TGenome: DeserializeOwned,
+    {
+        let checkpoint: Checkpoint<Generation<TGenome>> = serde_json::from_reader(reader)?;
+        if checkpoint.version > VERSION {
+            return Err(Error::UnsupportedVersion {
+                found: checkpoint.version,
+                supported: VERSION,
+            });
+        }
+
+        let Settings {
+            elitism,
+            tournament_size,
+            tournament_win_probability,
+            mate_attempts,
+            seed,
+        } = checkpoint.settings;
+        self.elitism = elitism;
+        self.tournament_size = tournament_size;
+        self.tournament_win_probability = tournament_win_probability;
+        self.mate_attempts = mate_attempts;
+        self.seed = seed;
+        *self.generation.get_mut() = checkpoint.generation;
+        Ok(checkpoint.population)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rng, FitnessCalc, TrainingRecord};
+    use rand::Rng;
+
+    #[derive(Clone, Debug, Deserialize, PartialEq, PartialOrd, Serialize)]
+    struct Genome {
+        value: f64,
+    }
+
+    impl Predict for Genome {
+        fn predict(&self, _input: &[f64]) -> Vec<f64> {
+            vec![self.value]
+        }
+    }
+
+    struct Breeder;
+
+    impl Breed<Genome> for Breeder {
+        fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+            pair.0.clone()
+        }
+
+        fn mutate(&self, genome: Genome) -> Genome {
+            Genome {
+                value: genome.value + rng::handle().gen_range(-1.0..1.0),
+            }
+        }
+    }
+
+    fn run(seed: u64) -> Run<Genome, Breeder> {
+        Run::builder()
+            .breeder(Breeder)
+            .fitness_calc(
+                FitnessCalc::builder()
+                    .add_training_record(TrainingRecord {
+                        input: vec![0.0],
+                        output: vec![0.0],
+                        weight: 1.0,
+                    })
+                    .build(),
+            )
+            .elitism(2)
+            .seed(seed)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_resume_continues_seeded_run() {
+        let initial = (0..8).map(|value| Genome {
+            value: f64::from(value),
+        });
+        let original = run(5);
+        let halfway = (0..3).fold(initial.collect(), |generation, _| {
+            original.run(generation).generation
+        });
+        let mut saved = vec![];
+        original.checkpoint(&halfway, &mut saved).unwrap();
+        let expected = (0..3).fold(halfway, |generation, _| original.run(generation).generation);
+
+        let mut resumed = run(6);
+        let generation = resumed.resume(saved.as_slice()).unwrap();
+        let actual = (0..3).fold(generation, |generation, _| {
+            resumed.run(generation).generation
+        });
+
+        assert_eq!(actual, expected);
+        assert_eq!(resumed.elitism, 2);
+    }
+
+    #[test]
+    fn test_resume_rejects_newer_version() {
+        let json = r#"{"version":99,"generation":0,"settings":{"elitism":1,
+            "tournament_size":10,"mate_attempts":5,"seed":null},"population":[]}"#;
+
+        let error = run(1).resume(json.as_bytes()).unwrap_err();
+
+        assert!(matches!(
+            error,
+            Error::UnsupportedVersion {
+                found: 99,
+                supported: VERSION
+            }
+        ));
+    }
+}
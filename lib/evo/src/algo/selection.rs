@@ -0,0 +1,286 @@
+use super::Tournament;
+use crate::{rng, CompareRecord, Predict};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// How parents are selected for breeding.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Selection {
+    /// The fittest of a random sample of genomes, sized by the tournament
+    /// size. This is the default.
+    #[default]
+    Tournament,
+
+    /// Lexicase selection: genomes are filtered through the training records
+    /// in a random order, keeping only those with the lowest error on each
+    /// record, until one is left.
+    ///
+    /// Genomes that solve different records all get to breed, which keeps
+    /// specialists alive that an averaged fitness would discard. Each
+    /// generation's genomes are checked once more to get their error on
+    /// each record, so this needs the fitness calculator's training records.
+    /// Within an evaluation budget, those checks count against it, and
+    /// tournament selection is used once it cannot cover them.
+    Lexicase,
+
+    /// Boltzmann selection: genomes are picked with a probability that falls
+    /// exponentially with how much worse their fitness is than the best, over
+    /// a temperature.
+    ///
+    /// A high temperature picks almost evenly, exploring, while a low one
+    /// almost always picks the fittest, exploiting. The temperature is set by
+    /// a schedule on the algorithm's builder, so it can cool as the run goes
+    /// on.
+    Boltzmann,
+}
+
+/// Selects parents from a ranked generation, as configured by a
+/// [`Selection`].
+pub(crate) enum Selector {
+    Tournament(Tournament),
+    Lexicase(Lexicase),
+    Boltzmann(Boltzmann),
+}
+
+impl Selector {
+    /// Select a parent.
+    ///
+    /// # Arguments
+    ///
+    /// - `candidates` is the ranked generation to select from.
+    ///
+    /// # Returns
+    ///
+    /// The selected candidate, or `None` if there are none.
+    pub fn select<'x, TGenome>(
+        &self,
+        candidates: &'x [CompareRecord<TGenome>],
+    ) -> Option<&'x CompareRecord<TGenome>>
+    where
+        TGenome: Predict + PartialOrd,
+    {
+        match self {
+            Self::Tournament(tournament) => tournament.select(candidates),
+            Self::Lexicase(lexicase) => lexicase.select(candidates),
+            Self::Boltzmann(boltzmann) => boltzmann.select(candidates),
+        }
+    }
+}
+
+/// Lexicase selection over the errors of one generation.
+pub(crate) struct Lexicase {
+    /// The error of each candidate on each training record, or `None` for
+    /// candidates whose errors could not be calculated.
+    errors: Vec<Option<Vec<f64>>>,
+}
+
+impl Lexicase {
+    /// Create a lexicase selector.
+    ///
+    /// # Arguments
+    ///
+    /// - `errors` is the error of each candidate on each training record, in
+    ///   the order of the candidates; `None` excludes a candidate.
+    ///
+    /// # Returns
+    ///
+    /// The selector.
+    pub fn new(errors: Vec<Option<Vec<f64>>>) -> Self {
+        Self { errors }
+    }
+
+    /// Select a candidate.
+    ///
+    /// # Arguments
+    ///
+    /// - `candidates` is the generation the errors were calculated for.
+    ///
+    /// # Returns
+    ///
+    /// The selected candidate, or `None` if no candidate has errors.
+    pub fn select<'x, TGenome>(
+        &self,
+        candidates: &'x [CompareRecord<TGenome>],
+    ) -> Option<&'x CompareRecord<TGenome>>
+    where
+        TGenome: Predict + PartialOrd,
+    {
+        let mut pool = self
+            .errors
+            .iter()
+            .take(candidates.len())
+            .enumerate()
+            .filter_map(|(index, errors)| Some((index, errors.as_deref()?)))
+            .collect::<Vec<_>>();
+        let records = pool.iter().map(|(_, errors)| errors.len()).max()?;
+
+        let mut order = (0..records).collect::<Vec<_>>();
+        order.shuffle(&mut rng::handle());
+        for record in order {
+            if pool.len() <= 1 {
+                break;
+            }
+            // Missing errors, e.g. from a record added mid-generation, lose.
+            let error = |errors: &[f64]| errors.get(record).copied().unwrap_or(f64::INFINITY);
+            let best = pool
+                .iter()
+                .map(|(_, errors)| error(errors))
+                .fold(f64::INFINITY, f64::min);
+            pool.retain(|(_, errors)| error(errors) <= best);
+        }
+
+        let (index, _) = pool[rng::handle().gen_range(0..pool.len())];
+        candidates.get(index)
+    }
+}
+
+/// Boltzmann selection over the fitness of one generation.
+pub(crate) struct Boltzmann {
+    /// Picks a candidate's index, or `None` if no candidate can be picked.
+    weights: Option<WeightedIndex<f64>>,
+}
+
+impl Boltzmann {
+    /// Create a Boltzmann selector.
+    ///
+    /// # Arguments
+    ///
+    /// - `candidates` is the generation to select from.
+    /// - `temperature` is the temperature, in units of fitness. At or below
+    ///   zero, only the fittest candidates are picked.
+    ///
+    /// # Returns
+    ///
+    /// The selector.
+    pub fn new<TGenome>(candidates: &[CompareRecord<TGenome>], temperature: f64) -> Self
+    where
+        TGenome: Predict + PartialOrd,
+    {
+        let best = candidates
+            .iter()
+            .map(|record| record.fitness)
+            .fold(f64::INFINITY, f64::min);
+        let weights = candidates.iter().map(|record| {
+            let weight = if temperature > 0.0 {
+                (-(record.fitness - best) / temperature).exp()
+            } else if record.fitness <= best {
+                1.0
+            } else {
+                0.0
+            };
+            if weight.is_finite() {
+                weight
+            } else {
+                0.0
+            }
+        });
+
+        Self {
+            weights: WeightedIndex::new(weights).ok(),
+        }
+    }
+
+    /// Select a candidate.
+    ///
+    /// # Arguments
+    ///
+    /// - `candidates` is the generation the selector was created for.
+    ///
+    /// # Returns
+    ///
+    /// The selected candidate, or `None` if there are none.
+    pub fn select<'x, TGenome>(
+        &self,
+        candidates: &'x [CompareRecord<TGenome>],
+    ) -> Option<&'x CompareRecord<TGenome>>
+    where
+        TGenome: Predict + PartialOrd,
+    {
+        let index = self.weights.as_ref()?.sample(&mut rng::handle());
+        candidates.get(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, PartialOrd)]
+    struct Genome(usize);
+
+    impl Predict for Genome {
+        fn predict(&self, _input: &[f64]) -> Vec<f64> {
+            vec![]
+        }
+    }
+
+    fn candidates(count: usize) -> Vec<CompareRecord<Genome>> {
+        ranked(&vec![0.0; count])
+    }
+
+    fn ranked(fitness: &[f64]) -> Vec<CompareRecord<Genome>> {
+        fitness
+            .iter()
+            .enumerate()
+            .map(|(index, &fitness)| CompareRecord {
+                fitness,
+                predict: Genome(index),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lexicase_selects_specialists() {
+        // The first two genomes are each best on one record; the third is
+        // better on average, but best on neither.
+        let lexicase = Lexicase::new(vec![
+            Some(vec![0.0, 9.0]),
+            Some(vec![9.0, 0.0]),
+            Some(vec![1.0, 1.0]),
+            None,
+        ]);
+        let candidates = candidates(4);
+
+        let mut selected = [0; 4];
+        for _ in 0..100 {
+            selected[lexicase.select(&candidates).unwrap().predict.0] += 1;
+        }
+
+        assert!(selected[0] > 0 && selected[1] > 0, "{selected:?}");
+        assert_eq!(selected[2..], [0, 0]);
+    }
+
+    #[test]
+    fn test_lexicase_without_errors() {
+        assert!(Lexicase::new(vec![None]).select(&candidates(1)).is_none());
+        assert!(Lexicase::new(vec![]).select(&candidates(0)).is_none());
+    }
+
+    #[test]
+    fn test_boltzmann_temperature() {
+        let candidates = ranked(&[0.0, 1.0, 2.0, f64::NAN]);
+        let count = |temperature| {
+            let boltzmann = Boltzmann::new(&candidates, temperature);
+            let mut selected = [0; 4];
+            for _ in 0..1000 {
+                selected[boltzmann.select(&candidates).unwrap().predict.0] += 1;
+            }
+            selected
+        };
+
+        let hot = count(100.0);
+        assert!(hot[..3].iter().all(|&count| count > 200), "{hot:?}");
+        let cold = count(0.1);
+        assert!(cold[0] > 950, "{cold:?}");
+        assert_eq!(count(0.0), [1000, 0, 0, 0]);
+        assert_eq!(hot[3] + cold[3], 0);
+    }
+
+    #[test]
+    fn test_boltzmann_without_candidates() {
+        assert!(Boltzmann::new(&candidates(0), 1.0)
+            .select(&candidates(0))
+            .is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{CompareRecord, Predict};
+use crate::{CompareRecord, NanSafeCompare, Predict};
 
 /// Sort a generation of candidates by fitness.
 ///
@@ -14,12 +14,63 @@ where
     TGenome: Predict + PartialOrd,
 {
     let mut candidates = candidates;
-    candidates.sort_by(|left, right| {
-        PartialOrd::partial_cmp(left, right).unwrap_or(std::cmp::Ordering::Equal)
-    });
+    candidates.sort_by(compare);
     candidates
 }
 
+/// Split the fittest candidates from the rest of a generation.
+///
+/// Only the fittest candidates are sorted, so this takes linear time when
+/// `count` is small compared to the generation.
+///
+/// # Arguments
+///
+/// * `candidates` - A vector of `CompareRecord` candidates.
+/// * `count` - The number of candidates to split off.
+///
+/// # Returns
+///
+/// The fittest `count` candidates sorted by fitness, and the rest in no
+/// particular order.
+pub fn partition<TGenome>(
+    mut candidates: Vec<CompareRecord<TGenome>>,
+    count: usize,
+) -> (Vec<CompareRecord<TGenome>>, Vec<CompareRecord<TGenome>>)
+where
+    TGenome: Predict + PartialOrd,
+{
+    let count = count.min(candidates.len());
+    if count == 0 {
+        return (vec![], candidates);
+    }
+    if count < candidates.len() {
+        candidates.select_nth_unstable_by(count - 1, compare);
+    }
+
+    let rest = candidates.split_off(count);
+    (generation(candidates), rest)
+}
+
+/// Compare two candidates by fitness, ranking `NaN` or infinite fitness last.
+///
+/// # Arguments
+///
+/// * `left` - The first candidate.
+/// * `right` - The second candidate.
+///
+/// # Returns
+///
+/// The order of the candidates.
+fn compare<TGenome>(
+    left: &CompareRecord<TGenome>,
+    right: &CompareRecord<TGenome>,
+) -> std::cmp::Ordering
+where
+    TGenome: Predict + PartialOrd,
+{
+    NanSafeCompare::fitness(left.fitness, right.fitness)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,6 +94,7 @@ mod tests {
 
     #[test]
     fn test_sort_generation() {
+        #[allow(non_local_definitions)]
         impl std::fmt::Debug for CompareRecord<Predictor> {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 f.debug_struct("CompareRecord")
@@ -83,4 +135,45 @@ mod tests {
         let actual = generation(candidates);
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_partition_generation() {
+        let candidates = [4.0, 1.0, 5.0, 3.0, 2.0]
+            .into_iter()
+            .map(|value| CompareRecord {
+                fitness: value,
+                predict: Predictor { value },
+            })
+            .collect::<Vec<_>>();
+
+        let (best, rest) = partition(candidates, 2);
+
+        let fitness = |records: &[CompareRecord<Predictor>]| {
+            records
+                .iter()
+                .map(|record| record.fitness)
+                .collect::<Vec<_>>()
+        };
+        let mut rest = fitness(&rest);
+        rest.sort_by(f64::total_cmp);
+        assert_eq!(fitness(&best), vec![1.0, 2.0]);
+        assert_eq!(rest, vec![3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_sort_generation_nan_last() {
+        let candidates = [f64::NAN, 2.0, f64::INFINITY, 1.0]
+            .into_iter()
+            .map(|value| CompareRecord {
+                fitness: value,
+                predict: Predictor { value },
+            })
+            .collect::<Vec<_>>();
+
+        let sorted = generation(candidates);
+
+        assert!((sorted[0].fitness - 1.0).abs() < f64::EPSILON);
+        assert!((sorted[1].fitness - 2.0).abs() < f64::EPSILON);
+        assert!(sorted[2..].iter().all(|record| !record.fitness.is_finite()));
+    }
 }
@@ -0,0 +1,271 @@
+use crate::stats::History;
+use crate::{CompareRecord, Generation, Predict};
+use std::time::Duration;
+
+/// When to stop evolving.
+///
+/// Evolution stops as soon as any criterion is met, checked after each
+/// generation is scored. Set at least one criterion, or evolution only stops
+/// if the population dies out.
+///
+/// # Examples
+///
+/// ```
+/// use evo::Termination;
+/// use std::time::Duration;
+///
+/// let termination = Termination::builder()
+///     .generations(500)
+///     .target_fitness(0.01)
+///     .time_limit(Duration::from_secs(60))
+///     .stagnation(50)
+///     .build();
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Termination {
+    generations: Option<usize>,
+    target_fitness: Option<f64>,
+    time_limit: Option<Duration>,
+    stagnation: Option<usize>,
+    validation_stagnation: Option<usize>,
+}
+
+/// Why evolution stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stop {
+    /// The maximum number of generations were bred.
+    Generations,
+
+    /// A genome reached the target fitness.
+    TargetFitness,
+
+    /// The time limit ran out.
+    TimeLimit,
+
+    /// The best fitness stopped improving.
+    Stagnation,
+
+    /// The validation fitness of the fittest genome stopped improving.
+    ValidationStagnation,
+
+    /// No genome in a generation could be scored.
+    Extinct,
+
+    /// An observer asked to stop.
+    Observer,
+
+    /// The evaluation budget was spent. The last generation may have been
+    /// scored only in part, or bred but not yet scored; its unscored genomes
+    /// come last.
+    Budget,
+}
+
+/// The result of evolving a population.
+pub struct Evolution<TGenome>
+where
+    TGenome: Predict + PartialOrd,
+{
+    /// The last generation, which was scored but not bred, unless the
+    /// budget ran out first.
+    pub generation: Generation<TGenome>,
+
+    /// The fittest genome seen in any generation, with its fitness.
+    pub best: Option<CompareRecord<TGenome>>,
+
+    /// The statistics of each generation, starting with the initial one.
+    pub history: History,
+
+    /// Why evolution stopped.
+    pub stop: Stop,
+
+    /// The number of genomes scored while evolving, including offspring
+    /// scored while breeding.
+    pub evaluations: usize,
+}
+
+impl Termination {
+    /// Create a new builder.
+    ///
+    /// # Returns
+    ///
+    /// A new builder.
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Check whether evolution should stop.
+    ///
+    /// # Arguments
+    ///
+    /// - `best` is the best fitness of the last generation.
+    /// - `generations` is the number of generations scored so far.
+    /// - `stagnant` is the number of generations since the best fitness
+    ///   improved.
+    /// - `validation_stagnant` is the number of generations since the
+    ///   validation fitness improved.
+    /// - `elapsed` is the time spent evolving so far.
+    ///
+    /// # Returns
+    ///
+    /// The first criterion that was met, if any.
+    pub(crate) fn check(
+        &self,
+        best: f64,
+        generations: usize,
+        stagnant: usize,
+        validation_stagnant: usize,
+        elapsed: Duration,
+    ) -> Option<Stop> {
+        if self.target_fitness.is_some_and(|target| best <= target) {
+            Some(Stop::TargetFitness)
+        } else if self.generations.is_some_and(|limit| generations > limit) {
+            Some(Stop::Generations)
+        } else if self.stagnation.is_some_and(|limit| stagnant >= limit) {
+            Some(Stop::Stagnation)
+        } else if self
+            .validation_stagnation
+            .is_some_and(|limit| validation_stagnant >= limit)
+        {
+            Some(Stop::ValidationStagnation)
+        } else if self.time_limit.is_some_and(|limit| elapsed >= limit) {
+            Some(Stop::TimeLimit)
+        } else {
+            None
+        }
+    }
+}
+
+/// A builder for termination criteria.
+#[derive(Default)]
+pub struct Builder {
+    termination: Termination,
+}
+
+impl Builder {
+    /// Stop once this many generations have been bred from the initial one.
+    ///
+    /// # Arguments
+    ///
+    /// - `generations` is the maximum number of generations.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn generations(mut self, generations: usize) -> Self {
+        self.termination.generations = Some(generations);
+        self
+    }
+
+    /// Stop once a genome's fitness is at or below a target.
+    ///
+    /// # Arguments
+    ///
+    /// - `target_fitness` is the target.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn target_fitness(mut self, target_fitness: f64) -> Self {
+        self.termination.target_fitness = Some(target_fitness);
+        self
+    }
+
+    /// Stop once evolving has taken this long. The generation in progress
+    /// is finished first.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_limit` is the time limit.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn time_limit(mut self, time_limit: Duration) -> Self {
+        self.termination.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Stop once the best fitness has not improved for this many
+    /// generations.
+    ///
+    /// # Arguments
+    ///
+    /// - `generations` is the number of generations without improvement.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn stagnation(mut self, generations: usize) -> Self {
+        self.termination.stagnation = Some(generations);
+        self
+    }
+
+    /// Stop once the validation fitness of the fittest genome has not
+    /// improved for this many generations, i.e. once the genomes have
+    /// started to overfit. Needs a fitness calculator with validation
+    /// records.
+    ///
+    /// # Arguments
+    ///
+    /// - `generations` is the number of generations without improvement.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn validation_stagnation(mut self, generations: usize) -> Self {
+        self.termination.validation_stagnation = Some(generations);
+        self
+    }
+
+    /// Build the termination criteria.
+    ///
+    /// # Returns
+    ///
+    /// The termination criteria.
+    #[must_use]
+    pub fn build(self) -> Termination {
+        self.termination
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let termination = Termination::builder()
+            .generations(3)
+            .target_fitness(0.5)
+            .time_limit(Duration::from_secs(10))
+            .stagnation(2)
+            .validation_stagnation(3)
+            .build();
+        let check = |best, generations, stagnant, validation_stagnant, seconds| {
+            termination.check(
+                best,
+                generations,
+                stagnant,
+                validation_stagnant,
+                Duration::from_secs(seconds),
+            )
+        };
+
+        assert_eq!(check(1.0, 2, 0, 0, 0), None);
+        assert_eq!(check(0.5, 2, 0, 0, 0), Some(Stop::TargetFitness));
+        assert_eq!(check(1.0, 4, 0, 0, 0), Some(Stop::Generations));
+        assert_eq!(check(2.0, 3, 2, 0, 0), Some(Stop::Stagnation));
+        assert_eq!(check(2.0, 3, 1, 3, 0), Some(Stop::ValidationStagnation));
+        assert_eq!(check(2.0, 1, 0, 0, 10), Some(Stop::TimeLimit));
+        assert_eq!(
+            Termination::default().check(2.0, 100, 100, 100, Duration::from_secs(100)),
+            None
+        );
+    }
+}
@@ -1,9 +1,10 @@
-use crate::{CompareRecord, Predict};
-use rand::prelude::*;
+use crate::{rng, CompareRecord, NanSafeCompare, Predict};
+use rand::Rng;
 
 /// Tournament selection algorithm.
 pub struct Tournament {
     tournament_size: usize,
+    win_probability: f64,
 }
 
 impl Tournament {
@@ -17,7 +18,30 @@ impl Tournament {
     ///
     /// The tournament selection algorithm.
     pub fn new(tournament_size: usize) -> Self {
-        Self { tournament_size }
+        Self {
+            tournament_size,
+            win_probability: 1.0,
+        }
+    }
+
+    /// Set the probability that the best candidate wins the tournament.
+    ///
+    /// The best candidate wins with probability `p`, the second best with
+    /// `p(1 - p)`, and so on, with the worst taking what is left. Below 1,
+    /// weaker genomes sometimes breed, which slows the loss of diversity in
+    /// small populations.
+    ///
+    /// # Arguments
+    ///
+    /// * `win_probability` - The probability, from 0 to 1. Defaults to 1,
+    ///   where the best candidate always wins.
+    ///
+    /// # Returns
+    ///
+    /// The tournament selection algorithm.
+    pub fn with_win_probability(mut self, win_probability: f64) -> Self {
+        self.win_probability = win_probability;
+        self
     }
 
     /// Select a candidate from a list of candidates.
@@ -36,12 +60,16 @@ impl Tournament {
     where
         TGenome: Predict + PartialOrd,
     {
-        let mut winner = None;
+        if self.win_probability < 1.0 {
+            return self.select_ranked(candidates);
+        }
+
+        let mut winner: Option<&CompareRecord<TGenome>> = None;
         for candidate in self.tournament_iter(candidates) {
             winner = Some(match winner {
                 None => candidate,
-                Some(winner) => match PartialOrd::partial_cmp(winner, candidate) {
-                    Some(std::cmp::Ordering::Less) => winner,
+                Some(winner) => match NanSafeCompare::fitness(winner.fitness, candidate.fitness) {
+                    std::cmp::Ordering::Less => winner,
                     _ => candidate,
                 },
             });
@@ -50,6 +78,36 @@ impl Tournament {
         winner
     }
 
+    /// Select a candidate by rank, giving each candidate in turn, from best
+    /// to worst, the win probability.
+    ///
+    /// # Arguments
+    ///
+    /// * `candidates` - The list of candidates to select from.
+    ///
+    /// # Returns
+    ///
+    /// The selected candidate.
+    fn select_ranked<'x, TGenome>(
+        &self,
+        candidates: &'x [CompareRecord<TGenome>],
+    ) -> Option<&'x CompareRecord<TGenome>>
+    where
+        TGenome: Predict + PartialOrd,
+    {
+        let mut entrants = self.tournament_iter(candidates).collect::<Vec<_>>();
+        entrants.sort_by(|left, right| NanSafeCompare::fitness(left.fitness, right.fitness));
+
+        let last = entrants.pop()?;
+        let mut rng = rng::handle();
+        Some(
+            entrants
+                .into_iter()
+                .find(|_| rng.gen_bool(self.win_probability.clamp(0.0, 1.0)))
+                .unwrap_or(last),
+        )
+    }
+
     /// Create an iterator over the tournament candidates.
     ///
     /// # Arguments
@@ -68,11 +126,10 @@ impl Tournament {
     {
         let tournament_size = self.tournament_size(candidates);
 
-        let mut indexes = (0..candidates.len()).collect::<Vec<_>>();
-        indexes.shuffle(&mut thread_rng());
-        indexes
+        // Samples only `tournament_size` distinct indexes (Floyd's algorithm or
+        // a partial shuffle) instead of shuffling the whole population.
+        rand::seq::index::sample(&mut rng::handle(), candidates.len(), tournament_size)
             .into_iter()
-            .take(tournament_size)
             .map(move |id| &candidates[id])
     }
 
@@ -203,4 +260,53 @@ mod tests {
 
         assert_eq!(result, 2);
     }
+
+    #[test]
+    fn test_tournament_iter_is_distinct() {
+        let candidates = (0..100)
+            .map(|value| CompareRecord {
+                fitness: f64::from(value),
+                predict: Predictor {
+                    value: f64::from(value),
+                },
+            })
+            .collect::<Vec<_>>();
+
+        let mut selected = Tournament::new(10)
+            .tournament_iter(&candidates)
+            .map(|candidate| candidate.fitness)
+            .collect::<Vec<_>>();
+        selected.sort_by(f64::total_cmp);
+        selected.dedup();
+
+        assert_eq!(selected.len(), 10);
+    }
+
+    #[test]
+    fn test_tournament_win_probability() {
+        let candidates = (0..3)
+            .map(|value| CompareRecord {
+                fitness: f64::from(value),
+                predict: Predictor {
+                    value: f64::from(value),
+                },
+            })
+            .collect::<Vec<_>>();
+        let tournament = Tournament::new(3).with_win_probability(0.5);
+
+        let mut wins = [0; 3];
+        for _ in 0..4000 {
+            let winner = tournament.select(&candidates).unwrap();
+            wins[candidates.iter().position(|c| c == winner).unwrap()] += 1;
+        }
+
+        // The best wins half the time, and the others a quarter each.
+        assert!((1800..2200).contains(&wins[0]), "{wins:?}");
+        assert!((800..1200).contains(&wins[1]), "{wins:?}");
+        assert!((800..1200).contains(&wins[2]), "{wins:?}");
+
+        let never = Tournament::new(3).with_win_probability(0.0);
+        assert_eq!(never.select(&candidates), Some(&candidates[2]));
+        assert!(never.select::<Predictor>(&[]).is_none());
+    }
 }
@@ -1,3 +1,7 @@
+mod pedigree;
+
+pub use self::pedigree::{Breeder as PedigreeBreeder, Pedigree};
+
 /// Breeder trait
 ///
 /// # Examples
@@ -38,6 +42,20 @@ pub trait Breed<TGenome> {
     /// The offspring genome.
     fn crossover(&self, pair: (&TGenome, &TGenome)) -> TGenome;
 
+    /// Crossover two genomes into a spare genome.
+    ///
+    /// The algorithm passes genomes that it would otherwise drop, so breeders
+    /// can reuse their allocations instead of building each offspring from
+    /// scratch. The default replaces the spare with [`Breed::crossover`].
+    ///
+    /// # Arguments
+    ///
+    /// - `pair` is the pair of genomes to crossover.
+    /// - `offspring` is the spare genome to overwrite with the offspring.
+    fn crossover_into(&self, pair: (&TGenome, &TGenome), offspring: &mut TGenome) {
+        *offspring = self.crossover(pair);
+    }
+
     /// Mutate a genome.
     ///
     /// # Arguments
@@ -82,6 +100,107 @@ pub trait Breed<TGenome> {
         genome
     }
 
+    /// Check whether two genomes may be bred together.
+    ///
+    /// # Arguments
+    ///
+    /// - `pair` is the pair of genomes to check.
+    ///
+    /// # Returns
+    ///
+    /// True if the genomes may be bred, false if another mate should be found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::Breed;
+    ///
+    /// struct Genome {
+    ///     value: f64,
+    /// }
+    ///
+    /// struct Breeder;
+    ///
+    /// impl Breed<Genome> for Breeder {
+    ///     fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+    ///         Genome {
+    ///             value: f64::midpoint(pair.0.value, pair.1.value),
+    ///         }
+    ///     }
+    ///
+    ///     fn compatible(&self, pair: (&Genome, &Genome)) -> bool {
+    ///         (pair.0.value - pair.1.value).abs() > f64::EPSILON
+    ///     }
+    /// }
+    ///
+    /// let breeder = Breeder;
+    /// assert!(breeder.compatible((&Genome { value: 1.0 }, &Genome { value: 2.0 })));
+    /// assert!(!breeder.compatible((&Genome { value: 1.0 }, &Genome { value: 1.0 })));
+    /// ```
+    fn compatible(&self, _pair: (&TGenome, &TGenome)) -> bool {
+        true
+    }
+
+    /// Set the mutation pressure. With adaptive mutation enabled, the
+    /// algorithm sets it before breeding each generation, raising it when the
+    /// generation's diversity collapses.
+    ///
+    /// Breeders that support it scale their mutation rate by the pressure.
+    /// The default ignores it.
+    ///
+    /// # Arguments
+    ///
+    /// - `pressure` is the factor to scale the mutation rate by: 1 for the
+    ///   configured rate, more to mutate more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::Breed;
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    ///
+    /// struct Genome {
+    ///     value: f64,
+    /// }
+    ///
+    /// struct Breeder {
+    ///     pressure: AtomicU64,
+    /// }
+    ///
+    /// impl Breed<Genome> for Breeder {
+    ///     fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+    ///         Genome {
+    ///             value: f64::midpoint(pair.0.value, pair.1.value),
+    ///         }
+    ///     }
+    ///
+    ///     fn mutate(&self, mut genome: Genome) -> Genome {
+    ///         genome.value += 0.1 * f64::from_bits(self.pressure.load(Ordering::Relaxed));
+    ///         genome
+    ///     }
+    ///
+    ///     fn set_pressure(&self, pressure: f64) {
+    ///         self.pressure.store(pressure.to_bits(), Ordering::Relaxed);
+    ///     }
+    /// }
+    ///
+    /// let breeder = Breeder { pressure: AtomicU64::new(1.0f64.to_bits()) };
+    /// breeder.set_pressure(2.0);
+    /// let mutated = breeder.mutate(Genome { value: 0.0 });
+    /// assert!((mutated.value - 0.2).abs() < f64::EPSILON);
+    /// ```
+    fn set_pressure(&self, _pressure: f64) {}
+
+    /// Set the generation being bred. The algorithm sets it before breeding
+    /// each generation, so breeders can follow schedules, such as a mutation
+    /// rate that decays as the run goes on. The default ignores it.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation` is the number of generations run before the parent
+    ///   generation.
+    fn set_generation(&self, _generation: usize) {}
+
     /// Convert this breeder into a manager.
     ///
     /// # Returns
@@ -219,6 +338,55 @@ where
         let offspring = self.breeder.crossover((left, right));
         self.breeder.mutate(offspring)
     }
+
+    /// Breed two genomes into a spare genome, reusing its allocations.
+    ///
+    /// # Arguments
+    ///
+    /// - `left` is the left genome.
+    /// - `right` is the right genome.
+    /// - `spare` is a genome that is no longer needed.
+    ///
+    /// # Returns
+    ///
+    /// The offspring genome.
+    pub fn breed_into(&self, left: &TGenome, right: &TGenome, mut spare: TGenome) -> TGenome {
+        self.breeder.crossover_into((left, right), &mut spare);
+        self.breeder.mutate(spare)
+    }
+
+    /// Check whether two genomes may be bred together.
+    ///
+    /// # Arguments
+    ///
+    /// - `left` is the left genome.
+    /// - `right` is the right genome.
+    ///
+    /// # Returns
+    ///
+    /// True if the breeder accepts the pair.
+    pub fn compatible(&self, left: &TGenome, right: &TGenome) -> bool {
+        self.breeder.compatible((left, right))
+    }
+
+    /// Set the breeder's mutation pressure.
+    ///
+    /// # Arguments
+    ///
+    /// - `pressure` is the factor to scale the mutation rate by.
+    pub fn set_pressure(&self, pressure: f64) {
+        self.breeder.set_pressure(pressure);
+    }
+
+    /// Set the generation the breeder is breeding.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation` is the number of generations run before the parent
+    ///   generation.
+    pub fn set_generation(&self, generation: usize) {
+        self.breeder.set_generation(generation);
+    }
 }
 
 #[cfg(test)]
@@ -235,6 +403,7 @@ mod tests {
         struct Breeder;
 
         impl Breed<Genome> for Breeder {
+            #[allow(clippy::manual_midpoint)]
             fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
                 Genome {
                     value: (pair.0.value + pair.1.value) / 2.0,
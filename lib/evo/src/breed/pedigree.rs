@@ -0,0 +1,311 @@
+use super::Breed;
+use crate::Predict;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The source of unique genome identifiers.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A genome annotated with its identity and recent ancestry.
+///
+/// # Examples
+///
+/// ```
+/// use evo::Pedigree;
+///
+/// let left = Pedigree::new(1.0);
+/// let right = Pedigree::new(2.0);
+///
+/// assert!(!left.is_related(&right));
+/// assert!(left.is_related(&left));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Pedigree<TGenome> {
+    /// The annotated genome.
+    genome: TGenome,
+
+    /// The unique identifier for this genome.
+    id: u64,
+
+    /// The identifiers of this genome's ancestors, with the number of
+    /// generations separating them from this genome.
+    ancestors: Vec<(u64, usize)>,
+}
+
+impl<TGenome> Pedigree<TGenome> {
+    /// Annotate a genome that has no known ancestors.
+    ///
+    /// # Arguments
+    ///
+    /// - `genome` is the genome to annotate.
+    ///
+    /// # Returns
+    ///
+    /// The annotated genome.
+    pub fn new(genome: TGenome) -> Self {
+        Self {
+            genome,
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            ancestors: vec![],
+        }
+    }
+
+    /// Annotate the offspring of two genomes.
+    ///
+    /// # Arguments
+    ///
+    /// - `genome` is the offspring genome.
+    /// - `parents` are the annotated parents of the offspring.
+    /// - `depth` is the number of generations of ancestry to remember.
+    ///
+    /// # Returns
+    ///
+    /// The annotated offspring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::Pedigree;
+    ///
+    /// let left = Pedigree::new(1.0);
+    /// let right = Pedigree::new(2.0);
+    /// let child = Pedigree::offspring(1.5, (&left, &right), 2);
+    ///
+    /// assert!(child.is_related(&left));
+    /// assert!(child.is_related(&right));
+    /// ```
+    pub fn offspring(genome: TGenome, parents: (&Self, &Self), depth: usize) -> Self {
+        Self {
+            genome,
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            ancestors: Self::lineage(parents, depth),
+        }
+    }
+
+    /// Collect the ancestors of the offspring of two genomes.
+    ///
+    /// # Arguments
+    ///
+    /// - `parents` are the annotated parents of the offspring.
+    /// - `depth` is the number of generations of ancestry to remember.
+    ///
+    /// # Returns
+    ///
+    /// The identifiers of the ancestors, with their distance from the offspring.
+    fn lineage(parents: (&Self, &Self), depth: usize) -> Vec<(u64, usize)> {
+        let mut ancestors: Vec<(u64, usize)> = vec![];
+        if depth > 0 {
+            let (left, right) = parents;
+            let lineage = [(left.id, 0), (right.id, 0)]
+                .into_iter()
+                .chain(left.ancestors.iter().copied())
+                .chain(right.ancestors.iter().copied());
+
+            for (id, distance) in lineage {
+                let distance = distance + 1;
+                if distance > depth {
+                    continue;
+                }
+                match ancestors.iter_mut().find(|(known, _)| *known == id) {
+                    Some(known) => known.1 = usize::min(known.1, distance),
+                    None => ancestors.push((id, distance)),
+                }
+            }
+        }
+        ancestors
+    }
+
+    /// Get the annotated genome.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the genome.
+    pub fn genome(&self) -> &TGenome {
+        &self.genome
+    }
+
+    /// Remove the annotation.
+    ///
+    /// # Returns
+    ///
+    /// The genome.
+    pub fn into_genome(self) -> TGenome {
+        self.genome
+    }
+
+    /// Get the unique identifier for this genome.
+    ///
+    /// # Returns
+    ///
+    /// The identifier.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Check whether two genomes are the same genome, or share a remembered ancestor.
+    ///
+    /// # Arguments
+    ///
+    /// - `other` is the genome to compare against.
+    ///
+    /// # Returns
+    ///
+    /// True if the genomes are related.
+    pub fn is_related(&self, other: &Self) -> bool {
+        let is_ancestor = |genome: &Self, id: u64| genome.ancestors.iter().any(|(a, _)| *a == id);
+
+        self.id == other.id
+            || is_ancestor(self, other.id)
+            || is_ancestor(other, self.id)
+            || self.ancestors.iter().any(|(id, _)| is_ancestor(other, *id))
+    }
+}
+
+impl<TGenome> Predict for Pedigree<TGenome>
+where
+    TGenome: Predict,
+{
+    fn predict(&self, input: &[f64]) -> Vec<f64> {
+        self.genome.predict(input)
+    }
+}
+
+impl<TGenome> PartialEq for Pedigree<TGenome>
+where
+    TGenome: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.genome.eq(&other.genome)
+    }
+}
+
+impl<TGenome> PartialOrd for Pedigree<TGenome>
+where
+    TGenome: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.genome.partial_cmp(&other.genome)
+    }
+}
+
+/// A breeder that records ancestry and refuses to breed related genomes.
+///
+/// # Examples
+///
+/// ```
+/// use evo::{Breed, Pedigree, PedigreeBreeder};
+///
+/// struct Breeder;
+///
+/// impl Breed<f64> for Breeder {
+///     fn crossover(&self, pair: (&f64, &f64)) -> f64 {
+///         f64::midpoint(*pair.0, *pair.1)
+///     }
+/// }
+///
+/// let breeder = PedigreeBreeder::new(Breeder, 2);
+///
+/// let left = Pedigree::new(1.0);
+/// let right = Pedigree::new(2.0);
+/// let child = breeder.crossover((&left, &right));
+///
+/// assert_eq!(child.genome(), &1.5);
+/// assert!(breeder.compatible((&left, &right)));
+/// assert!(!breeder.compatible((&left, &child)));
+/// ```
+pub struct Breeder<TBreeder> {
+    breeder: TBreeder,
+    depth: usize,
+}
+
+impl<TBreeder> Breeder<TBreeder> {
+    /// Create a new pedigree breeder.
+    ///
+    /// # Arguments
+    ///
+    /// - `breeder` is the breeder that combines the underlying genomes.
+    /// - `depth` is the number of generations of shared ancestry that count
+    ///   as inbreeding.
+    ///
+    /// # Returns
+    ///
+    /// The breeder.
+    pub fn new(breeder: TBreeder, depth: usize) -> Self {
+        Self { breeder, depth }
+    }
+}
+
+impl<TGenome, TBreeder> Breed<Pedigree<TGenome>> for Breeder<TBreeder>
+where
+    TBreeder: Breed<TGenome>,
+{
+    fn crossover(&self, pair: (&Pedigree<TGenome>, &Pedigree<TGenome>)) -> Pedigree<TGenome> {
+        let genome = self.breeder.crossover((&pair.0.genome, &pair.1.genome));
+        Pedigree::offspring(genome, pair, self.depth)
+    }
+
+    fn crossover_into(
+        &self,
+        pair: (&Pedigree<TGenome>, &Pedigree<TGenome>),
+        offspring: &mut Pedigree<TGenome>,
+    ) {
+        self.breeder
+            .crossover_into((&pair.0.genome, &pair.1.genome), &mut offspring.genome);
+        offspring.id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        offspring.ancestors = Pedigree::lineage(pair, self.depth);
+    }
+
+    fn mutate(&self, mut genome: Pedigree<TGenome>) -> Pedigree<TGenome> {
+        genome.genome = self.breeder.mutate(genome.genome);
+        genome
+    }
+
+    fn compatible(&self, pair: (&Pedigree<TGenome>, &Pedigree<TGenome>)) -> bool {
+        !pair.0.is_related(pair.1) && self.breeder.compatible((&pair.0.genome, &pair.1.genome))
+    }
+
+    fn set_pressure(&self, pressure: f64) {
+        self.breeder.set_pressure(pressure);
+    }
+
+    fn set_generation(&self, generation: usize) {
+        self.breeder.set_generation(generation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_siblings_are_related() {
+        let left = Pedigree::new(1.0);
+        let right = Pedigree::new(2.0);
+
+        let first = Pedigree::offspring(1.5, (&left, &right), 1);
+        let second = Pedigree::offspring(1.5, (&left, &right), 1);
+
+        assert!(first.is_related(&second));
+        assert!(!first.is_related(&Pedigree::new(1.5)));
+    }
+
+    #[test]
+    fn test_ancestry_is_forgotten_past_depth() {
+        let left = Pedigree::new(1.0);
+        let right = Pedigree::new(2.0);
+        let child = Pedigree::offspring(1.5, (&left, &right), 1);
+        let grandchild = Pedigree::offspring(1.5, (&child, &Pedigree::new(3.0)), 1);
+
+        assert!(grandchild.is_related(&child));
+        assert!(!grandchild.is_related(&left));
+    }
+
+    #[test]
+    fn test_no_ancestry_at_zero_depth() {
+        let left = Pedigree::new(1.0);
+        let right = Pedigree::new(2.0);
+        let child = Pedigree::offspring(1.5, (&left, &right), 0);
+
+        assert!(!child.is_related(&left));
+        assert!(child.is_related(&child));
+    }
+}
@@ -0,0 +1,459 @@
+//! Distributed evaluation: genomes are sent to worker processes, possibly on
+//! other machines, which score them and send their fitness back.
+//!
+//! Workers speak newline-delimited JSON over TCP. For each genome, the
+//! dispatcher writes a request and waits for the response before sending the
+//! next one on the same connection:
+//!
+//! ```text
+//! {"id":0,"genome":<the serialized genome>}
+//! {"id":0,"fitness":1.5}
+//! {"id":0,"error":"the simulation diverged"}
+//! ```
+//!
+//! A worker answers with either a fitness or an error. Since JSON cannot hold
+//! `NaN`, a fitness that is `null` means the genome scored `NaN`. Workers can
+//! be written in any language, or with [`serve`].
+
+use crate::{Error, Evaluate, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Condvar, Mutex, OnceLock, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+/// A request to score one genome.
+#[derive(Serialize, Deserialize)]
+struct Request<TGenome> {
+    /// Identifies the genome within the generation.
+    id: usize,
+
+    /// The genome to score.
+    genome: TGenome,
+}
+
+/// The identifier of a request, read on its own when the genome in it cannot
+/// be read.
+#[derive(Deserialize)]
+struct Header {
+    id: usize,
+}
+
+/// The fitness of one genome, or why it could not be scored.
+#[derive(Serialize, Deserialize)]
+struct Response {
+    /// Identifies the genome within the generation.
+    id: usize,
+
+    /// The fitness, or `None` if it was `NaN` or the genome was not scored.
+    #[serde(default)]
+    fitness: Option<f64>,
+
+    /// Why the genome was not scored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl Response {
+    /// Get the result the response carries.
+    ///
+    /// # Returns
+    ///
+    /// The fitness.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::Remote`] if the worker could not score the genome, or
+    /// [`Error::ResultNaN`] if it scored `NaN`.
+    fn into_result(self) -> Result<f64> {
+        match (self.fitness, self.error) {
+            (_, Some(error)) => Err(Error::Remote(error)),
+            (Some(fitness), None) => Ok(fitness),
+            (None, None) => Err(Error::ResultNaN),
+        }
+    }
+}
+
+/// The genomes of a generation that still need a fitness.
+#[derive(Default)]
+struct Queue {
+    /// The genomes no worker is scoring.
+    pending: VecDeque<usize>,
+
+    /// The number of genomes workers are scoring.
+    in_flight: usize,
+}
+
+/// An evaluator that farms fitness checks out to worker processes over TCP.
+///
+/// Each generation, the dispatcher connects to every worker and hands out
+/// genomes one at a time, so faster workers score more of them. A worker
+/// that cannot be reached, or whose connection fails, is dropped for the rest
+/// of the generation, and the genome it was scoring goes to another worker.
+/// Genomes no worker could score fail with [`Error::Unreachable`].
+///
+/// Each address is one connection, which scores one genome at a time. List a
+/// worker once for each genome it should score at the same time.
+///
+/// # Examples
+///
+/// ```no_run
+/// use evo::distributed::Dispatcher;
+/// use evo::Evaluate;
+/// use std::time::Duration;
+///
+/// let dispatcher = Dispatcher::new(vec![
+///     "10.0.0.2:7878".parse().unwrap(),
+///     "10.0.0.3:7878".parse().unwrap(),
+/// ])
+/// .timeout(Duration::from_secs(60));
+///
+/// let fitness = dispatcher.evaluate(&[vec![1.0, 2.0], vec![3.0, 4.0]]);
+///
+/// assert_eq!(fitness.len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Dispatcher {
+    workers: Vec<SocketAddr>,
+    timeout: Option<Duration>,
+}
+
+impl Dispatcher {
+    /// Create a new dispatcher.
+    ///
+    /// # Arguments
+    ///
+    /// - `workers` are the addresses of the workers.
+    ///
+    /// # Returns
+    ///
+    /// The dispatcher, which waits on workers for as long as they take.
+    #[must_use]
+    pub fn new(workers: Vec<SocketAddr>) -> Self {
+        Self {
+            workers,
+            timeout: None,
+        }
+    }
+
+    /// Set how long to wait to connect to a worker, and for it to score a
+    /// genome, before giving up on it.
+    ///
+    /// # Arguments
+    ///
+    /// - `timeout` is the longest wait.
+    ///
+    /// # Returns
+    ///
+    /// The dispatcher.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Connect to a worker.
+    ///
+    /// # Arguments
+    ///
+    /// - `worker` is the worker's address.
+    ///
+    /// # Returns
+    ///
+    /// The connection, for writing requests and reading responses.
+    ///
+    /// # Errors
+    ///
+    /// If the worker cannot be reached.
+    fn connect(&self, worker: &SocketAddr) -> io::Result<(TcpStream, BufReader<TcpStream>)> {
+        let stream = match self.timeout {
+            Some(timeout) => TcpStream::connect_timeout(worker, timeout)?,
+            None => TcpStream::connect(worker)?,
+        };
+        stream.set_read_timeout(self.timeout)?;
+        stream.set_write_timeout(self.timeout)?;
+        stream.set_nodelay(true)?;
+        let reader = BufReader::new(stream.try_clone()?);
+        Ok((stream, reader))
+    }
+
+    /// Score genomes on one worker until none are left, or the worker fails.
+    ///
+    /// # Arguments
+    ///
+    /// - `worker` is the worker's address.
+    /// - `requests` are the serialized requests, one per genome.
+    /// - `queue` holds the genomes that still need a fitness.
+    /// - `ready` is signalled whenever a genome is finished or put back.
+    /// - `results` receive the fitness of each genome.
+    fn work(
+        &self,
+        worker: &SocketAddr,
+        requests: &[String],
+        queue: &Mutex<Queue>,
+        ready: &Condvar,
+        results: &[OnceLock<Result<f64>>],
+    ) {
+        let Ok((mut stream, mut reader)) = self.connect(worker) else {
+            return;
+        };
+        loop {
+            let id = {
+                let mut queue = queue.lock().unwrap_or_else(PoisonError::into_inner);
+                // Another worker may yet fail and put its genome back.
+                while queue.pending.is_empty() && queue.in_flight > 0 {
+                    queue = ready.wait(queue).unwrap_or_else(PoisonError::into_inner);
+                }
+                let Some(id) = queue.pending.pop_front() else {
+                    return;
+                };
+                queue.in_flight += 1;
+                id
+            };
+
+            let response = score(&mut stream, &mut reader, &requests[id])
+                .ok()
+                .filter(|response| response.id == id);
+
+            let mut queue = queue.lock().unwrap_or_else(PoisonError::into_inner);
+            queue.in_flight -= 1;
+            if let Some(response) = response {
+                let _ = results[id].set(response.into_result());
+                ready.notify_all();
+            } else {
+                queue.pending.push_back(id);
+                ready.notify_all();
+                return;
+            }
+        }
+    }
+}
+
+impl<TGenome> Evaluate<TGenome> for Dispatcher
+where
+    TGenome: Serialize,
+{
+    fn evaluate(&self, generation: &[TGenome]) -> Vec<Result<f64>> {
+        let results = generation
+            .iter()
+            .map(|_| OnceLock::new())
+            .collect::<Vec<_>>();
+        let mut queue = Queue::default();
+        let mut requests = Vec::with_capacity(generation.len());
+        for (id, genome) in generation.iter().enumerate() {
+            if let Ok(request) = serde_json::to_string(&Request { id, genome }) {
+                queue.pending.push_back(id);
+                requests.push(request);
+            } else {
+                let _ = results[id].set(Err(Error::CannotConvert));
+                requests.push(String::new());
+            }
+        }
+
+        let queue = Mutex::new(queue);
+        let ready = Condvar::new();
+        thread::scope(|scope| {
+            for worker in &self.workers {
+                let (requests, queue, ready, results) = (&requests, &queue, &ready, &results);
+                scope.spawn(move || self.work(worker, requests, queue, ready, results));
+            }
+        });
+
+        results
+            .into_iter()
+            .map(|result| result.into_inner().unwrap_or(Err(Error::Unreachable)))
+            .collect()
+    }
+}
+
+/// Send a request to a worker and read its response.
+///
+/// # Arguments
+///
+/// - `stream` is the connection to the worker.
+/// - `reader` reads from the same connection.
+/// - `request` is the serialized request.
+///
+/// # Returns
+///
+/// The worker's response.
+///
+/// # Errors
+///
+/// If the connection fails or closes, or the response cannot be read.
+fn score(
+    stream: &mut TcpStream,
+    reader: &mut BufReader<TcpStream>,
+    request: &str,
+) -> io::Result<Response> {
+    stream.write_all(request.as_bytes())?;
+    stream.write_all(b"\n")?;
+    stream.flush()?;
+
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(serde_json::from_str(&line)?)
+}
+
+/// Run a worker: accept connections from dispatchers and score the genomes
+/// they send, each connection on its own thread.
+///
+/// # Arguments
+///
+/// - `listener` accepts the dispatchers' connections.
+/// - `fitness` scores a genome, e.g. by running a simulation or checking it
+///   with a [`crate::FitnessCalc`].
+///
+/// # Errors
+///
+/// If a connection cannot be accepted. Otherwise, this never returns.
+///
+/// # Examples
+///
+/// ```no_run
+/// use evo::distributed::serve;
+/// use std::net::TcpListener;
+///
+/// let listener = TcpListener::bind("0.0.0.0:7878").unwrap();
+///
+/// serve(&listener, |genome: &Vec<f64>| Ok(genome.iter().map(|x| x * x).sum())).unwrap();
+/// ```
+pub fn serve<TGenome, F>(listener: &TcpListener, fitness: F) -> io::Result<()>
+where
+    TGenome: DeserializeOwned,
+    F: Fn(&TGenome) -> Result<f64> + Sync,
+{
+    thread::scope(|scope| {
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let fitness = &fitness;
+            // A failed connection only ends that dispatcher's session.
+            scope.spawn(move || handle(&stream, fitness));
+        }
+        Ok(())
+    })
+}
+
+/// Answer a dispatcher's requests until it disconnects.
+///
+/// # Arguments
+///
+/// - `stream` is the connection to the dispatcher.
+/// - `fitness` scores a genome.
+///
+/// # Errors
+///
+/// If the connection fails, or a request is not even a request.
+fn handle<TGenome, F>(stream: &TcpStream, fitness: &F) -> io::Result<()>
+where
+    TGenome: DeserializeOwned,
+    F: Fn(&TGenome) -> Result<f64>,
+{
+    let mut writer = stream;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        let response = match serde_json::from_str::<Request<TGenome>>(&line) {
+            Ok(request) => {
+                let result = fitness(&request.genome);
+                Response {
+                    id: request.id,
+                    fitness: result.as_ref().ok().copied(),
+                    error: result.err().map(|error| error.to_string()),
+                }
+            }
+            Err(error) => Response {
+                id: serde_json::from_str::<Header>(&line)?.id,
+                fitness: None,
+                error: Some(error.to_string()),
+            },
+        };
+        serde_json::to_writer(&mut writer, &response)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn worker() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            serve(&listener, |genome: &f64| {
+                if *genome < 0.0 {
+                    Err(Error::Unscored)
+                } else if *genome == 0.0 {
+                    Ok(f64::NAN)
+                } else {
+                    Ok(genome * 2.0)
+                }
+            })
+        });
+        address
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let worker = worker();
+        let dispatcher = Dispatcher::new(vec![worker, worker]).timeout(Duration::from_secs(10));
+
+        let fitness = dispatcher.evaluate(&[1.0, -1.0, 2.5, 0.0]);
+
+        assert_eq!(
+            fitness,
+            vec![
+                Ok(2.0),
+                Err(Error::Remote("no score was given".to_string())),
+                Ok(5.0),
+                Err(Error::ResultNaN),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_failed_worker() {
+        // Accepts a connection and closes it without answering.
+        let broken = TcpListener::bind("127.0.0.1:0").unwrap();
+        let broken_address = broken.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in broken.incoming() {
+                drop(stream);
+            }
+        });
+        let dispatcher =
+            Dispatcher::new(vec![broken_address, worker()]).timeout(Duration::from_secs(10));
+
+        let fitness = dispatcher.evaluate(&[1.0, 2.0, 3.0]);
+
+        assert_eq!(fitness, vec![Ok(2.0), Ok(4.0), Ok(6.0)]);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let dispatcher = Dispatcher::new(vec![]);
+
+        assert_eq!(
+            dispatcher.evaluate(&[1.0, 2.0]),
+            vec![Err(Error::Unreachable), Err(Error::Unreachable)]
+        );
+    }
+
+    #[test]
+    fn test_bad_request() {
+        let mut stream = TcpStream::connect(worker()).unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        let response = score(&mut stream, &mut reader, r#"{"id":3,"genome":"text"}"#).unwrap();
+
+        assert_eq!(response.id, 3);
+        assert!(matches!(response.into_result(), Err(Error::Remote(_))));
+    }
+}
@@ -0,0 +1,119 @@
+use rayon::prelude::*;
+use std::sync::OnceLock;
+
+/// A trait for scheduling per-genome work, such as fitness checks.
+///
+/// The algorithm splits the work into numbered tasks and hands them all to
+/// one call, so an implementation decides whether they run one after another,
+/// on a thread pool, or on an async runtime, without the algorithm changing.
+/// Every task must run exactly once before `execute` returns.
+///
+/// # Examples
+///
+/// ```
+/// use evo::Execute;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// /// Runs the tasks in reverse order.
+/// struct Backwards;
+///
+/// impl Execute for Backwards {
+///     fn execute(&self, tasks: usize, task: &(dyn Fn(usize) + Sync)) {
+///         (0..tasks).rev().for_each(task);
+///     }
+/// }
+///
+/// let total = AtomicUsize::new(0);
+/// Backwards.execute(4, &|index| {
+///     total.fetch_add(index, Ordering::Relaxed);
+/// });
+///
+/// assert_eq!(total.into_inner(), 6);
+/// ```
+pub trait Execute {
+    /// Run every task.
+    ///
+    /// # Arguments
+    ///
+    /// - `tasks` is the number of tasks.
+    /// - `task` runs the task with the given index, from `0` to `tasks - 1`.
+    fn execute(&self, tasks: usize, task: &(dyn Fn(usize) + Sync));
+}
+
+/// Runs tasks one after another on the calling thread. This is the default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sequential;
+
+impl Execute for Sequential {
+    fn execute(&self, tasks: usize, task: &(dyn Fn(usize) + Sync)) {
+        (0..tasks).for_each(task);
+    }
+}
+
+/// Runs tasks concurrently on rayon's global thread pool.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Parallel;
+
+impl Execute for Parallel {
+    fn execute(&self, tasks: usize, task: &(dyn Fn(usize) + Sync)) {
+        (0..tasks).into_par_iter().for_each(task);
+    }
+}
+
+/// Apply a function to every item, scheduled by an executor.
+///
+/// # Arguments
+///
+/// - `executor` is the executor that runs the work.
+/// - `items` is the items to apply the function to.
+/// - `f` is the function.
+///
+/// # Returns
+///
+/// One result per item, in the same order, or `None` for items the executor
+/// did not run.
+pub(crate) fn map<T, R>(
+    executor: &dyn Execute,
+    items: &[T],
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<Option<R>>
+where
+    T: Sync,
+    R: Send + Sync,
+{
+    let slots = items.iter().map(|_| OnceLock::new()).collect::<Vec<_>>();
+    executor.execute(items.len(), &|index| {
+        let _ = slots[index].set(f(&items[index]));
+    });
+    slots.into_iter().map(OnceLock::into_inner).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_keeps_order() {
+        let items = (0..100).collect::<Vec<_>>();
+        let expected = items.iter().map(|item| Some(item * 2)).collect::<Vec<_>>();
+
+        assert_eq!(map(&Sequential, &items, |item| item * 2), expected);
+        assert_eq!(map(&Parallel, &items, |item| item * 2), expected);
+    }
+
+    #[test]
+    fn test_map_skipped_tasks() {
+        struct Evens;
+
+        impl Execute for Evens {
+            fn execute(&self, tasks: usize, task: &(dyn Fn(usize) + Sync)) {
+                (0..tasks).step_by(2).for_each(task);
+            }
+        }
+
+        assert_eq!(
+            map(&Evens, &[1, 2, 3], |item| item + 1),
+            vec![Some(2), None, Some(4)]
+        );
+    }
+}
@@ -1,5 +1,14 @@
-use super::{Error, Predict, Result, TrainingRecord};
-use crate::{Compare, CompareRecord};
+use super::{
+    updates::Handle, Curriculum, Error, FitnessReport, Predict, RecordReport, Result,
+    TrainingRecord,
+};
+use crate::{rng, Compare, CompareRecord, NanSafeCompare};
+use rand::seq::SliceRandom;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, PoisonError, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
 
 /// A fitness calculator for the evolutionary algorithm.
 ///
@@ -11,7 +20,134 @@ use crate::{Compare, CompareRecord};
 /// let fitness_calc = FitnessCalc::builder().build();
 /// ```
 pub struct Calc {
-    training_data: Vec<TrainingRecord>,
+    training_data: RwLock<Arc<Vec<Graded>>>,
+    validation_data: Vec<TrainingRecord>,
+    custom: Option<Custom>,
+    timeout: Option<Duration>,
+    updates: Handle,
+    curriculum: Option<Curriculum>,
+    generation: AtomicUsize,
+}
+
+/// Calculates fitness some other way than from training records, such as by
+/// running a simulation.
+type Custom = Arc<dyn Fn(&dyn Predict) -> Result<f64> + Send + Sync>;
+
+/// A timed fitness check handed to a watchdog worker.
+type Job = Box<dyn FnOnce() + Send>;
+
+thread_local! {
+    /// The watchdog worker this thread hands timed fitness checks to. It runs
+    /// one check at a time and lives as long as the thread.
+    static WATCHDOG: mpsc::Sender<Job> = {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        thread::spawn(move || receiver.into_iter().for_each(|job| job()));
+        sender
+    };
+}
+
+/// A training record and how hard it is.
+#[derive(Clone)]
+struct Graded {
+    record: TrainingRecord,
+    difficulty: u32,
+}
+
+impl From<TrainingRecord> for Graded {
+    fn from(record: TrainingRecord) -> Self {
+        Self {
+            record,
+            difficulty: 0,
+        }
+    }
+}
+
+/// The training records and deadline of one fitness check, taken when it
+/// starts.
+struct Snapshot {
+    training_data: Arc<Vec<Graded>>,
+    difficulty: Option<u32>,
+    custom: Option<Custom>,
+    deadline: Option<Instant>,
+}
+
+impl Snapshot {
+    /// Use the prediction function to check the fitness of an entity.
+    ///
+    /// # Arguments
+    ///
+    /// - `predict` is the prediction function.
+    ///
+    /// # Returns
+    ///
+    /// The fitness, or [`f64::MAX`] if the deadline passed first.
+    ///
+    /// # Errors
+    ///
+    /// If the fitness is `NaN` or infinite.
+    fn fitness<P>(&self, predict: &P) -> Result<f64>
+    where
+        P: Predict,
+    {
+        if let Some(custom) = &self.custom {
+            let fitness = checked(custom(predict)?)?;
+            return Ok(if self.timed_out() { f64::MAX } else { fitness });
+        }
+
+        let mut mse_sum = 0.0;
+        let mut weight_sum = 0.0;
+        let finished = self.scan(|record| {
+            mse_sum += Calc::record_mse(record, predict)? * record.weight;
+            weight_sum += record.weight;
+            Ok(())
+        })?;
+        if !finished {
+            return Ok(f64::MAX);
+        }
+
+        checked_divide(mse_sum, weight_sum)
+    }
+
+    /// Visit each training record the check uses, in order, until the
+    /// deadline passes.
+    ///
+    /// # Arguments
+    ///
+    /// - `on_record` checks a record.
+    ///
+    /// # Returns
+    ///
+    /// False if the deadline passed before every record was checked.
+    ///
+    /// # Errors
+    ///
+    /// If checking a record fails.
+    fn scan(&self, mut on_record: impl FnMut(&TrainingRecord) -> Result<()>) -> Result<bool> {
+        for graded in self.training_data.iter() {
+            if self
+                .difficulty
+                .is_some_and(|difficulty| graded.difficulty > difficulty)
+            {
+                continue;
+            }
+
+            on_record(&graded.record)?;
+            if self.timed_out() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Check whether the deadline has passed.
+    ///
+    /// # Returns
+    ///
+    /// True if there is a deadline and it has passed.
+    fn timed_out(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() > deadline)
+    }
 }
 
 /// Convert a `usize` to a `f64`.
@@ -53,7 +189,23 @@ fn convert(x: usize) -> Result<f64> {
 ///
 /// If the result is `NaN` or `Infinite`.
 fn checked_divide(numerator: f64, denominator: f64) -> Result<f64> {
-    let result = numerator / denominator;
+    checked(numerator / denominator)
+}
+
+/// Check that a fitness is a finite number.
+///
+/// # Arguments
+///
+/// - `result` is the fitness.
+///
+/// # Returns
+///
+/// The fitness.
+///
+/// # Errors
+///
+/// If the fitness is `NaN` or `Infinite`.
+fn checked(result: f64) -> Result<f64> {
     if result.is_nan() {
         Err(Error::ResultNaN)
     } else if result.is_infinite() {
@@ -68,96 +220,607 @@ impl Calc {
     ///
     /// # Returns
     ///
-    /// A new builder.
+    /// A new builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::FitnessCalc;
+    ///
+    /// let fitness_calc = FitnessCalc::builder().build();
+    /// ```
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Get a handle for changing the training data while the calculator is
+    /// in use.
+    ///
+    /// # Returns
+    ///
+    /// A handle that stages changes until [`Calc::apply_updates`] is called.
+    #[must_use]
+    pub fn updates(&self) -> Handle {
+        self.updates.clone()
+    }
+
+    /// Apply the changes staged through [`Calc::updates`] handles.
+    ///
+    /// The evolutionary algorithm calls this before scoring each generation.
+    ///
+    /// # Returns
+    ///
+    /// Whether the training data changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{FitnessCalc, Predict, TrainingRecord};
+    ///
+    /// struct Zero;
+    ///
+    /// impl Predict for Zero {
+    ///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+    ///         vec![0.0]
+    ///     }
+    /// }
+    ///
+    /// let record = |output| TrainingRecord { input: vec![0.0], output: vec![output], weight: 1.0 };
+    /// let fitness_calc = FitnessCalc::builder().add_training_record(record(0.0)).build();
+    ///
+    /// fitness_calc.updates().replace(vec![record(2.0)]);
+    /// assert_eq!(fitness_calc.check(&Zero), Ok(0.0));
+    ///
+    /// assert!(fitness_calc.apply_updates());
+    /// assert_eq!(fitness_calc.check(&Zero), Ok(4.0));
+    /// assert!(!fitness_calc.apply_updates());
+    /// ```
+    pub fn apply_updates(&self) -> bool {
+        let pending = std::mem::take(
+            &mut *self
+                .updates
+                .pending
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        if pending.replacement.is_none() && pending.additions.is_empty() {
+            return false;
+        }
+
+        let mut training_data = self
+            .training_data
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        let training_data = Arc::make_mut(&mut training_data);
+        if let Some(replacement) = pending.replacement {
+            *training_data = replacement.into_iter().map(Graded::from).collect();
+        }
+        training_data.extend(pending.additions.into_iter().map(Graded::from));
+        true
+    }
+
+    /// Set the generation being scored, which picks the training records a
+    /// curriculum allows.
+    ///
+    /// The evolutionary algorithm calls this before scoring each generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation` is the number of the generation, starting at 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{Curriculum, FitnessCalc, Predict, TrainingRecord};
+    ///
+    /// struct Zero;
+    ///
+    /// impl Predict for Zero {
+    ///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+    ///         vec![0.0]
+    ///     }
+    /// }
+    ///
+    /// let record = |output| TrainingRecord { input: vec![0.0], output: vec![output], weight: 1.0 };
+    /// let fitness_calc = FitnessCalc::builder()
+    ///     .add_training_records_with_difficulty(0, vec![record(0.0)])
+    ///     .add_training_records_with_difficulty(1, vec![record(2.0)])
+    ///     .curriculum(Curriculum::builder().stage(0, 0).stage(10, 1).build())
+    ///     .build();
+    ///
+    /// assert_eq!(fitness_calc.check(&Zero), Ok(0.0));
+    ///
+    /// fitness_calc.set_generation(10);
+    /// assert_eq!(fitness_calc.check(&Zero), Ok(2.0));
+    /// ```
+    pub fn set_generation(&self, generation: usize) {
+        self.generation.store(generation, Ordering::Relaxed);
+    }
+
+    /// Get the mean squared error of one training record in a single pass.
+    ///
+    /// # Arguments
+    ///
+    /// - `record` is the training record.
+    /// - `predict` is the prediction function.
+    ///
+    /// # Returns
+    ///
+    /// The mean squared error of the record.
+    ///
+    /// # Errors
+    ///
+    /// If the result is `NaN` or `Infinite`.
+    fn record_mse<P>(record: &TrainingRecord, predict: &P) -> Result<f64>
+    where
+        P: Predict,
+    {
+        let actual = predict.predict(&record.input);
+        let (sum, count) = record
+            .get_mse(&actual)
+            .fold((0.0, 0_usize), |(sum, count), error| {
+                (sum + error, count + 1)
+            });
+
+        checked_divide(sum, convert(count)?)
+    }
+
+    /// Get the errors of one training record.
+    ///
+    /// # Arguments
+    ///
+    /// - `record` is the training record.
+    /// - `predict` is the prediction function.
+    ///
+    /// # Returns
+    ///
+    /// The errors of the record.
+    ///
+    /// # Errors
+    ///
+    /// If the mean squared error is `NaN` or `Infinite`.
+    fn record_report<P>(record: &TrainingRecord, predict: &P) -> Result<RecordReport>
+    where
+        P: Predict,
+    {
+        let actual = predict.predict(&record.input);
+        let outputs = record.get_mse(&actual).collect::<Vec<_>>();
+
+        Ok(RecordReport {
+            mse: checked_divide(outputs.iter().sum(), convert(outputs.len())?)?,
+            weight: record.weight,
+            outputs,
+        })
+    }
+
+    /// Use the prediction function to check the fitness of an entity.
+    ///
+    /// # Arguments
+    ///
+    /// - `predict` is the prediction function.
+    ///
+    /// # Returns
+    ///
+    /// The fitness of the entity: the mean squared error of the training
+    /// records, weighted by each record's weight.
+    ///
+    /// If the calculator has a curriculum, only the training records allowed
+    /// in the current generation are used.
+    ///
+    /// If the calculator has a timeout and the entity takes longer than that
+    /// to predict the training data, the fitness is [`f64::MAX`], the worst
+    /// possible fitness.
+    ///
+    /// If the calculator has a custom fitness function, it is used instead of
+    /// the training records.
+    ///
+    /// # Errors
+    ///
+    /// If the fitness is `NaN` or infinite, e.g. because there are no
+    /// training records or their weights add up to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{FitnessCalc, Predict, TrainingRecord};
+    ///
+    /// struct Predictor;
+    ///
+    /// impl Predict for Predictor {
+    ///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+    ///         vec![0.0]
+    ///     }
+    /// }
+    ///
+    /// let fitness_calc = FitnessCalc::builder()
+    ///     .add_training_record(TrainingRecord {
+    ///         input: vec![1.0, 2.0, 3.0, 4.0],
+    ///         output: vec![1.0, 2.0, 3.0, 4.0],
+    ///         weight: 1.0,
+    ///     })
+    ///     .build();
+    /// let fitness = fitness_calc.check(&Predictor).unwrap();
+    ///
+    /// assert_eq!(fitness, 1.0);
+    /// ```
+    pub fn check<P>(&self, predict: &P) -> Result<f64>
+    where
+        P: Predict,
+    {
+        self.snapshot().fitness(predict)
+    }
+
+    /// Use the prediction function to check the fitness of an entity, as
+    /// [`Calc::check`] does, but give up on it once the timeout runs out even
+    /// if a single prediction never returns.
+    ///
+    /// The check runs on a watchdog worker that the calling thread reuses for
+    /// each of its timed checks. A prediction that hangs holds that worker
+    /// until it returns, and the checks queued behind it run out of time
+    /// too. Without a timeout, this is the same as [`Calc::check`].
+    ///
+    /// # Arguments
+    ///
+    /// - `predict` is the prediction function, which the worker clones.
+    ///
+    /// # Returns
+    ///
+    /// The fitness, or [`f64::MAX`] if the entity took longer than the
+    /// timeout.
+    ///
+    /// # Errors
+    ///
+    /// If the fitness is `NaN` or infinite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{FitnessCalc, Predict, TrainingRecord};
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Clone)]
+    /// struct Stuck;
+    ///
+    /// impl Predict for Stuck {
+    ///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+    ///         std::thread::sleep(Duration::from_secs(1));
+    ///         vec![0.0]
+    ///     }
+    /// }
+    ///
+    /// let fitness_calc = FitnessCalc::builder()
+    ///     .add_training_record(TrainingRecord::new(vec![0.0], vec![0.0]))
+    ///     .timeout(Duration::from_millis(10))
+    ///     .build();
+    ///
+    /// assert_eq!(fitness_calc.check_watched(&Stuck), Ok(f64::MAX));
+    /// ```
+    pub fn check_watched<P>(&self, predict: &P) -> Result<f64>
+    where
+        P: Predict + Clone + Send + 'static,
+    {
+        let snapshot = self.snapshot();
+        let Some(deadline) = snapshot.deadline else {
+            return snapshot.fitness(predict);
+        };
+
+        let predict = predict.clone();
+        let (sender, receiver) = mpsc::channel();
+        let job: Job = Box::new(move || {
+            // Skip a check whose caller gave up while it was queued.
+            if snapshot.timed_out() {
+                return;
+            }
+            let fitness = panic::catch_unwind(AssertUnwindSafe(|| snapshot.fitness(&predict)));
+            // The caller may have given up waiting.
+            let _ = sender.send(fitness);
+        });
+        if let Err(mpsc::SendError(job)) = WATCHDOG.with(|watchdog| watchdog.send(job)) {
+            job();
+        }
+
+        match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(Ok(fitness)) => fitness,
+            Ok(Err(payload)) => panic::resume_unwind(payload),
+            Err(_) => Ok(f64::MAX),
+        }
+    }
+
+    /// Use the prediction function to check the error on each training
+    /// record, e.g. to select genomes that solve different records, as
+    /// lexicase selection does.
+    ///
+    /// # Arguments
+    ///
+    /// - `predict` is the prediction function.
+    ///
+    /// # Returns
+    ///
+    /// The mean squared error of each training record the current generation
+    /// uses, in order. If the calculator has a timeout and the entity takes
+    /// longer than that, every error is [`f64::MAX`]. A custom fitness
+    /// function gives a single error, the fitness.
+    ///
+    /// # Errors
+    ///
+    /// If an error is `NaN` or infinite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{FitnessCalc, Predict, TrainingRecord};
+    ///
+    /// struct Zero;
+    ///
+    /// impl Predict for Zero {
+    ///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+    ///         vec![0.0]
+    ///     }
+    /// }
+    ///
+    /// let record = |output| TrainingRecord { input: vec![0.0], output: vec![output], weight: 1.0 };
+    /// let fitness_calc = FitnessCalc::builder()
+    ///     .add_training_records(vec![record(0.0), record(2.0)])
+    ///     .build();
+    ///
+    /// assert_eq!(fitness_calc.errors(&Zero), Ok(vec![0.0, 4.0]));
+    /// assert_eq!(fitness_calc.check(&Zero), Ok(2.0));
+    /// ```
+    pub fn errors<P>(&self, predict: &P) -> Result<Vec<f64>>
+    where
+        P: Predict,
+    {
+        if self.custom.is_some() {
+            return Ok(vec![self.check(predict)?]);
+        }
+
+        let mut errors = vec![];
+        let finished = self.snapshot().scan(|record| {
+            errors.push(Self::record_mse(record, predict)?);
+            Ok(())
+        })?;
+        if !finished {
+            errors = vec![f64::MAX; self.record_count()];
+        }
+        Ok(errors)
+    }
+
+    /// Use the prediction function to check the fitness of an entity, along
+    /// with its errors on each training record and output, e.g. to analyze
+    /// where the best genome goes wrong.
+    ///
+    /// # Arguments
+    ///
+    /// - `predict` is the prediction function.
+    ///
+    /// # Returns
+    ///
+    /// The report. Its fitness is the same as [`Calc::check`] gives, and its
+    /// records are those the current generation uses, in order. If the
+    /// calculator has a timeout and the entity takes longer than that, the
+    /// fitness is [`f64::MAX`] and only the records checked in time are
+    /// reported. A custom fitness function reports no records.
+    ///
+    /// # Errors
+    ///
+    /// If the fitness or an error is `NaN` or infinite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{FitnessCalc, Predict, TrainingRecord};
+    ///
+    /// struct Zero;
+    ///
+    /// impl Predict for Zero {
+    ///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+    ///         vec![0.0]
+    ///     }
+    /// }
+    ///
+    /// let record = |output| TrainingRecord { input: vec![0.0], output: vec![output], weight: 1.0 };
+    /// let fitness_calc = FitnessCalc::builder()
+    ///     .add_training_records(vec![record(0.0), record(2.0)])
+    ///     .build();
+    /// let report = fitness_calc.check_detailed(&Zero).unwrap();
+    ///
+    /// assert_eq!(report.fitness, 2.0);
+    /// assert_eq!(report.records[1].mse, 4.0);
+    /// ```
+    pub fn check_detailed<P>(&self, predict: &P) -> Result<FitnessReport>
+    where
+        P: Predict,
+    {
+        if self.custom.is_some() {
+            return Ok(FitnessReport {
+                fitness: self.check(predict)?,
+                records: vec![],
+            });
+        }
+
+        let mut records = vec![];
+        let finished = self.snapshot().scan(|record| {
+            records.push(Self::record_report(record, predict)?);
+            Ok(())
+        })?;
+        let fitness = if finished {
+            let (mse_sum, weight_sum) =
+                records
+                    .iter()
+                    .fold((0.0, 0.0), |(mse_sum, weight_sum), record| {
+                        (
+                            mse_sum + record.mse * record.weight,
+                            weight_sum + record.weight,
+                        )
+                    });
+            checked_divide(mse_sum, weight_sum)?
+        } else {
+            f64::MAX
+        };
+        Ok(FitnessReport { fitness, records })
+    }
+
+    /// Use the prediction function to check the fitness of an entity on the
+    /// validation records, which are held out of training to show how well
+    /// it generalizes.
+    ///
+    /// # Arguments
+    ///
+    /// - `predict` is the prediction function.
+    ///
+    /// # Returns
+    ///
+    /// The mean squared error of the validation records, weighted by each
+    /// record's weight, or `None` if the calculator has no validation
+    /// records. The curriculum and timeout do not apply.
+    ///
+    /// # Errors
+    ///
+    /// If the fitness is `NaN` or infinite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{FitnessCalc, Predict, TrainingRecord};
+    ///
+    /// struct Zero;
+    ///
+    /// impl Predict for Zero {
+    ///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+    ///         vec![0.0]
+    ///     }
+    /// }
     ///
-    /// # Examples
+    /// let record = |output| TrainingRecord { input: vec![0.0], output: vec![output], weight: 1.0 };
+    /// let fitness_calc = FitnessCalc::builder()
+    ///     .add_training_record(record(0.0))
+    ///     .add_validation_records(vec![record(2.0)])
+    ///     .build();
     ///
+    /// assert_eq!(fitness_calc.check(&Zero), Ok(0.0));
+    /// assert_eq!(fitness_calc.validate(&Zero), Some(Ok(4.0)));
     /// ```
-    /// use evo::FitnessCalc;
+    pub fn validate<P>(&self, predict: &P) -> Option<Result<f64>>
+    where
+        P: Predict,
+    {
+        if self.validation_data.is_empty() {
+            return None;
+        }
+
+        let mut mse_sum = 0.0;
+        let mut weight_sum = 0.0;
+        for record in &self.validation_data {
+            match Self::record_mse(record, predict) {
+                Ok(mse) => {
+                    mse_sum += mse * record.weight;
+                    weight_sum += record.weight;
+                }
+                Err(error) => return Some(Err(error)),
+            }
+        }
+        Some(checked_divide(mse_sum, weight_sum))
+    }
+
+    /// Get the hardest difficulty the current generation allows.
     ///
-    /// let fitness_calc = FitnessCalc::builder().build();
-    /// ```
-    #[must_use]
-    pub fn builder() -> Builder {
-        Builder::default()
+    /// # Returns
+    ///
+    /// The difficulty, or `None` if every record is allowed.
+    fn difficulty(&self) -> Option<u32> {
+        self.curriculum
+            .as_ref()
+            .and_then(|curriculum| curriculum.difficulty(self.generation.load(Ordering::Relaxed)))
     }
 
-    /// Get the mean squared error for each training record.
+    /// Count the training records the current generation uses.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// - `predict` is the prediction function.
+    /// The number of records.
+    fn record_count(&self) -> usize {
+        let difficulty = self.difficulty();
+        self.training_data
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|graded| difficulty.is_none_or(|difficulty| graded.difficulty <= difficulty))
+            .count()
+    }
+
+    /// Take what a fitness check that starts now needs, so that it can run
+    /// apart from the calculator.
     ///
     /// # Returns
     ///
-    /// An iterator of mean squared errors.
-    fn get_mse_iter<'n, P>(&'n self, predict: &'n P) -> impl Iterator<Item = Vec<f64>> + 'n
-    where
-        P: Predict,
-    {
-        self.training_data.iter().map(move |t_record| {
-            let actual = predict.predict(&t_record.input);
-            t_record.get_mse(&actual).collect()
-        })
+    /// The snapshot.
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            training_data: Arc::clone(
+                &self
+                    .training_data
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner),
+            ),
+            difficulty: self.difficulty(),
+            custom: self.custom.clone(),
+            deadline: self.timeout.map(|timeout| Instant::now() + timeout),
+        }
     }
 
-    /// Use the prediction function to check the fitness of an entity.
+    /// Get the best entity from a set of entities, where the best entity is
+    /// the one with the lowest fitness value, ranking `NaN` or infinite
+    /// fitness last as [`NanSafeCompare`] does. If two entities have the same
+    /// fitness value, the first one is returned.
     ///
     /// # Arguments
     ///
-    /// - `predict` is the prediction function.
+    /// - `entities` is the set of entities to check.
     ///
     /// # Returns
     ///
-    /// The fitness of the entity.
+    /// The best entity, or `None` if no entities are provided.
     ///
     /// # Errors
     ///
-    /// If the number of training records cannot be converted to a `f64`.
+    /// If the fitness of any entity cannot be calculated.
     ///
     /// # Examples
     ///
     /// ```
     /// use evo::{FitnessCalc, Predict, TrainingRecord};
     ///
-    /// struct Predictor;
+    /// #[derive(Debug, PartialEq, PartialOrd)]
+    /// struct Predictor(f64);
     ///
     /// impl Predict for Predictor {
     ///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
-    ///         vec![0.0]
+    ///         vec![self.0]
     ///     }
     /// }
     ///
     /// let fitness_calc = FitnessCalc::builder()
     ///     .add_training_record(TrainingRecord {
-    ///         input: vec![1.0, 2.0, 3.0, 4.0],
-    ///         output: vec![1.0, 2.0, 3.0, 4.0],
+    ///         input: vec![0.0],
+    ///         output: vec![0.0],
+    ///         weight: 1.0,
     ///     })
     ///     .build();
-    /// let fitness = fitness_calc.check(&Predictor).unwrap();
     ///
-    /// assert_eq!(fitness, 1.0);
+    /// let best = fitness_calc.best_entity(&[Predictor(2.0), Predictor(1.0)]);
+    ///
+    /// assert_eq!(best, Ok(Some(&Predictor(1.0))));
     /// ```
-    pub fn check<P>(&self, predict: &P) -> Result<f64>
+    pub fn best_entity<'x, P>(&self, entities: &'x [P]) -> Result<Option<&'x P>>
     where
-        P: Predict,
+        P: Predict + PartialOrd,
     {
-        let len = convert(self.training_data.len())?;
-        let mse_sum = self
-            .get_mse_iter(predict)
-            .map(|x| {
-                let x_len = convert(x.len())?;
-                let x_sum = x.iter().sum::<f64>();
-                checked_divide(x_sum, x_len)
-            })
-            .sum::<Result<f64>>()?;
-
-        checked_divide(mse_sum, len)
+        self.best_entity_by(entities, &NanSafeCompare)
     }
 
-    /// Get the best entity from a set of entities, where the best entity is the one with the lowest fitness value.
-    /// If two entities have the same fitness value, the first one is returned.
-    /// If no entities are provided, `None` is returned.
+    /// Get the best entity from a set of entities, as ordered by a
+    /// comparator. If two entities are equal, the first one is returned.
     ///
     /// # Arguments
     ///
@@ -176,56 +839,39 @@ impl Calc {
     ///
     /// ```
     /// use evo::{Compare, CompareRecord, FitnessCalc, Predict, TrainingRecord};
-    /// use std::cmp::{Ordering, Eq, PartialEq};
+    /// use std::cmp::Ordering;
     ///
-    /// #[derive(Debug)]
-    /// struct Predictor;
+    /// #[derive(Debug, PartialEq, PartialOrd)]
+    /// struct Predictor(f64);
     ///
     /// impl Predict for Predictor {
     ///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
-    ///         vec![0.0]
+    ///         vec![self.0]
     ///     }
     /// }
     ///
-    /// impl PartialEq for Predictor {
-    ///     fn eq(&self, _other: &Self) -> bool {
-    ///         true
-    ///     }
-    /// }
-    ///
-    /// impl Eq for Predictor {}
-    ///
-    /// impl PartialOrd for Predictor {
-    ///     fn partial_cmp(&self, _other: &Self) -> Option<Ordering> {
-    ///         Some(Ordering::Equal)
-    ///     }
-    /// }
+    /// /// Prefers the least fit entity.
+    /// struct Worst;
     ///
-    /// struct Comparator;
-    ///
-    /// impl Compare<Predictor> for Comparator {
+    /// impl Compare<Predictor> for Worst {
     ///     fn compare(&self, left: &CompareRecord<&Predictor>, right: &CompareRecord<&Predictor>) -> Ordering {
-    ///         match (left.fitness.is_nan() || left.fitness.is_infinite(), right.fitness.is_nan() || right.fitness.is_infinite()) {
-    ///             (true, true) => Ordering::Equal,
-    ///             (true, false) => Ordering::Greater,
-    ///             (false, true) => Ordering::Less,
-    ///             (false, false) => PartialOrd::partial_cmp(&left.fitness, &right.fitness).unwrap_or(Ordering::Equal),
-    ///         }
+    ///         right.fitness.total_cmp(&left.fitness)
     ///     }
     /// }
     ///
     /// let fitness_calc = FitnessCalc::builder()
     ///     .add_training_record(TrainingRecord {
-    ///         input: vec![1.0, 2.0, 3.0, 4.0],
-    ///         output: vec![1.0, 2.0, 3.0, 4.0],
+    ///         input: vec![0.0],
+    ///         output: vec![0.0],
+    ///         weight: 1.0,
     ///     })
     ///     .build();
     ///
-    /// let best = fitness_calc.best_entity(&[Predictor], &Comparator);
+    /// let worst = fitness_calc.best_entity_by(&[Predictor(2.0), Predictor(1.0)], &Worst);
     ///
-    /// assert_eq!(best, Ok(Some(&Predictor)));
+    /// assert_eq!(worst, Ok(Some(&Predictor(2.0))));
     /// ```
-    pub fn best_entity<'x, P, C>(&self, entities: &'x [P], compare: &C) -> Result<Option<&'x P>>
+    pub fn best_entity_by<'x, P, C>(&self, entities: &'x [P], compare: &C) -> Result<Option<&'x P>>
     where
         P: Predict + PartialOrd,
         C: Compare<P>,
@@ -282,6 +928,7 @@ impl Calc {
     ///     .add_training_record(TrainingRecord {
     ///         input: vec![1.0, 2.0, 3.0, 4.0],
     ///         output: vec![1.0, 2.0, 3.0, 4.0],
+    ///         weight: 1.0,
     ///     })
     ///     .build();
     ///
@@ -309,7 +956,12 @@ impl Calc {
 /// ```
 #[derive(Default)]
 pub struct Builder {
-    training_data: Vec<TrainingRecord>,
+    training_data: Vec<Graded>,
+    validation_data: Vec<TrainingRecord>,
+    validation_fraction: Option<f64>,
+    custom: Option<Custom>,
+    timeout: Option<Duration>,
+    curriculum: Option<Curriculum>,
 }
 
 impl Builder {
@@ -330,12 +982,214 @@ impl Builder {
     /// use evo::{FitnessCalc, TrainingRecord};
     ///
     /// let fitness_calc = FitnessCalc::builder()
-    ///     .add_training_record(TrainingRecord { input: vec![0.0, 0.0], output: vec![0.0] })
+    ///     .add_training_record(TrainingRecord { input: vec![0.0, 0.0], output: vec![0.0], weight: 1.0 })
     ///     .build();
     /// ```
     #[must_use]
     pub fn add_training_record(mut self, record: TrainingRecord) -> Self {
-        self.training_data.push(record);
+        self.training_data.push(record.into());
+        self
+    }
+
+    /// Add several training records to the fitness calc.
+    ///
+    /// # Arguments
+    ///
+    /// - `records` are the training records, such as a loaded dataset.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{FitnessCalc, TrainingRecord};
+    ///
+    /// let fitness_calc = FitnessCalc::builder()
+    ///     .add_training_records(vec![
+    ///         TrainingRecord { input: vec![0.0, 0.0], output: vec![0.0], weight: 1.0 },
+    ///         TrainingRecord { input: vec![0.0, 1.0], output: vec![1.0], weight: 1.0 },
+    ///     ])
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn add_training_records(mut self, records: Vec<TrainingRecord>) -> Self {
+        self.training_data
+            .extend(records.into_iter().map(Graded::from));
+        self
+    }
+
+    /// Add several training records of the same difficulty, for use with a
+    /// [`Curriculum`]. Records added without a difficulty have difficulty 0.
+    ///
+    /// # Arguments
+    ///
+    /// - `difficulty` is how hard the records are; higher is harder.
+    /// - `records` are the training records.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn add_training_records_with_difficulty(
+        mut self,
+        difficulty: u32,
+        records: Vec<TrainingRecord>,
+    ) -> Self {
+        self.training_data.extend(
+            records
+                .into_iter()
+                .map(|record| Graded { record, difficulty }),
+        );
+        self
+    }
+
+    /// Add records to check generalization with, instead of training. They
+    /// are never used to select genomes, only to report how well the fittest
+    /// genome does on data it was not trained on.
+    ///
+    /// # Arguments
+    ///
+    /// - `records` are the validation records.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn add_validation_records(mut self, records: Vec<TrainingRecord>) -> Self {
+        self.validation_data.extend(records);
+        self
+    }
+
+    /// Hold out a random share of the training records for validation when
+    /// the calculator is built. The split draws from [`crate::rng`], so
+    /// seeding it makes the split reproducible.
+    ///
+    /// # Arguments
+    ///
+    /// - `fraction` is the share of the training records to hold out, from 0
+    ///   to 1.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{FitnessCalc, TrainingRecord};
+    ///
+    /// let records = (0..10)
+    ///     .map(|index| TrainingRecord { input: vec![f64::from(index)], output: vec![0.0], weight: 1.0 })
+    ///     .collect();
+    /// let fitness_calc = FitnessCalc::builder()
+    ///     .add_training_records(records)
+    ///     .validation_fraction(0.2)
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn validation_fraction(mut self, fraction: f64) -> Self {
+        self.validation_fraction = Some(fraction);
+        self
+    }
+
+    /// Score genomes against harder training records as evolution goes on.
+    ///
+    /// # Arguments
+    ///
+    /// - `curriculum` is the schedule of difficulties.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn curriculum(mut self, curriculum: Curriculum) -> Self {
+        self.curriculum = Some(curriculum);
+        self
+    }
+
+    /// Calculate fitness with a function instead of training records, e.g. to
+    /// score genomes by how well they control a simulation. Training records
+    /// added to the builder are ignored.
+    ///
+    /// The function is given each genome's prediction function, and returns
+    /// its fitness, where lower is better. It runs to the end even with a
+    /// timeout, but a genome that ran over still gets the worst fitness.
+    ///
+    /// # Arguments
+    ///
+    /// - `fitness` is the fitness function.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::{FitnessCalc, Predict};
+    ///
+    /// struct Controller(f64);
+    ///
+    /// impl Predict for Controller {
+    ///     fn predict(&self, input: &[f64]) -> Vec<f64> {
+    ///         vec![input[0] * self.0]
+    ///     }
+    /// }
+    ///
+    /// // Steer a position towards zero, and score the distance left.
+    /// let fitness_calc = FitnessCalc::builder()
+    ///     .custom(|predict: &dyn Predict| {
+    ///         let mut position: f64 = 10.0;
+    ///         for _ in 0..10 {
+    ///             position += predict.predict(&[position])[0];
+    ///         }
+    ///         Ok(position.abs())
+    ///     })
+    ///     .build();
+    ///
+    /// assert_eq!(fitness_calc.check(&Controller(-1.0)), Ok(0.0));
+    /// assert_eq!(fitness_calc.check(&Controller(0.0)), Ok(10.0));
+    /// ```
+    #[must_use]
+    pub fn custom(
+        mut self,
+        fitness: impl Fn(&dyn Predict) -> Result<f64> + Send + Sync + 'static,
+    ) -> Self {
+        self.custom = Some(Arc::new(fitness));
+        self
+    }
+
+    /// Limit how long an entity may take to predict the training data, so a
+    /// slow or stuck predictor cannot hold up a whole generation. Entities
+    /// that run over are given the worst possible fitness.
+    ///
+    /// Checks poll the clock after each training record, so a single
+    /// prediction that never returns holds them up. [`Calc::check_watched`]
+    /// gives up on such a prediction instead.
+    ///
+    /// # Arguments
+    ///
+    /// - `timeout` is the wall-clock time allowed for each fitness check.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::FitnessCalc;
+    /// use std::time::Duration;
+    ///
+    /// let fitness_calc = FitnessCalc::builder()
+    ///     .timeout(Duration::from_millis(50))
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
         self
     }
 
@@ -352,9 +1206,33 @@ impl Builder {
     ///
     /// let fitness_calc = FitnessCalc::builder().build();
     /// ```
-    pub fn build(self) -> Calc {
+    #[must_use]
+    pub fn build(mut self) -> Calc {
+        if let Some(fraction) = self.validation_fraction {
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_precision_loss,
+                clippy::cast_sign_loss
+            )]
+            let count =
+                (self.training_data.len() as f64 * fraction.clamp(0.0, 1.0)).round() as usize;
+            self.training_data.shuffle(&mut rng::handle());
+            let held_out = self.training_data.len() - count;
+            self.validation_data.extend(
+                self.training_data
+                    .drain(held_out..)
+                    .map(|graded| graded.record),
+            );
+        }
+
         Calc {
-            training_data: self.training_data,
+            training_data: RwLock::new(Arc::new(self.training_data)),
+            validation_data: self.validation_data,
+            custom: self.custom,
+            timeout: self.timeout,
+            updates: Handle::default(),
+            curriculum: self.curriculum,
+            generation: AtomicUsize::default(),
         }
     }
 }
@@ -370,6 +1248,7 @@ mod tests {
             .add_training_record(TrainingRecord {
                 input: vec![0.0, 0.0],
                 output: vec![0.0],
+                weight: 1.0,
             })
             .build();
 
@@ -384,17 +1263,225 @@ mod tests {
             .add_training_record(TrainingRecord {
                 input: vec![0.0, 0.0],
                 output: vec![0.0],
+                weight: 1.0,
             })
             .build();
 
-        let actual = fitness_calc.best_entity(&[Predictor(1.0), Predictor(2.0)], &Comparator);
+        let actual = fitness_calc.best_entity_by(&[Predictor(1.0), Predictor(2.0)], &Comparator);
+        assert_eq!(actual, Ok(Some(&Predictor(1.0))));
 
+        let fitness_calc = Calc::builder()
+            .add_training_record(TrainingRecord {
+                input: vec![1.0, 1.0],
+                output: vec![0.0],
+                weight: 1.0,
+            })
+            .build();
+        let actual = fitness_calc.best_entity(&[Predictor(2.0), Predictor(1.0)]);
         assert_eq!(actual, Ok(Some(&Predictor(1.0))));
     }
 
-    #[derive(Ord, PartialOrd, Eq, PartialEq, Debug)]
+    #[test]
+    fn test_fitness_calc_timeout() {
+        let fitness_calc = Calc::builder()
+            .add_training_records(
+                (0..3)
+                    .map(|_| TrainingRecord {
+                        input: vec![0.0, 0.0],
+                        output: vec![0.0],
+                        weight: 1.0,
+                    })
+                    .collect(),
+            )
+            .timeout(Duration::from_millis(5))
+            .build();
+
+        assert!(fitness_calc.check(&TestPredict).unwrap().abs() <= f64::EPSILON);
+        assert!(fitness_calc
+            .check(&SlowPredict)
+            .unwrap()
+            .total_cmp(&f64::MAX)
+            .is_eq());
+    }
+
+    #[test]
+    fn test_fitness_calc_watchdog() {
+        #[derive(Clone)]
+        struct StuckPredict;
+
+        impl Predict for StuckPredict {
+            fn predict(&self, _input: &[f64]) -> Vec<f64> {
+                std::thread::sleep(Duration::from_secs(2));
+                vec![0.0]
+            }
+        }
+
+        let fitness_calc = Calc::builder()
+            .add_training_record(TrainingRecord::new(vec![0.0, 0.0], vec![0.0]))
+            .timeout(Duration::from_millis(20))
+            .build();
+
+        let started = Instant::now();
+        assert_eq!(fitness_calc.check_watched(&StuckPredict), Ok(f64::MAX));
+        // Queued behind the stuck check, which still holds the worker.
+        assert_eq!(fitness_calc.check_watched(&TestPredict), Ok(f64::MAX));
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        let untimed = Calc::builder()
+            .add_training_record(TrainingRecord::new(vec![0.0, 0.0], vec![2.0]))
+            .build();
+        assert_eq!(untimed.check_watched(&TestPredict), Ok(4.0));
+    }
+
+    #[test]
+    fn test_fitness_calc_weights() {
+        let record = |output, weight| TrainingRecord {
+            input: vec![0.0, 0.0],
+            output: vec![output],
+            weight,
+        };
+        let fitness_calc = Calc::builder()
+            .add_training_records(vec![record(0.0, 3.0), record(2.0, 1.0)])
+            .build();
+
+        // Errors of 0 and 4, weighted 3 to 1.
+        assert!((fitness_calc.check(&TestPredict).unwrap() - 1.0).abs() < f64::EPSILON);
+        assert_eq!(fitness_calc.errors(&TestPredict), Ok(vec![0.0, 4.0]));
+
+        let weightless = Calc::builder()
+            .add_training_record(record(0.0, 0.0))
+            .build();
+        assert_eq!(weightless.check(&TestPredict), Err(Error::ResultNaN));
+    }
+
+    #[test]
+    fn test_check_detailed() {
+        let record = |output, weight| TrainingRecord {
+            input: vec![0.0, 0.0],
+            output: vec![output],
+            weight,
+        };
+        let fitness_calc = Calc::builder()
+            .add_training_records(vec![record(0.0, 3.0), record(2.0, 1.0)])
+            .build();
+
+        let report = fitness_calc.check_detailed(&TestPredict).unwrap();
+        assert_eq!(Ok(report.fitness), fitness_calc.check(&TestPredict));
+        assert_eq!(
+            report.records,
+            vec![
+                RecordReport {
+                    mse: 0.0,
+                    weight: 3.0,
+                    outputs: vec![0.0],
+                },
+                RecordReport {
+                    mse: 4.0,
+                    weight: 1.0,
+                    outputs: vec![4.0],
+                },
+            ]
+        );
+
+        let custom = Calc::builder().custom(|_: &dyn Predict| Ok(2.0)).build();
+        let report = custom.check_detailed(&TestPredict).unwrap();
+        assert!((report.fitness - 2.0).abs() < f64::EPSILON);
+        assert!(report.records.is_empty());
+    }
+
+    #[test]
+    fn test_validation_fraction() {
+        let records = || {
+            (0..10)
+                .map(|index| TrainingRecord {
+                    input: vec![0.0, 0.0],
+                    output: vec![f64::from(index)],
+                    weight: 1.0,
+                })
+                .collect()
+        };
+        let split = |seed| {
+            rng::seeded(seed, || {
+                Calc::builder()
+                    .add_training_records(records())
+                    .validation_fraction(0.2)
+                    .build()
+            })
+        };
+
+        let fitness_calc = split(3);
+        assert_eq!(fitness_calc.record_count(), 8);
+        assert_eq!(fitness_calc.validation_data.len(), 2);
+        assert!(fitness_calc.validate(&TestPredict).is_some());
+        assert_eq!(
+            fitness_calc.validate(&TestPredict),
+            split(3).validate(&TestPredict)
+        );
+        assert!(Calc::builder().build().validate(&TestPredict).is_none());
+    }
+
+    #[test]
+    fn test_fitness_calc_custom() {
+        let fitness_calc = Calc::builder()
+            .add_training_record(TrainingRecord {
+                input: vec![0.0, 0.0],
+                output: vec![1.0],
+                weight: 1.0,
+            })
+            .custom(|predict: &dyn Predict| Ok(predict.predict(&[0.0, 0.0])[0] + 1.0))
+            .timeout(Duration::from_millis(5))
+            .build();
+
+        assert!((fitness_calc.check(&TestPredict).unwrap() - 1.0).abs() < f64::EPSILON);
+        assert_eq!(
+            fitness_calc.errors(&TestPredict).map(|errors| errors.len()),
+            Ok(1)
+        );
+        assert!(fitness_calc
+            .check(&SlowPredict)
+            .unwrap()
+            .total_cmp(&f64::MAX)
+            .is_eq());
+
+        let nan = Calc::builder()
+            .custom(|_: &dyn Predict| Ok(f64::NAN))
+            .build();
+        assert_eq!(nan.check(&TestPredict), Err(Error::ResultNaN));
+    }
+
+    #[test]
+    fn test_apply_updates() {
+        let record = |output| TrainingRecord {
+            input: vec![0.0],
+            output: vec![output],
+            weight: 1.0,
+        };
+        let fitness_calc = Calc::builder().add_training_record(record(0.0)).build();
+        let handle = fitness_calc.updates();
+
+        handle.append(vec![record(2.0)]);
+        assert!(fitness_calc.apply_updates());
+        assert_eq!(fitness_calc.check(&TestPredict), Ok(2.0));
+
+        handle.append(vec![record(4.0)]);
+        handle.replace(vec![record(1.0)]);
+        handle.append(vec![record(3.0)]);
+        assert!(fitness_calc.apply_updates());
+        assert_eq!(fitness_calc.check(&TestPredict), Ok(5.0));
+    }
+
+    #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Debug)]
     struct TestPredict;
 
+    struct SlowPredict;
+
+    impl Predict for SlowPredict {
+        fn predict(&self, _input: &[f64]) -> Vec<f64> {
+            std::thread::sleep(Duration::from_millis(10));
+            vec![0.0]
+        }
+    }
+
     impl Predict for TestPredict {
         fn predict(&self, _input: &[f64]) -> Vec<f64> {
             vec![0.0]
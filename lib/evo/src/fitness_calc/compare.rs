@@ -1,4 +1,5 @@
 use super::Predict;
+use std::cmp::Ordering;
 
 /// A trait for comparing entities.
 ///
@@ -63,7 +64,70 @@ where
     P: Predict + PartialOrd,
 {
     /// Compare two entities.
-    fn compare(&self, left: &Record<&P>, right: &Record<&P>) -> std::cmp::Ordering;
+    fn compare(&self, left: &Record<&P>, right: &Record<&P>) -> Ordering;
+}
+
+/// Compares entities by fitness, ranking a fitness that is `NaN` or infinite
+/// after every finite one. This is how the algorithm ranks genomes, and what
+/// [`crate::FitnessCalc::best_entity`] uses.
+///
+/// # Examples
+///
+/// ```
+/// use evo::{Compare, CompareRecord, NanSafeCompare, Predict};
+/// use std::cmp::Ordering;
+///
+/// #[derive(PartialEq, PartialOrd)]
+/// struct Predictor;
+///
+/// impl Predict for Predictor {
+///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+///         vec![0.0]
+///     }
+/// }
+///
+/// let record = |fitness| CompareRecord {
+///     fitness,
+///     predict: &Predictor,
+/// };
+///
+/// assert_eq!(NanSafeCompare.compare(&record(1.0), &record(2.0)), Ordering::Less);
+/// assert_eq!(NanSafeCompare.compare(&record(f64::NAN), &record(2.0)), Ordering::Greater);
+/// assert_eq!(NanSafeCompare.compare(&record(1.0), &record(f64::NEG_INFINITY)), Ordering::Less);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NanSafe;
+
+impl NanSafe {
+    /// Compare two fitness values.
+    ///
+    /// # Arguments
+    ///
+    /// - `left` is the first fitness.
+    /// - `right` is the second fitness.
+    ///
+    /// # Returns
+    ///
+    /// The order of the fitness values, where smaller is better and values
+    /// that are `NaN` or infinite come last, equal to each other.
+    #[must_use]
+    pub fn fitness(left: f64, right: f64) -> Ordering {
+        match (left.is_finite(), right.is_finite()) {
+            (true, true) => left.total_cmp(&right),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => Ordering::Equal,
+        }
+    }
+}
+
+impl<P> Compare<P> for NanSafe
+where
+    P: Predict + PartialOrd,
+{
+    fn compare(&self, left: &Record<&P>, right: &Record<&P>) -> Ordering {
+        Self::fitness(left.fitness, right.fitness)
+    }
 }
 
 /// A record for comparing entities.
@@ -91,7 +155,7 @@ impl<P> PartialOrd for Record<P>
 where
     P: Predict + PartialOrd,
 {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.fitness.partial_cmp(&other.fitness)
     }
 }
@@ -111,7 +175,6 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::cmp::Ordering;
 
     struct Predictor;
 
@@ -128,6 +191,7 @@ mod tests {
 
     impl Eq for Predictor {}
 
+    #[allow(clippy::non_canonical_partial_ord_impl)]
     impl PartialOrd for Predictor {
         fn partial_cmp(&self, _other: &Self) -> Option<Ordering> {
             Some(Ordering::Equal)
@@ -164,4 +228,19 @@ mod tests {
 
         assert_eq!(ordering, Ordering::Less);
     }
+
+    #[test]
+    fn nan_safe() {
+        let record = |fitness| Record {
+            fitness,
+            predict: &Predictor,
+        };
+        let mut fitness = [f64::NAN, 2.0, f64::INFINITY, -1.0, f64::NEG_INFINITY, 0.0];
+
+        fitness.sort_by(|left, right| NanSafe.compare(&record(*left), &record(*right)));
+
+        assert_eq!(fitness[..3], [-1.0, 0.0, 2.0]);
+        assert!(fitness[3..].iter().all(|fitness| !fitness.is_finite()));
+        assert_eq!(NanSafe::fitness(f64::NAN, f64::INFINITY), Ordering::Equal);
+    }
 }
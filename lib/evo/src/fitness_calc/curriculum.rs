@@ -0,0 +1,108 @@
+/// A schedule that introduces harder training records as evolution goes on,
+/// so genomes learn the simple cases before the hard ones.
+///
+/// Each stage starts at a generation and allows records up to a difficulty.
+/// Before the first stage, and when there are no stages, every record is used.
+///
+/// # Examples
+///
+/// ```
+/// use evo::Curriculum;
+///
+/// let curriculum = Curriculum::builder()
+///     .stage(0, 0)
+///     .stage(50, 1)
+///     .stage(200, u32::MAX)
+///     .build();
+///
+/// assert_eq!(curriculum.difficulty(10), Some(0));
+/// assert_eq!(curriculum.difficulty(50), Some(1));
+/// assert_eq!(curriculum.difficulty(500), Some(u32::MAX));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Curriculum {
+    stages: Vec<(usize, u32)>,
+}
+
+impl Curriculum {
+    /// Create a new builder.
+    ///
+    /// # Returns
+    ///
+    /// A new builder.
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Get the hardest difficulty allowed in a generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation` is the number of the generation, starting at 0.
+    ///
+    /// # Returns
+    ///
+    /// The difficulty, or `None` if every record is allowed.
+    #[must_use]
+    pub fn difficulty(&self, generation: usize) -> Option<u32> {
+        self.stages
+            .iter()
+            .take_while(|(start, _)| *start <= generation)
+            .last()
+            .map(|(_, difficulty)| *difficulty)
+    }
+}
+
+/// A builder for a curriculum.
+#[derive(Default)]
+pub struct Builder {
+    stages: Vec<(usize, u32)>,
+}
+
+impl Builder {
+    /// Add a stage to the curriculum.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation` is the generation the stage starts at.
+    /// - `difficulty` is the hardest difficulty allowed during the stage.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn stage(mut self, generation: usize, difficulty: u32) -> Self {
+        self.stages.push((generation, difficulty));
+        self
+    }
+
+    /// Build the curriculum.
+    ///
+    /// # Returns
+    ///
+    /// The curriculum, with its stages in order of generation.
+    #[must_use]
+    pub fn build(mut self) -> Curriculum {
+        self.stages.sort_by_key(|(generation, _)| *generation);
+        Curriculum {
+            stages: self.stages,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difficulty() {
+        let curriculum = Curriculum::builder().stage(20, 2).stage(5, 1).build();
+
+        assert_eq!(Curriculum::default().difficulty(100), None);
+        assert_eq!(
+            [0, 5, 19, 20, 100].map(|generation| curriculum.difficulty(generation)),
+            [None, Some(1), Some(1), Some(2), Some(2)]
+        );
+    }
+}
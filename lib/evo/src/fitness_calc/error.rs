@@ -9,6 +9,18 @@ pub enum Error {
 
     #[error("result is infinite")]
     ResultInfinite,
+
+    #[error("no score was given")]
+    Unscored,
+
+    #[error("the worker could not score the genome: {0}")]
+    Remote(String),
+
+    #[error("no worker could be reached")]
+    Unreachable,
+
+    #[error("the evaluation budget is spent")]
+    OverBudget,
 }
 
 /// A result that can occur when calculating fitness.
@@ -0,0 +1,64 @@
+use super::{FitnessCalc, Predict, Result};
+
+/// A trait for scoring a whole generation at once.
+///
+/// The algorithm hands every genome that needs a fitness to a single call,
+/// so an implementation can batch the work, e.g. by uploading the generation
+/// and the training data to a GPU and computing every fitness in one
+/// dispatch. [`FitnessCalc`] implements it by checking each genome in turn.
+///
+/// # Examples
+///
+/// ```
+/// use evo::{Evaluate, Result};
+///
+/// struct Genome(f64);
+///
+/// struct Evaluator;
+///
+/// impl Evaluate<Genome> for Evaluator {
+///     fn evaluate(&self, generation: &[Genome]) -> Vec<Result<f64>> {
+///         generation.iter().map(|genome| Ok(genome.0.abs())).collect()
+///     }
+/// }
+///
+/// let fitness = Evaluator.evaluate(&[Genome(-1.0), Genome(0.5)]);
+///
+/// assert_eq!(fitness, vec![Ok(1.0), Ok(0.5)]);
+/// ```
+pub trait Evaluate<TGenome> {
+    /// Calculate the fitness of every genome in a generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation` is the genomes to score.
+    ///
+    /// # Returns
+    ///
+    /// One fitness per genome, in the same order. Genomes whose fitness
+    /// cannot be calculated are discarded by the algorithm.
+    fn evaluate(&self, generation: &[TGenome]) -> Vec<Result<f64>>;
+}
+
+impl<P> Evaluate<P> for FitnessCalc
+where
+    P: Predict,
+{
+    fn evaluate(&self, generation: &[P]) -> Vec<Result<f64>> {
+        generation
+            .iter()
+            .map(|predict| self.check(predict))
+            .collect()
+    }
+}
+
+/// Lets an evaluator be shared with the algorithm while the caller keeps a
+/// handle to it, e.g. to read back state it gathers during evaluation.
+impl<TGenome, E> Evaluate<TGenome> for std::sync::Arc<E>
+where
+    E: Evaluate<TGenome> + ?Sized,
+{
+    fn evaluate(&self, generation: &[TGenome]) -> Vec<Result<f64>> {
+        (**self).evaluate(generation)
+    }
+}
@@ -0,0 +1,159 @@
+use super::{Error, Evaluate, Predict, Result};
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+/// An evaluator that asks a person to score each genome, for tasks with no
+/// programmable objective, such as evolving pleasing outputs.
+///
+/// Each genome predicts a fixed set of prompt inputs, and its outputs are
+/// passed to a callback, which presents them and returns the person's score.
+/// Scores are fitness values, so lower is better. They are cached by output,
+/// so a genome is never presented twice, and neither are two genomes that
+/// behave the same.
+///
+/// # Examples
+///
+/// ```
+/// use evo::{Evaluate, InteractiveEvaluator, Predict};
+///
+/// struct Genome(f64);
+///
+/// impl Predict for Genome {
+///     fn predict(&self, input: &[f64]) -> Vec<f64> {
+///         input.iter().map(|x| x * self.0).collect()
+///     }
+/// }
+///
+/// let evaluator = InteractiveEvaluator::new(vec![vec![1.0]], |outputs: &[Vec<f64>]| {
+///     // Present `outputs` and read a score; here, prefer outputs near 2.0.
+///     Some((outputs[0][0] - 2.0).abs())
+/// });
+///
+/// let fitness = evaluator.evaluate(&[Genome(1.0), Genome(2.0)]);
+///
+/// assert_eq!(fitness, vec![Ok(1.0), Ok(0.0)]);
+/// ```
+pub struct Interactive<F> {
+    inputs: Vec<Vec<f64>>,
+    score: F,
+    scores: Mutex<HashMap<Vec<Vec<u64>>, f64>>,
+}
+
+impl<F> Interactive<F>
+where
+    F: Fn(&[Vec<f64>]) -> Option<f64>,
+{
+    /// Create a new interactive evaluator.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` are the prompts every genome predicts.
+    /// - `score` presents a genome's outputs, one per prompt, and returns its
+    ///   score, or `None` if the person skipped it.
+    ///
+    /// # Returns
+    ///
+    /// The evaluator.
+    pub fn new(inputs: Vec<Vec<f64>>, score: F) -> Self {
+        Self {
+            inputs,
+            score,
+            scores: Mutex::default(),
+        }
+    }
+
+    /// Get the number of distinct outputs that have been scored.
+    ///
+    /// # Returns
+    ///
+    /// The number of cached scores.
+    pub fn scored(&self) -> usize {
+        self.scores
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Score one genome, asking for a score only if its outputs are new.
+    ///
+    /// # Arguments
+    ///
+    /// - `predict` is the genome.
+    ///
+    /// # Returns
+    ///
+    /// The score.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::Unscored`] if the person skipped the genome.
+    fn score<P>(&self, predict: &P) -> Result<f64>
+    where
+        P: Predict,
+    {
+        let outputs = self
+            .inputs
+            .iter()
+            .map(|input| predict.predict(input))
+            .collect::<Vec<_>>();
+        let key = outputs
+            .iter()
+            .map(|output| output.iter().map(|value| value.to_bits()).collect())
+            .collect::<Vec<_>>();
+
+        let mut scores = self.scores.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(score) = scores.get(&key) {
+            return Ok(*score);
+        }
+        let score = (self.score)(&outputs).ok_or(Error::Unscored)?;
+        scores.insert(key, score);
+        Ok(score)
+    }
+}
+
+impl<P, F> Evaluate<P> for Interactive<F>
+where
+    P: Predict,
+    F: Fn(&[Vec<f64>]) -> Option<f64>,
+{
+    fn evaluate(&self, generation: &[P]) -> Vec<Result<f64>> {
+        generation
+            .iter()
+            .map(|predict| self.score(predict))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Genome(f64);
+
+    impl Predict for Genome {
+        fn predict(&self, input: &[f64]) -> Vec<f64> {
+            input.iter().map(|x| x * self.0).collect()
+        }
+    }
+
+    #[test]
+    fn test_caches_scores() {
+        let asked = AtomicUsize::new(0);
+        let evaluator = Interactive::new(vec![vec![1.0], vec![2.0]], |outputs: &[Vec<f64>]| {
+            asked.fetch_add(1, Ordering::Relaxed);
+            (outputs[0][0] >= 0.0).then_some(outputs[1][0])
+        });
+
+        let fitness = evaluator.evaluate(&[Genome(1.0), Genome(3.0), Genome(1.0)]);
+        assert_eq!(fitness, vec![Ok(2.0), Ok(6.0), Ok(2.0)]);
+        assert_eq!(
+            evaluator.evaluate(&[Genome(-1.0)]),
+            vec![Err(Error::Unscored)]
+        );
+
+        assert_eq!(evaluator.evaluate(&[Genome(3.0)]), vec![Ok(6.0)]);
+        assert_eq!(asked.load(Ordering::Relaxed), 3);
+        assert_eq!(evaluator.scored(), 2);
+    }
+}
@@ -1,13 +1,23 @@
 mod calc;
 mod compare;
+mod curriculum;
 mod error;
+mod evaluate;
+mod interactive;
 mod predict;
+mod report;
 mod training;
+mod updates;
 
 pub use self::{
     calc::{Builder, Calc as FitnessCalc},
-    compare::{Compare, Record as CompareRecord},
+    compare::{Compare, NanSafe as NanSafeCompare, Record as CompareRecord},
+    curriculum::Curriculum,
     error::{Error, Result},
+    evaluate::Evaluate,
+    interactive::Interactive as InteractiveEvaluator,
     predict::Predict,
+    report::{Record as RecordReport, Report as FitnessReport},
     training::Record as TrainingRecord,
+    updates::Handle as TrainingDataHandle,
 };
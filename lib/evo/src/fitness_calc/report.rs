@@ -0,0 +1,114 @@
+/// A breakdown of an entity's fitness by training record and output, for
+/// error analysis.
+///
+/// # Examples
+///
+/// ```
+/// use evo::{FitnessCalc, Predict, TrainingRecord};
+///
+/// struct Zero;
+///
+/// impl Predict for Zero {
+///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+///         vec![0.0, 0.0]
+///     }
+/// }
+///
+/// let fitness_calc = FitnessCalc::builder()
+///     .add_training_record(TrainingRecord {
+///         input: vec![0.0],
+///         output: vec![1.0, 3.0],
+///         weight: 1.0,
+///     })
+///     .build();
+/// let report = fitness_calc.check_detailed(&Zero).unwrap();
+///
+/// assert_eq!(report.fitness, 5.0);
+/// assert_eq!(report.records[0].outputs, vec![1.0, 9.0]);
+/// assert_eq!(report.output_errors(), vec![1.0, 9.0]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    /// The fitness, the same as [`crate::FitnessCalc::check`] gives.
+    pub fitness: f64,
+
+    /// The errors on each training record the current generation uses, in
+    /// order. Empty if the fitness came from a custom fitness function, and
+    /// cut short if the check ran out of time.
+    pub records: Vec<Record>,
+}
+
+/// The errors of an entity on one training record.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    /// The mean squared error of the record.
+    pub mse: f64,
+
+    /// The weight of the record.
+    pub weight: f64,
+
+    /// The squared error of each output.
+    pub outputs: Vec<f64>,
+}
+
+impl Report {
+    /// Get the mean squared error of each output over every record, e.g. to
+    /// find which output an entity gets wrong most.
+    ///
+    /// # Returns
+    ///
+    /// The mean error of each output, weighted by each record's weight. An
+    /// output whose records weigh nothing in total is `NaN`.
+    #[must_use]
+    pub fn output_errors(&self) -> Vec<f64> {
+        let outputs = self
+            .records
+            .iter()
+            .map(|record| record.outputs.len())
+            .max()
+            .unwrap_or_default();
+        let mut sums = vec![0.0; outputs];
+        let mut weights = vec![0.0; outputs];
+        for record in &self.records {
+            for (output, error) in record.outputs.iter().enumerate() {
+                sums[output] += error * record.weight;
+                weights[output] += record.weight;
+            }
+        }
+        Iterator::zip(sums.into_iter(), weights)
+            .map(|(sum, weight)| sum / weight)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_errors() {
+        let report = Report {
+            fitness: 0.0,
+            records: vec![
+                Record {
+                    mse: 1.0,
+                    weight: 3.0,
+                    outputs: vec![0.0, 2.0],
+                },
+                Record {
+                    mse: 2.0,
+                    weight: 1.0,
+                    outputs: vec![4.0],
+                },
+            ],
+        };
+
+        assert_eq!(report.output_errors(), vec![1.0, 2.0]);
+        assert!(Report {
+            fitness: 0.0,
+            records: vec![],
+        }
+        .output_errors()
+        .is_empty());
+    }
+}
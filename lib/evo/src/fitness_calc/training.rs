@@ -1,13 +1,72 @@
+use serde::{Deserialize, Serialize};
+
 /// A fitness calculator record.
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Record {
     /// The input data for this training record.
     pub input: Vec<f64>,
 
     /// The expected output data for this training record.
     pub output: Vec<f64>,
+
+    /// How much this record counts towards the fitness, compared to the
+    /// others; 1 for an ordinary record. Raise it for rare cases that matter
+    /// more than their share of the data.
+    #[serde(default = "Record::default_weight")]
+    pub weight: f64,
 }
 
 impl Record {
+    /// Create a record that counts as much as any ordinary record.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` is the input data.
+    /// - `output` is the expected output data.
+    ///
+    /// # Returns
+    ///
+    /// The record, with a weight of 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::TrainingRecord;
+    ///
+    /// let record = TrainingRecord::new(vec![1.0], vec![2.0]);
+    ///
+    /// assert!((record.weight - 1.0).abs() < f64::EPSILON);
+    /// ```
+    #[must_use]
+    pub fn new(input: Vec<f64>, output: Vec<f64>) -> Self {
+        Self::weighted(input, output, Self::default_weight())
+    }
+
+    /// Create a record that counts more or less than an ordinary record.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` is the input data.
+    /// - `output` is the expected output data.
+    /// - `weight` is how much the record counts; 1 for an ordinary record.
+    ///
+    /// # Returns
+    ///
+    /// The record.
+    #[must_use]
+    pub fn weighted(input: Vec<f64>, output: Vec<f64>, weight: f64) -> Self {
+        Self {
+            input,
+            output,
+            weight,
+        }
+    }
+
+    /// The weight of records saved before they could be weighted.
+    fn default_weight() -> f64 {
+        1.0
+    }
+
     /// Calculate the mean squared error between the actual values provided
     /// and the expected outputs. Values closer to 0.0 are better.
     ///
@@ -24,10 +83,7 @@ impl Record {
     /// ```
     /// use evo::TrainingRecord;
     ///
-    /// let record = TrainingRecord {
-    ///     input: vec![1.0, 2.0, 3.0],
-    ///     output: vec![2.0, 4.0, 6.0],
-    /// };
+    /// let record = TrainingRecord::new(vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 6.0]);
     ///
     /// let actual = vec![2.0, 4.0, 6.0];
     ///
@@ -50,6 +106,7 @@ mod tests {
         let record = Record {
             input: vec![1.0, 2.0, 3.0],
             output: vec![2.0, 4.0, 6.0],
+            weight: 1.0,
         };
 
         let actual = vec![2.0, 4.0, 6.0];
@@ -58,4 +115,13 @@ mod tests {
 
         assert_eq!(mse, vec![0.0, 0.0, 0.0]);
     }
+
+    #[test]
+    fn test_record_without_weight() {
+        let record: Record = serde_json::from_str(r#"{"input":[1.0],"output":[2.0]}"#).unwrap();
+
+        assert!((record.weight - 1.0).abs() < f64::EPSILON);
+        assert_eq!(record.input, vec![1.0]);
+        assert_eq!(record.output, vec![2.0]);
+    }
 }
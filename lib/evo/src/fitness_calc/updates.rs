@@ -0,0 +1,60 @@
+use super::TrainingRecord;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Changes to a fitness calculator's training data, waiting to be applied.
+#[derive(Default)]
+pub(super) struct Pending {
+    pub(super) replacement: Option<Vec<TrainingRecord>>,
+    pub(super) additions: Vec<TrainingRecord>,
+}
+
+/// A handle for changing a fitness calculator's training data while it is in
+/// use, e.g. to add records collected online from a simulator.
+///
+/// Changes are staged, and take effect the next time the calculator applies
+/// its updates, which the evolutionary algorithm does at the start of every
+/// generation. Every genome of a generation is scored against the same data.
+///
+/// # Examples
+///
+/// ```
+/// use evo::{FitnessCalc, TrainingRecord};
+///
+/// let fitness_calc = FitnessCalc::builder().build();
+/// let handle = fitness_calc.updates();
+///
+/// std::thread::spawn(move || {
+///     handle.append(vec![TrainingRecord::new(vec![1.0], vec![1.0])]);
+/// })
+/// .join()
+/// .unwrap();
+///
+/// assert!(fitness_calc.apply_updates());
+/// ```
+#[derive(Clone, Default)]
+pub struct Handle {
+    pub(super) pending: Arc<Mutex<Pending>>,
+}
+
+impl Handle {
+    /// Replace every training record, discarding earlier staged changes.
+    ///
+    /// # Arguments
+    ///
+    /// - `records` are the new training records.
+    pub fn replace(&self, records: Vec<TrainingRecord>) {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        pending.replacement = Some(records);
+        pending.additions.clear();
+    }
+
+    /// Add training records to the existing ones.
+    ///
+    /// # Arguments
+    ///
+    /// - `records` are the training records to add.
+    pub fn append(&self, records: Vec<TrainingRecord>) {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        pending.additions.extend(records);
+    }
+}
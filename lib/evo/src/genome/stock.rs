@@ -1,3 +1,5 @@
+use rayon::prelude::*;
+
 /// Stock a generation with genomes.
 ///
 /// # Examples
@@ -36,6 +38,26 @@ pub trait Stock<TGenome> {
             .take(generation_size)
             .collect()
     }
+
+    /// Stock a generation, generating the genomes in parallel.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation_size` is the number of genomes to generate.
+    ///
+    /// # Returns
+    ///
+    /// The generation.
+    fn par_stock(&self, generation_size: usize) -> super::Generation<TGenome>
+    where
+        Self: Sync,
+        TGenome: Send,
+    {
+        (0..generation_size)
+            .into_par_iter()
+            .map(|_| self.generate())
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -51,6 +73,13 @@ mod tests {
         assert_eq!(generation, expected);
     }
 
+    #[test]
+    fn test_par_stock() {
+        let generation = Stocker.par_stock(100);
+
+        assert_eq!(generation, vec![Genome { value: 2.0 }; 100]);
+    }
+
     #[derive(Debug, PartialEq, Copy, Clone)]
     struct Genome {
         value: f64,
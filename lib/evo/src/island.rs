@@ -0,0 +1,669 @@
+//! The island model: several populations evolve apart and trade their
+//! fittest genomes now and then.
+//!
+//! A single population tends to converge on one solution early. Islands
+//! explore different solutions side by side, and migration spreads good
+//! genes between them without letting any one take over at once.
+
+use crate::algo::unrank_generation;
+use crate::stats::{GenerationStats, History};
+use crate::{
+    Breed, CompareRecord, EvoAlgorithm, Evolution, Generation, NanSafeCompare, Predict, Stop,
+    Termination,
+};
+use rayon::prelude::*;
+use std::ops::ControlFlow;
+use std::time::Instant;
+
+/// Which islands migrants travel to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Topology {
+    /// Each island sends its migrants to the next one, and the last island
+    /// sends them to the first. Good genes spread slowly, which keeps the
+    /// islands diverse. This is the default.
+    #[default]
+    Ring,
+
+    /// Each island sends its migrants to every other island.
+    FullyConnected,
+}
+
+impl Topology {
+    /// Get the islands that send migrants to an island.
+    ///
+    /// # Arguments
+    ///
+    /// - `island` is the index of the receiving island.
+    /// - `islands` is the number of islands.
+    ///
+    /// # Returns
+    ///
+    /// The indexes of the sending islands.
+    fn sources(self, island: usize, islands: usize) -> Vec<usize> {
+        match self {
+            _ if islands < 2 => vec![],
+            Self::Ring => vec![(island + islands - 1) % islands],
+            Self::FullyConnected => (0..islands).filter(|&source| source != island).collect(),
+        }
+    }
+}
+
+/// An island's generation, once scored.
+struct Scored<TGenome>
+where
+    TGenome: Predict + PartialOrd,
+{
+    index: usize,
+    ranked: Vec<CompareRecord<TGenome>>,
+    stats: Option<GenerationStats>,
+    flow: ControlFlow<()>,
+}
+
+/// Runs several populations, each with its own algorithm, and migrates the
+/// fittest genomes between them.
+///
+/// Every island is scored, bred, and observed by its own algorithm, so
+/// islands may use different settings. Migrants keep the fitness their home
+/// island gave them, so islands should share a fitness calculator.
+///
+/// # Examples
+///
+/// ```
+/// use evo::island::{Archipelago, Topology};
+/// use evo::{Breed, EvoAlgorithm, FitnessCalc, Predict, Termination, TrainingRecord};
+///
+/// #[derive(Clone, Debug, PartialEq, PartialOrd)]
+/// struct Genome {
+///     value: f64,
+/// }
+///
+/// impl Predict for Genome {
+///     fn predict(&self, _input: &[f64]) -> Vec<f64> {
+///         vec![self.value]
+///     }
+/// }
+///
+/// struct Breeder;
+///
+/// impl Breed<Genome> for Breeder {
+///     fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+///         Genome {
+///             value: (pair.0.value + pair.1.value) / 2.0,
+///         }
+///     }
+/// }
+///
+/// let algo = || {
+///     EvoAlgorithm::builder()
+///         .breeder(Breeder)
+///         .fitness_calc(
+///             FitnessCalc::builder()
+///                 .add_training_record(TrainingRecord {
+///                     input: vec![0.0],
+///                     output: vec![1.0],
+///                     weight: 1.0,
+///                 })
+///                 .build(),
+///         )
+///         .build()
+///         .unwrap()
+/// };
+/// let population = |offset: i32| (0..10).map(|value| Genome { value: f64::from(value + offset) }).collect();
+///
+/// let mut archipelago = Archipelago::builder()
+///     .island(algo(), population(0))
+///     .island(algo(), population(10))
+///     .topology(Topology::Ring)
+///     .migration_interval(5)
+///     .migrants(2)
+///     .parallel(true)
+///     .build();
+/// let evolution = archipelago.evolve(Termination::builder().generations(20).build());
+///
+/// assert_eq!(evolution.best.unwrap().predict, Genome { value: 1.0 });
+/// ```
+pub struct Archipelago<TGenome, TBreeder>
+where
+    TGenome: Predict + PartialOrd + Send + Sync,
+    TBreeder: Breed<TGenome> + Sync,
+{
+    runs: Vec<EvoAlgorithm<TGenome, TBreeder>>,
+    populations: Vec<Generation<TGenome>>,
+    topology: Topology,
+    migration_interval: usize,
+    migrants: usize,
+    parallel: bool,
+
+    /// The number of generations run so far.
+    generation: usize,
+}
+
+impl<TGenome, TBreeder> Archipelago<TGenome, TBreeder>
+where
+    TGenome: Predict + PartialOrd + Clone + Send + Sync,
+    TBreeder: Breed<TGenome> + Sync,
+{
+    /// Creates a new builder.
+    ///
+    /// # Returns
+    ///
+    /// A new builder.
+    #[must_use]
+    pub fn builder() -> Builder<TGenome, TBreeder> {
+        Builder::default()
+    }
+
+    /// Gets the current population of each island.
+    ///
+    /// # Returns
+    ///
+    /// The populations, in the order the islands were added.
+    #[must_use]
+    pub fn populations(&self) -> &[Generation<TGenome>] {
+        &self.populations
+    }
+
+    /// Runs one generation on every island, migrating first if it is due.
+    pub fn run(&mut self) {
+        let mut scored = self.score();
+        self.migrate(&mut scored);
+        self.breed(scored);
+    }
+
+    /// Runs every island until a termination criterion is met for the
+    /// archipelago as a whole.
+    ///
+    /// The criteria are checked against the fittest genome on any island.
+    /// When evolution stops, each island keeps its last generation, which
+    /// was scored but not bred.
+    ///
+    /// # Arguments
+    ///
+    /// - `termination` is when to stop.
+    ///
+    /// # Returns
+    ///
+    /// The last generation of every island, combined; the fittest genome
+    /// seen on any island; and the statistics of each generation across all
+    /// islands.
+    pub fn evolve(&mut self, termination: Termination) -> Evolution<TGenome> {
+        let started = Instant::now();
+        let evaluations = self.evaluations();
+        let mut previous = started;
+        let mut best: Option<CompareRecord<TGenome>> = None;
+        let mut history = History::default();
+        let mut stagnant = 0;
+        loop {
+            let mut scored = self.score();
+            let now = Instant::now();
+            let fitness = scored
+                .iter()
+                .flat_map(|island| island.ranked.iter().map(|record| record.fitness))
+                .collect();
+            let fittest = scored
+                .iter()
+                .flat_map(|island| &island.ranked)
+                .min_by(|left, right| NanSafeCompare::fitness(left.fitness, right.fitness));
+            let stats = GenerationStats::from_fitness(self.generation - 1, fitness);
+            let (Some(fittest), Some(mut stats)) = (fittest, stats) else {
+                return self.finish(scored, best, history, Stop::Extinct, evaluations);
+            };
+
+            stats.evaluations = scored
+                .iter()
+                .filter_map(|island| island.stats)
+                .map(|stats| stats.evaluations)
+                .sum();
+            stats.duration = now.duration_since(previous);
+            stats.elapsed = now.duration_since(started);
+            previous = now;
+            history.push(stats);
+            if best
+                .as_ref()
+                .is_none_or(|best| fittest.fitness < best.fitness)
+            {
+                best = Some(CompareRecord {
+                    fitness: fittest.fitness,
+                    predict: fittest.predict.clone(),
+                });
+                stagnant = 0;
+            } else {
+                stagnant += 1;
+            }
+
+            let stop = if scored.iter().any(|island| island.flow.is_break()) {
+                Some(Stop::Observer)
+            } else {
+                termination.check(stats.best, history.len(), stagnant, 0, started.elapsed())
+            };
+            if let Some(stop) = stop {
+                return self.finish(scored, best, history, stop, evaluations);
+            }
+            self.migrate(&mut scored);
+            self.breed(scored);
+        }
+    }
+
+    /// Keeps the last scored generation of every island, and reports how
+    /// evolution went.
+    ///
+    /// # Arguments
+    ///
+    /// - `scored` is the last scored generation of every island.
+    /// - `best` is the fittest genome seen on any island.
+    /// - `history` is the statistics of each generation.
+    /// - `stop` is why evolution stopped.
+    /// - `evaluations` is the number of genomes scored before evolving.
+    ///
+    /// # Returns
+    ///
+    /// The result of evolving.
+    fn finish(
+        &mut self,
+        scored: Vec<Scored<TGenome>>,
+        best: Option<CompareRecord<TGenome>>,
+        history: History,
+        stop: Stop,
+        evaluations: usize,
+    ) -> Evolution<TGenome> {
+        self.populations = scored
+            .into_iter()
+            .map(|island| unrank_generation(island.ranked))
+            .collect();
+        Evolution {
+            generation: self.populations.concat(),
+            best,
+            history,
+            stop,
+            evaluations: self.evaluations() - evaluations,
+        }
+    }
+
+    /// Gets the number of genomes scored on every island so far.
+    ///
+    /// # Returns
+    ///
+    /// The number of fitness checks.
+    fn evaluations(&self) -> usize {
+        self.runs.iter().map(EvoAlgorithm::evaluations).sum()
+    }
+
+    /// Scores the current generation of every island.
+    ///
+    /// # Returns
+    ///
+    /// The scored generation of every island.
+    fn score(&mut self) -> Vec<Scored<TGenome>> {
+        self.generation += 1;
+        let populations = std::mem::take(&mut self.populations);
+        self.map(populations, |run, population| {
+            let index = run.begin_generation();
+            let ranked = run.rank_generation(population);
+            let (stats, flow) = run.record(index, &ranked);
+            Scored {
+                index,
+                ranked,
+                stats,
+                flow,
+            }
+        })
+    }
+
+    /// Breeds the next generation of every island.
+    ///
+    /// # Arguments
+    ///
+    /// - `scored` is the scored generation of every island.
+    fn breed(&mut self, scored: Vec<Scored<TGenome>>) {
+        self.populations = self.map(scored, |run, island| {
+            run.breed_generation(island.index, island.ranked)
+        });
+    }
+
+    /// Replaces the least fit genomes of each island with the fittest of the
+    /// islands that send it migrants, if migration is due.
+    ///
+    /// # Arguments
+    ///
+    /// - `scored` is the scored generation of every island.
+    fn migrate(&self, scored: &mut [Scored<TGenome>]) {
+        if !self.generation.is_multiple_of(self.migration_interval) {
+            return;
+        }
+
+        let emigrants = scored
+            .iter()
+            .map(|island| {
+                fittest(&island.ranked, self.migrants)
+                    .into_iter()
+                    .map(|record| (record.fitness, record.predict.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for (index, island) in scored.iter_mut().enumerate() {
+            let ranked = &mut island.ranked;
+            let immigrants = self
+                .topology
+                .sources(index, emigrants.len())
+                .into_iter()
+                .flat_map(|source| &emigrants[source])
+                .take(ranked.len())
+                .map(|(fitness, predict)| CompareRecord {
+                    fitness: *fitness,
+                    predict: predict.clone(),
+                })
+                .collect::<Vec<_>>();
+
+            ranked.sort_by(|left, right| NanSafeCompare::fitness(left.fitness, right.fitness));
+            ranked.truncate(ranked.len() - immigrants.len());
+            ranked.extend(immigrants);
+        }
+    }
+
+    /// Applies a function to every island, on separate threads if the
+    /// archipelago is parallel.
+    ///
+    /// # Arguments
+    ///
+    /// - `items` is one item per island, in order.
+    /// - `f` is the function, given each island's algorithm and item.
+    ///
+    /// # Returns
+    ///
+    /// One result per island, in order.
+    fn map<T, R>(
+        &self,
+        items: Vec<T>,
+        f: impl Fn(&EvoAlgorithm<TGenome, TBreeder>, T) -> R + Send + Sync,
+    ) -> Vec<R>
+    where
+        T: Send,
+        R: Send,
+    {
+        if self.parallel {
+            self.runs
+                .par_iter()
+                .zip(items)
+                .map(|(run, item)| f(run, item))
+                .collect()
+        } else {
+            self.runs
+                .iter()
+                .zip(items)
+                .map(|(run, item)| f(run, item))
+                .collect()
+        }
+    }
+}
+
+/// Get the fittest genomes of a scored generation.
+///
+/// # Arguments
+///
+/// - `ranked` is the scored generation.
+/// - `count` is the number of genomes to get.
+///
+/// # Returns
+///
+/// The fittest genomes, fittest first.
+fn fittest<TGenome>(ranked: &[CompareRecord<TGenome>], count: usize) -> Vec<&CompareRecord<TGenome>>
+where
+    TGenome: Predict + PartialOrd,
+{
+    let mut fittest = ranked.iter().collect::<Vec<_>>();
+    fittest.sort_by(|left, right| NanSafeCompare::fitness(left.fitness, right.fitness));
+    fittest.truncate(count);
+    fittest
+}
+
+/// Builds an archipelago.
+pub struct Builder<TGenome, TBreeder>
+where
+    TGenome: Predict + PartialOrd + Send + Sync,
+    TBreeder: Breed<TGenome> + Sync,
+{
+    runs: Vec<EvoAlgorithm<TGenome, TBreeder>>,
+    populations: Vec<Generation<TGenome>>,
+    topology: Topology,
+    migration_interval: usize,
+    migrants: usize,
+    parallel: bool,
+}
+
+impl<TGenome, TBreeder> Default for Builder<TGenome, TBreeder>
+where
+    TGenome: Predict + PartialOrd + Send + Sync,
+    TBreeder: Breed<TGenome> + Sync,
+{
+    fn default() -> Self {
+        Self {
+            runs: vec![],
+            populations: vec![],
+            topology: Topology::Ring,
+            migration_interval: 10,
+            migrants: 1,
+            parallel: false,
+        }
+    }
+}
+
+impl<TGenome, TBreeder> Builder<TGenome, TBreeder>
+where
+    TGenome: Predict + PartialOrd + Send + Sync,
+    TBreeder: Breed<TGenome> + Sync,
+{
+    /// Builds the archipelago.
+    ///
+    /// # Returns
+    ///
+    /// The archipelago.
+    #[must_use]
+    pub fn build(self) -> Archipelago<TGenome, TBreeder> {
+        Archipelago {
+            runs: self.runs,
+            populations: self.populations,
+            topology: self.topology,
+            migration_interval: self.migration_interval,
+            migrants: self.migrants,
+            parallel: self.parallel,
+            generation: 0,
+        }
+    }
+
+    /// Adds an island.
+    ///
+    /// # Arguments
+    ///
+    /// - `run` is the algorithm that evolves the island.
+    /// - `population` is the island's initial population.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn island(
+        mut self,
+        run: EvoAlgorithm<TGenome, TBreeder>,
+        population: Generation<TGenome>,
+    ) -> Self {
+        self.runs.push(run);
+        self.populations.push(population);
+        self
+    }
+
+    /// Sets which islands migrants travel to. Defaults to [`Topology::Ring`].
+    ///
+    /// # Arguments
+    ///
+    /// - `topology` is the topology.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Sets how many generations pass between migrations. Defaults to 10;
+    /// 0 never migrates.
+    ///
+    /// # Arguments
+    ///
+    /// - `generations` is the number of generations.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn migration_interval(mut self, generations: usize) -> Self {
+        self.migration_interval = generations;
+        self
+    }
+
+    /// Sets how many of its fittest genomes each island sends to each
+    /// destination. They replace the least fit genomes there. Defaults to 1.
+    ///
+    /// # Arguments
+    ///
+    /// - `migrants` is the number of genomes.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn migrants(mut self, migrants: usize) -> Self {
+        self.migrants = migrants;
+        self
+    }
+
+    /// Sets whether the islands run on separate threads. Defaults to false.
+    ///
+    /// Seeded islands produce the same genomes either way.
+    ///
+    /// # Arguments
+    ///
+    /// - `parallel` is whether to run the islands in parallel.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rng, FitnessCalc, TrainingRecord};
+    use rand::Rng;
+
+    #[derive(Clone, Debug, PartialEq, PartialOrd)]
+    struct Genome {
+        value: f64,
+    }
+
+    impl Predict for Genome {
+        fn predict(&self, _input: &[f64]) -> Vec<f64> {
+            vec![self.value]
+        }
+    }
+
+    struct Breeder {
+        mutate: bool,
+    }
+
+    impl Breed<Genome> for Breeder {
+        fn crossover(&self, pair: (&Genome, &Genome)) -> Genome {
+            pair.0.clone()
+        }
+
+        fn mutate(&self, genome: Genome) -> Genome {
+            if self.mutate {
+                Genome {
+                    value: genome.value + rng::handle().gen_range(-0.5..0.5),
+                }
+            } else {
+                genome
+            }
+        }
+    }
+
+    fn run(mutate: bool, seed: u64) -> EvoAlgorithm<Genome, Breeder> {
+        EvoAlgorithm::builder()
+            .breeder(Breeder { mutate })
+            .fitness_calc(
+                FitnessCalc::builder()
+                    .add_training_record(TrainingRecord {
+                        input: vec![0.0],
+                        output: vec![0.0],
+                        weight: 1.0,
+                    })
+                    .build(),
+            )
+            .elitism(0)
+            .seed(seed)
+            .build()
+            .unwrap()
+    }
+
+    fn population(value: f64) -> Generation<Genome> {
+        vec![Genome { value }; 4]
+    }
+
+    #[test]
+    fn test_sources() {
+        assert_eq!(Topology::Ring.sources(0, 3), vec![2]);
+        assert_eq!(Topology::Ring.sources(2, 3), vec![1]);
+        assert_eq!(Topology::FullyConnected.sources(1, 3), vec![0, 2]);
+        assert!(Topology::Ring.sources(0, 1).is_empty());
+    }
+
+    #[test]
+    fn test_migration() {
+        let mut archipelago = Archipelago::builder()
+            .island(run(false, 1), population(0.0))
+            .island(run(false, 2), population(5.0))
+            .island(run(false, 3), population(9.0))
+            .migration_interval(2)
+            .build();
+
+        // Without migration, each island keeps to itself.
+        archipelago.run();
+        assert_eq!(archipelago.populations()[1], population(5.0));
+
+        // The fittest genome reaches the next island, and wins every
+        // tournament there.
+        archipelago.run();
+        let populations = archipelago.populations();
+        assert_eq!(populations[0], population(0.0));
+        assert_eq!(populations[1], population(0.0));
+        assert_eq!(populations[2], population(5.0));
+    }
+
+    #[test]
+    fn test_evolve_parallel_matches_sequential() {
+        let evolve = |parallel| {
+            let mut archipelago = Archipelago::builder()
+                .island(run(true, 1), population(4.0))
+                .island(run(true, 2), population(-4.0))
+                .topology(Topology::FullyConnected)
+                .migration_interval(3)
+                .parallel(parallel)
+                .build();
+            archipelago.evolve(Termination::builder().generations(10).build())
+        };
+
+        let sequential = evolve(false);
+        let parallel = evolve(true);
+
+        assert_eq!(sequential.stop, Stop::Generations);
+        assert_eq!(sequential.generation.len(), 8);
+        assert_eq!(sequential.history.len(), 11);
+        assert_eq!(sequential.generation, parallel.generation);
+    }
+}
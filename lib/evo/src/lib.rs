@@ -7,14 +7,42 @@
     clippy::pedantic
 )]
 
+mod adaptive;
 mod algo;
 mod breed;
+pub mod distributed;
+mod executor;
 mod fitness_calc;
 mod genome;
+pub mod island;
+#[cfg(feature = "npy")]
+mod npy;
+pub mod rng;
+mod schedule;
+mod speciation;
+pub mod stats;
+#[cfg(feature = "image")]
+mod vision;
 
 pub use self::{
-    algo::Algorithm as EvoAlgorithm,
-    breed::{Breed, Manager as BreedManager},
-    fitness_calc::{Compare, CompareRecord, FitnessCalc, Predict, TrainingRecord},
+    algo::{
+        Algorithm as EvoAlgorithm, CheckpointError, Evolution, InjectionPolicy, RunOutcome,
+        Selection, Stop, Termination, TerminationBuilder,
+    },
+    breed::{Breed, Manager as BreedManager, Pedigree, PedigreeBreeder},
+    executor::{Execute, Parallel, Sequential},
+    fitness_calc::{
+        Builder as FitnessCalcBuilder, Compare, CompareRecord, Curriculum, Error, Evaluate,
+        FitnessCalc, FitnessReport, InteractiveEvaluator, NanSafeCompare, Predict, RecordReport,
+        Result, TrainingDataHandle, TrainingRecord,
+    },
     genome::{Generation, Stock},
+    schedule::Schedule,
+    speciation::Distance,
 };
+
+#[cfg(feature = "npy")]
+pub use self::npy::{load_npy, load_npz, read_npy, Error as NpyError};
+
+#[cfg(feature = "image")]
+pub use self::vision::{Channels, Error as ImageError, LabeledImages, Loader as ImageLoader};
@@ -0,0 +1,332 @@
+use crate::TrainingRecord;
+use npyz::{npz::NpzArchive, NpyFile, Order};
+use std::io::{Read, Seek};
+use std::path::Path;
+
+/// An error that can occur when loading `NumPy` arrays.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("the archive has no array named `{0}`")]
+    MissingArray(String),
+
+    #[error("unsupported element type {0}; expected floats or integers")]
+    UnsupportedType(String),
+
+    #[error("expected a 1- or 2-dimensional array, found {0} dimensions")]
+    Dimensions(usize),
+
+    #[error("the inputs have {inputs} rows, but the outputs have {outputs}")]
+    RowMismatch { inputs: usize, outputs: usize },
+}
+
+/// A result that can occur when loading `NumPy` arrays.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Read the rows of a `.npy` array.
+///
+/// A 1-dimensional array is a single column. Floats and integers of any width
+/// are converted to `f64`.
+///
+/// # Arguments
+///
+/// - `path` - The path to the `.npy` file.
+///
+/// # Returns
+///
+/// The rows of the array.
+///
+/// # Errors
+///
+/// If the file cannot be read, or is not a 1- or 2-dimensional numeric array.
+pub fn read_npy(path: &Path) -> Result<Vec<Vec<f64>>> {
+    let bytes = std::fs::read(path)?;
+    read_rows(NpyFile::new(&bytes[..])?)
+}
+
+/// Load training records from a pair of `.npy` arrays.
+///
+/// # Arguments
+///
+/// - `inputs` - The path to the array of inputs, one row per record.
+/// - `outputs` - The path to the array of expected outputs, one row per record.
+///
+/// # Returns
+///
+/// One training record for each row.
+///
+/// # Errors
+///
+/// If either array cannot be read, or they have different numbers of rows.
+///
+/// # Examples
+///
+/// ```no_run
+/// use evo::{load_npy, FitnessCalc};
+/// use std::path::Path;
+///
+/// let records = load_npy(Path::new("x.npy"), Path::new("y.npy")).unwrap();
+/// let calc = FitnessCalc::builder().add_training_records(records).build();
+/// ```
+pub fn load_npy(inputs: &Path, outputs: &Path) -> Result<Vec<TrainingRecord>> {
+    records(read_npy(inputs)?, read_npy(outputs)?)
+}
+
+/// Load training records from two arrays of a `.npz` archive.
+///
+/// # Arguments
+///
+/// - `path` - The path to the `.npz` archive, as written by `numpy.savez`.
+/// - `inputs` - The name of the array of inputs, one row per record.
+/// - `outputs` - The name of the array of expected outputs, one row per record.
+///
+/// # Returns
+///
+/// One training record for each row.
+///
+/// # Errors
+///
+/// If the archive cannot be read, either array is missing or invalid, or they
+/// have different numbers of rows.
+pub fn load_npz(path: &Path, inputs: &str, outputs: &str) -> Result<Vec<TrainingRecord>> {
+    let file = std::fs::File::open(path)?;
+    read_npz(std::io::BufReader::new(file), inputs, outputs)
+}
+
+/// Load training records from two arrays of a `.npz` archive.
+///
+/// # Arguments
+///
+/// - `reader` - The archive.
+/// - `inputs` - The name of the array of inputs.
+/// - `outputs` - The name of the array of expected outputs.
+///
+/// # Returns
+///
+/// One training record for each row.
+///
+/// # Errors
+///
+/// If the archive cannot be read, either array is missing or invalid, or they
+/// have different numbers of rows.
+fn read_npz(reader: impl Read + Seek, inputs: &str, outputs: &str) -> Result<Vec<TrainingRecord>> {
+    let mut archive = NpzArchive::new(reader)?;
+    let mut array = |name: &str| match archive.by_name(name)? {
+        Some(file) => read_rows(file),
+        None => Err(Error::MissingArray(name.to_string())),
+    };
+
+    let inputs = array(inputs)?;
+    records(inputs, array(outputs)?)
+}
+
+/// Pair the rows of an input array with the rows of an output array.
+///
+/// # Arguments
+///
+/// - `inputs` - The inputs of each record.
+/// - `outputs` - The expected outputs of each record.
+///
+/// # Returns
+///
+/// One training record for each row.
+///
+/// # Errors
+///
+/// If the arrays have different numbers of rows.
+fn records(inputs: Vec<Vec<f64>>, outputs: Vec<Vec<f64>>) -> Result<Vec<TrainingRecord>> {
+    if inputs.len() != outputs.len() {
+        return Err(Error::RowMismatch {
+            inputs: inputs.len(),
+            outputs: outputs.len(),
+        });
+    }
+
+    Ok(Iterator::zip(inputs.into_iter(), outputs)
+        .map(|(input, output)| TrainingRecord::new(input, output))
+        .collect())
+}
+
+/// Read the rows of a 1- or 2-dimensional array.
+///
+/// # Arguments
+///
+/// - `file` - The array.
+///
+/// # Returns
+///
+/// The rows of the array.
+///
+/// # Errors
+///
+/// If the array cannot be read, has another number of dimensions, or holds
+/// something other than numbers.
+fn read_rows(file: NpyFile<impl Read>) -> Result<Vec<Vec<f64>>> {
+    #[allow(clippy::cast_possible_truncation)]
+    let shape = file
+        .shape()
+        .iter()
+        .map(|size| *size as usize)
+        .collect::<Vec<_>>();
+    let (rows, columns) = match shape[..] {
+        [rows] => (rows, 1),
+        [rows, columns] => (rows, columns),
+        _ => return Err(Error::Dimensions(shape.len())),
+    };
+    let order = file.order();
+    let values = read_values(file)?;
+
+    Ok((0..rows)
+        .map(|row| {
+            (0..columns)
+                .map(|column| match order {
+                    Order::C => values[row * columns + column],
+                    Order::Fortran => values[column * rows + row],
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Read every element of an array as a float, in storage order.
+///
+/// # Arguments
+///
+/// - `file` - The array.
+///
+/// # Returns
+///
+/// The elements of the array.
+///
+/// # Errors
+///
+/// If the array cannot be read or holds something other than numbers.
+fn read_values(file: NpyFile<impl Read>) -> Result<Vec<f64>> {
+    let file = match file.try_data::<f64>() {
+        Ok(data) => return collect(data, std::convert::identity),
+        Err(file) => file,
+    };
+    let file = match file.try_data::<f32>() {
+        Ok(data) => return collect(data, f64::from),
+        Err(file) => file,
+    };
+    let file = match file.try_data::<i32>() {
+        Ok(data) => return collect(data, f64::from),
+        Err(file) => file,
+    };
+    #[allow(clippy::cast_precision_loss)]
+    let file = match file.try_data::<i64>() {
+        Ok(data) => return collect(data, |value| value as f64),
+        Err(file) => file,
+    };
+    match file.try_data::<u8>() {
+        Ok(data) => collect(data, f64::from),
+        Err(file) => Err(Error::UnsupportedType(file.dtype().descr())),
+    }
+}
+
+/// Convert the elements of an array to floats.
+///
+/// # Arguments
+///
+/// - `data` - The elements.
+/// - `convert` - Converts an element to a float.
+///
+/// # Returns
+///
+/// The converted elements.
+///
+/// # Errors
+///
+/// If an element cannot be read.
+fn collect<T>(
+    data: impl Iterator<Item = std::io::Result<T>>,
+    convert: fn(T) -> f64,
+) -> Result<Vec<f64>> {
+    Ok(data
+        .map(|value| value.map(convert))
+        .collect::<std::io::Result<_>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use npyz::WriterBuilder;
+    use std::io::Cursor;
+
+    fn npy<T: npyz::AutoSerialize + Copy>(shape: &[u64], order: Order, values: &[T]) -> Vec<u8> {
+        let mut bytes = vec![];
+        let mut writer = npyz::WriteOptions::new()
+            .default_dtype()
+            .shape(shape)
+            .order(order)
+            .writer(&mut bytes)
+            .begin_nd()
+            .unwrap();
+        writer.extend(values.iter().copied()).unwrap();
+        writer.finish().unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_read_rows() {
+        let c = npy(&[2, 3], Order::C, &[1.0_f64, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let fortran = npy(&[2, 3], Order::Fortran, &[1_i32, 4, 2, 5, 3, 6]);
+        let column = npy(&[2], Order::C, &[1.0_f32, 2.0]);
+
+        let expected = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+        assert_eq!(read_rows(NpyFile::new(&c[..]).unwrap()).unwrap(), expected);
+        assert_eq!(
+            read_rows(NpyFile::new(&fortran[..]).unwrap()).unwrap(),
+            expected
+        );
+        assert_eq!(
+            read_rows(NpyFile::new(&column[..]).unwrap()).unwrap(),
+            vec![vec![1.0], vec![2.0]]
+        );
+    }
+
+    #[test]
+    fn test_read_npz() {
+        let mut archive = npyz::npz::NpzWriter::new(Cursor::new(vec![]));
+        for (name, shape, values) in [
+            ("x", [3_u64, 2], &[0.0, 0.0, 0.0, 1.0, 1.0, 0.0][..]),
+            ("y", [3, 1], &[0.0, 1.0, 1.0]),
+        ] {
+            let mut writer = archive
+                .array::<f64>(name, npyz::zip::write::FileOptions::default())
+                .unwrap()
+                .default_dtype()
+                .shape(&shape)
+                .begin_nd()
+                .unwrap();
+            writer.extend(values.iter().copied()).unwrap();
+            writer.finish().unwrap();
+        }
+        let mut bytes = archive.zip_writer().finish().unwrap();
+        bytes.set_position(0);
+
+        let records = read_npz(bytes.clone(), "x", "y").unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1].input, vec![0.0, 1.0]);
+        assert_eq!(records[1].output, vec![1.0]);
+        assert!(matches!(
+            read_npz(bytes.clone(), "x", "z"),
+            Err(Error::MissingArray(name)) if name == "z"
+        ));
+    }
+
+    #[test]
+    fn test_records_rejects_mismatched_rows() {
+        assert!(matches!(
+            records(vec![vec![1.0]], vec![]),
+            Err(Error::RowMismatch {
+                inputs: 1,
+                outputs: 0
+            })
+        ));
+    }
+}
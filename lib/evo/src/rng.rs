@@ -0,0 +1,151 @@
+//! The random number generator behind every random choice made while
+//! breeding, such as tournament selection, crossover, and mutation.
+//!
+//! Each thread has its own generator, seeded from entropy. Seed it, or run
+//! code with [`seeded`], to make those choices reproducible, e.g. to track
+//! down why a run's fitness regressed.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::cell::RefCell;
+
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+/// A handle to the current thread's generator.
+///
+/// Every draw borrows the generator only for that draw, so handles can be
+/// used while other handles are alive, and inside [`seeded`].
+///
+/// # Examples
+///
+/// ```
+/// use rand::Rng;
+///
+/// evo::rng::seed(7);
+/// let first: f64 = evo::rng::handle().gen();
+///
+/// evo::rng::seed(7);
+/// assert_eq!(evo::rng::handle().gen::<f64>(), first);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Handle;
+
+impl RngCore for Handle {
+    fn next_u32(&mut self) -> u32 {
+        RNG.with(|rng| rng.borrow_mut().next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        RNG.with(|rng| rng.borrow_mut().next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        RNG.with(|rng| rng.borrow_mut().fill_bytes(dest));
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        RNG.with(|rng| rng.borrow_mut().try_fill_bytes(dest))
+    }
+}
+
+/// Get a handle to the current thread's generator.
+///
+/// # Returns
+///
+/// The handle.
+#[must_use]
+pub fn handle() -> Handle {
+    Handle
+}
+
+/// Reseed the current thread's generator.
+///
+/// # Arguments
+///
+/// - `seed` is the seed.
+pub fn seed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+/// Run code with the current thread's generator seeded, then restore the
+/// generator it had before, even if the code panics.
+///
+/// # Arguments
+///
+/// - `seed` is the seed.
+/// - `f` is the code to run.
+///
+/// # Returns
+///
+/// What the code returns.
+///
+/// # Examples
+///
+/// ```
+/// use rand::Rng;
+///
+/// let draw = || evo::rng::handle().gen::<u64>();
+///
+/// assert_eq!(evo::rng::seeded(7, draw), evo::rng::seeded(7, draw));
+/// ```
+pub fn seeded<T>(seed: u64, f: impl FnOnce() -> T) -> T {
+    /// Puts the previous generator back when dropped.
+    struct Restore(Option<StdRng>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            if let Some(previous) = self.0.take() {
+                RNG.with(|rng| *rng.borrow_mut() = previous);
+            }
+        }
+    }
+
+    let previous = RNG.with(|rng| rng.replace(StdRng::seed_from_u64(seed)));
+    let _restore = Restore(Some(previous));
+    f()
+}
+
+/// Derive the seed of one step of a seeded process, e.g. one generation of a
+/// run, so that each step draws different numbers.
+///
+/// # Arguments
+///
+/// - `seed` is the seed of the whole process.
+/// - `step` is the index of the step.
+///
+/// # Returns
+///
+/// The seed of the step.
+pub(crate) fn derive(seed: u64, step: u64) -> u64 {
+    // SplitMix64, which spreads nearby inputs far apart.
+    let mut z = seed.wrapping_add(step.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_seeded_restores_generator() {
+        seed(1);
+        let expected = handle().gen::<u64>();
+
+        seed(1);
+        let inner = seeded(2, || handle().gen::<u64>());
+        assert_eq!(handle().gen::<u64>(), expected);
+        assert_eq!(inner, seeded(2, || handle().gen::<u64>()));
+    }
+
+    #[test]
+    fn test_derive() {
+        assert_eq!(derive(1, 2), derive(1, 2));
+        assert_ne!(derive(1, 2), derive(1, 3));
+        assert_ne!(derive(1, 2), derive(2, 2));
+    }
+}
@@ -0,0 +1,146 @@
+/// A value that changes as generations pass, such as the temperature of
+/// Boltzmann selection.
+///
+/// # Examples
+///
+/// ```
+/// use evo::Schedule;
+///
+/// let schedule = Schedule::Linear {
+///     start: 1.0,
+///     end: 0.0,
+///     generations: 4,
+/// };
+///
+/// assert_eq!(schedule.value(0), 1.0);
+/// assert_eq!(schedule.value(2), 0.5);
+/// assert_eq!(schedule.value(10), 0.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Schedule {
+    /// The same value in every generation.
+    Constant(f64),
+
+    /// Moves evenly from `start` to `end` over `generations`, then stays at
+    /// `end`.
+    Linear {
+        start: f64,
+        end: f64,
+        generations: usize,
+    },
+
+    /// Starts at `start` and is multiplied by `rate` every generation.
+    Exponential { start: f64, rate: f64 },
+
+    /// Starts at `start` and is multiplied by `factor` every `every`
+    /// generations.
+    Step {
+        start: f64,
+        factor: f64,
+        every: usize,
+    },
+}
+
+impl Schedule {
+    /// Get the value in a generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation` is the number of generations run before this one.
+    ///
+    /// # Returns
+    ///
+    /// The value.
+    #[must_use]
+    pub fn value(&self, generation: usize) -> f64 {
+        let exponent = |steps: usize| i32::try_from(steps).unwrap_or(i32::MAX);
+        match *self {
+            Self::Constant(value) => value,
+            Self::Linear {
+                start,
+                end,
+                generations,
+            } => {
+                if generation >= generations {
+                    end
+                } else {
+                    #[allow(clippy::cast_precision_loss)]
+                    let progress = generation as f64 / generations as f64;
+                    start + (end - start) * progress
+                }
+            }
+            Self::Exponential { start, rate } => start * rate.powi(exponent(generation)),
+            Self::Step {
+                start,
+                factor,
+                every,
+            } => start * factor.powi(exponent(generation / every.max(1))),
+        }
+    }
+
+    /// Get the value in a generation as a count, such as a number of genomes.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation` is the number of generations run before this one.
+    ///
+    /// # Returns
+    ///
+    /// The value, rounded to the nearest whole number; zero if it is negative
+    /// or `NaN`.
+    #[must_use]
+    pub fn count(&self, generation: usize) -> usize {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let count = self.value(generation).round().max(0.0) as usize;
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value() {
+        let close = |left: f64, right: f64| (left - right).abs() < 1e-12;
+
+        assert!(close(Schedule::Constant(2.0).value(100), 2.0));
+
+        let exponential = Schedule::Exponential {
+            start: 8.0,
+            rate: 0.5,
+        };
+        assert!(close(exponential.value(0), 8.0));
+        assert!(close(exponential.value(3), 1.0));
+
+        let step = Schedule::Step {
+            start: 1.0,
+            factor: 0.1,
+            every: 10,
+        };
+        assert!(close(step.value(9), 1.0));
+        assert!(close(step.value(10), 0.1));
+        assert!(close(step.value(25), 0.01));
+
+        let linear = Schedule::Linear {
+            start: 0.0,
+            end: 1.0,
+            generations: 0,
+        };
+        assert!(close(linear.value(0), 1.0));
+    }
+
+    #[test]
+    fn test_count() {
+        let linear = Schedule::Linear {
+            start: 10.0,
+            end: 0.0,
+            generations: 4,
+        };
+        assert_eq!(linear.count(0), 10);
+        assert_eq!(linear.count(1), 8);
+        assert_eq!(linear.count(4), 0);
+        assert_eq!(Schedule::Constant(-3.0).count(0), 0);
+        assert_eq!(Schedule::Constant(f64::NAN).count(0), 0);
+    }
+}
@@ -0,0 +1,279 @@
+//! Speciation, as in NEAT: genomes are clustered into species of similar
+//! genomes, and each species breeds its own share of the next generation.
+//!
+//! A new structure usually scores worse than the tuned genomes around it
+//! until its weights catch up. Competing only within its species, and
+//! sharing the species' fitness instead of its own, gives it that time.
+
+use crate::{CompareRecord, NanSafeCompare, Predict};
+use std::ops::Range;
+
+/// Measures how different two genomes are, to decide whether they belong to
+/// the same species.
+///
+/// # Examples
+///
+/// ```
+/// use evo::Distance;
+///
+/// struct Genome {
+///     value: f64,
+/// }
+///
+/// struct Difference;
+///
+/// impl Distance<Genome> for Difference {
+///     fn distance(&self, pair: (&Genome, &Genome)) -> f64 {
+///         (pair.0.value - pair.1.value).abs()
+///     }
+/// }
+///
+/// let distance = Difference.distance((&Genome { value: 1.0 }, &Genome { value: 3.0 }));
+/// assert!((distance - 2.0).abs() < f64::EPSILON);
+/// ```
+pub trait Distance<TGenome> {
+    /// Measure the distance between two genomes.
+    ///
+    /// # Arguments
+    ///
+    /// - `pair` is the pair of genomes to compare.
+    ///
+    /// # Returns
+    ///
+    /// The distance, which is zero for identical genomes and should be the
+    /// same either way round.
+    fn distance(&self, pair: (&TGenome, &TGenome)) -> f64;
+}
+
+/// How a generation is split into species.
+pub(crate) struct Speciation<TGenome> {
+    /// Measures the distance between genomes.
+    pub distance: Box<dyn Distance<TGenome> + Send + Sync>,
+
+    /// The greatest distance from a species' representative at which a
+    /// genome joins the species.
+    pub threshold: f64,
+}
+
+/// A species within a generation that has been sorted into species.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Species {
+    /// Where the species' members are in the generation.
+    pub members: Range<usize>,
+
+    /// The number of offspring the species breeds.
+    pub offspring: usize,
+}
+
+impl<TGenome> Speciation<TGenome>
+where
+    TGenome: Predict + PartialOrd,
+{
+    /// Sort a scored generation into species, and share out the offspring of
+    /// the next generation between them.
+    ///
+    /// Genomes are taken fittest first, and each joins the first species
+    /// whose representative, its fittest member, is within the threshold, or
+    /// else founds a new species.
+    ///
+    /// # Arguments
+    ///
+    /// - `ranked` is the scored generation.
+    /// - `offspring` is the number of offspring to share out.
+    ///
+    /// # Returns
+    ///
+    /// The generation, with each species' members next to each other, and the
+    /// species.
+    pub fn speciate(
+        &self,
+        mut ranked: Vec<CompareRecord<TGenome>>,
+        offspring: usize,
+    ) -> (Vec<CompareRecord<TGenome>>, Vec<Species>) {
+        ranked.sort_by(|left, right| NanSafeCompare::fitness(left.fitness, right.fitness));
+
+        let mut clusters: Vec<Vec<usize>> = vec![];
+        for (index, record) in ranked.iter().enumerate() {
+            let species = clusters.iter_mut().find(|members| {
+                let representative = &ranked[members[0]].predict;
+                self.distance.distance((representative, &record.predict)) <= self.threshold
+            });
+            match species {
+                Some(members) => members.push(index),
+                None => clusters.push(vec![index]),
+            }
+        }
+
+        let mut slots = ranked.into_iter().map(Some).collect::<Vec<_>>();
+        let mut sorted = Vec::with_capacity(slots.len());
+        let mut species = Vec::with_capacity(clusters.len());
+        for members in &clusters {
+            let start = sorted.len();
+            sorted.extend(members.iter().filter_map(|&index| slots[index].take()));
+            species.push(Species {
+                members: start..sorted.len(),
+                offspring: 0,
+            });
+        }
+
+        let shares = species
+            .iter()
+            .map(|species| shared_fitness(&sorted[species.members.clone()]))
+            .collect::<Vec<_>>();
+        for (species, offspring) in species.iter_mut().zip(allocate(&shares, offspring)) {
+            species.offspring = offspring;
+        }
+        (sorted, species)
+    }
+}
+
+/// Get how much of the next generation a species has earned: the mean of
+/// its members' goodness, where fitness `f`, as a non-negative error, has a
+/// goodness of `1 / (1 + f)`.
+///
+/// This is NEAT's fitness sharing, under which each member's fitness is
+/// divided among the species, turned around for errors.
+///
+/// # Arguments
+///
+/// - `members` are the species' members.
+///
+/// # Returns
+///
+/// The species' share, from 0 to 1.
+fn shared_fitness<TGenome>(members: &[CompareRecord<TGenome>]) -> f64
+where
+    TGenome: Predict + PartialOrd,
+{
+    if members.is_empty() {
+        return 0.0;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let count = members.len() as f64;
+    members
+        .iter()
+        .map(|record| {
+            let goodness = 1.0 / (1.0 + record.fitness.max(0.0));
+            if goodness.is_nan() {
+                0.0
+            } else {
+                goodness
+            }
+        })
+        .sum::<f64>()
+        / count
+}
+
+/// Share out a number of offspring in proportion to shares, so that they add
+/// up exactly.
+///
+/// # Arguments
+///
+/// - `shares` is each species' share.
+/// - `total` is the number of offspring.
+///
+/// # Returns
+///
+/// The number of offspring of each species.
+fn allocate(shares: &[f64], total: usize) -> Vec<usize> {
+    let sum = shares.iter().sum::<f64>();
+    if shares.is_empty() || sum <= 0.0 || !sum.is_finite() {
+        // With nothing to tell the species apart, the first breeds them all.
+        let mut offspring = vec![0; shares.len()];
+        if let Some(first) = offspring.first_mut() {
+            *first = total;
+        }
+        return offspring;
+    }
+
+    // Largest remainder: round down, then hand out what is left to the
+    // species that lost the most by rounding.
+    #[allow(clippy::cast_precision_loss)]
+    let quotas = shares
+        .iter()
+        .map(|share| share / sum * total as f64)
+        .collect::<Vec<_>>();
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let mut offspring = quotas
+        .iter()
+        .map(|quota| quota.floor() as usize)
+        .collect::<Vec<_>>();
+    let mut order = (0..quotas.len()).collect::<Vec<_>>();
+    order.sort_by(|&left, &right| {
+        let remainder = |index: usize| quotas[index] - quotas[index].floor();
+        remainder(right).total_cmp(&remainder(left))
+    });
+    let missing = total.saturating_sub(offspring.iter().sum());
+    for index in order.into_iter().cycle().take(missing) {
+        offspring[index] += 1;
+    }
+    offspring
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, PartialOrd)]
+    struct Genome(f64);
+
+    impl Predict for Genome {
+        fn predict(&self, _input: &[f64]) -> Vec<f64> {
+            vec![self.0]
+        }
+    }
+
+    struct Difference;
+
+    impl Distance<Genome> for Difference {
+        fn distance(&self, pair: (&Genome, &Genome)) -> f64 {
+            (pair.0 .0 - pair.1 .0).abs()
+        }
+    }
+
+    fn ranked(values: &[f64]) -> Vec<CompareRecord<Genome>> {
+        values
+            .iter()
+            .map(|&value| CompareRecord {
+                fitness: value.abs(),
+                predict: Genome(value),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_speciate() {
+        let speciation = Speciation {
+            distance: Box::new(Difference),
+            threshold: 1.0,
+        };
+
+        let (sorted, species) = speciation.speciate(ranked(&[10.0, 0.5, 9.5, 0.0, 20.0]), 10);
+
+        let values = sorted
+            .iter()
+            .map(|record| record.predict.0)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![0.0, 0.5, 9.5, 10.0, 20.0]);
+        let members = species
+            .iter()
+            .map(|species| species.members.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(members, vec![0..2, 2..4, 4..5]);
+        let offspring = species
+            .iter()
+            .map(|species| species.offspring)
+            .collect::<Vec<_>>();
+        assert_eq!(offspring.iter().sum::<usize>(), 10);
+        assert!(offspring[0] > offspring[1] && offspring[1] >= offspring[2]);
+    }
+
+    #[test]
+    fn test_allocate() {
+        assert_eq!(allocate(&[1.0, 1.0, 1.0], 10), vec![4, 3, 3]);
+        assert_eq!(allocate(&[3.0, 1.0], 4), vec![3, 1]);
+        assert_eq!(allocate(&[0.0, 0.0], 5), vec![5, 0]);
+        assert!(allocate(&[], 5).is_empty());
+    }
+}
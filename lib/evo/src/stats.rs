@@ -0,0 +1,296 @@
+//! Statistics describing how evolution progresses.
+
+use crate::{CompareRecord, Predict};
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Statistics of one generation, once it has been scored.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GenerationStats {
+    /// The number of generations run before this one.
+    pub generation: usize,
+
+    /// The best (lowest) fitness in the generation.
+    pub best: f64,
+
+    /// The median fitness of the generation.
+    pub median: f64,
+
+    /// The mean fitness of the generation.
+    pub mean: f64,
+
+    /// The standard deviation of the fitness of the generation.
+    pub std: f64,
+
+    /// The worst (highest) fitness in the generation.
+    pub worst: f64,
+
+    /// The fitness of the fittest genome on the validation records, if the
+    /// fitness calculator holds any. Rising while `best` falls means the
+    /// genomes are overfitting the training records.
+    pub validation: Option<f64>,
+
+    /// The diversity of the generation, if the algorithm was given a way to
+    /// measure it.
+    pub diversity: Option<f64>,
+
+    /// The number of genomes scored since the algorithm started, including
+    /// offspring scored while breeding.
+    pub evaluations: usize,
+
+    /// The time since the previous generation was scored, or since the first
+    /// generation started.
+    pub duration: Duration,
+
+    /// The time since the algorithm started its first generation.
+    pub elapsed: Duration,
+}
+
+impl GenerationStats {
+    /// Summarize the fitness of a scored generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation` is the number of generations run before this one.
+    /// - `ranked` is the scored generation.
+    ///
+    /// # Returns
+    ///
+    /// The statistics, with no validation, diversity, evaluations, or time
+    /// recorded, or `None` if no genome was scored.
+    pub(crate) fn new<TGenome>(generation: usize, ranked: &[CompareRecord<TGenome>]) -> Option<Self>
+    where
+        TGenome: Predict + PartialOrd,
+    {
+        Self::from_fitness(
+            generation,
+            ranked.iter().map(|record| record.fitness).collect(),
+        )
+    }
+
+    /// Summarize the fitness of a scored generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation` is the number of generations run before this one.
+    /// - `fitness` is the fitness of each scored genome, in any order.
+    ///
+    /// # Returns
+    ///
+    /// The statistics, with no validation, diversity, evaluations, or time
+    /// recorded, or `None` if no genome was scored.
+    pub(crate) fn from_fitness(generation: usize, mut fitness: Vec<f64>) -> Option<Self> {
+        fitness.sort_by(f64::total_cmp);
+        let (&best, &worst) = Option::zip(fitness.first(), fitness.last())?;
+
+        #[allow(clippy::cast_precision_loss)]
+        let count = fitness.len() as f64;
+        let middle = fitness.len() / 2;
+        let median = if fitness.len().is_multiple_of(2) {
+            f64::midpoint(fitness[middle - 1], fitness[middle])
+        } else {
+            fitness[middle]
+        };
+        let mean = fitness.iter().sum::<f64>() / count;
+        let variance = fitness
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / count;
+
+        Some(Self {
+            generation,
+            best,
+            median,
+            mean,
+            std: variance.sqrt(),
+            worst,
+            validation: None,
+            diversity: None,
+            evaluations: 0,
+            duration: Duration::ZERO,
+            elapsed: Duration::ZERO,
+        })
+    }
+}
+
+/// The statistics of every generation of a run, in order.
+///
+/// # Examples
+///
+/// ```
+/// use evo::stats::History;
+///
+/// let history = History::default();
+///
+/// assert!(history.is_empty());
+/// assert_eq!(
+///     history.to_csv(),
+///     "generation,best,median,mean,std,worst,validation,diversity,evaluations,duration,elapsed\n"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct History {
+    generations: Vec<GenerationStats>,
+}
+
+impl History {
+    /// Add the statistics of the next generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `stats` are the statistics.
+    pub fn push(&mut self, stats: GenerationStats) {
+        self.generations.push(stats);
+    }
+
+    /// Get the statistics of every generation.
+    ///
+    /// # Returns
+    ///
+    /// The statistics, in order.
+    #[must_use]
+    pub fn generations(&self) -> &[GenerationStats] {
+        &self.generations
+    }
+
+    /// Get the number of generations recorded.
+    ///
+    /// # Returns
+    ///
+    /// The number of generations.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.generations.len()
+    }
+
+    /// Check whether any generation was recorded.
+    ///
+    /// # Returns
+    ///
+    /// True if no generation was recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.generations.is_empty()
+    }
+
+    /// Get the best fitness of each generation, e.g. to plot a training
+    /// curve.
+    ///
+    /// # Returns
+    ///
+    /// The best fitness of each generation, in order.
+    pub fn best(&self) -> impl Iterator<Item = f64> + '_ {
+        self.generations.iter().map(|stats| stats.best)
+    }
+
+    /// Write the statistics as CSV, with a header and one row per generation.
+    ///
+    /// Times are in seconds, and a validation fitness or diversity that was
+    /// not measured is left empty.
+    ///
+    /// # Returns
+    ///
+    /// The CSV text.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "generation,best,median,mean,std,worst,validation,diversity,evaluations,duration,elapsed\n",
+        );
+        for stats in &self.generations {
+            let validation = stats.validation.map(|validation| validation.to_string());
+            let diversity = stats.diversity.map(|diversity| diversity.to_string());
+            // Writing to a `String` cannot fail.
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{},{},{}",
+                stats.generation,
+                stats.best,
+                stats.median,
+                stats.mean,
+                stats.std,
+                stats.worst,
+                validation.unwrap_or_default(),
+                diversity.unwrap_or_default(),
+                stats.evaluations,
+                stats.duration.as_secs_f64(),
+                stats.elapsed.as_secs_f64(),
+            );
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Genome;
+
+    impl Predict for Genome {
+        fn predict(&self, _input: &[f64]) -> Vec<f64> {
+            vec![]
+        }
+    }
+
+    impl PartialEq for Genome {
+        fn eq(&self, _other: &Self) -> bool {
+            true
+        }
+    }
+
+    impl PartialOrd for Genome {
+        fn partial_cmp(&self, _other: &Self) -> Option<std::cmp::Ordering> {
+            Some(std::cmp::Ordering::Equal)
+        }
+    }
+
+    fn ranked(fitness: &[f64]) -> Vec<CompareRecord<Genome>> {
+        fitness
+            .iter()
+            .map(|&fitness| CompareRecord {
+                fitness,
+                predict: Genome,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let stats = GenerationStats::new(4, &ranked(&[3.0, 1.0, 2.0, 6.0])).unwrap();
+
+        assert_eq!(stats.generation, 4);
+        assert!((stats.best - 1.0).abs() < f64::EPSILON);
+        assert!((stats.median - 2.5).abs() < f64::EPSILON);
+        assert!((stats.mean - 3.0).abs() < f64::EPSILON);
+        assert!((stats.std - 3.5f64.sqrt()).abs() < f64::EPSILON);
+        assert!((stats.worst - 6.0).abs() < f64::EPSILON);
+
+        let odd = GenerationStats::new(0, &ranked(&[3.0, 1.0, 2.0])).unwrap();
+        assert!((odd.median - 2.0).abs() < f64::EPSILON);
+        assert!(GenerationStats::new(0, &ranked(&[])).is_none());
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let mut history = History::default();
+        let mut stats = GenerationStats::new(0, &ranked(&[1.0, 3.0])).unwrap();
+        stats.evaluations = 2;
+        stats.duration = Duration::from_millis(500);
+        stats.elapsed = Duration::from_millis(500);
+        history.push(stats);
+        stats.generation = 1;
+        stats.diversity = Some(0.25);
+        stats.validation = Some(1.5);
+        history.push(stats);
+
+        let csv = history.to_csv();
+
+        let rows = csv.lines().skip(1).collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec!["0,1,2,2,1,3,,,2,0.5,0.5", "1,1,2,2,1,3,1.5,0.25,2,0.5,0.5"]
+        );
+        assert_eq!(history.best().collect::<Vec<_>>(), vec![1.0, 1.0]);
+    }
+}
@@ -0,0 +1,319 @@
+use crate::TrainingRecord;
+use image::{imageops::FilterType, DynamicImage, ImageFormat};
+use std::path::{Path, PathBuf};
+
+/// An error that can occur when loading images.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+
+    #[error("`{0}` has no label directories")]
+    NoLabels(PathBuf),
+
+    #[error("`{path}` has {found} inputs, but earlier images have {expected}; resize them")]
+    SizeMismatch {
+        path: PathBuf,
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// A result that can occur when loading images.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The color channels an image is flattened into.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Channels {
+    /// One input per pixel.
+    #[default]
+    Grayscale,
+
+    /// Three inputs per pixel: red, green, then blue.
+    Rgb,
+}
+
+/// Training records loaded from a directory of labeled images.
+pub struct LabeledImages {
+    /// The labels, in the order of the outputs.
+    pub labels: Vec<String>,
+
+    /// One record per image, with a one-hot output for its label.
+    pub records: Vec<TrainingRecord>,
+}
+
+/// Converts images into network inputs.
+///
+/// Each pixel becomes one input per channel, scaled to `0.0..=1.0`, row by row
+/// from the top left.
+///
+/// # Examples
+///
+/// ```
+/// use evo::{Channels, ImageLoader};
+///
+/// let loader = ImageLoader::builder()
+///     .channels(Channels::Rgb)
+///     .resize(28, 28)
+///     .build();
+/// ```
+pub struct Loader {
+    channels: Channels,
+    size: Option<(u32, u32)>,
+}
+
+impl Loader {
+    /// Create a new builder.
+    ///
+    /// # Returns
+    ///
+    /// A new builder.
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Convert an image into inputs.
+    ///
+    /// # Arguments
+    ///
+    /// - `image` is the image to convert.
+    ///
+    /// # Returns
+    ///
+    /// The inputs.
+    #[must_use]
+    pub fn input(&self, image: &DynamicImage) -> Vec<f64> {
+        let resized;
+        let image = match self.size {
+            Some((width, height)) if image.width() != width || image.height() != height => {
+                resized = image.resize_exact(width, height, FilterType::Triangle);
+                &resized
+            }
+            _ => image,
+        };
+
+        let bytes = match self.channels {
+            Channels::Grayscale => image.to_luma8().into_raw(),
+            Channels::Rgb => image.to_rgb8().into_raw(),
+        };
+        bytes
+            .into_iter()
+            .map(|byte| f64::from(byte) / f64::from(u8::MAX))
+            .collect()
+    }
+
+    /// Load an image file as inputs.
+    ///
+    /// # Arguments
+    ///
+    /// - `path` is the path to the image.
+    ///
+    /// # Returns
+    ///
+    /// The inputs.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read or decoded.
+    pub fn load(&self, path: &Path) -> Result<Vec<f64>> {
+        Ok(self.input(&image::open(path)?))
+    }
+
+    /// Load training records from a directory of labeled images.
+    ///
+    /// Each subdirectory is a label, and holds the images of that label, such
+    /// as `digits/0/a.png` and `digits/1/b.png`. Labels are sorted by name,
+    /// and each record's output is 1.0 for its label and 0.0 for the rest.
+    /// Files that are not images are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// - `path` is the directory of labels.
+    ///
+    /// # Returns
+    ///
+    /// The labels and the records.
+    ///
+    /// # Errors
+    ///
+    /// If a directory or image cannot be read, there are no labels, or the
+    /// images are different sizes and the loader does not resize them.
+    pub fn load_dir(&self, path: &Path) -> Result<LabeledImages> {
+        let labels = sorted_entries(path)?
+            .into_iter()
+            .filter(|entry| entry.is_dir())
+            .collect::<Vec<_>>();
+        if labels.is_empty() {
+            return Err(Error::NoLabels(path.to_path_buf()));
+        }
+
+        let mut records: Vec<TrainingRecord> = vec![];
+        for (index, label) in labels.iter().enumerate() {
+            for file in sorted_entries(label)? {
+                if !file.is_file() || ImageFormat::from_path(&file).is_err() {
+                    continue;
+                }
+
+                let input = self.load(&file)?;
+                if let Some(first) = records.first() {
+                    if first.input.len() != input.len() {
+                        return Err(Error::SizeMismatch {
+                            path: file,
+                            expected: first.input.len(),
+                            found: input.len(),
+                        });
+                    }
+                }
+
+                let mut output = vec![0.0; labels.len()];
+                output[index] = 1.0;
+                records.push(TrainingRecord::new(input, output));
+            }
+        }
+
+        Ok(LabeledImages {
+            labels: labels
+                .iter()
+                .filter_map(|label| label.file_name())
+                .map(|name| name.to_string_lossy().into_owned())
+                .collect(),
+            records,
+        })
+    }
+}
+
+/// List the entries of a directory, sorted by name.
+///
+/// # Arguments
+///
+/// - `path` is the directory.
+///
+/// # Returns
+///
+/// The paths of the entries.
+///
+/// # Errors
+///
+/// If the directory cannot be read.
+fn sorted_entries(path: &Path) -> Result<Vec<PathBuf>> {
+    let mut entries = std::fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+    Ok(entries)
+}
+
+/// A builder for an image loader.
+#[derive(Default)]
+pub struct Builder {
+    channels: Channels,
+    size: Option<(u32, u32)>,
+}
+
+impl Builder {
+    /// Set the channels each pixel is flattened into. Defaults to grayscale.
+    ///
+    /// # Arguments
+    ///
+    /// - `channels` are the channels.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn channels(mut self, channels: Channels) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Resize every image before converting it, so images of different sizes
+    /// produce the same number of inputs.
+    ///
+    /// # Arguments
+    ///
+    /// - `width` is the width in pixels.
+    /// - `height` is the height in pixels.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn resize(mut self, width: u32, height: u32) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Build the image loader.
+    ///
+    /// # Returns
+    ///
+    /// The image loader.
+    #[must_use]
+    pub fn build(self) -> Loader {
+        Loader {
+            channels: self.channels,
+            size: self.size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    fn image(width: u32, height: u32, pixel: [u8; 3]) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_pixel(width, height, Rgb(pixel)))
+    }
+
+    #[test]
+    fn test_input() {
+        let image = image(2, 1, [255, 0, 0]);
+
+        let rgb = Loader::builder().channels(Channels::Rgb).build();
+        assert_eq!(rgb.input(&image), vec![1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+        let gray = Loader::builder().resize(3, 2).build().input(&image);
+        assert_eq!(gray.len(), 6);
+        assert!(gray.iter().all(|value| (0.0..=1.0).contains(value)));
+    }
+
+    #[test]
+    fn test_load_dir() {
+        let root = std::env::temp_dir().join(format!("brain-farm-images-{}", std::process::id()));
+        for (label, name, size) in [
+            ("cat", "a.png", 2),
+            ("dog", "b.png", 2),
+            ("dog", "c.png", 3),
+        ] {
+            std::fs::create_dir_all(root.join(label)).unwrap();
+            image(size, size, [0, 0, 0])
+                .save(root.join(label).join(name))
+                .unwrap();
+        }
+        std::fs::write(root.join("dog").join("notes.txt"), "not an image").unwrap();
+
+        let unsized_result = Loader::builder().build().load_dir(&root);
+        let loaded = Loader::builder().resize(2, 2).build().load_dir(&root);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert!(matches!(
+            unsized_result,
+            Err(Error::SizeMismatch {
+                expected: 4,
+                found: 9,
+                ..
+            })
+        ));
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.labels, vec!["cat", "dog"]);
+        assert_eq!(loaded.records.len(), 3);
+        assert_eq!(loaded.records[0].output, vec![1.0, 0.0]);
+        assert_eq!(loaded.records[2].output, vec![0.0, 1.0]);
+        assert!(loaded.records.iter().all(|record| record.input.len() == 4));
+    }
+}
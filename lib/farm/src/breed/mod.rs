@@ -1,8 +1,10 @@
 use crate::{
-    genome::Crossover,
+    genome::{network, Crossover},
     mutate::{Mutator, Target},
+    organism::Organism,
 };
-pub use evo::Breed;
+pub use evo::{Breed, Distance};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
 /// Breeds activation functions.
 ///
@@ -17,14 +19,20 @@ pub use evo::Breed;
 ///
 /// let mutator = Mutator::builder().build();
 ///
-/// let left = Genome { activator: Gene::Linear };
-/// let right = Genome { activator: Gene::Sigmoid };
+/// let left = Genome { activator: Gene::Linear, parameter: None };
+/// let right = Genome { activator: Gene::Sigmoid, parameter: None };
 ///
 /// let breeder = Breeder::new(mutator);
 /// let offspring = breeder.crossover((&left, &right));
 /// ```
 pub struct Breeder {
     mutator: Mutator,
+
+    /// The mutation pressure, as the bits of an `f64`.
+    pressure: AtomicU64,
+
+    /// The generation being bred, which the mutator's schedules are read at.
+    generation: AtomicUsize,
 }
 
 impl Breeder {
@@ -39,7 +47,11 @@ impl Breeder {
     /// The new breeder.
     #[must_use]
     pub fn new(mutator: Mutator) -> Self {
-        Self { mutator }
+        Self {
+            mutator,
+            pressure: AtomicU64::new(1.0f64.to_bits()),
+            generation: AtomicUsize::new(0),
+        }
     }
 }
 
@@ -82,6 +94,17 @@ where
         pair.0.crossover(pair.1)
     }
 
+    /// Breed offspring from two parents into a spare genome, reusing its
+    /// allocations.
+    ///
+    /// # Arguments
+    ///
+    /// - `pair` - The parents to breed.
+    /// - `offspring` - The spare genome to overwrite.
+    fn crossover_into(&self, pair: (&TGenome, &TGenome), offspring: &mut TGenome) {
+        pair.0.crossover_into(pair.1, offspring);
+    }
+
     /// Mutate the genome.
     ///
     /// # Arguments
@@ -103,10 +126,161 @@ where
     ///
     /// let mutator = Mutator::builder().build();
     ///
-    /// let genome = Genome { activator: Gene::Linear };
+    /// let genome = Genome { activator: Gene::Linear, parameter: None };
     /// let genome = mutator.mutate(genome);
     /// ```
     fn mutate(&self, genome: TGenome) -> TGenome {
-        genome.mutate(&self.mutator)
+        let pressure = f64::from_bits(self.pressure.load(Ordering::Relaxed));
+        let generation = self.generation.load(Ordering::Relaxed);
+        genome.mutate(
+            &self
+                .mutator
+                .at_generation(generation)
+                .with_pressure(pressure),
+        )
+    }
+
+    /// Scale the mutation rate for the offspring bred from now on.
+    ///
+    /// # Arguments
+    ///
+    /// - `pressure` - The factor to scale the mutator's mutation rate by.
+    fn set_pressure(&self, pressure: f64) {
+        self.pressure.store(pressure.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Read the mutator's schedules at a generation for the offspring bred
+    /// from now on.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation` - The number of generations run before the parent
+    ///   generation.
+    fn set_generation(&self, generation: usize) {
+        self.generation.store(generation, Ordering::Relaxed);
+    }
+}
+
+/// Measures how different two network genomes are, for speciation, in the
+/// manner of NEAT's compatibility distance.
+///
+/// Weights and biases are matched by position. The distance adds the share
+/// of genes that have no match, or whose neuron's activation function
+/// differs, weighted by `structural`, to the mean difference of the matched
+/// genes, weighted by `weights`.
+///
+/// # Examples
+///
+/// ```
+/// use farm::{
+///     breed::{Compatibility, Distance},
+///     genome::{activator, layer, network, neuron},
+/// };
+///
+/// let neuron = |weights: Vec<f64>| neuron::Genome {
+///     activator: activator::Genome { activator: activator::Gene::Linear, parameter: None },
+///     weights,
+///     bias: 0.0,
+///     gates: None,
+/// };
+/// let network = |neurons| network::Genome {
+///     layers: vec![layer::Genome { neurons }],
+///     frozen: vec![],
+///     sources: vec![],
+/// };
+///
+/// let small = network(vec![neuron(vec![1.0])]);
+/// let large = network(vec![neuron(vec![1.0]), neuron(vec![1.0])]);
+///
+/// let compatibility = Compatibility::default();
+/// assert!(compatibility.distance((&small, &small)).abs() < f64::EPSILON);
+/// assert!((compatibility.distance((&small, &large)) - 0.5).abs() < f64::EPSILON);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Compatibility {
+    /// The weight of the share of unmatched genes.
+    pub structural: f64,
+
+    /// The weight of the mean difference of matched genes.
+    pub weights: f64,
+}
+
+impl Default for Compatibility {
+    fn default() -> Self {
+        Self {
+            structural: 1.0,
+            weights: 0.4,
+        }
+    }
+}
+
+impl Distance<network::Genome> for Compatibility {
+    /// Measure the distance between two network genomes.
+    ///
+    /// # Arguments
+    ///
+    /// - `pair` - The genomes to compare.
+    ///
+    /// # Returns
+    ///
+    /// The distance.
+    fn distance(&self, pair: (&network::Genome, &network::Genome)) -> f64 {
+        let genes = |genome: &network::Genome| -> usize {
+            genome
+                .layers
+                .iter()
+                .flat_map(|layer| &layer.neurons)
+                .map(|neuron| neuron.weights.len() + 1)
+                .sum()
+        };
+        let total = usize::max(genes(pair.0), genes(pair.1));
+        if total == 0 {
+            return 0.0;
+        }
+
+        let mut matched = 0;
+        let mut unmatched = 0;
+        let mut difference = 0.0;
+        for (left, right) in pair.0.layers.iter().zip(&pair.1.layers) {
+            for (left, right) in left.neurons.iter().zip(&right.neurons) {
+                let pairs = Iterator::zip(left.weights.iter(), &right.weights)
+                    .chain(std::iter::once((&left.bias, &right.bias)));
+                let count = left.weights.len().min(right.weights.len()) + 1;
+                if left.activator == right.activator {
+                    matched += count;
+                    difference += pairs.map(|(left, right)| (left - right).abs()).sum::<f64>();
+                } else {
+                    unmatched += count;
+                }
+            }
+        }
+        // Genes past the end of the other genome's layers, neurons, or
+        // weights have no match either.
+        unmatched += total.saturating_sub(matched + unmatched);
+
+        #[allow(clippy::cast_precision_loss)]
+        let structural = unmatched as f64 / total as f64;
+        #[allow(clippy::cast_precision_loss)]
+        let weights = if matched == 0 {
+            0.0
+        } else {
+            difference / matched as f64
+        };
+        self.structural * structural + self.weights * weights
+    }
+}
+
+impl Distance<Organism> for Compatibility {
+    /// Measure the distance between the genomes of two organisms.
+    ///
+    /// # Arguments
+    ///
+    /// - `pair` - The organisms to compare.
+    ///
+    /// # Returns
+    ///
+    /// The distance.
+    fn distance(&self, pair: (&Organism, &Organism)) -> f64 {
+        self.distance((pair.0.genome(), pair.1.genome()))
     }
 }
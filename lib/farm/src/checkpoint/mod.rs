@@ -0,0 +1,248 @@
+use crate::genome::Diff;
+use evo::Generation;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// A generation of genomes stored as deltas against a shared baseline genome.
+///
+/// Genomes in a generation share most of their ancestry, so each genome only
+/// records the genes that differ from the baseline. This keeps checkpoints a
+/// fraction of the size of a full JSON dump of the generation.
+///
+/// # Examples
+///
+/// ```
+/// use farm::checkpoint::DeltaGeneration;
+/// use farm::genome::{activator, neuron, layer, network};
+///
+/// let neuron = neuron::Genome {
+///     activator: activator::Genome { activator: activator::Gene::Linear, parameter: None },
+///     weights: vec![0.0, 1.0, 2.0],
+///     bias: 3.0,
+///     gates: None,
+/// };
+/// let baseline = network::Genome {
+///     layers: vec![layer::Genome { neurons: vec![neuron.clone()] }],
+///     frozen: vec![],
+///     sources: vec![],
+/// };
+/// let mut variant = baseline.clone();
+/// variant.layers[0].neurons[0].bias = 4.0;
+///
+/// let generation = vec![baseline.clone(), variant];
+/// let checkpoint = DeltaGeneration::encode(baseline, &generation);
+///
+/// assert_eq!(checkpoint.decode(), generation);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeltaGeneration<TGenome>
+where
+    TGenome: Diff,
+{
+    /// The genome every delta is computed against.
+    baseline: TGenome,
+
+    /// The delta for each genome, or `None` if it matches the baseline.
+    deltas: Vec<Option<TGenome::Delta>>,
+}
+
+impl<TGenome> DeltaGeneration<TGenome>
+where
+    TGenome: Diff,
+{
+    /// Encode a generation as deltas against a baseline genome.
+    ///
+    /// # Arguments
+    ///
+    /// - `baseline` - The genome to compute deltas against. The closer it is
+    ///   to the rest of the generation (e.g. the parent or elite genome), the
+    ///   smaller the encoding.
+    /// - `generation` - The generation to encode.
+    ///
+    /// # Returns
+    ///
+    /// The delta-encoded generation.
+    pub fn encode(baseline: TGenome, generation: &[TGenome]) -> Self {
+        let deltas = generation
+            .iter()
+            .map(|genome| genome.diff(&baseline))
+            .collect();
+
+        Self { baseline, deltas }
+    }
+
+    /// Decode the generation.
+    ///
+    /// # Returns
+    ///
+    /// The generation, in the order it was encoded.
+    pub fn decode(&self) -> Generation<TGenome>
+    where
+        TGenome: Clone,
+    {
+        self.deltas
+            .iter()
+            .map(|delta| match delta {
+                Some(delta) => TGenome::patch(&self.baseline, delta),
+                None => self.baseline.clone(),
+            })
+            .collect()
+    }
+
+    /// Get the baseline genome.
+    ///
+    /// # Returns
+    ///
+    /// The genome every delta is computed against.
+    pub fn baseline(&self) -> &TGenome {
+        &self.baseline
+    }
+
+    /// Get the number of genomes in the generation.
+    ///
+    /// # Returns
+    ///
+    /// The number of genomes.
+    pub fn len(&self) -> usize {
+        self.deltas.len()
+    }
+
+    /// Check whether the generation is empty.
+    ///
+    /// # Returns
+    ///
+    /// True if the generation has no genomes.
+    pub fn is_empty(&self) -> bool {
+        self.deltas.is_empty()
+    }
+
+    /// Serialize the checkpoint to a JSON string.
+    ///
+    /// # Returns
+    ///
+    /// The serialized checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// If the checkpoint cannot be serialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use farm::checkpoint::DeltaGeneration;
+    /// use farm::genome::network::Genome;
+    ///
+    /// let baseline = Genome { layers: vec![], frozen: vec![], sources: vec![] };
+    /// let checkpoint = DeltaGeneration::encode(baseline.clone(), &[baseline]);
+    ///
+    /// let json = checkpoint.to_json().unwrap();
+    /// let parsed = DeltaGeneration::<Genome>::parse_json(&json).unwrap();
+    ///
+    /// assert_eq!(checkpoint, parsed);
+    /// ```
+    pub fn to_json(&self) -> Result<String, serde_json::Error>
+    where
+        TGenome: Serialize,
+    {
+        serde_json::to_string(self)
+    }
+
+    /// Parse a checkpoint from a JSON string.
+    ///
+    /// # Arguments
+    ///
+    /// - `json` - The JSON string to parse.
+    ///
+    /// # Returns
+    ///
+    /// The parsed checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// If the checkpoint cannot be parsed.
+    pub fn parse_json(json: &str) -> Result<Self, serde_json::Error>
+    where
+        TGenome: DeserializeOwned,
+    {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{activator, layer, network, neuron};
+
+    fn genome(bias: f64) -> network::Genome {
+        let neuron = neuron::Genome {
+            activator: activator::Genome {
+                activator: activator::Gene::Sigmoid,
+                parameter: None,
+            },
+            weights: vec![0.1, 0.2, 0.3, 0.4],
+            bias,
+            gates: None,
+        };
+
+        network::Genome {
+            layers: vec![
+                layer::Genome {
+                    neurons: vec![neuron.clone(); 4],
+                },
+                layer::Genome {
+                    neurons: vec![neuron; 1],
+                },
+            ],
+            frozen: vec![],
+            sources: vec![],
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let baseline = genome(0.0);
+        let mut shrunk = genome(1.0);
+        shrunk.layers.pop();
+        let mut grown = genome(0.0);
+        grown.layers[0].neurons[2].weights.push(0.5);
+        grown.layers[1].neurons[0].activator.activator = activator::Gene::Linear;
+
+        let generation = vec![baseline.clone(), shrunk, grown];
+        let checkpoint = DeltaGeneration::encode(baseline, &generation);
+
+        assert_eq!(checkpoint.len(), 3);
+        assert_eq!(checkpoint.decode(), generation);
+
+        let parsed =
+            DeltaGeneration::<network::Genome>::parse_json(&checkpoint.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.decode(), generation);
+
+        // Binary formats read fields by position, so every field is written.
+        let bytes = bincode::serialize(&checkpoint).unwrap();
+        let parsed = bincode::deserialize::<DeltaGeneration<network::Genome>>(&bytes).unwrap();
+        assert_eq!(parsed.decode(), generation);
+    }
+
+    #[test]
+    fn test_smaller_than_full_generation() {
+        let baseline = genome(0.0);
+        let generation = (0..50)
+            .map(|i| {
+                let mut genome = baseline.clone();
+                genome.layers[0].neurons[i % 4].bias = 1.0;
+                genome
+            })
+            .collect::<Vec<_>>();
+
+        let full = serde_json::to_string(&generation).unwrap();
+        let delta = DeltaGeneration::encode(baseline, &generation)
+            .to_json()
+            .unwrap();
+
+        assert!(
+            delta.len() * 4 < full.len(),
+            "expected {} to be much smaller than {}",
+            delta.len(),
+            full.len()
+        );
+    }
+}
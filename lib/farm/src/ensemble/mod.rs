@@ -0,0 +1,490 @@
+use crate::genome::Create;
+use crate::organism::Organism;
+use evo::{Evaluate, Predict, TrainingRecord};
+use nnet::Network;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, PoisonError};
+
+/// A committee of networks that predicts the mean of its members' outputs.
+///
+/// # Examples
+///
+/// ```
+/// use evo::Predict;
+/// use farm::ensemble::Ensemble;
+/// use nnet::{ActivationFunction, BasicNeuron, Layer, Network};
+///
+/// let network = |weight| {
+///     let neuron = BasicNeuron::builder()
+///         .weights(vec![weight])
+///         .activation(ActivationFunction::linear())
+///         .build();
+///     Network::builder()
+///         .add_layer(Layer::builder().add_neuron(neuron).build())
+///         .build()
+/// };
+///
+/// let ensemble = Ensemble::new(vec![network(1.0), network(3.0)]);
+///
+/// assert_eq!(ensemble.predict(&[1.0]), vec![2.0]);
+/// ```
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Ensemble {
+    members: Vec<Network>,
+}
+
+impl Ensemble {
+    /// Create an ensemble.
+    ///
+    /// # Arguments
+    ///
+    /// - `members` - The networks of the committee.
+    ///
+    /// # Returns
+    ///
+    /// The ensemble.
+    #[must_use]
+    pub fn new(members: Vec<Network>) -> Self {
+        Self { members }
+    }
+
+    /// Get the networks of the committee.
+    ///
+    /// # Returns
+    ///
+    /// The members.
+    #[must_use]
+    pub fn members(&self) -> &[Network] {
+        &self.members
+    }
+}
+
+impl Predict for Ensemble {
+    fn predict(&self, input: &[f64]) -> Vec<f64> {
+        mean(self.members.iter().map(|member| member.activate(input)))
+    }
+}
+
+/// An evaluator that evolves a committee instead of a single champion.
+///
+/// It keeps up to `size` diverse organisms whose mean prediction has the
+/// lowest error on the training data. A candidate's fitness is the change in
+/// that error when the candidate joins the committee, so candidates that fix
+/// the committee's mistakes score best, even if they are poor on their own.
+/// While the committee is empty, a candidate's fitness is its own error.
+///
+/// After each batch, candidates that improve the committee join it, replacing
+/// a member once it is full. Candidates whose predictions are within
+/// `min_distance` of a member's are never added, which keeps it diverse.
+///
+/// Share it with the algorithm through an [`std::sync::Arc`] and call
+/// [`Evaluator::ensemble`] once evolution is done.
+///
+/// # Examples
+///
+/// ```
+/// use evo::{EvoAlgorithm, TrainingRecord};
+/// use farm::breed::Breeder;
+/// use farm::ensemble::Evaluator;
+/// use farm::mutate::Mutator;
+/// use std::sync::Arc;
+///
+/// let evaluator = Arc::new(
+///     Evaluator::builder()
+///         .training_records(vec![TrainingRecord::new(vec![1.0], vec![1.0])])
+///         .size(5)
+///         .build(),
+/// );
+/// let algorithm = EvoAlgorithm::builder()
+///     .breeder(Breeder::new(Mutator::builder().build()))
+///     .evaluator(Arc::clone(&evaluator))
+///     .build()
+///     .unwrap();
+///
+/// // ...run the algorithm...
+///
+/// let ensemble = evaluator.ensemble();
+/// ```
+pub struct Evaluator {
+    records: Vec<TrainingRecord>,
+    size: usize,
+    min_distance: f64,
+    committee: Mutex<Vec<Member>>,
+}
+
+/// A member of the committee and its predictions for each training record.
+struct Member {
+    organism: Organism,
+    outputs: Vec<Vec<f64>>,
+}
+
+impl Evaluator {
+    /// Create a new builder.
+    ///
+    /// # Returns
+    ///
+    /// A new builder.
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Get the current committee.
+    ///
+    /// # Returns
+    ///
+    /// The organisms of the committee.
+    pub fn members(&self) -> Vec<Organism> {
+        self.lock()
+            .iter()
+            .map(|member| member.organism.clone())
+            .collect()
+    }
+
+    /// Build an ensemble from the current committee.
+    ///
+    /// # Returns
+    ///
+    /// The ensemble.
+    pub fn ensemble(&self) -> Ensemble {
+        Ensemble::new(
+            self.members()
+                .into_iter()
+                .map(|organism| organism.genome().create())
+                .collect(),
+        )
+    }
+
+    /// Lock the committee.
+    ///
+    /// # Returns
+    ///
+    /// The committee.
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Member>> {
+        self.committee
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Get the error of a committee's mean prediction.
+    ///
+    /// # Arguments
+    ///
+    /// - `members` - The predictions of each member.
+    ///
+    /// # Returns
+    ///
+    /// The mean squared error over the training records, or 0.0 if there are
+    /// no members.
+    fn error(&self, members: &[&[Vec<f64>]]) -> f64 {
+        if members.is_empty() || self.records.is_empty() {
+            return 0.0;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let len = self.records.len() as f64;
+        self.records
+            .iter()
+            .enumerate()
+            .map(|(index, record)| {
+                let actual = mean(members.iter().map(|outputs| outputs[index].clone()));
+                let errors = record.get_mse(&actual).collect::<Vec<_>>();
+                #[allow(clippy::cast_precision_loss)]
+                let count = errors.len().max(1) as f64;
+                errors.iter().sum::<f64>() / count
+            })
+            .sum::<f64>()
+            / len
+    }
+
+    /// Add a candidate to the committee if it makes the committee better.
+    ///
+    /// # Arguments
+    ///
+    /// - `committee` - The committee.
+    /// - `candidate` - The candidate.
+    fn admit(&self, committee: &mut Vec<Member>, candidate: Member) {
+        if committee
+            .iter()
+            .any(|member| distance(&member.outputs, &candidate.outputs) <= self.min_distance)
+        {
+            return;
+        }
+
+        let mut outputs = committee
+            .iter()
+            .map(|member| &member.outputs[..])
+            .collect::<Vec<_>>();
+        let current = self.error(&outputs);
+        if committee.len() < self.size {
+            outputs.push(&candidate.outputs);
+            let joined = self.error(&outputs);
+            if committee.is_empty() || joined < current {
+                committee.push(candidate);
+            }
+            return;
+        }
+
+        let best_swap = (0..committee.len())
+            .map(|replaced| {
+                let mut swapped = outputs.clone();
+                swapped[replaced] = &candidate.outputs;
+                (replaced, self.error(&swapped))
+            })
+            .min_by(|left, right| left.1.total_cmp(&right.1));
+        if let Some((replaced, error)) = best_swap {
+            if error < current {
+                committee[replaced] = candidate;
+            }
+        }
+    }
+}
+
+impl Evaluate<Organism> for Evaluator {
+    fn evaluate(&self, generation: &[Organism]) -> Vec<evo::Result<f64>> {
+        let mut committee = self.lock();
+        let members = committee
+            .iter()
+            .map(|member| &member.outputs[..])
+            .collect::<Vec<_>>();
+        let current = self.error(&members);
+
+        let candidates = generation
+            .iter()
+            .map(|organism| {
+                let outputs = self
+                    .records
+                    .iter()
+                    .map(|record| organism.predict(&record.input))
+                    .collect::<Vec<_>>();
+                let mut joined = members.clone();
+                joined.push(&outputs);
+                let joined = self.error(&joined);
+                let fitness = if joined.is_nan() {
+                    Err(evo::Error::ResultNaN)
+                } else if joined.is_infinite() {
+                    Err(evo::Error::ResultInfinite)
+                } else {
+                    Ok(joined - current)
+                };
+                (organism, outputs, fitness)
+            })
+            .collect::<Vec<_>>();
+
+        let mut improving = candidates
+            .iter()
+            .filter_map(|(organism, outputs, fitness)| {
+                let fitness = *fitness.as_ref().ok()?;
+                (committee.is_empty() || fitness < 0.0).then_some((fitness, organism, outputs))
+            })
+            .collect::<Vec<_>>();
+        improving.sort_by(|left, right| left.0.total_cmp(&right.0));
+        for (_, organism, outputs) in improving {
+            let candidate = Member {
+                organism: (*organism).clone(),
+                outputs: outputs.clone(),
+            };
+            self.admit(&mut committee, candidate);
+        }
+
+        candidates
+            .into_iter()
+            .map(|(_, _, fitness)| fitness)
+            .collect()
+    }
+}
+
+/// A builder for an ensemble evaluator.
+pub struct Builder {
+    records: Vec<TrainingRecord>,
+    size: usize,
+    min_distance: f64,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            records: vec![],
+            size: 5,
+            min_distance: 0.0,
+        }
+    }
+}
+
+impl Builder {
+    /// Set the training records the committee is scored on.
+    ///
+    /// # Arguments
+    ///
+    /// - `records` - The training records.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn training_records(mut self, records: Vec<TrainingRecord>) -> Self {
+        self.records = records;
+        self
+    }
+
+    /// Set the largest number of members in the committee. Defaults to 5.
+    ///
+    /// # Arguments
+    ///
+    /// - `size` - The number of members.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set how far apart the predictions of two members must be, as the root
+    /// mean squared difference over the training records. Defaults to 0.0,
+    /// which only keeps out members that predict exactly the same.
+    ///
+    /// # Arguments
+    ///
+    /// - `min_distance` - The smallest distance between members.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn min_distance(mut self, min_distance: f64) -> Self {
+        self.min_distance = min_distance;
+        self
+    }
+
+    /// Build the evaluator, with an empty committee.
+    ///
+    /// # Returns
+    ///
+    /// The evaluator.
+    #[must_use]
+    pub fn build(self) -> Evaluator {
+        Evaluator {
+            records: self.records,
+            size: self.size.max(1),
+            min_distance: self.min_distance,
+            committee: Mutex::default(),
+        }
+    }
+}
+
+/// Average several predictions, output by output.
+///
+/// # Arguments
+///
+/// - `outputs` - The predictions.
+///
+/// # Returns
+///
+/// The mean prediction, as long as the longest prediction.
+fn mean(outputs: impl Iterator<Item = Vec<f64>>) -> Vec<f64> {
+    let mut sums: Vec<f64> = vec![];
+    let mut counts: Vec<usize> = vec![];
+    for output in outputs {
+        if output.len() > sums.len() {
+            sums.resize(output.len(), 0.0);
+            counts.resize(output.len(), 0);
+        }
+        for (index, value) in output.into_iter().enumerate() {
+            sums[index] += value;
+            counts[index] += 1;
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    Iterator::zip(sums.into_iter(), counts)
+        .map(|(sum, count)| sum / count as f64)
+        .collect()
+}
+
+/// Get the root mean squared difference between two sets of predictions.
+///
+/// # Arguments
+///
+/// - `left` - The first predictions.
+/// - `right` - The second predictions.
+///
+/// # Returns
+///
+/// The distance.
+fn distance(left: &[Vec<f64>], right: &[Vec<f64>]) -> f64 {
+    let (sum, count) = Iterator::zip(left.iter(), right.iter())
+        .flat_map(|(left, right)| Iterator::zip(left.iter(), right.iter()))
+        .fold((0.0, 0_usize), |(sum, count), (left, right)| {
+            (sum + (left - right).powi(2), count + 1)
+        });
+
+    #[allow(clippy::cast_precision_loss)]
+    if count == 0 {
+        0.0
+    } else {
+        (sum / count as f64).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{activator, layer, network, neuron};
+
+    fn organism(bias: f64) -> Organism {
+        Organism::from(network::Genome {
+            layers: vec![layer::Genome {
+                neurons: vec![neuron::Genome {
+                    activator: activator::Genome {
+                        activator: activator::Gene::Linear,
+                        parameter: None,
+                    },
+                    weights: vec![0.0],
+                    bias,
+                    gates: None,
+                }],
+            }],
+            frozen: vec![],
+            sources: vec![],
+        })
+    }
+
+    fn evaluator(size: usize) -> Evaluator {
+        Evaluator::builder()
+            .training_records(vec![TrainingRecord {
+                input: vec![0.0],
+                output: vec![1.0],
+                weight: 1.0,
+            }])
+            .size(size)
+            .build()
+    }
+
+    #[test]
+    fn test_scores_marginal_improvement() {
+        let evaluator = evaluator(2);
+
+        assert_eq!(evaluator.evaluate(&[organism(3.0)]), vec![Ok(4.0)]);
+        assert_eq!(
+            evaluator.evaluate(&[organism(-1.0), organism(3.0), organism(5.0)]),
+            vec![Ok(-4.0), Ok(0.0), Ok(5.0)]
+        );
+
+        let ensemble = evaluator.ensemble();
+        assert_eq!(ensemble.members().len(), 2);
+        assert_eq!(ensemble.predict(&[0.0]), vec![1.0]);
+    }
+
+    #[test]
+    fn test_replaces_members_when_full() {
+        let evaluator = evaluator(1);
+
+        let _ = evaluator.evaluate(&[organism(3.0)]);
+        let _ = evaluator.evaluate(&[organism(2.0), organism(0.5)]);
+
+        assert_eq!(evaluator.members(), vec![organism(0.5)]);
+    }
+}
@@ -0,0 +1,31 @@
+/// An error caused by misconfiguring the farm.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum Error {
+    #[error("cannot generate a value from the empty range {start}..{end}")]
+    EmptyRange { start: f64, end: f64 },
+
+    #[error("range bounds must be finite, found {start}..{end}")]
+    NonFiniteRange { start: f64, end: f64 },
+
+    #[error("cannot stock an empty generation")]
+    EmptyGeneration,
+
+    #[error("cannot mutate an empty vector")]
+    EmptyVec,
+
+    #[error("the mutation rate must be between 0 and 1, found {0}")]
+    MutationRate(f64),
+
+    #[error("the mutation size must be finite and non-negative, found {0}")]
+    MutationSize(f64),
+
+    #[error("preprocessing step {step} uses column {column}, but its rows have {width} columns")]
+    Column {
+        step: usize,
+        column: usize,
+        width: usize,
+    },
+}
+
+/// A result that can fail with a farm [`Error`].
+pub type Result<T> = std::result::Result<T, Error>;
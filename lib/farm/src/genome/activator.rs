@@ -1,8 +1,11 @@
 use super::Crossover;
-use crate::genome::{Create, Extract};
+use crate::genome::{Create, Diff, Extract};
 use crate::mutate::{Mutator, Target};
+use evo::rng;
+use nnet::activation::{LeakyRelu, Sigmoid};
 use nnet::ActivationFunction;
 use rand::distributions::{Distribution, Standard};
+use rand::Rng;
 
 /// The gene for an activation function.
 ///
@@ -13,9 +16,60 @@ use rand::distributions::{Distribution, Standard};
 ///
 /// let gene = Gene::Linear;
 /// ```
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
 pub struct Genome {
     pub activator: Gene,
+
+    /// The parameter of the activation function, such as the steepness of a
+    /// sigmoid or the negative slope of a leaky rectifier. `None` uses the
+    /// function's default, and functions without a parameter ignore it.
+    /// Left out of text formats when `None`.
+    #[serde(default)]
+    pub parameter: Option<f64>,
+}
+
+impl serde::Serialize for Genome {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let parameter = super::keep(&serializer, self.parameter.is_some());
+        let mut state = serializer.serialize_struct("Genome", 1 + usize::from(parameter))?;
+        state.serialize_field("activator", &self.activator)?;
+        if parameter {
+            state.serialize_field("parameter", &self.parameter)?;
+        } else {
+            state.skip_field("parameter")?;
+        }
+        state.end()
+    }
+}
+
+impl Genome {
+    /// Get the parameter the activation function uses.
+    ///
+    /// # Returns
+    ///
+    /// The parameter, or its default if the genome does not set one, or
+    /// `None` if the function has no parameter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use farm::genome::activator::{Gene, Genome};
+    ///
+    /// let genome = Genome { activator: Gene::Sigmoid, parameter: None };
+    ///
+    /// assert_eq!(genome.effective_parameter(), Some(1.0));
+    /// ```
+    #[must_use]
+    pub fn effective_parameter(&self) -> Option<f64> {
+        let default = match self.activator {
+            Gene::Linear | Gene::Tanh => return None,
+            Gene::Sigmoid => Sigmoid::DEFAULT_STEEPNESS,
+            Gene::LeakyRelu => LeakyRelu::DEFAULT_ALPHA,
+        };
+        Some(self.parameter.unwrap_or(default))
+    }
 }
 
 impl super::Generate<()> for Genome {
@@ -53,8 +107,8 @@ impl super::Generate<&()> for Genome {
     ///
     /// let genome = Genome::generate(&());
     /// ```
-    fn generate(_: &()) -> Self {
-        Self::generate(rand::random::<Gene>)
+    fn generate((): &()) -> Self {
+        Self::generate(|| rng::handle().gen::<Gene>())
     }
 }
 
@@ -80,6 +134,7 @@ where
     fn generate(activator_generator: F) -> Self {
         Self {
             activator: activator_generator(),
+            parameter: None,
         }
     }
 }
@@ -96,12 +151,23 @@ where
 ///
 /// let mutator = Mutator::builder().build();
 ///
-/// let genome = Genome { activator: Gene::Linear };
+/// let genome = Genome { activator: Gene::Linear, parameter: None };
 /// let genome = genome.mutate(&mutator);
 /// ```
 impl Target for Genome {
+    /// Mutate the activation function. If it is kept, its parameter is
+    /// mutated like a weight; if it changes, the parameter is reset.
     fn mutate(mut self, mutator: &Mutator) -> Self {
-        self.activator = self.activator.mutate(mutator);
+        let activator = self.activator.clone().mutate(mutator);
+        if activator != self.activator {
+            self.activator = activator;
+            self.parameter = None;
+        } else if let Some(parameter) = self.effective_parameter() {
+            let mutated = parameter.mutate(mutator);
+            if mutated.to_bits() != parameter.to_bits() {
+                self.parameter = Some(mutated);
+            }
+        }
         self
     }
 }
@@ -113,19 +179,64 @@ impl Target for Genome {
 /// ```
 /// use farm::genome::{Crossover, activator::{Genome, Gene}};
 ///
-/// let left = Genome { activator: Gene::Linear };
-/// let right = Genome { activator: Gene::Sigmoid };
+/// let left = Genome { activator: Gene::Linear, parameter: None };
+/// let right = Genome { activator: Gene::Sigmoid, parameter: Some(2.0) };
 ///
 /// let target = left.crossover(&right);
 /// ```
 impl Crossover for Genome {
+    /// Cross over the activation functions. When both parents share one, the
+    /// parameters are crossed over like weights; otherwise the offspring
+    /// keeps the parameter of the parent whose function it inherits.
     fn crossover(&self, other: &Self) -> Self {
+        let activator = self.activator.crossover(&other.activator);
+        let parameter = if self.activator == other.activator {
+            match (self.parameter, other.parameter) {
+                (None, None) => None,
+                _ => Option::zip(self.effective_parameter(), other.effective_parameter())
+                    .map(|(left, right)| left.crossover(&right)),
+            }
+        } else if activator == self.activator {
+            self.parameter
+        } else {
+            other.parameter
+        };
         Self {
-            activator: self.activator.crossover(&other.activator),
+            activator,
+            parameter,
         }
     }
 }
 
+/// Enable delta encoding for [`Genome`].
+///
+/// # Examples
+///
+/// ```
+/// use farm::genome::{activator::{Genome, Gene}, Diff};
+///
+/// let base = Genome { activator: Gene::Linear, parameter: None };
+/// let target = Genome { activator: Gene::Sigmoid, parameter: Some(2.0) };
+///
+/// let delta = target.diff(&base).unwrap();
+/// assert_eq!(Genome::patch(&base, &delta), target);
+/// ```
+impl Diff for Genome {
+    type Delta = Genome;
+
+    fn diff(&self, base: &Self) -> Option<Self::Delta> {
+        if self == base {
+            None
+        } else {
+            Some(self.clone())
+        }
+    }
+
+    fn patch(_base: &Self, delta: &Self::Delta) -> Self {
+        delta.clone()
+    }
+}
+
 /// Enable creation for [`Genome`].
 ///
 /// # Examples
@@ -134,16 +245,22 @@ impl Crossover for Genome {
 /// use farm::genome::{activator::{Genome, Gene}, Create};
 /// use nnet::ActivationFunction;
 ///
-/// let genome = Genome { activator: Gene::Linear };
+/// let genome = Genome { activator: Gene::Linear, parameter: None };
 /// let activator = genome.create();
 ///
 /// assert_eq!(activator, ActivationFunction::linear());
 /// ```
 impl Create<ActivationFunction> for Genome {
     fn create(&self) -> ActivationFunction {
-        match self.activator {
-            Gene::Linear => ActivationFunction::linear(),
-            Gene::Sigmoid => ActivationFunction::sigmoid(),
+        match (&self.activator, self.effective_parameter()) {
+            (Gene::Sigmoid, Some(steepness)) => {
+                ActivationFunction::Sigmoid(Sigmoid::new(steepness))
+            }
+            (Gene::LeakyRelu, Some(alpha)) => ActivationFunction::LeakyRelu(LeakyRelu::new(alpha)),
+            (Gene::Sigmoid, None) => ActivationFunction::sigmoid(),
+            (Gene::LeakyRelu, None) => ActivationFunction::leaky_relu(),
+            (Gene::Linear, _) => ActivationFunction::linear(),
+            (Gene::Tanh, _) => ActivationFunction::tanh(),
         }
     }
 }
@@ -158,16 +275,25 @@ impl Create<ActivationFunction> for Genome {
 ///
 /// let genome = ActivationFunction::linear().genome();
 ///
-/// assert_eq!(genome, Genome { activator: Gene::Linear });
+/// assert_eq!(genome, Genome { activator: Gene::Linear, parameter: None });
 /// ```
 impl Extract<Genome> for ActivationFunction {
     fn genome(&self) -> Genome {
-        let activator = match self {
-            Self::Linear(_) => Gene::Linear,
-            Self::Sigmoid(_) => Gene::Sigmoid,
+        let (activator, parameter) = match self {
+            Self::Linear(_) => (Gene::Linear, None),
+            Self::Sigmoid(sig) => (Gene::Sigmoid, Some(sig.steepness)),
+            Self::LeakyRelu(relu) => (Gene::LeakyRelu, Some(relu.alpha)),
+            Self::Tanh(_) => (Gene::Tanh, None),
         };
 
-        Genome { activator }
+        let mut genome = Genome {
+            activator,
+            parameter: None,
+        };
+        if genome.effective_parameter().map(f64::to_bits) != parameter.map(f64::to_bits) {
+            genome.parameter = parameter;
+        }
+        genome
     }
 }
 
@@ -179,13 +305,21 @@ pub enum Gene {
 
     /// Sigmoid activation function.
     Sigmoid,
+
+    /// Leaky rectified linear activation function.
+    LeakyRelu,
+
+    /// Hyperbolic tangent activation function.
+    Tanh,
 }
 
 impl Distribution<Gene> for Standard {
     fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Gene {
-        match rng.gen_range(0..2) {
+        match rng.gen_range(0..4) {
             0 => Gene::Linear,
-            _ => Gene::Sigmoid,
+            1 => Gene::Sigmoid,
+            2 => Gene::LeakyRelu,
+            _ => Gene::Tanh,
         }
     }
 }
@@ -207,8 +341,10 @@ impl Crossover for Gene {
         match (self, other) {
             (Self::Linear, Self::Linear) => Self::Linear,
             (Self::Sigmoid, Self::Sigmoid) => Self::Sigmoid,
+            (Self::LeakyRelu, Self::LeakyRelu) => Self::LeakyRelu,
+            (Self::Tanh, Self::Tanh) => Self::Tanh,
             _ => {
-                if rand::random() {
+                if rng::handle().gen() {
                     self.clone()
                 } else {
                     other.clone()
@@ -236,7 +372,7 @@ impl Crossover for Gene {
 impl Target for Gene {
     fn mutate(mut self, mutator: &crate::mutate::Mutator) -> Self {
         if mutator.mutation_size() > 0.0 && mutator.check_mutate() {
-            self = rand::random::<Gene>();
+            self = rng::handle().gen::<Gene>();
         }
 
         self
@@ -251,6 +387,7 @@ mod tests {
     fn test_serialize() {
         let genome = Genome {
             activator: Gene::Linear,
+            parameter: None,
         };
 
         let serialized = serde_json::to_string(&genome).unwrap();
@@ -261,9 +398,61 @@ mod tests {
     fn test_deserialize() {
         let genome = Genome {
             activator: Gene::Linear,
+            parameter: None,
         };
 
         let deserialized: Genome = serde_json::from_str(r#"{"activator":"Linear"}"#).unwrap();
         assert_eq!(deserialized, genome);
     }
+
+    #[test]
+    fn test_serialize_binary() {
+        for parameter in [None, Some(3.0)] {
+            let genome = Genome {
+                activator: Gene::Sigmoid,
+                parameter,
+            };
+
+            let serialized = bincode::serialize(&genome).unwrap();
+            let deserialized: Genome = bincode::deserialize(&serialized).unwrap();
+            assert_eq!(deserialized, genome);
+        }
+    }
+
+    #[test]
+    fn test_parameters() {
+        let steep = ActivationFunction::Sigmoid(Sigmoid::new(3.0));
+        let genome = steep.genome();
+        assert_eq!(genome.parameter, Some(3.0));
+        assert_eq!(genome.create(), steep);
+        assert_eq!(
+            ActivationFunction::leaky_relu().genome(),
+            Genome {
+                activator: Gene::LeakyRelu,
+                parameter: None,
+            }
+        );
+
+        let mutator = Mutator::builder()
+            .mutation_rate(1.0)
+            .mutation_size(1.0)
+            .build();
+        assert!((0..100).any(|_| {
+            let mutated = genome.clone().mutate(&mutator);
+            mutated.activator == genome.activator && mutated.parameter != genome.parameter
+        }));
+
+        let left = Genome {
+            activator: Gene::Sigmoid,
+            parameter: Some(2.0),
+        };
+        let right = Genome {
+            activator: Gene::Sigmoid,
+            parameter: None,
+        };
+        let offspring = left.crossover(&right);
+        assert!(offspring
+            .parameter
+            .is_some_and(|parameter| (1.0..=2.0).contains(&parameter)));
+    }
 }
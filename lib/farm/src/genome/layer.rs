@@ -1,5 +1,5 @@
 use super::neuron;
-use crate::genome::{Create, Extract, Generate};
+use crate::genome::{Create, Diff, Extract, Generate, VecDelta};
 use crate::{
     genome::Crossover,
     mutate::{Mutator, Target},
@@ -16,10 +16,12 @@ use nnet::Layer;
 /// let neurons = vec![
 ///     neuron::Genome {
 ///         activator: activator::Genome {
-///             activator: activator::Gene::Linear
+///             activator: activator::Gene::Linear,
+///             parameter: None,
 ///         },
 ///         weights: vec![0.0, 1.0, 2.0],
 ///         bias: 3.0,
+///         gates: None,
 ///    },
 /// ];
 /// let genome = layer::Genome { neurons: neurons.clone() };
@@ -35,7 +37,6 @@ pub struct Genome {
 /// # Examples
 ///
 /// ```
-/// use rand::{thread_rng, Rng};
 /// use farm::genome::{neuron, activator, layer};
 /// use farm::genome::Generate;
 ///
@@ -93,6 +94,11 @@ impl Crossover for Genome {
             neurons: Vec::crossover(&self.neurons, &other.neurons),
         }
     }
+
+    fn crossover_into(&self, other: &Self, offspring: &mut Self) {
+        self.neurons
+            .crossover_into(&other.neurons, &mut offspring.neurons);
+    }
 }
 
 /// Enable mutation for [`Genome`].
@@ -107,9 +113,10 @@ impl Crossover for Genome {
 ///
 /// let neurons = vec![
 ///     neuron::Genome {
-///         activator: activator::Genome { activator: activator::Gene::Linear },
+///         activator: activator::Genome { activator: activator::Gene::Linear, parameter: None },
 ///         weights: vec![0.0, 1.0, 2.0],
 ///         bias: 3.0,
+///         gates: None,
 ///     },
 /// ];
 /// let genome = Genome { neurons: neurons.clone() };
@@ -123,6 +130,39 @@ impl Target for Genome {
     }
 }
 
+/// Enable delta encoding for [`Genome`].
+///
+/// # Examples
+///
+/// ```
+/// use farm::genome::{layer::Genome, neuron, activator, Diff};
+///
+/// let neuron = neuron::Genome {
+///     activator: activator::Genome { activator: activator::Gene::Linear, parameter: None },
+///     weights: vec![0.0, 1.0, 2.0],
+///     bias: 3.0,
+///     gates: None,
+/// };
+/// let base = Genome { neurons: vec![neuron.clone()] };
+/// let target = Genome { neurons: vec![neuron.clone(), neuron] };
+///
+/// let delta = target.diff(&base).unwrap();
+/// assert_eq!(Genome::patch(&base, &delta), target);
+/// ```
+impl Diff for Genome {
+    type Delta = VecDelta<neuron::Genome>;
+
+    fn diff(&self, base: &Self) -> Option<Self::Delta> {
+        self.neurons.diff(&base.neurons)
+    }
+
+    fn patch(base: &Self, delta: &Self::Delta) -> Self {
+        Self {
+            neurons: Vec::patch(&base.neurons, delta),
+        }
+    }
+}
+
 impl Create<Layer> for Genome {
     /// Create a new layer from the genome.
     ///
@@ -134,9 +174,10 @@ impl Create<Layer> for Genome {
     ///
     /// let neurons = vec![
     ///     neuron::Genome {
-    ///         activator: activator::Genome { activator: activator::Gene::Linear },
+    ///         activator: activator::Genome { activator: activator::Gene::Linear, parameter: None },
     ///         weights: vec![0.0, 1.0, 2.0],
     ///         bias: 3.0,
+    ///         gates: None,
     ///     },
     /// ];
     /// let genome = Genome { neurons };
@@ -161,9 +202,10 @@ impl Extract<Genome> for Layer {
     ///
     /// let neurons = vec![
     ///     neuron::Genome {
-    ///         activator: activator::Genome { activator: activator::Gene::Linear },
+    ///         activator: activator::Genome { activator: activator::Gene::Linear, parameter: None },
     ///         weights: vec![0.0, 1.0, 2.0],
     ///         bias: 3.0,
+    ///         gates: None,
     ///     },
     /// ];
     /// let layer = Layer::builder()
@@ -193,9 +235,11 @@ mod tests {
             neurons: vec![neuron::Genome {
                 activator: activator::Genome {
                     activator: activator::Gene::Linear,
+                    parameter: None,
                 },
                 weights: vec![0.0, 1.0, 2.0],
                 bias: 3.0,
+                gates: None,
             }],
         };
 
@@ -210,9 +254,11 @@ mod tests {
             neurons: vec![neuron::Genome {
                 activator: activator::Genome {
                     activator: activator::Gene::Linear,
+                    parameter: None,
                 },
                 weights: vec![0.0, 1.0, 2.0],
                 bias: 3.0,
+                gates: None,
             }],
         };
 
@@ -3,14 +3,19 @@ pub mod layer;
 pub mod network;
 pub mod neuron;
 
-use rand::{random, thread_rng, Rng};
+use crate::{Error, Result};
+use evo::rng;
+use rand::Rng;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 /// Enable automatic generation of a gene or genome.
 ///
+/// Draw random values from [`evo::rng`], so that seeded runs are reproducible.
+///
 /// # Examples
 ///
 /// ```
-/// use rand::{Rng, thread_rng};
+/// use rand::Rng;
 /// use farm::genome::Generate;
 ///
 /// struct Genome {
@@ -19,7 +24,7 @@ use rand::{random, thread_rng, Rng};
 ///
 /// impl Generate<std::ops::Range<f64>> for Genome {
 ///     fn generate(config: std::ops::Range<f64>) -> Self {
-///         let value = thread_rng().gen_range(config.start..config.end);
+///         let value = evo::rng::handle().gen_range(config.start..config.end);
 ///         Self { value }
 ///     }
 /// }
@@ -39,15 +44,93 @@ pub trait Generate<TConfig> {
     fn generate(config: TConfig) -> Self;
 }
 
+/// Enable generation from a configuration that may be invalid.
+///
+/// # Examples
+///
+/// ```
+/// use farm::{genome::TryGenerate, Error};
+///
+/// assert!(f64::try_generate(0.0..1.0).is_ok());
+/// assert_eq!(
+///     f64::try_generate(1.0..1.0),
+///     Err(Error::EmptyRange { start: 1.0, end: 1.0 })
+/// );
+/// ```
+pub trait TryGenerate<TConfig>: Sized {
+    /// Generate a gene or genome.
+    ///
+    /// # Arguments
+    ///
+    /// - `config` - The configuration to generate from.
+    ///
+    /// # Returns
+    ///
+    /// The generated value.
+    ///
+    /// # Errors
+    ///
+    /// If the configuration cannot generate a value.
+    fn try_generate(config: TConfig) -> Result<Self>;
+}
+
+/// Check that a range of floats has finite bounds.
+///
+/// # Arguments
+///
+/// - `start` - The lower bound.
+/// - `end` - The upper bound.
+///
+/// # Errors
+///
+/// If either bound is `NaN` or infinite.
+fn check_finite(start: f64, end: f64) -> Result<()> {
+    if start.is_finite() && end.is_finite() {
+        Ok(())
+    } else {
+        Err(Error::NonFiniteRange { start, end })
+    }
+}
+
+/// # Panics
+///
+/// If the range is empty or unbounded; use [`TryGenerate`] to check it.
 impl Generate<std::ops::Range<f64>> for f64 {
     fn generate(config: std::ops::Range<f64>) -> Self {
-        thread_rng().gen_range(config.start..config.end)
+        rng::handle().gen_range(config.start..config.end)
     }
 }
 
+/// # Panics
+///
+/// If the range is empty or unbounded; use [`TryGenerate`] to check it.
 impl Generate<std::ops::RangeInclusive<f64>> for f64 {
     fn generate(config: std::ops::RangeInclusive<f64>) -> Self {
-        thread_rng().gen_range(*config.start()..=*config.end())
+        rng::handle().gen_range(*config.start()..=*config.end())
+    }
+}
+
+impl TryGenerate<std::ops::Range<f64>> for f64 {
+    fn try_generate(config: std::ops::Range<f64>) -> Result<Self> {
+        check_finite(config.start, config.end)?;
+        if config.is_empty() {
+            return Err(Error::EmptyRange {
+                start: config.start,
+                end: config.end,
+            });
+        }
+        Ok(Self::generate(config))
+    }
+}
+
+impl TryGenerate<std::ops::RangeInclusive<f64>> for f64 {
+    fn try_generate(config: std::ops::RangeInclusive<f64>) -> Result<Self> {
+        let (start, end) = (*config.start(), *config.end());
+        check_finite(start, end)?;
+        if config.is_empty() {
+            return Err(Error::EmptyRange { start, end });
+        }
+        Ok(Self::generate(config))
     }
 }
 
@@ -102,6 +185,199 @@ pub trait Extract<TEntity> {
     fn genome(&self) -> TEntity;
 }
 
+/// Describe a gene or genome as a delta against a base value.
+///
+/// Most genomes in a generation share ancestry, so storing the differences
+/// against a shared baseline is far cheaper than storing every genome in full.
+///
+/// # Examples
+///
+/// ```
+/// use farm::genome::Diff;
+///
+/// let base = vec![0.0, 1.0, 2.0];
+/// let target = vec![0.0, 1.5, 2.0, 3.0];
+///
+/// let delta = target.diff(&base).unwrap();
+/// assert_eq!(Vec::patch(&base, &delta), target);
+/// assert_eq!(base.diff(&base), None);
+/// ```
+pub trait Diff: Sized {
+    /// The changes needed to turn a base value into `Self`.
+    type Delta: Clone + std::fmt::Debug + PartialEq + Serialize + DeserializeOwned;
+
+    /// Compute the delta from `base` to `self`.
+    ///
+    /// # Arguments
+    ///
+    /// - `base` - The value to compare against.
+    ///
+    /// # Returns
+    ///
+    /// The delta, or `None` if `self` is identical to `base`.
+    fn diff(&self, base: &Self) -> Option<Self::Delta>;
+
+    /// Apply a delta to a base value.
+    ///
+    /// # Arguments
+    ///
+    /// - `base` - The value the delta was computed against.
+    /// - `delta` - The delta to apply.
+    ///
+    /// # Returns
+    ///
+    /// The reconstructed value.
+    fn patch(base: &Self, delta: &Self::Delta) -> Self;
+}
+
+/// Implement `Diff` for `f64`.
+///
+/// # Examples
+///
+/// ```
+/// use farm::genome::Diff;
+///
+/// assert_eq!(1.5.diff(&1.0), Some(1.5));
+/// assert_eq!(1.0.diff(&1.0), None);
+/// assert_eq!(f64::patch(&1.0, &1.5), 1.5);
+/// ```
+impl Diff for f64 {
+    type Delta = f64;
+
+    fn diff(&self, base: &Self) -> Option<Self::Delta> {
+        if self.to_bits() == base.to_bits() {
+            None
+        } else {
+            Some(*self)
+        }
+    }
+
+    fn patch(_base: &Self, delta: &Self::Delta) -> Self {
+        *delta
+    }
+}
+
+/// The delta between two vectors.
+///
+/// Elements that exist in both vectors are stored as element deltas, elements
+/// past the end of the base vector are stored in full.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(bound(deserialize = "T: DeserializeOwned"))]
+pub struct VecDelta<T>
+where
+    T: Diff,
+{
+    /// The length of the target vector.
+    pub len: usize,
+
+    /// Element deltas, keyed by their index in the base vector. Left out of
+    /// text formats when empty.
+    #[serde(default)]
+    pub changes: Vec<(usize, T::Delta)>,
+
+    /// Elements appended after the end of the base vector. Left out of text
+    /// formats when empty.
+    #[serde(default)]
+    pub appended: Vec<T>,
+}
+
+impl<T> Serialize for VecDelta<T>
+where
+    T: Diff + Serialize,
+{
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let changes = keep(&serializer, !self.changes.is_empty());
+        let appended = keep(&serializer, !self.appended.is_empty());
+        let fields = 1 + usize::from(changes) + usize::from(appended);
+        let mut state = serializer.serialize_struct("VecDelta", fields)?;
+        state.serialize_field("len", &self.len)?;
+        if changes {
+            state.serialize_field("changes", &self.changes)?;
+        } else {
+            state.skip_field("changes")?;
+        }
+        if appended {
+            state.serialize_field("appended", &self.appended)?;
+        } else {
+            state.skip_field("appended")?;
+        }
+        state.end()
+    }
+}
+
+/// Decide whether to write an optional field of a genome.
+///
+/// Text formats leave out fields that hold their default, to keep files small
+/// and readable. Binary formats such as bincode read fields by position, so
+/// they must write every one.
+///
+/// # Arguments
+///
+/// - `serializer` - The serializer the genome is written with.
+/// - `set` - Whether the field holds something other than its default.
+///
+/// # Returns
+///
+/// Whether to write the field.
+fn keep<S: serde::Serializer>(serializer: &S, set: bool) -> bool {
+    set || !serializer.is_human_readable()
+}
+
+/// Implement `Diff` for `Vec<Diff + Clone>`.
+///
+/// # Examples
+///
+/// ```
+/// use farm::genome::Diff;
+///
+/// let base = vec![0.0, 1.0, 2.0];
+/// let target = vec![0.5];
+///
+/// let delta = target.diff(&base).unwrap();
+/// assert_eq!(delta.len, 1);
+/// assert_eq!(Vec::patch(&base, &delta), target);
+/// ```
+impl<T> Diff for Vec<T>
+where
+    T: Diff + Clone + Serialize + DeserializeOwned + std::fmt::Debug + PartialEq,
+{
+    type Delta = VecDelta<T>;
+
+    fn diff(&self, base: &Self) -> Option<Self::Delta> {
+        let changes = Iterator::zip(self.iter(), base.iter())
+            .enumerate()
+            .filter_map(|(index, (target, base))| Some((index, target.diff(base)?)))
+            .collect::<Vec<_>>();
+        let appended = self.iter().skip(base.len()).cloned().collect::<Vec<_>>();
+
+        if changes.is_empty() && self.len() == base.len() {
+            None
+        } else {
+            Some(VecDelta {
+                len: self.len(),
+                changes,
+                appended,
+            })
+        }
+    }
+
+    fn patch(base: &Self, delta: &Self::Delta) -> Self {
+        let mut target = base.iter().take(delta.len).cloned().collect::<Vec<_>>();
+        for (index, change) in &delta.changes {
+            if let Some(element) = target.get_mut(*index) {
+                *element = T::patch(&base[*index], change);
+            }
+        }
+        target.extend(delta.appended.iter().cloned());
+        target
+    }
+}
+
 /// Enable crossover for a gene or genome.
 pub trait Crossover {
     /// Crossover the target.
@@ -115,6 +391,20 @@ pub trait Crossover {
     /// The crossovered target.
     #[must_use]
     fn crossover(&self, other: &Self) -> Self;
+
+    /// Crossover the target into an existing offspring, reusing its
+    /// allocations where the genome allows it.
+    ///
+    /// # Arguments
+    ///
+    /// - `other` - The other target to crossover with.
+    /// - `offspring` - A spare target to overwrite with the result.
+    fn crossover_into(&self, other: &Self, offspring: &mut Self)
+    where
+        Self: Sized,
+    {
+        *offspring = self.crossover(other);
+    }
 }
 
 /// Implement `Target` for `f64`.
@@ -133,12 +423,12 @@ pub trait Crossover {
 impl Crossover for f64 {
     fn crossover(&self, other: &Self) -> Self {
         let a = if self.is_nan() || self.is_infinite() {
-            thread_rng().gen_range(-1.0..=1.0)
+            rng::handle().gen_range(-1.0..=1.0)
         } else {
             *self
         };
         let b = if other.is_nan() || other.is_infinite() {
-            thread_rng().gen_range(-1.0..=1.0)
+            rng::handle().gen_range(-1.0..=1.0)
         } else {
             *other
         };
@@ -148,7 +438,7 @@ impl Crossover for f64 {
         if f64::abs(max - min) < f64::EPSILON {
             min
         } else {
-            thread_rng().gen_range(min..=max)
+            rng::handle().gen_range(min..=max)
         }
     }
 }
@@ -167,7 +457,7 @@ impl Crossover for f64 {
 /// ```
 impl Crossover for bool {
     fn crossover(&self, other: &Self) -> Self {
-        if random::<bool>() {
+        if rng::handle().gen::<bool>() {
             *self
         } else {
             *other
@@ -208,4 +498,26 @@ where
             .chain(rest.map(Clone::clone))
             .collect()
     }
+
+    fn crossover_into(&self, other: &Self, offspring: &mut Self) {
+        let longer = if self.len() < other.len() {
+            other
+        } else {
+            self
+        };
+        offspring.truncate(longer.len());
+
+        for (index, (a, b)) in Iterator::zip(self.iter(), other.iter()).enumerate() {
+            match offspring.get_mut(index) {
+                Some(element) => a.crossover_into(b, element),
+                None => offspring.push(a.crossover(b)),
+            }
+        }
+        for (index, rest) in longer.iter().enumerate().skip(self.len().min(other.len())) {
+            match offspring.get_mut(index) {
+                Some(element) => element.clone_from(rest),
+                None => offspring.push(rest.clone()),
+            }
+        }
+    }
 }
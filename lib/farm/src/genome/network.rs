@@ -1,7 +1,9 @@
-use super::layer;
-use crate::genome::{Create, Crossover, Extract, Generate};
+use super::{activator, layer, neuron};
+use crate::genome::{Create, Crossover, Diff, Extract, Generate, VecDelta};
 use crate::mutate::Target;
-use nnet::Network;
+use nnet::format::{self, Format};
+use nnet::{InputSource, Network, WeightStats};
+use rand::Rng;
 
 /// A neural network genome.
 ///
@@ -30,9 +32,310 @@ use nnet::Network;
 /// assert_eq!(genome.layers[0].neurons.len(), 4);
 /// assert_eq!(genome.layers[0].neurons[0].weights.len(), 3);
 /// ```
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(try_from = "Stored")]
 pub struct Genome {
     pub layers: Vec<layer::Genome>,
+
+    /// Which layers are frozen, by index. Frozen layers are never mutated,
+    /// and crossover copies them from the first parent, so pretrained layers
+    /// stay fixed while evolution adapts the rest. Layers past the end of the
+    /// mask are not frozen. Left out of text formats when empty.
+    #[serde(default)]
+    pub frozen: Vec<bool>,
+
+    /// Where each layer takes its inputs from, by index, as in
+    /// [`Network::input_source`]. Layers past the end take the output of the
+    /// layer before them. Like the freeze mask, this is part of the run's
+    /// topology: it is never mutated, and offspring take it from the first
+    /// parent. Left out of text formats when empty.
+    pub sources: Vec<InputSource>,
+}
+
+/// A genome as written by any version of the crate.
+#[derive(serde::Deserialize)]
+#[serde(rename = "Genome")]
+struct Stored {
+    /// The schema version; missing before versions were added.
+    #[serde(default)]
+    version: u32,
+
+    layers: Vec<layer::Genome>,
+
+    #[serde(default)]
+    frozen: Vec<bool>,
+
+    #[serde(default)]
+    sources: Vec<InputSource>,
+}
+
+impl TryFrom<Stored> for Genome {
+    type Error = nnet::Error;
+
+    fn try_from(stored: Stored) -> Result<Self, Self::Error> {
+        let Stored {
+            version,
+            layers,
+            frozen,
+            sources,
+        } = stored;
+        match version {
+            // Version 0 only lacks the version itself; the fields added since
+            // default to empty.
+            0 | 1 => Ok(Self {
+                layers,
+                frozen,
+                sources,
+            }),
+            found => Err(nnet::Error::UnsupportedVersion {
+                found,
+                supported: Self::SCHEMA_VERSION,
+            }),
+        }
+    }
+}
+
+impl serde::Serialize for Genome {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let frozen = super::keep(&serializer, !self.frozen.is_empty());
+        let sources = super::keep(&serializer, !self.sources.is_empty());
+        let fields = 2 + usize::from(frozen) + usize::from(sources);
+        let mut state = serializer.serialize_struct("Genome", fields)?;
+        state.serialize_field("version", &Self::SCHEMA_VERSION)?;
+        state.serialize_field("layers", &self.layers)?;
+        if frozen {
+            state.serialize_field("frozen", &self.frozen)?;
+        } else {
+            state.skip_field("frozen")?;
+        }
+        if sources {
+            state.serialize_field("sources", &self.sources)?;
+        } else {
+            state.skip_field("sources")?;
+        }
+        state.end()
+    }
+}
+
+impl Genome {
+    /// The version of the serialized form written by this crate. Genomes
+    /// written by older versions are migrated as they are read, as with
+    /// [`Network::SCHEMA_VERSION`].
+    pub const SCHEMA_VERSION: u32 = 1;
+
+    /// Freeze the first layers, and unfreeze the rest.
+    ///
+    /// # Arguments
+    ///
+    /// - `count` - The number of layers to freeze, from the input side.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use farm::genome::{layer, network};
+    ///
+    /// let layer = layer::Genome { neurons: vec![] };
+    /// let mut genome = network::Genome {
+    ///     layers: vec![layer.clone(), layer.clone(), layer],
+    ///     frozen: vec![],
+    ///     sources: vec![],
+    /// };
+    ///
+    /// genome.freeze_layers(2);
+    ///
+    /// assert_eq!(genome.frozen, vec![true, true, false]);
+    /// assert!(genome.is_frozen(1));
+    /// ```
+    pub fn freeze_layers(&mut self, count: usize) {
+        self.frozen = (0..self.layers.len()).map(|index| index < count).collect();
+    }
+
+    /// Check whether a layer is frozen.
+    ///
+    /// # Arguments
+    ///
+    /// - `layer` - The index of the layer.
+    ///
+    /// # Returns
+    ///
+    /// Whether the layer is frozen.
+    #[must_use]
+    pub fn is_frozen(&self, layer: usize) -> bool {
+        self.frozen.get(layer).copied().unwrap_or(false)
+    }
+
+    /// Summarize the weights of each layer, as [`Network::weight_stats`] does
+    /// for the network this genome creates.
+    ///
+    /// # Returns
+    ///
+    /// One set of statistics per layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use farm::genome::{activator, layer, network, neuron};
+    ///
+    /// let neuron = neuron::Genome {
+    ///     activator: activator::Genome { activator: activator::Gene::Linear, parameter: None },
+    ///     weights: vec![0.5, 1.5],
+    ///     bias: 0.0,
+    ///     gates: None,
+    /// };
+    /// let genome = network::Genome {
+    ///     layers: vec![layer::Genome { neurons: vec![neuron] }],
+    ///     frozen: vec![],
+    ///     sources: vec![],
+    /// };
+    ///
+    /// assert_eq!(genome.weight_stats()[0].mean, 1.0);
+    /// ```
+    #[must_use]
+    pub fn weight_stats(&self) -> Vec<WeightStats> {
+        self.layers
+            .iter()
+            .map(|layer| {
+                let weights = layer
+                    .neurons
+                    .iter()
+                    .flat_map(|neuron| neuron.weights.iter().copied())
+                    .collect::<Vec<_>>();
+                WeightStats::new(&weights)
+            })
+            .collect()
+    }
+
+    /// Create a genome with random genes.
+    ///
+    /// Weights and biases are drawn from `-1.0..=1.0`, and each neuron gets a
+    /// random activation function.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` - The number of inputs to the first layer.
+    /// - `layers` - The number of neurons in each layer.
+    /// - `rng` - The random number generator.
+    ///
+    /// # Returns
+    ///
+    /// The genome.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use farm::genome::network;
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let genome = network::Genome::random(3, &[4, 2], &mut StdRng::seed_from_u64(1));
+    ///
+    /// assert_eq!(genome.layers[0].neurons[0].weights.len(), 3);
+    /// assert_eq!(genome.layers[1].neurons.len(), 2);
+    /// ```
+    pub fn random(inputs: usize, layers: &[usize], rng: &mut impl Rng) -> Self {
+        let mut input_size = inputs;
+        let layers = layers
+            .iter()
+            .map(|&size| {
+                let neurons = (0..size)
+                    .map(|_| neuron::Genome {
+                        activator: activator::Genome {
+                            activator: rng.gen(),
+                            parameter: None,
+                        },
+                        weights: (0..input_size).map(|_| rng.gen_range(-1.0..=1.0)).collect(),
+                        bias: rng.gen_range(-1.0..=1.0),
+                        gates: None,
+                    })
+                    .collect();
+                input_size = size;
+                layer::Genome { neurons }
+            })
+            .collect();
+
+        Self {
+            layers,
+            frozen: vec![],
+            sources: vec![],
+        }
+    }
+
+    /// Write the genome in an interchange format, e.g. to share a checkpoint
+    /// with tools written in other languages.
+    ///
+    /// # Arguments
+    ///
+    /// - `writer` - Receives the serialized genome.
+    /// - `format` - The format to write in.
+    ///
+    /// # Errors
+    ///
+    /// If the genome cannot be serialized or the writer fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use farm::genome::{layer, network};
+    /// use nnet::format::Format;
+    ///
+    /// let genome = network::Genome {
+    ///     layers: vec![layer::Genome { neurons: vec![] }],
+    ///     frozen: vec![true],
+    ///     sources: vec![],
+    /// };
+    ///
+    /// let mut bytes = vec![];
+    /// genome.serialize_to(&mut bytes, Format::Json).unwrap();
+    ///
+    /// assert_eq!(network::Genome::deserialize_from(bytes.as_slice(), Format::Json).unwrap(), genome);
+    /// ```
+    pub fn serialize_to<W: std::io::Write>(
+        &self,
+        writer: W,
+        format: Format,
+    ) -> Result<(), format::Error> {
+        format::serialize_to(self, writer, format)
+    }
+
+    /// Read a genome written in an interchange format.
+    ///
+    /// # Arguments
+    ///
+    /// - `reader` - Supplies the serialized genome.
+    /// - `format` - The format to read.
+    ///
+    /// # Returns
+    ///
+    /// The genome.
+    ///
+    /// # Errors
+    ///
+    /// If the input is not a genome in the format, or the reader fails.
+    pub fn deserialize_from<R: std::io::Read>(
+        reader: R,
+        format: Format,
+    ) -> Result<Self, format::Error> {
+        format::deserialize_from(reader, format)
+    }
+
+    /// Restore the frozen layers of the first parent into an offspring.
+    ///
+    /// # Arguments
+    ///
+    /// - `parent` - The parent whose frozen layers are kept.
+    fn restore_frozen(&mut self, parent: &Self) {
+        self.frozen.clone_from(&parent.frozen);
+        for (index, layer) in parent.layers.iter().enumerate() {
+            if !parent.is_frozen(index) {
+                continue;
+            }
+            match self.layers.get_mut(index) {
+                Some(offspring) => offspring.clone_from(layer),
+                None => self.layers.push(layer.clone()),
+            }
+        }
+    }
 }
 
 /// Configuration for generating a [`Genome`].
@@ -76,7 +379,11 @@ where
     fn generate(config: &GenerateConfig<TLayerGenerator>) -> Self {
         let layers = (config.layer_generator)();
 
-        Self { layers }
+        Self {
+            layers,
+            frozen: vec![],
+            sources: vec![],
+        }
     }
 }
 
@@ -104,8 +411,20 @@ where
 /// ```
 impl Crossover for Genome {
     fn crossover(&self, other: &Self) -> Self {
-        let layers = Vec::crossover(&self.layers, &other.layers);
-        Self { layers }
+        let mut offspring = Self {
+            layers: Vec::crossover(&self.layers, &other.layers),
+            frozen: vec![],
+            sources: self.sources.clone(),
+        };
+        offspring.restore_frozen(self);
+        offspring
+    }
+
+    fn crossover_into(&self, other: &Self, offspring: &mut Self) {
+        self.layers
+            .crossover_into(&other.layers, &mut offspring.layers);
+        offspring.sources.clone_from(&self.sources);
+        offspring.restore_frozen(self);
     }
 }
 
@@ -130,12 +449,57 @@ impl Crossover for Genome {
 /// ```
 impl Target for Genome {
     fn mutate(mut self, mutator: &crate::mutate::Mutator) -> Self {
-        self.layers = self.layers.mutate(mutator);
+        let frozen = std::mem::take(&mut self.frozen);
+        self.layers = self
+            .layers
+            .into_iter()
+            .enumerate()
+            .map(|(index, layer)| {
+                if frozen.get(index).copied().unwrap_or(false) {
+                    layer
+                } else {
+                    layer.mutate(mutator)
+                }
+            })
+            .collect();
+        self.frozen = frozen;
         // TODO: mutate the network layer vector.
         self
     }
 }
 
+/// Enable delta encoding for [`Genome`].
+///
+/// The freeze mask and input sources are not part of the delta; patched
+/// genomes take them from the base, since every genome of a run shares them.
+///
+/// # Examples
+///
+/// ```
+/// use farm::genome::{network::Genome, layer, Diff};
+///
+/// let base = Genome { layers: vec![layer::Genome { neurons: vec![] }], frozen: vec![], sources: vec![] };
+/// let target = Genome { layers: vec![], frozen: vec![], sources: vec![] };
+///
+/// let delta = target.diff(&base).unwrap();
+/// assert_eq!(Genome::patch(&base, &delta), target);
+/// ```
+impl Diff for Genome {
+    type Delta = VecDelta<layer::Genome>;
+
+    fn diff(&self, base: &Self) -> Option<Self::Delta> {
+        self.layers.diff(&base.layers)
+    }
+
+    fn patch(base: &Self, delta: &Self::Delta) -> Self {
+        Self {
+            layers: Vec::patch(&base.layers, delta),
+            frozen: base.frozen.clone(),
+            sources: base.sources.clone(),
+        }
+    }
+}
+
 impl Create<Network> for Genome {
     /// Create a new [`Network`] from the genome.
     ///
@@ -163,7 +527,10 @@ impl Create<Network> for Genome {
     /// ```
     fn create(&self) -> Network {
         let layers = self.layers.iter().map(layer::Genome::create).collect();
-        Network::builder().layers(layers).build()
+        Network::builder()
+            .layers(layers)
+            .input_sources(self.sources.clone())
+            .build()
     }
 }
 
@@ -200,22 +567,36 @@ impl Extract<Genome> for Network {
     /// ```
     fn genome(&self) -> Genome {
         let layers = self.layers().iter().map(nnet::Layer::genome).collect();
-        Genome { layers }
+        let sources = if self.has_skips() {
+            (0..self.layers().len())
+                .map(|index| self.input_source(index).clone())
+                .collect()
+        } else {
+            vec![]
+        };
+        Genome {
+            layers,
+            frozen: vec![],
+            sources,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
 
     #[test]
     fn test_serialize() {
         let genome = Genome {
             layers: vec![layer::Genome { neurons: vec![] }],
+            frozen: vec![],
+            sources: vec![],
         };
 
         let serialized = serde_json::to_string(&genome).unwrap();
-        let expected = r#"{"layers":[{"neurons":[]}]}"#;
+        let expected = r#"{"version":1,"layers":[{"neurons":[]}]}"#;
 
         assert_eq!(serialized, expected);
     }
@@ -224,6 +605,8 @@ mod tests {
     fn test_deserialize() {
         let genome = Genome {
             layers: vec![layer::Genome { neurons: vec![] }],
+            frozen: vec![],
+            sources: vec![],
         };
 
         let serialized = r#"
@@ -239,4 +622,218 @@ mod tests {
 
         assert_eq!(genome, deserialized);
     }
+
+    #[test]
+    fn test_serialize_binary() {
+        let neuron = nnet::BasicNeuron::builder().weights(vec![0.5]).build();
+        let plain = Network::builder()
+            .add_layer(nnet::Layer::builder().add_neuron(neuron).build())
+            .build()
+            .genome();
+        let mut frozen = plain.clone();
+        frozen.frozen = vec![true];
+        let mut sourced = plain.clone();
+        sourced.sources = vec![InputSource::Layers(vec![0])];
+
+        // Binary formats read fields by position, so empty fields are written
+        // too.
+        for genome in [plain, frozen, sourced] {
+            let bytes = bincode::serialize(&genome).unwrap();
+            assert_eq!(bincode::deserialize::<Genome>(&bytes).unwrap(), genome);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_newer_version() {
+        let serialized = r#"{"version":99,"layers":[]}"#;
+
+        let error = serde_json::from_str::<Genome>(serialized).unwrap_err();
+
+        assert!(error.to_string().contains("schema version 99"), "{error}");
+    }
+
+    #[test]
+    fn test_interchange_formats() {
+        let gate = neuron::Gate {
+            weights: vec![1.0, 2.0],
+            recurrent: -0.5,
+            bias: 0.0,
+        };
+        let neuron = neuron::Genome {
+            activator: activator::Genome {
+                activator: activator::Gene::LeakyRelu,
+                parameter: Some(0.2),
+            },
+            weights: vec![0.5, -1.0],
+            bias: 0.25,
+            gates: Some(neuron::Gates {
+                recurrent: 0.1,
+                input: gate.clone(),
+                forget: gate.clone(),
+                output: gate,
+            }),
+        };
+        let genome = Genome {
+            layers: vec![layer::Genome {
+                neurons: vec![neuron],
+            }],
+            frozen: vec![true],
+            sources: vec![InputSource::Layers(vec![])],
+        };
+        let formats = [
+            Format::Json,
+            #[cfg(feature = "msgpack")]
+            Format::MessagePack,
+            #[cfg(feature = "cbor")]
+            Format::Cbor,
+        ];
+
+        for format in formats {
+            let mut bytes = vec![];
+            genome.serialize_to(&mut bytes, format).unwrap();
+            let parsed = Genome::deserialize_from(bytes.as_slice(), format).unwrap();
+            assert_eq!(parsed, genome, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn test_crossover_into_reuses_spare() {
+        let neuron = |weights: Vec<f64>| neuron::Genome {
+            activator: activator::Genome {
+                activator: activator::Gene::Linear,
+                parameter: None,
+            },
+            weights,
+            bias: 0.5,
+            gates: None,
+        };
+        let parent = Genome {
+            layers: vec![layer::Genome {
+                neurons: vec![neuron(vec![1.0, 2.0])],
+            }],
+            frozen: vec![],
+            sources: vec![],
+        };
+        let mut spare = Genome {
+            layers: vec![
+                layer::Genome {
+                    neurons: vec![neuron(vec![0.0; 8]), neuron(vec![0.0; 8])],
+                },
+                layer::Genome { neurons: vec![] },
+            ],
+            frozen: vec![],
+            sources: vec![],
+        };
+        let weights = spare.layers[0].neurons[0].weights.as_ptr();
+
+        parent.crossover_into(&parent, &mut spare);
+
+        assert_eq!(spare, parent);
+        assert_eq!(spare.layers[0].neurons[0].weights.as_ptr(), weights);
+    }
+
+    #[test]
+    fn test_frozen_layers() {
+        use crate::mutate::Mutator;
+
+        let layer = |weight: f64| layer::Genome {
+            neurons: vec![neuron::Genome {
+                activator: activator::Genome {
+                    activator: activator::Gene::Linear,
+                    parameter: None,
+                },
+                weights: vec![weight; 4],
+                bias: weight,
+                gates: None,
+            }],
+        };
+        let mut left = Genome {
+            layers: vec![layer(1.0), layer(1.0)],
+            frozen: vec![],
+            sources: vec![],
+        };
+        left.freeze_layers(1);
+        let right = Genome {
+            layers: vec![layer(2.0), layer(2.0)],
+            frozen: vec![],
+            sources: vec![],
+        };
+
+        let mutator = Mutator::builder()
+            .mutation_rate(1.0)
+            .mutation_size(1.0)
+            .build();
+        // Seeded, since a random mutation can leave a layer as it was.
+        let mutated = evo::rng::seeded(1, || left.clone().mutate(&mutator));
+        assert_eq!(mutated.layers[0], left.layers[0]);
+        assert_ne!(mutated.layers[1], left.layers[1]);
+        assert_eq!(mutated.frozen, left.frozen);
+
+        let child = left.crossover(&right);
+        assert_eq!(child.layers[0], left.layers[0]);
+        assert_eq!(child.frozen, vec![true, false]);
+
+        let mut spare = right.clone();
+        left.crossover_into(&right, &mut spare);
+        assert_eq!(spare.layers[0], left.layers[0]);
+    }
+
+    #[test]
+    fn test_input_sources() {
+        let layer = |count, weights: usize| layer::Genome {
+            neurons: (0..count)
+                .map(|_| neuron::Genome {
+                    activator: activator::Genome {
+                        activator: activator::Gene::Linear,
+                        parameter: None,
+                    },
+                    weights: vec![1.0; weights],
+                    bias: 0.0,
+                    gates: None,
+                })
+                .collect(),
+        };
+        let genome = Genome {
+            layers: vec![layer(2, 1), layer(1, 2), layer(1, 3)],
+            frozen: vec![],
+            sources: vec![
+                InputSource::Previous,
+                InputSource::Previous,
+                InputSource::Layers(vec![0, 1]),
+            ],
+        };
+
+        let network = genome.create();
+        assert!(network.validate().is_ok());
+        assert_eq!(network.activate(&[1.0]), vec![4.0]);
+        assert_eq!(network.genome(), genome);
+
+        let child = genome.crossover(&genome);
+        assert_eq!(child.sources, genome.sources);
+
+        let plain = Genome {
+            sources: vec![],
+            ..genome.clone()
+        };
+        assert_eq!(plain.diff(&genome), None);
+    }
+
+    #[test]
+    fn test_random_shape() {
+        let genome = Genome::random(3, &[4, 2], &mut StdRng::seed_from_u64(1));
+
+        assert_eq!(genome.layers.len(), 2);
+        assert_eq!(genome.layers[0].neurons.len(), 4);
+        assert_eq!(genome.layers[0].neurons[0].weights.len(), 3);
+        assert_eq!(genome.layers[1].neurons.len(), 2);
+        assert_eq!(genome.layers[1].neurons[0].weights.len(), 4);
+    }
+
+    #[test]
+    fn test_random_is_seeded() {
+        let left = Genome::random(3, &[4, 2], &mut StdRng::seed_from_u64(1));
+        let right = Genome::random(3, &[4, 2], &mut StdRng::seed_from_u64(1));
+
+        assert_eq!(left, right);
+    }
 }
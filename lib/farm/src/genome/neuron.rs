@@ -1,5 +1,5 @@
 use super::activator;
-use crate::genome::{Create, Crossover, Extract, Generate};
+use crate::genome::{Create, Crossover, Diff, Extract, Generate, VecDelta};
 use crate::mutate::{Mutator, Target, VecMutation};
 use nnet::Neuron;
 
@@ -10,19 +10,86 @@ use nnet::Neuron;
 /// ```
 /// use farm::genome::{activator, neuron::Genome};
 ///
-/// let activator = activator::Genome { activator: activator::Gene::Linear };
+/// let activator = activator::Genome { activator: activator::Gene::Linear, parameter: None };
 /// let weights = vec![0.0, 1.0, 2.0];
 /// let bias = 3.0;
-/// let genome = Genome { activator: activator.clone(), weights: weights.clone(), bias };
+/// let genome = Genome { activator: activator.clone(), weights: weights.clone(), bias, gates: None };
 /// assert_eq!(genome.activator, activator);
 /// assert_eq!(genome.weights, weights);
 /// assert_eq!(genome.bias, bias);
 /// ```
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
 pub struct Genome {
     pub activator: activator::Genome,
     pub weights: Vec<Gene>,
     pub bias: Gene,
+
+    /// The gates of an LSTM neuron, or `None` for a basic neuron. The
+    /// neuron's own activator, weights, and bias make up the LSTM's candidate.
+    /// Left out of text formats when `None`.
+    #[serde(default)]
+    pub gates: Option<Gates>,
+}
+
+impl serde::Serialize for Genome {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let gates = super::keep(&serializer, self.gates.is_some());
+        let mut state = serializer.serialize_struct("Genome", 3 + usize::from(gates))?;
+        state.serialize_field("activator", &self.activator)?;
+        state.serialize_field("weights", &self.weights)?;
+        state.serialize_field("bias", &self.bias)?;
+        if gates {
+            state.serialize_field("gates", &self.gates)?;
+        } else {
+            state.skip_field("gates")?;
+        }
+        state.end()
+    }
+}
+
+/// The gates of an LSTM neuron.
+///
+/// # Examples
+///
+/// ```
+/// use farm::genome::neuron::{Gate, Gates};
+///
+/// let gate = Gate { weights: vec![0.5, -0.5], recurrent: 0.1, bias: 1.0 };
+/// let gates = Gates {
+///     recurrent: 0.2,
+///     input: gate.clone(),
+///     forget: gate.clone(),
+///     output: gate,
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Gates {
+    /// The weight the candidate applies to the neuron's previous output.
+    pub recurrent: Gene,
+
+    /// Decides how much of the candidate is added to the cell.
+    pub input: Gate,
+
+    /// Decides how much of the cell is kept.
+    pub forget: Gate,
+
+    /// Decides how much of the activated cell is output.
+    pub output: Gate,
+}
+
+/// A gate of an LSTM neuron.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Gate {
+    /// The weights the gate applies to the inputs.
+    pub weights: Vec<Gene>,
+
+    /// The weight the gate applies to the neuron's previous output.
+    pub recurrent: Gene,
+
+    /// Shifts how far the gate opens.
+    pub bias: Gene,
 }
 
 /// Configuration for generating a [`Genome`].
@@ -33,7 +100,7 @@ pub struct Genome {
 /// use farm::genome::{neuron::{Genome, GenerateConfig}, activator};
 /// use farm::genome::Generate;
 ///
-/// let activator_generator = || activator::Genome { activator: activator::Gene::Linear };
+/// let activator_generator = || activator::Genome { activator: activator::Gene::Linear, parameter: None };
 /// let weight_generator = || vec![0.0, 1.0, 2.0];
 /// let bias_generator = || 3.0;
 ///
@@ -78,6 +145,7 @@ where
             activator,
             weights,
             bias,
+            gates: None,
         }
     }
 }
@@ -90,14 +158,16 @@ where
 /// use farm::genome::{Crossover, neuron::Genome, activator};
 ///
 /// let left = Genome {
-///     activator: activator::Genome { activator: activator::Gene::Linear },
+///     activator: activator::Genome { activator: activator::Gene::Linear, parameter: None },
 ///     weights: vec![],
 ///     bias: 0.0,
+///     gates: None,
 /// };
 /// let right = Genome {
-///     activator: activator::Genome { activator: activator::Gene::Sigmoid },
+///     activator: activator::Genome { activator: activator::Gene::Sigmoid, parameter: None },
 ///     weights: vec![],
 ///     bias: 0.0,
+///     gates: None,
 /// };
 ///
 /// let target = left.crossover(&right);
@@ -108,6 +178,202 @@ impl Crossover for Genome {
             activator: self.activator.crossover(&other.activator),
             weights: self.weights.crossover(&other.weights),
             bias: self.bias.crossover(&other.bias),
+            gates: crossover_gates(self.gates.as_ref(), other.gates.as_ref()),
+        }
+    }
+
+    fn crossover_into(&self, other: &Self, offspring: &mut Self) {
+        offspring.activator = self.activator.crossover(&other.activator);
+        self.weights
+            .crossover_into(&other.weights, &mut offspring.weights);
+        offspring.bias = self.bias.crossover(&other.bias);
+        offspring.gates = crossover_gates(self.gates.as_ref(), other.gates.as_ref());
+    }
+}
+
+/// Cross over the gates of two neurons.
+///
+/// # Arguments
+///
+/// - `left` - The gates of the first parent.
+/// - `right` - The gates of the second parent.
+///
+/// # Returns
+///
+/// The crossed-over gates if both parents are LSTM neurons, or else the
+/// first parent's, so the offspring keeps the first parent's kind of neuron.
+fn crossover_gates(left: Option<&Gates>, right: Option<&Gates>) -> Option<Gates> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(left.crossover(right)),
+        (left, _) => left.cloned(),
+    }
+}
+
+/// Enable crossover for [`Gates`].
+impl Crossover for Gates {
+    fn crossover(&self, other: &Self) -> Self {
+        Self {
+            recurrent: self.recurrent.crossover(&other.recurrent),
+            input: self.input.crossover(&other.input),
+            forget: self.forget.crossover(&other.forget),
+            output: self.output.crossover(&other.output),
+        }
+    }
+}
+
+/// Enable crossover for [`Gate`].
+impl Crossover for Gate {
+    fn crossover(&self, other: &Self) -> Self {
+        Self {
+            weights: self.weights.crossover(&other.weights),
+            recurrent: self.recurrent.crossover(&other.recurrent),
+            bias: self.bias.crossover(&other.bias),
+        }
+    }
+}
+
+/// The delta between two neuron genomes.
+///
+/// Fields that did not change are left out of text formats.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+pub struct Delta {
+    /// The new activator, if it changed.
+    #[serde(default)]
+    pub activator: Option<activator::Genome>,
+
+    /// The changed weights, if any.
+    #[serde(default)]
+    pub weights: Option<VecDelta<Gene>>,
+
+    /// The new bias, if it changed.
+    #[serde(default)]
+    pub bias: Option<Gene>,
+
+    /// The new gates, if they changed; `Some(None)` if they were removed.
+    #[serde(default, deserialize_with = "deserialize_changed")]
+    pub gates: Option<Option<Gates>>,
+}
+
+/// Deserialize a field that is only present when it changed, so a present
+/// `null` means it changed to `None`. Binary formats write the field in
+/// full, so it is read back as is.
+///
+/// # Arguments
+///
+/// - `deserializer` - The deserializer.
+///
+/// # Returns
+///
+/// The changed value.
+///
+/// # Errors
+///
+/// If the value cannot be deserialized.
+#[allow(clippy::option_option)]
+fn deserialize_changed<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    if deserializer.is_human_readable() {
+        serde::Deserialize::deserialize(deserializer).map(Some)
+    } else {
+        serde::Deserialize::deserialize(deserializer)
+    }
+}
+
+impl serde::Serialize for Delta {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let activator = super::keep(&serializer, self.activator.is_some());
+        let weights = super::keep(&serializer, self.weights.is_some());
+        let bias = super::keep(&serializer, self.bias.is_some());
+        let gates = super::keep(&serializer, self.gates.is_some());
+        let fields =
+            usize::from(activator) + usize::from(weights) + usize::from(bias) + usize::from(gates);
+        let mut state = serializer.serialize_struct("Delta", fields)?;
+        if activator {
+            state.serialize_field("activator", &self.activator)?;
+        } else {
+            state.skip_field("activator")?;
+        }
+        if weights {
+            state.serialize_field("weights", &self.weights)?;
+        } else {
+            state.skip_field("weights")?;
+        }
+        if bias {
+            state.serialize_field("bias", &self.bias)?;
+        } else {
+            state.skip_field("bias")?;
+        }
+        if gates {
+            state.serialize_field("gates", &self.gates)?;
+        } else {
+            state.skip_field("gates")?;
+        }
+        state.end()
+    }
+}
+
+impl Diff for Genome {
+    type Delta = Delta;
+
+    /// Compute the delta between two neuron genomes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use farm::genome::{activator, neuron::Genome, Diff};
+    ///
+    /// let base = Genome {
+    ///     activator: activator::Genome { activator: activator::Gene::Linear, parameter: None },
+    ///     weights: vec![0.0, 1.0, 2.0],
+    ///     bias: 3.0,
+    ///     gates: None,
+    /// };
+    /// let target = Genome { bias: 4.0, ..base.clone() };
+    ///
+    /// let delta = target.diff(&base).unwrap();
+    /// assert_eq!(delta.bias, Some(4.0));
+    /// assert_eq!(delta.weights, None);
+    /// assert_eq!(Genome::patch(&base, &delta), target);
+    /// ```
+    fn diff(&self, base: &Self) -> Option<Self::Delta> {
+        let delta = Delta {
+            activator: self.activator.diff(&base.activator),
+            weights: self.weights.diff(&base.weights),
+            bias: self.bias.diff(&base.bias),
+            gates: (self.gates != base.gates).then(|| self.gates.clone()),
+        };
+
+        if delta.activator.is_none()
+            && delta.weights.is_none()
+            && delta.bias.is_none()
+            && delta.gates.is_none()
+        {
+            None
+        } else {
+            Some(delta)
+        }
+    }
+
+    fn patch(base: &Self, delta: &Self::Delta) -> Self {
+        Self {
+            activator: delta.activator.as_ref().map_or_else(
+                || base.activator.clone(),
+                |delta| activator::Genome::patch(&base.activator, delta),
+            ),
+            weights: delta.weights.as_ref().map_or_else(
+                || base.weights.clone(),
+                |weights| Vec::patch(&base.weights, weights),
+            ),
+            bias: delta.bias.unwrap_or(base.bias),
+            gates: delta
+                .gates
+                .as_ref()
+                .map_or_else(|| base.gates.clone(), Clone::clone),
         }
     }
 }
@@ -123,16 +389,17 @@ impl Target for Genome {
     ///
     /// let mutator = Mutator::builder().build();
     ///
-    /// let activator = activator::Genome { activator: activator::Gene::Linear };
+    /// let activator = activator::Genome { activator: activator::Gene::Linear, parameter: None };
     /// let weights = vec![0.0, 1.0, 2.0];
     /// let bias = 3.0;
-    /// let genome = Genome { activator: activator.clone(), weights: weights.clone(), bias };
+    /// let genome = Genome { activator: activator.clone(), weights: weights.clone(), bias, gates: None };
     /// let genome = genome.mutate(&mutator);
     /// ```
     fn mutate(mut self, mutator: &Mutator) -> Self {
         self.activator = mutator.mutate(self.activator);
         self.weights = mutator.mutate(self.weights);
         self.bias = mutator.mutate(self.bias);
+        self.gates = self.gates.map(|gates| mutator.mutate(gates));
 
         // Transposition mutation swaps two weights.
         if mutator.check_mutate() {
@@ -143,6 +410,29 @@ impl Target for Genome {
     }
 }
 
+/// Enable mutation for [`Gates`].
+impl Target for Gates {
+    fn mutate(self, mutator: &Mutator) -> Self {
+        Self {
+            recurrent: mutator.mutate(self.recurrent),
+            input: mutator.mutate(self.input),
+            forget: mutator.mutate(self.forget),
+            output: mutator.mutate(self.output),
+        }
+    }
+}
+
+/// Enable mutation for [`Gate`].
+impl Target for Gate {
+    fn mutate(self, mutator: &Mutator) -> Self {
+        Self {
+            weights: mutator.mutate(self.weights),
+            recurrent: mutator.mutate(self.recurrent),
+            bias: mutator.mutate(self.bias),
+        }
+    }
+}
+
 /// Mutate the weights of a neuron.
 ///
 /// # Arguments
@@ -168,7 +458,7 @@ fn mutate_weights(weights: &mut Vec<Gene>) {
                 vec_mutation.apply(weights);
             }
         }
-    };
+    }
 }
 
 impl Create<Neuron> for Genome {
@@ -180,19 +470,53 @@ impl Create<Neuron> for Genome {
     /// use farm::genome::{neuron::Genome, activator};
     /// use farm::genome::Create;
     ///
-    /// let activator = activator::Genome { activator: activator::Gene::Linear };
+    /// let activator = activator::Genome { activator: activator::Gene::Linear, parameter: None };
     /// let weights = vec![0.0, 1.0, 2.0];
     /// let bias = 3.0;
-    /// let genome = Genome { activator: activator.clone(), weights: weights.clone(), bias };
+    /// let genome = Genome { activator: activator.clone(), weights: weights.clone(), bias, gates: None };
     /// let neuron = genome.create();
     /// ```
     fn create(&self) -> Neuron {
-        Neuron::basic()
-            .activation(self.activator.create())
-            .weights(self.weights.clone())
-            .bias(self.bias)
-            .build()
-            .into()
+        match &self.gates {
+            Some(gates) => Neuron::lstm()
+                .activation(self.activator.create())
+                .weights(self.weights.clone())
+                .bias(self.bias)
+                .recurrent(gates.recurrent)
+                .input_gate(gates.input.create())
+                .forget_gate(gates.forget.create())
+                .output_gate(gates.output.create())
+                .build()
+                .into(),
+            None => Neuron::basic()
+                .activation(self.activator.create())
+                .weights(self.weights.clone())
+                .bias(self.bias)
+                .build()
+                .into(),
+        }
+    }
+}
+
+/// Enable creation for [`Gate`].
+impl Create<nnet::neuron::Gate> for Gate {
+    fn create(&self) -> nnet::neuron::Gate {
+        nnet::neuron::Gate {
+            weights: self.weights.clone(),
+            recurrent: self.recurrent,
+            bias: self.bias,
+        }
+    }
+}
+
+/// Enable extraction for [`Gate`].
+impl Extract<Gate> for nnet::neuron::Gate {
+    fn genome(&self) -> Gate {
+        Gate {
+            weights: self.weights.clone(),
+            recurrent: self.recurrent,
+            bias: self.bias,
+        }
     }
 }
 
@@ -206,7 +530,7 @@ impl Extract<Genome> for Neuron {
     /// use farm::genome::Extract;
     /// use nnet::Neuron;
     ///
-    /// let activator = activator::Genome { activator: activator::Gene::Linear };
+    /// let activator = activator::Genome { activator: activator::Gene::Linear, parameter: None };
     /// let weights = vec![0.0, 1.0, 2.0];
     /// let bias = 3.0;
     /// let neuron: Neuron = Neuron::basic()
@@ -224,11 +548,21 @@ impl Extract<Genome> for Neuron {
         let activator = self.activator().genome();
         let weights = self.weights().to_vec();
         let bias = self.bias();
+        let gates = match self {
+            Neuron::Lstm(lstm) => Some(Gates {
+                recurrent: lstm.recurrent(),
+                input: lstm.input_gate().genome(),
+                forget: lstm.forget_gate().genome(),
+                output: lstm.output_gate().genome(),
+            }),
+            _ => None,
+        };
 
         Genome {
             activator,
             weights,
             bias,
+            gates,
         }
     }
 }
@@ -243,6 +577,7 @@ mod tests {
     fn test_serialize() {
         let activator = activator::Genome {
             activator: activator::Gene::Linear,
+            parameter: None,
         };
         let weights = vec![0.0, 1.0, 2.0];
         let bias = 3.0;
@@ -250,6 +585,7 @@ mod tests {
             activator: activator.clone(),
             weights: weights.clone(),
             bias,
+            gates: None,
         };
         let serialized = serde_json::to_string(&genome).unwrap();
         let expected = r#"{"activator":{"activator":"Linear"},"weights":[0.0,1.0,2.0],"bias":3.0}"#;
@@ -261,6 +597,7 @@ mod tests {
     fn test_deserialize() {
         let activator = activator::Genome {
             activator: activator::Gene::Linear,
+            parameter: None,
         };
         let weights = vec![0.0, 1.0, 2.0];
         let bias = 3.0;
@@ -268,6 +605,7 @@ mod tests {
             activator: activator.clone(),
             weights: weights.clone(),
             bias,
+            gates: None,
         };
         let serialized = r#"
             {
@@ -286,4 +624,82 @@ mod tests {
 
         assert_eq!(deserialized, genome);
     }
+
+    #[test]
+    fn test_lstm_round_trip() {
+        let gate = |bias| Gate {
+            weights: vec![0.5, -0.5],
+            recurrent: 0.25,
+            bias,
+        };
+        let genome = Genome {
+            activator: activator::Genome {
+                activator: activator::Gene::Tanh,
+                parameter: None,
+            },
+            weights: vec![1.0, 2.0],
+            bias: 0.1,
+            gates: Some(Gates {
+                recurrent: -0.75,
+                input: gate(0.0),
+                forget: gate(1.0),
+                output: gate(-1.0),
+            }),
+        };
+        let neuron = genome.create();
+
+        assert!(matches!(neuron, Neuron::Lstm(_)));
+        assert_eq!(neuron.genome(), genome);
+
+        let basic = Genome {
+            gates: None,
+            ..genome.clone()
+        };
+        let delta = genome.diff(&basic).unwrap();
+        assert_eq!(Genome::patch(&basic, &delta), genome);
+
+        let delta = basic.diff(&genome).unwrap();
+        let json = serde_json::to_string(&delta).unwrap();
+        let delta: Delta = serde_json::from_str(&json).unwrap();
+        assert_eq!(Genome::patch(&genome, &delta), basic);
+    }
+
+    #[test]
+    fn test_lstm_serialize_binary() {
+        let gate = |bias| Gate {
+            weights: vec![0.5],
+            recurrent: 0.25,
+            bias,
+        };
+        let genome = Genome {
+            activator: activator::Genome {
+                activator: activator::Gene::Tanh,
+                parameter: None,
+            },
+            weights: vec![1.0],
+            bias: 0.1,
+            gates: Some(Gates {
+                recurrent: -0.75,
+                input: gate(0.0),
+                forget: gate(1.0),
+                output: gate(-1.0),
+            }),
+        };
+        let basic = Genome {
+            gates: None,
+            ..genome.clone()
+        };
+
+        for (target, base) in [(&genome, &basic), (&basic, &genome)] {
+            let serialized = bincode::serialize(target).unwrap();
+            let deserialized: Genome = bincode::deserialize(&serialized).unwrap();
+            assert_eq!(&deserialized, target);
+
+            let delta = target.diff(base).unwrap();
+            let serialized = bincode::serialize(&delta).unwrap();
+            let deserialized: Delta = bincode::deserialize(&serialized).unwrap();
+            assert_eq!(deserialized, delta);
+            assert_eq!(&Genome::patch(base, &deserialized), target);
+        }
+    }
 }
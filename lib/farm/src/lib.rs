@@ -8,10 +8,17 @@
 )]
 
 pub mod breed;
+pub mod checkpoint;
+pub mod ensemble;
+mod error;
 pub mod genome;
 pub mod mutate;
+pub mod organism;
+pub mod preprocess;
 pub mod stock;
 
+pub use crate::error::{Error, Result};
+
 use crate::genome::{Create, Extract};
 use crate::stock::Stock;
 use evo::Breed;
@@ -60,7 +67,7 @@ pub fn run() {
     let genome = network.genome();
     println!("{genome:?}");
 
-    println!("{network:?}");
+    println!("{network}");
     let output = network.activate(&[1.0, 2.0, 3.0, 4.0]);
     println!("{output:?}");
 }
@@ -1,4 +1,6 @@
-use rand::{thread_rng, Rng};
+use crate::{Error, Result};
+use evo::{rng, Schedule};
+use rand::Rng;
 
 /// A struct that manages the chances for mutating a genome.
 ///
@@ -11,11 +13,17 @@ use rand::{thread_rng, Rng};
 /// ```
 #[derive(Clone, Copy)]
 pub struct Mutator {
-    /// The chance to mutate a genome.
-    mutation_rate: f64,
+    /// The chance to mutate a genome, in each generation.
+    mutation_rate: Schedule,
 
-    /// The degree of mutation.
-    mutation_size: f64,
+    /// The degree of mutation, in each generation.
+    mutation_size: Schedule,
+
+    /// The generation the schedules are read at.
+    generation: usize,
+
+    /// The factor the mutation rate is scaled by.
+    pressure: f64,
 }
 
 impl Mutator {
@@ -50,7 +58,8 @@ impl Mutator {
     /// ```
     #[must_use]
     pub fn check_mutate(&self) -> bool {
-        thread_rng().gen_range(0.0..1.0) < self.mutation_rate
+        let rate = self.mutation_rate.value(self.generation) * self.pressure;
+        rng::handle().gen_range(0.0..1.0) < rate.min(1.0)
     }
 
     /// Get the degree of mutation.
@@ -70,7 +79,65 @@ impl Mutator {
     /// ```
     #[must_use]
     pub fn mutation_size(&self) -> f64 {
-        self.mutation_size * thread_rng().gen_range(-1.0..1.0)
+        self.mutation_size.value(self.generation) * rng::handle().gen_range(-1.0..1.0)
+    }
+
+    /// Get a copy of the mutator with its mutation rate scaled, e.g. by the
+    /// mutation pressure of adaptive mutation.
+    ///
+    /// # Arguments
+    ///
+    /// - `pressure` - The factor to scale the mutation rate by.
+    ///
+    /// # Returns
+    ///
+    /// The mutator, whose mutation rate is at most 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use farm::mutate::Mutator;
+    ///
+    /// let mutator = Mutator::builder().mutation_rate(0.5).build();
+    ///
+    /// let mutator = mutator.with_pressure(4.0);
+    /// assert!(mutator.check_mutate());
+    /// ```
+    #[must_use]
+    pub fn with_pressure(self, pressure: f64) -> Self {
+        Self { pressure, ..self }
+    }
+
+    /// Get a copy of the mutator that reads its schedules at a generation.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation` - The number of generations run before this one.
+    ///
+    /// # Returns
+    ///
+    /// The mutator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::Schedule;
+    /// use farm::mutate::Mutator;
+    ///
+    /// let mutator = Mutator::builder()
+    ///     .mutation_rate_schedule(Schedule::Step {
+    ///         start: 1.0,
+    ///         factor: 0.0,
+    ///         every: 100,
+    ///     })
+    ///     .build();
+    ///
+    /// assert!(mutator.at_generation(99).check_mutate());
+    /// assert!(!mutator.at_generation(100).check_mutate());
+    /// ```
+    #[must_use]
+    pub fn at_generation(self, generation: usize) -> Self {
+        Self { generation, ..self }
     }
 
     /// Mutate a target.
@@ -111,15 +178,15 @@ impl Mutator {
 /// let mutator = Builder::default().build();
 /// ```
 pub struct Builder {
-    mutation_rate: f64,
-    mutation_size: f64,
+    mutation_rate: Schedule,
+    mutation_size: Schedule,
 }
 
 impl Default for Builder {
     fn default() -> Self {
         Self {
-            mutation_rate: 0.15,
-            mutation_size: 0.15,
+            mutation_rate: Schedule::Constant(0.15),
+            mutation_size: Schedule::Constant(0.15),
         }
     }
 }
@@ -144,7 +211,34 @@ impl Builder {
     /// ```
     #[must_use]
     pub fn mutation_rate(mut self, mutation_rate: f64) -> Self {
-        self.mutation_rate = mutation_rate;
+        self.mutation_rate = Schedule::Constant(mutation_rate);
+        self
+    }
+
+    /// Set the mutation rate in each generation, e.g. to mutate less as the
+    /// run settles.
+    ///
+    /// # Arguments
+    ///
+    /// - `schedule` - The mutation rate schedule, between 0.0 and 1.0.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::Schedule;
+    /// use farm::mutate::Builder;
+    ///
+    /// let mutator = Builder::default()
+    ///     .mutation_rate_schedule(Schedule::Exponential { start: 0.5, rate: 0.999 })
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn mutation_rate_schedule(mut self, schedule: Schedule) -> Self {
+        self.mutation_rate = schedule;
         self
     }
 
@@ -167,7 +261,38 @@ impl Builder {
     /// ```
     #[must_use]
     pub fn mutation_size(mut self, mutation_size: f64) -> Self {
-        self.mutation_size = mutation_size;
+        self.mutation_size = Schedule::Constant(mutation_size);
+        self
+    }
+
+    /// Set the mutation size in each generation, e.g. to fine-tune with
+    /// smaller mutations as the run settles.
+    ///
+    /// # Arguments
+    ///
+    /// - `schedule` - The mutation size schedule.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evo::Schedule;
+    /// use farm::mutate::Builder;
+    ///
+    /// let mutator = Builder::default()
+    ///     .mutation_size_schedule(Schedule::Linear {
+    ///         start: 0.5,
+    ///         end: 0.01,
+    ///         generations: 10_000,
+    ///     })
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn mutation_size_schedule(mut self, schedule: Schedule) -> Self {
+        self.mutation_size = schedule;
         self
     }
 
@@ -189,6 +314,44 @@ impl Builder {
         Mutator {
             mutation_rate: self.mutation_rate,
             mutation_size: self.mutation_size,
+            generation: 0,
+            pressure: 1.0,
+        }
+    }
+
+    /// Build the mutator, checking its settings.
+    ///
+    /// # Returns
+    ///
+    /// The new mutator.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::MutationRate`] if the rate is not between 0.0 and 1.0.
+    /// - [`Error::MutationSize`] if the size is negative or not finite.
+    ///
+    /// Schedules are checked at their first generation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use farm::{mutate::Builder, Error};
+    ///
+    /// assert!(Builder::default().try_build().is_ok());
+    /// assert_eq!(
+    ///     Builder::default().mutation_rate(1.5).try_build().err(),
+    ///     Some(Error::MutationRate(1.5))
+    /// );
+    /// ```
+    pub fn try_build(self) -> Result<Mutator> {
+        let mutation_rate = self.mutation_rate.value(0);
+        if !(0.0..=1.0).contains(&mutation_rate) {
+            return Err(Error::MutationRate(mutation_rate));
+        }
+        let mutation_size = self.mutation_size.value(0);
+        if !mutation_size.is_finite() || mutation_size < 0.0 {
+            return Err(Error::MutationSize(mutation_size));
         }
+        Ok(self.build())
     }
 }
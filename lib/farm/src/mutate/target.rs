@@ -1,4 +1,6 @@
-use rand::{thread_rng, Rng};
+use crate::{Error, Result};
+use evo::rng;
+use rand::Rng;
 
 /// Trait for types that can be mutated.
 ///
@@ -41,7 +43,7 @@ pub trait Target {
 impl Target for f64 {
     fn mutate(mut self, mutator: &super::Mutator) -> Self {
         if mutator.mutation_size() > 0.0 && mutator.check_mutate() {
-            self += rand::random::<f64>() * mutator.mutation_size();
+            self += rng::handle().gen::<f64>() * mutator.mutation_size();
         }
 
         self
@@ -127,8 +129,12 @@ impl<T> VecMutation<T> {
     ///
     /// let mutation = VecMutation::new(10, || 0);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `len` is 0; use [`VecMutation::try_new`] to check it.
     pub fn new(len: usize, factory: impl Fn() -> T) -> Self {
-        let mut rng = thread_rng();
+        let mut rng = rng::handle();
         match rng.gen_range(0..5) {
             0 => Self::Insert(rng.gen_range(0..len), factory()),
             1 => Self::Replace(rng.gen_range(0..len), factory()),
@@ -138,6 +144,36 @@ impl<T> VecMutation<T> {
         }
     }
 
+    /// Generate a random mutation for a vector that may be empty.
+    ///
+    /// # Arguments
+    ///
+    /// - `len` - The length of the vector.
+    /// - `factory` - A factory function to create new elements.
+    ///
+    /// # Returns
+    ///
+    /// The random mutation.
+    ///
+    /// # Errors
+    ///
+    /// If `len` is 0, since there is no index to mutate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use farm::{mutate::VecMutation, Error};
+    ///
+    /// assert!(VecMutation::try_new(10, || 0).is_ok());
+    /// assert!(matches!(VecMutation::try_new(0, || 0), Err(Error::EmptyVec)));
+    /// ```
+    pub fn try_new(len: usize, factory: impl Fn() -> T) -> Result<Self> {
+        if len == 0 {
+            return Err(Error::EmptyVec);
+        }
+        Ok(Self::new(len, factory))
+    }
+
     /// Apply the mutation to a vector.
     ///
     /// # Arguments
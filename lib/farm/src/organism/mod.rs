@@ -0,0 +1,168 @@
+use crate::genome::{network, Create, Crossover};
+use crate::mutate::{Mutator, Target};
+use evo::Predict;
+use nnet::{ActivationBuffers, Network};
+
+/// A network genome paired with the network it creates, so that it can be
+/// evaluated by the evolutionary algorithm.
+///
+/// # Examples
+///
+/// ```
+/// use evo::Predict;
+/// use farm::genome::{activator, layer, network, neuron};
+/// use farm::organism::Organism;
+///
+/// let neuron = neuron::Genome {
+///     activator: activator::Genome { activator: activator::Gene::Linear, parameter: None },
+///     weights: vec![2.0],
+///     bias: 1.0,
+///     gates: None,
+/// };
+/// let genome = network::Genome {
+///     layers: vec![layer::Genome { neurons: vec![neuron] }],
+///     frozen: vec![],
+///     sources: vec![],
+/// };
+///
+/// let organism = Organism::from(genome);
+///
+/// assert_eq!(organism.predict(&[3.0]), vec![7.0]);
+/// ```
+#[derive(Debug)]
+pub struct Organism {
+    genome: network::Genome,
+    network: Network,
+}
+
+impl Organism {
+    /// Get the genome.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the genome.
+    #[must_use]
+    pub fn genome(&self) -> &network::Genome {
+        &self.genome
+    }
+
+    /// Get the network created from the genome.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the network.
+    #[must_use]
+    pub fn network(&self) -> &Network {
+        &self.network
+    }
+
+    /// Discard the network.
+    ///
+    /// # Returns
+    ///
+    /// The genome.
+    #[must_use]
+    pub fn into_genome(self) -> network::Genome {
+        self.genome
+    }
+}
+
+impl From<network::Genome> for Organism {
+    fn from(genome: network::Genome) -> Self {
+        let network = genome.create();
+        Self { genome, network }
+    }
+}
+
+impl Clone for Organism {
+    fn clone(&self) -> Self {
+        Self::from(self.genome.clone())
+    }
+}
+
+thread_local! {
+    /// Buffers shared by every prediction on a thread, so scoring a
+    /// population only allocates the returned outputs.
+    static SCRATCH: std::cell::RefCell<ActivationBuffers> = std::cell::RefCell::default();
+}
+
+impl Predict for Organism {
+    fn predict(&self, input: &[f64]) -> Vec<f64> {
+        SCRATCH.with_borrow_mut(|scratch| self.network.activate_into(input, scratch).to_vec())
+    }
+}
+
+impl PartialEq for Organism {
+    fn eq(&self, other: &Self) -> bool {
+        self.genome == other.genome
+    }
+}
+
+/// Organisms have no natural order; they are ranked by fitness instead.
+impl PartialOrd for Organism {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        (self == other).then_some(std::cmp::Ordering::Equal)
+    }
+}
+
+impl Crossover for Organism {
+    fn crossover(&self, other: &Self) -> Self {
+        Self::from(self.genome.crossover(&other.genome))
+    }
+
+    fn crossover_into(&self, other: &Self, offspring: &mut Self) {
+        self.genome
+            .crossover_into(&other.genome, &mut offspring.genome);
+        offspring.network = offspring.genome.create();
+    }
+}
+
+impl Target for Organism {
+    fn mutate(self, mutator: &Mutator) -> Self {
+        Self::from(self.genome.mutate(mutator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::breed::Breeder;
+    use crate::genome::{activator, layer, neuron};
+    use evo::Breed;
+
+    fn organism(weight: f64) -> Organism {
+        let neuron = neuron::Genome {
+            activator: activator::Genome {
+                activator: activator::Gene::Linear,
+                parameter: None,
+            },
+            weights: vec![weight, weight],
+            bias: 0.0,
+            gates: None,
+        };
+        Organism::from(network::Genome {
+            layers: vec![layer::Genome {
+                neurons: vec![neuron],
+            }],
+            frozen: vec![],
+            sources: vec![],
+        })
+    }
+
+    #[test]
+    fn test_breed_keeps_network_in_sync() {
+        let breeder = Breeder::new(Mutator::builder().mutation_rate(1.0).build()).to_manager();
+        let child = breeder.breed(&organism(1.0), &organism(2.0));
+
+        assert_eq!(child.network(), &child.genome().create());
+    }
+
+    #[test]
+    fn test_partial_cmp() {
+        assert_eq!(
+            organism(1.0).partial_cmp(&organism(1.0)),
+            Some(std::cmp::Ordering::Equal)
+        );
+        assert_eq!(organism(1.0).partial_cmp(&organism(2.0)), None);
+    }
+}
@@ -0,0 +1,481 @@
+use crate::{Error, Result};
+use evo::{Predict, TrainingRecord};
+use nnet::Network;
+use serde::{Deserialize, Serialize};
+
+/// A preprocessing step that has been fitted to training data.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Transform {
+    /// Center each column on its mean and divide it by its standard deviation.
+    StandardScaler {
+        means: Vec<f64>,
+        deviations: Vec<f64>,
+    },
+
+    /// Scale each column so its training values span `0.0..=1.0`.
+    MinMaxScaler {
+        minimums: Vec<f64>,
+        maximums: Vec<f64>,
+    },
+
+    /// Replace a categorical column with one column per category, set to 1.0
+    /// for the row's category and 0.0 for the rest. Values that were not seen
+    /// during fitting are all zeros.
+    OneHot { column: usize, categories: Vec<f64> },
+
+    /// Keep only these columns, in this order.
+    Select { columns: Vec<usize> },
+}
+
+impl Transform {
+    /// Apply the step to a row.
+    ///
+    /// Columns beyond those seen during fitting are passed through unscaled.
+    ///
+    /// # Arguments
+    ///
+    /// - `row` - The row to transform.
+    ///
+    /// # Returns
+    ///
+    /// The transformed row.
+    #[must_use]
+    pub fn apply(&self, row: &[f64]) -> Vec<f64> {
+        match self {
+            Self::StandardScaler { means, deviations } => row
+                .iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    let mean = means.get(index).copied().unwrap_or(0.0);
+                    let deviation = deviations.get(index).copied().unwrap_or(1.0);
+                    (value - mean) / if deviation > 0.0 { deviation } else { 1.0 }
+                })
+                .collect(),
+            Self::MinMaxScaler { minimums, maximums } => row
+                .iter()
+                .enumerate()
+                .map(
+                    |(index, value)| match (minimums.get(index), maximums.get(index)) {
+                        (Some(min), Some(max)) if max > min => (value - min) / (max - min),
+                        (Some(_), Some(_)) => 0.0,
+                        _ => *value,
+                    },
+                )
+                .collect(),
+            Self::OneHot { column, categories } => {
+                let mut row = row.to_vec();
+                let value = row.remove(*column);
+                let encoded = categories.iter().map(|category| {
+                    if category.total_cmp(&value).is_eq() {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                });
+                row.splice(*column..*column, encoded);
+                row
+            }
+            Self::Select { columns } => columns.iter().map(|column| row[*column]).collect(),
+        }
+    }
+}
+
+/// A preprocessing step before it is fitted.
+#[derive(Clone, Debug)]
+enum Step {
+    StandardScaler,
+    MinMaxScaler,
+    OneHot(usize),
+    Select(Vec<usize>),
+}
+
+impl Step {
+    /// Fit the step to training data.
+    ///
+    /// # Arguments
+    ///
+    /// - `rows` - The training inputs, as transformed by the earlier steps.
+    ///
+    /// # Returns
+    ///
+    /// The fitted step.
+    ///
+    /// # Errors
+    ///
+    /// If the step uses a column the rows do not have. The error's `step` is 0.
+    fn fit(self, rows: &[Vec<f64>]) -> Result<Transform> {
+        let width = rows.first().map_or(0, Vec::len);
+        let column = |index: usize| -> Result<Vec<f64>> {
+            if index >= width {
+                return Err(Error::Column {
+                    step: 0,
+                    column: index,
+                    width,
+                });
+            }
+            Ok(rows.iter().map(|row| row[index]).collect())
+        };
+
+        match self {
+            Self::StandardScaler => {
+                let (means, deviations) = (0..width)
+                    .map(|index| {
+                        let values = column(index)?;
+                        #[allow(clippy::cast_precision_loss)]
+                        let count = values.len() as f64;
+                        let mean = values.iter().sum::<f64>() / count;
+                        let variance = values
+                            .iter()
+                            .map(|value| (value - mean).powi(2))
+                            .sum::<f64>()
+                            / count;
+                        Ok((mean, variance.sqrt()))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .unzip();
+                Ok(Transform::StandardScaler { means, deviations })
+            }
+            Self::MinMaxScaler => {
+                let (minimums, maximums) = (0..width)
+                    .map(|index| {
+                        let values = column(index)?;
+                        Ok(values
+                            .iter()
+                            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                                (min.min(*value), max.max(*value))
+                            }))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .unzip();
+                Ok(Transform::MinMaxScaler { minimums, maximums })
+            }
+            Self::OneHot(index) => {
+                let mut categories = column(index)?;
+                categories.sort_by(f64::total_cmp);
+                categories.dedup();
+                Ok(Transform::OneHot {
+                    column: index,
+                    categories,
+                })
+            }
+            Self::Select(columns) => {
+                for index in &columns {
+                    column(*index)?;
+                }
+                Ok(Transform::Select { columns })
+            }
+        }
+    }
+}
+
+/// A sequence of preprocessing steps fitted to training inputs.
+///
+/// Fit the pipeline once on the training data, evolve against the transformed
+/// records, and save it with the best network as a [`Model`] so predictions
+/// use the same transforms.
+///
+/// # Examples
+///
+/// ```
+/// use farm::preprocess::Pipeline;
+///
+/// let rows = vec![vec![0.0, 10.0, 1.0], vec![5.0, 20.0, 2.0], vec![10.0, 30.0, 1.0]];
+/// let pipeline = Pipeline::builder()
+///     .select(vec![0, 2])
+///     .one_hot(1)
+///     .min_max_scaler()
+///     .fit(&rows)
+///     .unwrap();
+///
+/// assert_eq!(pipeline.apply(&[5.0, 99.0, 2.0]), vec![0.5, 0.0, 1.0]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pipeline {
+    transforms: Vec<Transform>,
+}
+
+impl Pipeline {
+    /// Create a new builder.
+    ///
+    /// # Returns
+    ///
+    /// A new builder.
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Get the fitted steps.
+    ///
+    /// # Returns
+    ///
+    /// The steps, in the order they are applied.
+    #[must_use]
+    pub fn transforms(&self) -> &[Transform] {
+        &self.transforms
+    }
+
+    /// Apply every step to a row of inputs.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` - The inputs.
+    ///
+    /// # Returns
+    ///
+    /// The transformed inputs.
+    #[must_use]
+    pub fn apply(&self, input: &[f64]) -> Vec<f64> {
+        self.transforms
+            .iter()
+            .fold(input.to_vec(), |row, transform| transform.apply(&row))
+    }
+
+    /// Apply every step to the inputs of training records. The expected
+    /// outputs and weights are unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// - `records` - The training records.
+    ///
+    /// # Returns
+    ///
+    /// The transformed records.
+    #[must_use]
+    pub fn apply_records(&self, records: Vec<TrainingRecord>) -> Vec<TrainingRecord> {
+        records
+            .into_iter()
+            .map(|record| TrainingRecord {
+                input: self.apply(&record.input),
+                output: record.output,
+                weight: record.weight,
+            })
+            .collect()
+    }
+}
+
+/// A builder for a preprocessing pipeline.
+#[derive(Default)]
+pub struct Builder {
+    steps: Vec<Step>,
+}
+
+impl Builder {
+    /// Standardize every column to a mean of 0 and a standard deviation of 1.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn standard_scaler(mut self) -> Self {
+        self.steps.push(Step::StandardScaler);
+        self
+    }
+
+    /// Scale every column to `0.0..=1.0` over the training data.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn min_max_scaler(mut self) -> Self {
+        self.steps.push(Step::MinMaxScaler);
+        self
+    }
+
+    /// One-hot encode a categorical column.
+    ///
+    /// # Arguments
+    ///
+    /// - `column` - The index of the column, after the earlier steps.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn one_hot(mut self, column: usize) -> Self {
+        self.steps.push(Step::OneHot(column));
+        self
+    }
+
+    /// Keep only some columns.
+    ///
+    /// # Arguments
+    ///
+    /// - `columns` - The indexes of the columns to keep, after the earlier
+    ///   steps, in the order they should appear.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn select(mut self, columns: Vec<usize>) -> Self {
+        self.steps.push(Step::Select(columns));
+        self
+    }
+
+    /// Fit each step, in order, to the output of the steps before it.
+    ///
+    /// # Arguments
+    ///
+    /// - `rows` - The training inputs.
+    ///
+    /// # Returns
+    ///
+    /// The fitted pipeline.
+    ///
+    /// # Errors
+    ///
+    /// If a step uses a column that its input rows do not have.
+    pub fn fit(self, rows: &[Vec<f64>]) -> Result<Pipeline> {
+        let mut rows = rows.to_vec();
+        let mut transforms = vec![];
+        for (index, step) in self.steps.into_iter().enumerate() {
+            let transform = step.fit(&rows).map_err(|error| match error {
+                Error::Column { column, width, .. } => Error::Column {
+                    step: index,
+                    column,
+                    width,
+                },
+                error => error,
+            })?;
+            rows = rows.iter().map(|row| transform.apply(row)).collect();
+            transforms.push(transform);
+        }
+
+        Ok(Pipeline { transforms })
+    }
+}
+
+/// A network together with the pipeline that prepares its inputs.
+///
+/// # Examples
+///
+/// ```
+/// use evo::Predict;
+/// use farm::preprocess::{Model, Pipeline};
+/// use nnet::{ActivationFunction, BasicNeuron, Layer, Network};
+///
+/// let pipeline = Pipeline::builder()
+///     .min_max_scaler()
+///     .fit(&[vec![0.0], vec![10.0]])
+///     .unwrap();
+/// let neuron = BasicNeuron::builder()
+///     .weights(vec![2.0])
+///     .bias(0.0)
+///     .activation(ActivationFunction::linear())
+///     .build();
+/// let network = Network::builder()
+///     .add_layer(Layer::builder().add_neuron(neuron).build())
+///     .build();
+/// let model = Model { pipeline, network };
+///
+/// let json = model.to_json().unwrap();
+/// let model = Model::parse_json(&json).unwrap();
+///
+/// assert_eq!(model.predict(&[5.0]), vec![1.0]);
+/// ```
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Model {
+    /// The transforms applied to every input.
+    pub pipeline: Pipeline,
+
+    /// The network that runs on the transformed inputs.
+    pub network: Network,
+}
+
+impl Model {
+    /// Serialize the model to a JSON string.
+    ///
+    /// # Returns
+    ///
+    /// The serialized model.
+    ///
+    /// # Errors
+    ///
+    /// If the model cannot be serialized.
+    pub fn to_json(&self) -> std::result::Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Parse a model from a JSON string.
+    ///
+    /// # Arguments
+    ///
+    /// - `json` - The JSON string to parse.
+    ///
+    /// # Returns
+    ///
+    /// The parsed model.
+    ///
+    /// # Errors
+    ///
+    /// If the model cannot be parsed.
+    pub fn parse_json(json: &str) -> std::result::Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl Predict for Model {
+    fn predict(&self, input: &[f64]) -> Vec<f64> {
+        self.network.activate(&self.pipeline.apply(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<Vec<f64>> {
+        vec![vec![1.0, 3.0], vec![3.0, 3.0], vec![5.0, 6.0]]
+    }
+
+    #[test]
+    fn test_standard_scaler() {
+        let pipeline = Pipeline::builder().standard_scaler().fit(&rows()).unwrap();
+
+        let scaled = pipeline.apply(&[3.0, 4.0]);
+        assert!(scaled[0].abs() < f64::EPSILON);
+        assert!(scaled[1].abs() < f64::EPSILON);
+        assert_eq!(pipeline.apply(&[3.0, 4.0, 7.0])[2..], [7.0]);
+    }
+
+    #[test]
+    fn test_one_hot() {
+        let pipeline = Pipeline::builder().one_hot(1).fit(&rows()).unwrap();
+
+        assert_eq!(pipeline.apply(&[2.0, 6.0]), vec![2.0, 0.0, 1.0]);
+        assert_eq!(pipeline.apply(&[2.0, 9.0]), vec![2.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_fit_rejects_missing_column() {
+        let result = Pipeline::builder().select(vec![0]).one_hot(1).fit(&rows());
+
+        assert_eq!(
+            result,
+            Err(Error::Column {
+                step: 1,
+                column: 1,
+                width: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        let pipeline = Pipeline::builder()
+            .min_max_scaler()
+            .select(vec![1, 0])
+            .fit(&rows())
+            .unwrap();
+
+        let json = serde_json::to_string(&pipeline).unwrap();
+
+        assert_eq!(serde_json::from_str::<Pipeline>(&json).unwrap(), pipeline);
+        assert_eq!(pipeline.apply(&[5.0, 3.0]), vec![0.0, 1.0]);
+    }
+}
@@ -1,4 +1,6 @@
 use crate::genome::Generate;
+use crate::{Error, Result};
+use evo::Generation;
 pub use evo::Stock;
 
 /// A stocker for the given genome using the given config.
@@ -13,12 +15,12 @@ pub use evo::Stock;
 /// let genome = stocker.generate();
 /// let generation = stocker.stock(3);
 /// let expected = vec![
-///     Genome { activator: Gene::Linear },
-///     Genome { activator: Gene::Linear },
-///     Genome { activator: Gene::Linear },
+///     Genome { activator: Gene::Linear, parameter: None },
+///     Genome { activator: Gene::Linear, parameter: None },
+///     Genome { activator: Gene::Linear, parameter: None },
 /// ];
 ///
-/// assert_eq!(genome, Genome { activator: Gene::Linear });
+/// assert_eq!(genome, Genome { activator: Gene::Linear, parameter: None });
 /// assert_eq!(generation, expected);
 /// ```
 pub struct Stocker<TConfig, TGenome>
@@ -39,6 +41,39 @@ where
             _phantom: std::marker::PhantomData,
         }
     }
+
+    /// Stock a generation, rejecting an empty one.
+    ///
+    /// # Arguments
+    ///
+    /// - `generation_size` - The number of genomes to generate.
+    ///
+    /// # Returns
+    ///
+    /// The generation.
+    ///
+    /// # Errors
+    ///
+    /// If `generation_size` is 0, since an empty generation cannot evolve.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use farm::genome::activator::{Gene, Genome};
+    /// use farm::stock::Stocker;
+    /// use farm::Error;
+    ///
+    /// let stocker = Stocker::<_, Genome>::new(|| Gene::Linear);
+    ///
+    /// assert_eq!(stocker.try_stock(2).unwrap().len(), 2);
+    /// assert_eq!(stocker.try_stock(0), Err(Error::EmptyGeneration));
+    /// ```
+    pub fn try_stock(&self, generation_size: usize) -> Result<Generation<TGenome>> {
+        if generation_size == 0 {
+            return Err(Error::EmptyGeneration);
+        }
+        Ok(self.stock(generation_size))
+    }
 }
 
 impl<TConfig, TGenome> Stock<TGenome> for Stocker<TConfig, TGenome>
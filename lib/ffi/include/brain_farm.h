@@ -0,0 +1,80 @@
+/*
+ * C interface for running networks evolved by Brain Farm.
+ *
+ * Link against the `brain_farm` shared or static library built from
+ * `lib/ffi`. Every function accepts null pointers and reports them instead of
+ * crashing.
+ */
+#ifndef BRAIN_FARM_H
+#define BRAIN_FARM_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The call succeeded. */
+#define BF_OK 0
+
+/* A required pointer was null. */
+#define BF_ERROR_NULL -1
+
+/* The number of inputs does not match the network. */
+#define BF_ERROR_INPUTS -2
+
+/* The output buffer is too small for the network. */
+#define BF_ERROR_OUTPUTS -3
+
+/* The network failed while activating. */
+#define BF_ERROR_ACTIVATE -4
+
+/* A loaded network. */
+typedef struct BfNetwork BfNetwork;
+
+/*
+ * Load a network from a null-terminated JSON string, as written by
+ * `brain-farm`. Returns null if the JSON is not a valid network. Release the
+ * network with `bf_network_free`.
+ */
+BfNetwork *bf_network_load_json(const char *json);
+
+/* Get the number of inputs a network expects, or 0 if it is null. */
+size_t bf_network_inputs(const BfNetwork *network);
+
+/* Get the number of outputs a network produces, or 0 if it is null. */
+size_t bf_network_outputs(const BfNetwork *network);
+
+/*
+ * Run a network. `input_len` must equal `bf_network_inputs`, and `output_len`
+ * must be at least `bf_network_outputs`. Returns `BF_OK` or a `BF_ERROR_*`
+ * code; `outputs` is only written on success.
+ */
+int32_t bf_network_activate(
+    const BfNetwork *network,
+    const double *inputs,
+    size_t input_len,
+    double *outputs,
+    size_t output_len);
+
+/* Release a network. Does nothing if it is null. */
+void bf_network_free(BfNetwork *network);
+
+/* The same functions under the `nnet_` prefix. */
+BfNetwork *nnet_load_json(const char *json);
+
+int32_t nnet_activate(
+    const BfNetwork *network,
+    const double *inputs,
+    size_t input_len,
+    double *outputs,
+    size_t output_len);
+
+void nnet_free(BfNetwork *network);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* BRAIN_FARM_H */
@@ -0,0 +1,350 @@
+#![deny(
+    clippy::complexity,
+    clippy::correctness,
+    clippy::perf,
+    clippy::style,
+    clippy::suspicious,
+    clippy::pedantic
+)]
+
+//! C bindings for running evolved networks.
+//!
+//! Networks are opaque `BfNetwork` pointers owned by the caller: load one with
+//! `bf_network_load_json`, run it with `bf_network_activate`, and release it
+//! with `bf_network_free`. The declarations are in `include/brain_farm.h`.
+//! `nnet_load_json`, `nnet_activate`, and `nnet_free` are the same functions
+//! under the library's `nnet_` prefix.
+//!
+//! With the `python` feature, the library is also the `brain_farm` Python
+//! module.
+
+#[cfg(feature = "python")]
+mod python;
+
+use nnet::Network;
+use std::ffi::{c_char, CStr};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// The call succeeded.
+pub const BF_OK: i32 = 0;
+
+/// A required pointer was null.
+pub const BF_ERROR_NULL: i32 = -1;
+
+/// The number of inputs does not match the network.
+pub const BF_ERROR_INPUTS: i32 = -2;
+
+/// The output buffer is too small for the network.
+pub const BF_ERROR_OUTPUTS: i32 = -3;
+
+/// The network failed while activating.
+pub const BF_ERROR_ACTIVATE: i32 = -4;
+
+/// A network loaded through the C interface.
+pub struct BfNetwork {
+    network: Network,
+    inputs: usize,
+    outputs: usize,
+}
+
+impl BfNetwork {
+    fn new(network: Network) -> Self {
+        let inputs = network.input_size().unwrap_or(0);
+        let outputs = network.output_size().unwrap_or(0);
+
+        Self {
+            network,
+            inputs,
+            outputs,
+        }
+    }
+}
+
+/// Load a network from a null-terminated JSON string.
+///
+/// # Arguments
+///
+/// - `json` - The serialized network, as written by `brain-farm`.
+///
+/// # Returns
+///
+/// The network, or null if `json` is null or not a valid network. Release it
+/// with `bf_network_free`.
+///
+/// # Safety
+///
+/// `json` must be null or point to a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn bf_network_load_json(json: *const c_char) -> *mut BfNetwork {
+    if json.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let Ok(json) = CStr::from_ptr(json).to_str() else {
+        return std::ptr::null_mut();
+    };
+    match Network::parse_json(json) {
+        Ok(network) => Box::into_raw(Box::new(BfNetwork::new(network))),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Get the number of inputs a network expects.
+///
+/// # Arguments
+///
+/// - `network` - The network.
+///
+/// # Returns
+///
+/// The number of inputs, or 0 if `network` is null.
+///
+/// # Safety
+///
+/// `network` must be null or a network from `bf_network_load_json` that has
+/// not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn bf_network_inputs(network: *const BfNetwork) -> usize {
+    network.as_ref().map_or(0, |network| network.inputs)
+}
+
+/// Get the number of outputs a network produces.
+///
+/// # Arguments
+///
+/// - `network` - The network.
+///
+/// # Returns
+///
+/// The number of outputs, or 0 if `network` is null.
+///
+/// # Safety
+///
+/// `network` must be null or a network from `bf_network_load_json` that has
+/// not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn bf_network_outputs(network: *const BfNetwork) -> usize {
+    network.as_ref().map_or(0, |network| network.outputs)
+}
+
+/// Run a network on a set of inputs.
+///
+/// # Arguments
+///
+/// - `network` - The network.
+/// - `inputs` - The input values.
+/// - `input_len` - The number of input values; must match `bf_network_inputs`.
+/// - `outputs` - The buffer the output values are written to.
+/// - `output_len` - The size of `outputs`; must be at least `bf_network_outputs`.
+///
+/// # Returns
+///
+/// `BF_OK`, or one of the `BF_ERROR_*` codes. Nothing is written to `outputs`
+/// unless the call succeeds.
+///
+/// # Safety
+///
+/// `network` must be null or a network from `bf_network_load_json` that has
+/// not been freed, `inputs` must be null or point to `input_len` values, and
+/// `outputs` must be null or point to `output_len` writable values.
+#[no_mangle]
+pub unsafe extern "C" fn bf_network_activate(
+    network: *const BfNetwork,
+    inputs: *const f64,
+    input_len: usize,
+    outputs: *mut f64,
+    output_len: usize,
+) -> i32 {
+    let Some(network) = network.as_ref() else {
+        return BF_ERROR_NULL;
+    };
+    if inputs.is_null() || outputs.is_null() {
+        return BF_ERROR_NULL;
+    }
+    if input_len != network.inputs {
+        return BF_ERROR_INPUTS;
+    }
+    if output_len < network.outputs {
+        return BF_ERROR_OUTPUTS;
+    }
+
+    let inputs = std::slice::from_raw_parts(inputs, input_len);
+    // Unwinding into C is undefined behavior, so a panic becomes an error code.
+    let Ok(values) = catch_unwind(AssertUnwindSafe(|| network.network.activate(inputs))) else {
+        return BF_ERROR_ACTIVATE;
+    };
+    if values.len() > output_len {
+        return BF_ERROR_OUTPUTS;
+    }
+
+    std::slice::from_raw_parts_mut(outputs, values.len()).copy_from_slice(&values);
+    BF_OK
+}
+
+/// Release a network.
+///
+/// # Arguments
+///
+/// - `network` - The network; does nothing if null.
+///
+/// # Safety
+///
+/// `network` must be null or a network from `bf_network_load_json` that has
+/// not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn bf_network_free(network: *mut BfNetwork) {
+    if !network.is_null() {
+        drop(Box::from_raw(network));
+    }
+}
+
+/// Load a network from a null-terminated JSON string, as
+/// `bf_network_load_json` does.
+///
+/// # Arguments
+///
+/// - `json` - The serialized network, as written by `brain-farm`.
+///
+/// # Returns
+///
+/// The network, or null if `json` is null or not a valid network. Release it
+/// with `nnet_free`.
+///
+/// # Safety
+///
+/// `json` must be null or point to a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn nnet_load_json(json: *const c_char) -> *mut BfNetwork {
+    bf_network_load_json(json)
+}
+
+/// Run a network on a set of inputs, as `bf_network_activate` does.
+///
+/// # Arguments
+///
+/// - `network` - The network.
+/// - `inputs` - The input values.
+/// - `input_len` - The number of input values; must match `bf_network_inputs`.
+/// - `outputs` - The buffer the output values are written to.
+/// - `output_len` - The size of `outputs`; must be at least `bf_network_outputs`.
+///
+/// # Returns
+///
+/// `BF_OK`, or one of the `BF_ERROR_*` codes.
+///
+/// # Safety
+///
+/// As for `bf_network_activate`.
+#[no_mangle]
+pub unsafe extern "C" fn nnet_activate(
+    network: *const BfNetwork,
+    inputs: *const f64,
+    input_len: usize,
+    outputs: *mut f64,
+    output_len: usize,
+) -> i32 {
+    bf_network_activate(network, inputs, input_len, outputs, output_len)
+}
+
+/// Release a network, as `bf_network_free` does.
+///
+/// # Arguments
+///
+/// - `network` - The network; does nothing if null.
+///
+/// # Safety
+///
+/// `network` must be null or a network from `nnet_load_json` or
+/// `bf_network_load_json` that has not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn nnet_free(network: *mut BfNetwork) {
+    bf_network_free(network);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nnet::{ActivationFunction, BasicNeuron, Layer};
+    use std::ffi::CString;
+
+    fn json() -> CString {
+        let neuron = BasicNeuron::builder()
+            .weights(vec![1.0, 2.0])
+            .bias(0.5)
+            .activation(ActivationFunction::linear())
+            .build();
+        let network = Network::builder()
+            .add_layer(Layer::builder().add_neuron(neuron).build())
+            .build();
+        CString::new(network.to_json().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_activate() {
+        let json = json();
+        let mut outputs = [0.0; 2];
+
+        unsafe {
+            let network = bf_network_load_json(json.as_ptr());
+            assert!(!network.is_null());
+            assert_eq!(bf_network_inputs(network), 2);
+            assert_eq!(bf_network_outputs(network), 1);
+
+            let status =
+                bf_network_activate(network, [1.0, 1.0].as_ptr(), 2, outputs.as_mut_ptr(), 2);
+            assert_eq!(status, BF_OK);
+
+            bf_network_free(network);
+        }
+
+        assert!((outputs[0] - 3.5).abs() < f64::EPSILON);
+        assert!(outputs[1].abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_errors() {
+        let json = json();
+        let invalid = CString::new("not a network").unwrap();
+        let mut outputs = [0.0; 1];
+
+        unsafe {
+            assert!(bf_network_load_json(std::ptr::null()).is_null());
+            assert!(bf_network_load_json(invalid.as_ptr()).is_null());
+
+            let network = bf_network_load_json(json.as_ptr());
+            let output = outputs.as_mut_ptr();
+            let activate = |input_len, output_len| {
+                bf_network_activate(network, [1.0; 3].as_ptr(), input_len, output, output_len)
+            };
+            assert_eq!(activate(3, 1), BF_ERROR_INPUTS);
+            assert_eq!(activate(2, 0), BF_ERROR_OUTPUTS);
+            assert_eq!(
+                bf_network_activate(std::ptr::null(), [1.0].as_ptr(), 1, outputs.as_mut_ptr(), 1),
+                BF_ERROR_NULL
+            );
+
+            bf_network_free(network);
+            bf_network_free(std::ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_nnet_aliases() {
+        let json = json();
+        let mut outputs = [0.0; 1];
+
+        unsafe {
+            assert!(nnet_load_json(std::ptr::null()).is_null());
+
+            let network = nnet_load_json(json.as_ptr());
+            assert!(!network.is_null());
+            let status = nnet_activate(network, [1.0, 1.0].as_ptr(), 2, outputs.as_mut_ptr(), 1);
+            assert_eq!(status, BF_OK);
+
+            nnet_free(network);
+            nnet_free(std::ptr::null_mut());
+        }
+
+        assert!((outputs[0] - 3.5).abs() < f64::EPSILON);
+    }
+}
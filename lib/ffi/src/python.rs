@@ -0,0 +1,251 @@
+//! Python bindings, behind the `python` feature.
+//!
+//! The module is named `brain_farm`, like the library. Build it into the
+//! active virtual environment with `maturin develop --features python`, then
+//! drive experiments from Python while the hot loops stay in Rust:
+//!
+//! ```python
+//! import brain_farm
+//!
+//! calc = brain_farm.FitnessCalc([([0.0, 0.0], [0.0]), ([1.0, 1.0], [1.0])])
+//! network = brain_farm.evolve(calc, layers=[4, 1], generations=50)
+//! print(calc.check(network), network.activate([1.0, 1.0]))
+//! ```
+
+use evo::{EvoAlgorithm, FitnessCalc, Predict, Termination, TrainingRecord};
+use farm::genome::{network, Create};
+use farm::{breed::Breeder, mutate::Mutator, organism::Organism};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// A neural network.
+#[pyclass(name = "Network", module = "brain_farm")]
+struct PyNetwork {
+    network: nnet::Network,
+}
+
+#[pymethods]
+impl PyNetwork {
+    /// Load a network from JSON, as written by `brain-farm`.
+    ///
+    /// # Errors
+    ///
+    /// `ValueError` if the JSON is not a valid network.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        let network = nnet::Network::parse_json(json).map_err(value_error)?;
+        Ok(Self { network })
+    }
+
+    /// Serialize the network to JSON.
+    ///
+    /// # Errors
+    ///
+    /// `ValueError` if the network cannot be serialized.
+    fn to_json(&self) -> PyResult<String> {
+        self.network.to_json().map_err(value_error)
+    }
+
+    /// Run the network on a set of inputs.
+    ///
+    /// # Errors
+    ///
+    /// `ValueError` if the number of inputs does not match the network.
+    #[allow(clippy::needless_pass_by_value)]
+    fn activate(&self, inputs: Vec<f64>) -> PyResult<Vec<f64>> {
+        self.network.try_activate(&inputs).map_err(value_error)
+    }
+
+    /// The number of inputs the network takes.
+    #[getter]
+    fn input_size(&self) -> Option<usize> {
+        self.network.input_size()
+    }
+
+    /// The number of outputs the network gives.
+    #[getter]
+    fn output_size(&self) -> Option<usize> {
+        self.network.output_size()
+    }
+
+    /// The number of neurons in each layer.
+    #[getter]
+    fn shape(&self) -> Vec<usize> {
+        self.network.shape()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Network(shape={:?})", self.network.shape())
+    }
+
+    fn __str__(&self) -> String {
+        self.network.to_string()
+    }
+}
+
+/// Lets the fitness calculator score a bare network.
+struct Scored<'a>(&'a nnet::Network);
+
+impl Predict for Scored<'_> {
+    fn predict(&self, input: &[f64]) -> Vec<f64> {
+        self.0.activate(input)
+    }
+}
+
+/// Scores networks by their mean squared error on a set of samples, so lower
+/// is better.
+#[pyclass(name = "FitnessCalc", module = "brain_farm")]
+struct PyFitnessCalc {
+    samples: Vec<(Vec<f64>, Vec<f64>)>,
+    calc: FitnessCalc,
+}
+
+#[pymethods]
+impl PyFitnessCalc {
+    /// Create a fitness calculator from `(inputs, outputs)` pairs.
+    ///
+    /// # Errors
+    ///
+    /// `ValueError` if there are no samples, or they differ in size.
+    #[new]
+    fn new(samples: Vec<(Vec<f64>, Vec<f64>)>) -> PyResult<Self> {
+        let Some((input, output)) = samples.first() else {
+            return Err(PyValueError::new_err("at least one sample is needed"));
+        };
+        if samples
+            .iter()
+            .any(|sample| sample.0.len() != input.len() || sample.1.len() != output.len())
+        {
+            return Err(PyValueError::new_err("every sample must be the same size"));
+        }
+
+        let calc = fitness_calc(&samples);
+        Ok(Self { samples, calc })
+    }
+
+    /// Score a network.
+    ///
+    /// # Errors
+    ///
+    /// `ValueError` if the network cannot be scored, e.g. because its outputs
+    /// do not fit the samples.
+    fn check(&self, network: &PyNetwork) -> PyResult<f64> {
+        self.calc
+            .check(&Scored(&network.network))
+            .map_err(value_error)
+    }
+
+    fn __len__(&self) -> usize {
+        self.samples.len()
+    }
+}
+
+/// Evolve a network that fits the samples of a fitness calculator.
+///
+/// The Python lock is released while evolving, so other Python threads keep
+/// running.
+///
+/// # Returns
+///
+/// The fittest network seen in any generation.
+///
+/// # Errors
+///
+/// `ValueError` if a layer is empty, the settings are invalid, or no network
+/// could be scored.
+#[pyfunction]
+#[pyo3(signature = (
+    calc,
+    layers,
+    population = 100,
+    generations = 100,
+    mutation_rate = 0.15,
+    mutation_size = 0.15,
+    elitism = 1,
+    tournament_size = 10,
+    target_fitness = None,
+    seed = None,
+))]
+#[allow(clippy::too_many_arguments, clippy::needless_pass_by_value)]
+fn evolve(
+    py: Python<'_>,
+    calc: &PyFitnessCalc,
+    layers: Vec<usize>,
+    population: usize,
+    generations: usize,
+    mutation_rate: f64,
+    mutation_size: f64,
+    elitism: usize,
+    tournament_size: usize,
+    target_fitness: Option<f64>,
+    seed: Option<u64>,
+) -> PyResult<PyNetwork> {
+    if layers.is_empty() || layers.contains(&0) {
+        return Err(PyValueError::new_err(
+            "layers must be given, each with at least one neuron",
+        ));
+    }
+    let mutator = Mutator::builder()
+        .mutation_rate(mutation_rate)
+        .mutation_size(mutation_size)
+        .build();
+    let mut rng = seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+    let algorithm = EvoAlgorithm::builder()
+        .breeder(Breeder::new(mutator))
+        .fitness_calc(fitness_calc(&calc.samples))
+        .elitism(elitism)
+        .tournament_size(tournament_size)
+        .seed(rng.gen())
+        .build()
+        .map_err(value_error)?;
+
+    let inputs = calc.samples[0].0.len();
+    let mut termination = Termination::builder().generations(generations);
+    if let Some(target_fitness) = target_fitness {
+        termination = termination.target_fitness(target_fitness);
+    }
+    let best = py.allow_threads(|| {
+        let population = (0..population)
+            .map(|_| Organism::from(network::Genome::random(inputs, &layers, &mut rng)))
+            .collect::<Vec<_>>();
+        algorithm.evolve(population, termination.build()).best
+    });
+
+    let best = best.ok_or_else(|| PyValueError::new_err("no network could be scored"))?;
+    Ok(PyNetwork {
+        network: best.predict.genome().create(),
+    })
+}
+
+/// Build a fitness calculator.
+///
+/// # Arguments
+///
+/// - `samples` - The input and expected output values of each sample.
+///
+/// # Returns
+///
+/// The fitness calculator.
+fn fitness_calc(samples: &[(Vec<f64>, Vec<f64>)]) -> FitnessCalc {
+    samples
+        .iter()
+        .fold(FitnessCalc::builder(), |builder, (input, output)| {
+            builder.add_training_record(TrainingRecord::new(input.clone(), output.clone()))
+        })
+        .build()
+}
+
+/// Report a Rust error to Python.
+fn value_error(error: impl std::fmt::Display) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+/// The `brain_farm` Python module.
+#[pymodule]
+fn brain_farm(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyNetwork>()?;
+    module.add_class::<PyFitnessCalc>()?;
+    module.add_function(wrap_pyfunction!(evolve, module)?)?;
+    Ok(())
+}
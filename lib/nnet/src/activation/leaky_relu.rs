@@ -0,0 +1,97 @@
+use super::{Activate, Function};
+use serde::{Deserialize, Serialize};
+
+/// Leaky rectified linear activation function.
+///
+/// Positive inputs pass through unchanged, and negative inputs are scaled by
+/// `alpha`, so neurons keep a small gradient instead of going silent.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LeakyRelu {
+    /// The slope for negative inputs.
+    pub alpha: f64,
+}
+
+impl LeakyRelu {
+    /// The slope used by [`Function::leaky_relu`].
+    pub const DEFAULT_ALPHA: f64 = 0.01;
+
+    /// Create a leaky rectified linear function with a slope for negative inputs.
+    ///
+    /// # Arguments
+    ///
+    /// - `alpha` is the slope for negative inputs.
+    ///
+    /// # Returns
+    ///
+    /// The activation function.
+    #[must_use]
+    pub fn new(alpha: f64) -> Self {
+        Self { alpha }
+    }
+}
+
+impl Default for LeakyRelu {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_ALPHA)
+    }
+}
+
+impl Activate for LeakyRelu {
+    fn activate(&self, input: f64) -> f64 {
+        if input < 0.0 {
+            input * self.alpha
+        } else {
+            input
+        }
+    }
+
+    fn derivative(&self, input: f64) -> f64 {
+        if input < 0.0 {
+            self.alpha
+        } else {
+            1.0
+        }
+    }
+}
+
+impl Function {
+    /// Leaky rectified linear activation function, with a slope of 0.01 for negative
+    /// inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{activation::LeakyRelu, ActivationFunction};
+    ///
+    /// let relu = ActivationFunction::leaky_relu();
+    /// let steep = ActivationFunction::LeakyRelu(LeakyRelu::new(0.2));
+    /// ```
+    #[must_use]
+    pub fn leaky_relu() -> Self {
+        Self::LeakyRelu(LeakyRelu::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leaky_relu() {
+        let relu = LeakyRelu::new(0.5);
+
+        assert!((relu.activate(2.0) - 2.0).abs() < f64::EPSILON);
+        assert!((relu.activate(-2.0) + 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_serialize() {
+        let relu = LeakyRelu::new(0.5);
+        let serialized = serde_json::to_string(&relu).unwrap();
+        assert_eq!(serialized, r#"{"alpha":0.5}"#);
+        assert_eq!(
+            serde_json::from_str::<LeakyRelu>(&serialized).unwrap(),
+            relu
+        );
+    }
+}
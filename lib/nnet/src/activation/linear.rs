@@ -9,6 +9,10 @@ impl Activate for Linear {
     fn activate(&self, input: f64) -> f64 {
         input
     }
+
+    fn derivative(&self, _input: f64) -> f64 {
+        1.0
+    }
 }
 
 impl Function {
@@ -28,6 +32,7 @@ impl Function {
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_raw_string_hashes)]
 mod tests {
     use super::*;
 
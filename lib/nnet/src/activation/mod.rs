@@ -1,9 +1,13 @@
+mod leaky_relu;
 mod linear;
 mod sigmoid;
+mod tanh;
 
+pub use leaky_relu::LeakyRelu;
 pub use linear::Linear;
 use serde::{Deserialize, Serialize};
 pub use sigmoid::Sigmoid;
+pub use tanh::Tanh;
 
 /// [`Neuron`] activation function.
 ///
@@ -18,6 +22,8 @@ pub use sigmoid::Sigmoid;
 pub enum Function {
     Linear(Linear),
     Sigmoid(Sigmoid),
+    LeakyRelu(LeakyRelu),
+    Tanh(Tanh),
 }
 
 impl Activate for Function {
@@ -41,6 +47,36 @@ impl Activate for Function {
         match self {
             Self::Linear(lin) => lin.activate(input),
             Self::Sigmoid(sig) => sig.activate(input),
+            Self::LeakyRelu(relu) => relu.activate(input),
+            Self::Tanh(tanh) => tanh.activate(input),
+        }
+    }
+
+    /// Get the slope of the function.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` is the value the function is activated with.
+    ///
+    /// # Returns
+    ///
+    /// The derivative of the function at `input`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{Activate, ActivationFunction};
+    ///
+    /// let sig = ActivationFunction::sigmoid();
+    ///
+    /// assert_eq!(sig.derivative(0.0), 0.25);
+    /// ```
+    fn derivative(&self, input: f64) -> f64 {
+        match self {
+            Self::Linear(lin) => lin.derivative(input),
+            Self::Sigmoid(sig) => sig.derivative(input),
+            Self::LeakyRelu(relu) => relu.derivative(input),
+            Self::Tanh(tanh) => tanh.derivative(input),
         }
     }
 }
@@ -57,6 +93,49 @@ pub trait Activate {
     ///
     /// The output of the function.
     fn activate(&self, input: f64) -> f64;
+
+    /// Get the slope of the function, for gradient-based training or for
+    /// fitness functions that use gradient information.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` is the value the function is activated with.
+    ///
+    /// # Returns
+    ///
+    /// The derivative of the function at `input`. Where the function is not
+    /// differentiable, e.g. at the kink of a rectifier, one side's slope.
+    fn derivative(&self, input: f64) -> f64;
+}
+
+impl std::fmt::Display for Function {
+    /// Write the name of the function, followed by its parameter unless it
+    /// is the default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{activation::Sigmoid, ActivationFunction};
+    ///
+    /// assert_eq!(ActivationFunction::sigmoid().to_string(), "sigmoid");
+    /// assert_eq!(
+    ///     ActivationFunction::Sigmoid(Sigmoid::new(2.0)).to_string(),
+    ///     "sigmoid(2)"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (name, parameter, default) = match self {
+            Self::Linear(_) => return f.write_str("linear"),
+            Self::Tanh(_) => return f.write_str("tanh"),
+            Self::Sigmoid(sig) => ("sigmoid", sig.steepness, Sigmoid::DEFAULT_STEEPNESS),
+            Self::LeakyRelu(relu) => ("leaky_relu", relu.alpha, LeakyRelu::DEFAULT_ALPHA),
+        };
+        if parameter.total_cmp(&default).is_eq() {
+            f.write_str(name)
+        } else {
+            write!(f, "{name}({parameter})")
+        }
+    }
 }
 
 #[cfg(test)]
@@ -74,6 +153,34 @@ mod tests {
         let serialized = serde_json::to_string(&sig).unwrap();
         let expected = r#"{"Sigmoid":null}"#;
         assert_eq!(serialized, expected);
+
+        let relu = Function::leaky_relu();
+        let serialized = serde_json::to_string(&relu).unwrap();
+        let expected = r#"{"LeakyRelu":{"alpha":0.01}}"#;
+        assert_eq!(serialized, expected);
+    }
+
+    #[test]
+    fn test_derivative() {
+        // Compare against the slope of a small secant around each point.
+        let step = 1e-6;
+        for function in [
+            Function::linear(),
+            Function::sigmoid(),
+            Function::Sigmoid(Sigmoid::new(3.0)),
+            Function::leaky_relu(),
+            Function::tanh(),
+        ] {
+            for input in [-2.0, -0.5, 0.3, 1.5] {
+                let secant = (function.activate(input + step) - function.activate(input - step))
+                    / (2.0 * step);
+                let derivative = function.derivative(input);
+                assert!(
+                    (derivative - secant).abs() < 1e-6,
+                    "{function} at {input}: {derivative} != {secant}"
+                );
+            }
+        }
     }
 
     #[test]
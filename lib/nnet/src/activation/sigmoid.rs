@@ -4,14 +4,75 @@ use serde::{Deserialize, Serialize};
 /// Sigmoid activation function
 ///
 /// This function is used to squash the output of a neuron to a value between 0 and 1.
+/// The steepness scales the input, so steeper sigmoids switch more sharply.
+///
+/// The default steepness serializes as `null`, like sigmoids without one.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct Sigmoid;
+#[serde(from = "Option<Steepness>", into = "Option<Steepness>")]
+pub struct Sigmoid {
+    /// The factor the input is scaled by.
+    pub steepness: f64,
+}
+
+/// The serialized parameters of a sigmoid that is not the default.
+#[derive(Clone, Serialize, Deserialize)]
+struct Steepness {
+    steepness: f64,
+}
+
+impl Sigmoid {
+    /// The steepness used by [`Function::sigmoid`].
+    pub const DEFAULT_STEEPNESS: f64 = 1.0;
+
+    /// Create a sigmoid with a steepness.
+    ///
+    /// # Arguments
+    ///
+    /// - `steepness` is the factor the input is scaled by.
+    ///
+    /// # Returns
+    ///
+    /// The activation function.
+    #[must_use]
+    pub fn new(steepness: f64) -> Self {
+        Self { steepness }
+    }
+}
+
+impl Default for Sigmoid {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_STEEPNESS)
+    }
+}
+
+impl From<Option<Steepness>> for Sigmoid {
+    fn from(steepness: Option<Steepness>) -> Self {
+        steepness.map_or_else(Self::default, |params| Self::new(params.steepness))
+    }
+}
+
+impl From<Sigmoid> for Option<Steepness> {
+    fn from(sigmoid: Sigmoid) -> Self {
+        let default = sigmoid
+            .steepness
+            .total_cmp(&Sigmoid::DEFAULT_STEEPNESS)
+            .is_eq();
+        (!default).then_some(Steepness {
+            steepness: sigmoid.steepness,
+        })
+    }
+}
 
 impl Activate for Sigmoid {
     fn activate(&self, x: f64) -> f64 {
-        let n_exp = (-x).exp();
+        let n_exp = (-x * self.steepness).exp();
         1.0 / (1.0 + n_exp)
     }
+
+    fn derivative(&self, x: f64) -> f64 {
+        let output = self.activate(x);
+        self.steepness * output * (1.0 - output)
+    }
 }
 
 impl Function {
@@ -26,11 +87,12 @@ impl Function {
     /// ```
     #[must_use]
     pub fn sigmoid() -> Self {
-        Self::Sigmoid(Sigmoid)
+        Self::Sigmoid(Sigmoid::default())
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_raw_string_hashes)]
 mod tests {
     use super::*;
 
@@ -40,7 +102,7 @@ mod tests {
             / (1.0
                 + std::f64::consts::E.powf(-(0.1 * 0.1 + 0.5 * 0.2 + 1.0 * 0.3 + 1.5 * 0.4 + 1.0)));
 
-        let sig = Sigmoid;
+        let sig = Sigmoid::default();
         let outputs = sig.activate(0.1 * 0.1 + 0.5 * 0.2 + 1.0 * 0.3 + 1.5 * 0.4 + 1.0);
         assert!(
             (outputs - expected).abs() < f64::EPSILON,
@@ -50,7 +112,7 @@ mod tests {
 
     #[test]
     fn test_serialize() {
-        let sig = Sigmoid;
+        let sig = Sigmoid::default();
         let serialized = serde_json::to_string(&sig).unwrap();
         let expected = r#"null"#;
         assert_eq!(serialized, expected);
@@ -58,8 +120,18 @@ mod tests {
 
     #[test]
     fn test_deserialize() {
-        let sig = Sigmoid;
+        let sig = Sigmoid::default();
         let deserialized = serde_json::from_str(r#"null"#).unwrap();
         assert_eq!(sig, deserialized);
     }
+
+    #[test]
+    fn test_steepness() {
+        let steep = Sigmoid::new(4.0);
+        assert!((steep.activate(0.5) - Sigmoid::default().activate(2.0)).abs() < f64::EPSILON);
+
+        let serialized = serde_json::to_string(&steep).unwrap();
+        assert_eq!(serialized, r#"{"steepness":4.0}"#);
+        assert_eq!(serde_json::from_str::<Sigmoid>(&serialized).unwrap(), steep);
+    }
 }
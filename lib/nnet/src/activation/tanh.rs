@@ -0,0 +1,46 @@
+use super::{Activate, Function};
+use serde::{Deserialize, Serialize};
+
+/// Hyperbolic tangent activation function.
+///
+/// This function squashes the output of a neuron to a value between -1 and 1.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Tanh;
+
+impl Activate for Tanh {
+    fn activate(&self, input: f64) -> f64 {
+        input.tanh()
+    }
+
+    fn derivative(&self, input: f64) -> f64 {
+        1.0 - input.tanh().powi(2)
+    }
+}
+
+impl Function {
+    /// Hyperbolic tangent activation function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::ActivationFunction;
+    ///
+    /// let tanh = ActivationFunction::tanh();
+    /// ```
+    #[must_use]
+    pub fn tanh() -> Self {
+        Self::Tanh(Tanh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tanh() {
+        assert!(Tanh.activate(0.0).abs() < f64::EPSILON);
+        assert!((Tanh.activate(1.0) + Tanh.activate(-1.0)).abs() < f64::EPSILON);
+        assert!(Tanh.activate(100.0) <= 1.0);
+    }
+}
@@ -0,0 +1,62 @@
+/// An error describing a network that cannot work as built.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    #[error("the neuron has no weights")]
+    NoWeights,
+
+    #[error("layer {layer} has no neurons")]
+    EmptyLayer { layer: usize },
+
+    #[error("the network has no layers")]
+    EmptyNetwork,
+
+    #[error(
+        "neuron {neuron} of layer {layer} has {found} weights, but its inputs need {expected}"
+    )]
+    WeightCount {
+        layer: usize,
+        neuron: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error(
+        "a gate of neuron {neuron} of layer {layer} has {found} weights, but its inputs need {expected}"
+    )]
+    GateWeightCount {
+        layer: usize,
+        neuron: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("layer {layer} cannot take inputs from layer {from}, which does not come before it")]
+    InvalidSource { layer: usize, from: usize },
+
+    #[error("the network takes {expected} inputs, but {found} were given")]
+    InputCount { expected: usize, found: usize },
+
+    #[error("the network gives {expected} outputs, but {found} targets were given")]
+    OutputCount { expected: usize, found: usize },
+
+    #[error("node {node} does not exist")]
+    UnknownNode { node: usize },
+
+    #[error("node {node} is an input, so no edge can lead into it")]
+    IntoInput { node: usize },
+
+    #[error("the graph has a cycle")]
+    Cycle,
+
+    #[error("neuron {neuron} of layer {layer} is recurrent and cannot be trained")]
+    Untrainable { layer: usize, neuron: usize },
+
+    #[error("schema version {found} is newer than the supported version {supported}")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error("neuron {neuron} of layer {layer} is recurrent and cannot be quantized")]
+    Unquantizable { layer: usize, neuron: usize },
+}
+
+/// A result that can fail with an nnet [`Error`].
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,180 @@
+//! Reading and writing values in interchange formats that tools in other
+//! languages understand.
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::io::{Read, Write};
+
+/// A serialization format.
+///
+/// `MessagePack` and CBOR are behind the `msgpack` and `cbor` features, which
+/// are on by default. Structs are written as maps keyed by field name in
+/// every format, so other tools can read them without knowing field order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// JSON text.
+    Json,
+
+    /// `MessagePack` binary.
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+
+    /// CBOR binary.
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+/// An error reading or writing a value.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[cfg(feature = "msgpack")]
+    #[error("MessagePack: {0}")]
+    MessagePackEncode(#[from] rmp_serde::encode::Error),
+
+    #[cfg(feature = "msgpack")]
+    #[error("MessagePack: {0}")]
+    MessagePackDecode(#[from] rmp_serde::decode::Error),
+
+    #[cfg(feature = "cbor")]
+    #[error("CBOR: {0}")]
+    CborEncode(#[from] ciborium::ser::Error<std::io::Error>),
+
+    #[cfg(feature = "cbor")]
+    #[error("CBOR: {0}")]
+    CborDecode(#[from] ciborium::de::Error<std::io::Error>),
+}
+
+/// Write a value.
+///
+/// # Arguments
+///
+/// - `value` is the value to write.
+/// - `writer` receives the serialized value.
+/// - `format` is the format to write in.
+///
+/// # Errors
+///
+/// If the value cannot be serialized or the writer fails.
+///
+/// # Examples
+///
+/// ```
+/// use nnet::format::{serialize_to, Format};
+///
+/// let mut bytes = vec![];
+/// serialize_to(&[1.0, 2.0], &mut bytes, Format::Json).unwrap();
+///
+/// assert_eq!(bytes, b"[1.0,2.0]");
+/// ```
+pub fn serialize_to<T, W>(value: &T, writer: W, format: Format) -> Result<(), Error>
+where
+    T: Serialize + ?Sized,
+    W: Write,
+{
+    match format {
+        Format::Json => serde_json::to_writer(writer, value)?,
+        #[cfg(feature = "msgpack")]
+        Format::MessagePack => rmp_serde::encode::write_named(&mut { writer }, value)?,
+        #[cfg(feature = "cbor")]
+        Format::Cbor => ciborium::into_writer(value, writer)?,
+    }
+    Ok(())
+}
+
+/// Read a value.
+///
+/// # Arguments
+///
+/// - `reader` supplies the serialized value.
+/// - `format` is the format to read.
+///
+/// # Returns
+///
+/// The value.
+///
+/// # Errors
+///
+/// If the input is not a value of the type in the format, or the reader
+/// fails.
+///
+/// # Examples
+///
+/// ```
+/// use nnet::format::{deserialize_from, Format};
+///
+/// let values: Vec<f64> = deserialize_from(&b"[1.0,2.0]"[..], Format::Json).unwrap();
+///
+/// assert_eq!(values, vec![1.0, 2.0]);
+/// ```
+pub fn deserialize_from<T, R>(reader: R, format: Format) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+    R: Read,
+{
+    Ok(match format {
+        Format::Json => serde_json::from_reader(reader)?,
+        #[cfg(feature = "msgpack")]
+        Format::MessagePack => rmp_serde::from_read(reader)?,
+        #[cfg(feature = "cbor")]
+        Format::Cbor => ciborium::from_reader(reader)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActivationFunction, BasicNeuron, InputSource, Layer, LstmNeuron, Network};
+
+    fn network() -> Network {
+        let basic = |weights: Vec<f64>| {
+            BasicNeuron::builder()
+                .weights(weights)
+                .bias(0.25)
+                .activation(ActivationFunction::tanh())
+                .build()
+        };
+        Network::builder()
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(basic(vec![0.5, -1.5]))
+                    .add_neuron(LstmNeuron::builder().weights(vec![1.0, 2.0]).build())
+                    .build(),
+            )
+            .add_layer_with_input(
+                Layer::builder().add_neuron(basic(vec![1.0, 0.1])).build(),
+                InputSource::Layers(vec![0]),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let network = network();
+        let formats = [
+            Format::Json,
+            #[cfg(feature = "msgpack")]
+            Format::MessagePack,
+            #[cfg(feature = "cbor")]
+            Format::Cbor,
+        ];
+
+        for format in formats {
+            let mut bytes = vec![];
+            network.serialize_to(&mut bytes, format).unwrap();
+            let parsed = Network::deserialize_from(bytes.as_slice(), format).unwrap();
+            assert_eq!(parsed, network, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn test_rejects_wrong_format() {
+        let mut bytes = vec![];
+        network().serialize_to(&mut bytes, Format::Json).unwrap();
+
+        assert!(Network::deserialize_from(&bytes[1..], Format::Json).is_err());
+        #[cfg(feature = "cbor")]
+        assert!(Network::deserialize_from(bytes.as_slice(), Format::Cbor).is_err());
+    }
+}
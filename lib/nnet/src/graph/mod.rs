@@ -0,0 +1,622 @@
+use crate::{Activate, ActivationFunction, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// A neuron of a graph [`Network`]: a bias and an activation function. Its
+/// weights live on the edges that lead into it.
+///
+/// # Examples
+///
+/// ```
+/// use nnet::{graph::Node, ActivationFunction};
+///
+/// let node = Node { bias: 0.5, activation: ActivationFunction::sigmoid() };
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    /// Shifts the node's overall sensitivity.
+    pub bias: f64,
+
+    /// The activation function to use.
+    pub activation: ActivationFunction,
+}
+
+/// A weighted connection between two nodes of a graph [`Network`].
+///
+/// # Examples
+///
+/// ```
+/// use nnet::graph::Edge;
+///
+/// let edge = Edge::new(0, 2, 0.5);
+///
+/// assert!(edge.enabled);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Edge {
+    /// The node the edge reads from.
+    pub from: usize,
+
+    /// The node the edge feeds.
+    pub to: usize,
+
+    /// The weight applied to the value of `from`.
+    pub weight: f64,
+
+    /// Whether the edge is used. Disabled edges are kept so that evolution
+    /// can turn them back on, as in NEAT.
+    #[serde(default = "enabled")]
+    pub enabled: bool,
+}
+
+impl Edge {
+    /// Create an enabled edge.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` is the node the edge reads from.
+    /// - `to` is the node the edge feeds.
+    /// - `weight` is applied to the value of `from`.
+    ///
+    /// # Returns
+    ///
+    /// The edge.
+    #[must_use]
+    pub fn new(from: usize, to: usize, weight: f64) -> Self {
+        Self {
+            from,
+            to,
+            weight,
+            enabled: true,
+        }
+    }
+}
+
+/// The default of [`Edge::enabled`].
+fn enabled() -> bool {
+    true
+}
+
+/// A neural network whose neurons are nodes of a directed acyclic graph,
+/// joined by explicit weighted edges, rather than dense layers. This lets
+/// structure evolve along with the weights, as in NEAT.
+///
+/// Nodes are numbered with the inputs first: with `n` inputs, nodes `0` to
+/// `n - 1` hold the inputs and node `n + i` is the `i`th entry of
+/// [`Network::nodes`]. Each node sums its incoming edges and its bias, then
+/// applies its activation function; nodes are activated in topological order.
+///
+/// # Examples
+///
+/// ```
+/// use nnet::graph::{Edge, Network, Node};
+/// use nnet::ActivationFunction;
+///
+/// let linear = |bias| Node { bias, activation: ActivationFunction::linear() };
+///
+/// // Nodes 0 and 1 are inputs; node 2 is hidden; node 3 is the output, which
+/// // also reads input 0 directly.
+/// let network = Network::builder()
+///     .inputs(2)
+///     .add_node(linear(1.0))
+///     .add_node(linear(0.0))
+///     .add_edge(Edge::new(0, 2, 1.0))
+///     .add_edge(Edge::new(1, 2, 1.0))
+///     .add_edge(Edge::new(2, 3, 2.0))
+///     .add_edge(Edge::new(0, 3, -1.0))
+///     .add_output(3)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(network.activate(&[1.0, 2.0]), vec![7.0]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Parts")]
+pub struct Network {
+    /// The number of inputs.
+    inputs: usize,
+
+    /// The nodes after the inputs.
+    nodes: Vec<Node>,
+
+    /// The connections between nodes.
+    edges: Vec<Edge>,
+
+    /// The nodes whose values are the outputs, in order.
+    outputs: Vec<usize>,
+
+    /// Each node, by index into `nodes`, in the order it is activated, with
+    /// the indices of its enabled incoming edges.
+    #[serde(skip)]
+    plan: Vec<(usize, Vec<usize>)>,
+}
+
+/// The serialized form of a [`Network`], checked before it is used.
+#[derive(Deserialize)]
+struct Parts {
+    inputs: usize,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    outputs: Vec<usize>,
+}
+
+impl TryFrom<Parts> for Network {
+    type Error = Error;
+
+    fn try_from(parts: Parts) -> Result<Self> {
+        let Parts {
+            inputs,
+            nodes,
+            edges,
+            outputs,
+        } = parts;
+        let plan = plan(inputs, nodes.len(), &edges, &outputs)?;
+        Ok(Self {
+            inputs,
+            nodes,
+            edges,
+            outputs,
+            plan,
+        })
+    }
+}
+
+/// Work out the order to activate a graph's nodes in.
+///
+/// # Arguments
+///
+/// - `inputs` is the number of inputs.
+/// - `nodes` is the number of nodes after the inputs.
+/// - `edges` are the connections between nodes.
+/// - `outputs` are the output nodes.
+///
+/// # Returns
+///
+/// Each node, by index after the inputs, in topological order, with the
+/// indices of its enabled incoming edges.
+///
+/// # Errors
+///
+/// If an edge or output names a node that does not exist, an edge leads
+/// into an input, or the enabled edges form a cycle.
+fn plan(
+    inputs: usize,
+    nodes: usize,
+    edges: &[Edge],
+    outputs: &[usize],
+) -> Result<Vec<(usize, Vec<usize>)>> {
+    let total = inputs + nodes;
+    if let Some(node) = outputs.iter().find(|node| **node >= total) {
+        return Err(Error::UnknownNode { node: *node });
+    }
+
+    let mut incoming = vec![vec![]; nodes];
+    let mut outgoing = vec![vec![]; nodes];
+    let mut waiting = vec![0; nodes];
+    for (index, edge) in edges.iter().enumerate() {
+        if let Some(node) = [edge.from, edge.to].into_iter().find(|node| *node >= total) {
+            return Err(Error::UnknownNode { node });
+        }
+        if edge.to < inputs {
+            return Err(Error::IntoInput { node: edge.to });
+        }
+        if !edge.enabled {
+            continue;
+        }
+        incoming[edge.to - inputs].push(index);
+        if edge.from >= inputs {
+            outgoing[edge.from - inputs].push(edge.to - inputs);
+            waiting[edge.to - inputs] += 1;
+        }
+    }
+
+    // Kahn's algorithm: a node is ready once every node feeding it is.
+    let mut ready = (0..nodes)
+        .filter(|node| waiting[*node] == 0)
+        .collect::<VecDeque<_>>();
+    let mut plan = Vec::with_capacity(nodes);
+    while let Some(node) = ready.pop_front() {
+        for next in &outgoing[node] {
+            waiting[*next] -= 1;
+            if waiting[*next] == 0 {
+                ready.push_back(*next);
+            }
+        }
+        plan.push((node, std::mem::take(&mut incoming[node])));
+    }
+
+    if plan.len() < nodes {
+        return Err(Error::Cycle);
+    }
+    Ok(plan)
+}
+
+impl Network {
+    /// Create a new builder.
+    ///
+    /// # Returns
+    ///
+    /// A new builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::graph::Network;
+    ///
+    /// let network = Network::builder().inputs(2).add_output(1).build().unwrap();
+    ///
+    /// assert_eq!(network.activate(&[3.0, 4.0]), vec![4.0]);
+    /// ```
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Activate the network.
+    ///
+    /// This has the same signature as [`crate::Network::activate`], so graph
+    /// networks can be evaluated wherever layered ones are.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` to activate the network with. Missing inputs are zero, and
+    ///   extra ones are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The values of the output nodes.
+    #[must_use]
+    pub fn activate(&self, inputs: &[f64]) -> Vec<f64> {
+        let mut values = vec![0.0; self.inputs + self.nodes.len()];
+        for (value, input) in values.iter_mut().zip(inputs.iter().take(self.inputs)) {
+            *value = *input;
+        }
+
+        for (node, incoming) in &self.plan {
+            let Node { bias, activation } = &self.nodes[*node];
+            let sum = incoming
+                .iter()
+                .map(|edge| {
+                    let Edge { from, weight, .. } = &self.edges[*edge];
+                    values[*from] * weight
+                })
+                .sum::<f64>();
+            values[self.inputs + node] = activation.activate(sum + bias);
+        }
+
+        self.outputs.iter().map(|node| values[*node]).collect()
+    }
+
+    /// Get the number of inputs.
+    ///
+    /// # Returns
+    ///
+    /// The number of inputs, which are also the first nodes.
+    #[must_use]
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    /// Get the nodes after the inputs.
+    ///
+    /// # Returns
+    ///
+    /// The nodes; node `inputs() + i` is entry `i`.
+    #[must_use]
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Get the connections between nodes.
+    ///
+    /// # Returns
+    ///
+    /// The edges, including disabled ones.
+    #[must_use]
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    /// Get the output nodes.
+    ///
+    /// # Returns
+    ///
+    /// The nodes whose values are the outputs, in order.
+    #[must_use]
+    pub fn outputs(&self) -> &[usize] {
+        &self.outputs
+    }
+}
+
+/// A builder for graph [`Network`]s.
+///
+/// # Examples
+///
+/// ```
+/// use nnet::graph::{Edge, Network, Node};
+/// use nnet::ActivationFunction;
+///
+/// let network = Network::builder()
+///     .inputs(1)
+///     .add_node(Node { bias: 0.0, activation: ActivationFunction::sigmoid() })
+///     .add_edge(Edge::new(0, 1, 1.0))
+///     .add_output(1)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(network.activate(&[0.0]), vec![0.5]);
+/// ```
+#[derive(Default)]
+pub struct Builder {
+    inputs: usize,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    outputs: Vec<usize>,
+}
+
+impl Builder {
+    /// Set the number of inputs.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` is the number of inputs, which become the first nodes.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn inputs(mut self, inputs: usize) -> Self {
+        self.inputs = inputs;
+        self
+    }
+
+    /// Add a node after the inputs and any nodes already added.
+    ///
+    /// # Arguments
+    ///
+    /// - `node` to add.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn add_node(mut self, node: Node) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Set the nodes after the inputs.
+    ///
+    /// # Arguments
+    ///
+    /// - `nodes` to set.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn nodes(mut self, nodes: Vec<Node>) -> Self {
+        self.nodes = nodes;
+        self
+    }
+
+    /// Add a connection between two nodes.
+    ///
+    /// # Arguments
+    ///
+    /// - `edge` to add.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn add_edge(mut self, edge: Edge) -> Self {
+        self.edges.push(edge);
+        self
+    }
+
+    /// Set the connections between nodes.
+    ///
+    /// # Arguments
+    ///
+    /// - `edges` to set.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn edges(mut self, edges: Vec<Edge>) -> Self {
+        self.edges = edges;
+        self
+    }
+
+    /// Add an output node.
+    ///
+    /// # Arguments
+    ///
+    /// - `node` whose value becomes the next output.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn add_output(mut self, node: usize) -> Self {
+        self.outputs.push(node);
+        self
+    }
+
+    /// Set the output nodes.
+    ///
+    /// # Arguments
+    ///
+    /// - `outputs` are the nodes whose values are the outputs, in order.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn outputs(mut self, outputs: Vec<usize>) -> Self {
+        self.outputs = outputs;
+        self
+    }
+
+    /// Build the network, working out the order to activate its nodes in.
+    ///
+    /// # Returns
+    ///
+    /// The network.
+    ///
+    /// # Errors
+    ///
+    /// If an edge or output names a node that does not exist, an edge leads
+    /// into an input, or the enabled edges form a cycle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::graph::{Edge, Network, Node};
+    /// use nnet::{ActivationFunction, Error};
+    ///
+    /// let node = || Node { bias: 0.0, activation: ActivationFunction::linear() };
+    ///
+    /// assert_eq!(
+    ///     Network::builder()
+    ///         .inputs(1)
+    ///         .add_node(node())
+    ///         .add_node(node())
+    ///         .add_edge(Edge::new(1, 2, 1.0))
+    ///         .add_edge(Edge::new(2, 1, 1.0))
+    ///         .build(),
+    ///     Err(Error::Cycle)
+    /// );
+    /// ```
+    pub fn build(self) -> Result<Network> {
+        Network::try_from(Parts {
+            inputs: self.inputs,
+            nodes: self.nodes,
+            edges: self.edges,
+            outputs: self.outputs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(bias: f64, activation: ActivationFunction) -> Node {
+        Node { bias, activation }
+    }
+
+    /// XOR from two hidden nodes, added after the output to check that nodes
+    /// are activated in topological order rather than by index.
+    fn xor() -> Network {
+        let step = || ActivationFunction::Sigmoid(crate::activation::Sigmoid::new(100.0));
+        Network::builder()
+            .inputs(2)
+            .add_node(node(-0.5, step()))
+            .add_node(node(-0.5, step()))
+            .add_node(node(-1.5, step()))
+            .add_edge(Edge::new(3, 2, 1.0))
+            .add_edge(Edge::new(4, 2, -2.0))
+            .add_edge(Edge::new(0, 3, 1.0))
+            .add_edge(Edge::new(1, 3, 1.0))
+            .add_edge(Edge::new(0, 4, 1.0))
+            .add_edge(Edge::new(1, 4, 1.0))
+            .add_output(2)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_activate() {
+        let network = xor();
+
+        for (inputs, expected) in [
+            ([0.0, 0.0], 0.0),
+            ([0.0, 1.0], 1.0),
+            ([1.0, 0.0], 1.0),
+            ([1.0, 1.0], 0.0),
+        ] {
+            let output = network.activate(&inputs)[0];
+            assert!((output - expected).abs() < 1e-3, "{inputs:?} gave {output}");
+        }
+    }
+
+    #[test]
+    fn test_disabled_edges() {
+        let linear = ActivationFunction::linear;
+        let network = Network::builder()
+            .inputs(1)
+            .add_node(node(0.0, linear()))
+            .add_node(node(0.0, linear()))
+            .add_edge(Edge::new(0, 1, 2.0))
+            .add_edge(Edge::new(1, 2, 3.0))
+            .add_edge(Edge {
+                enabled: false,
+                ..Edge::new(2, 1, 1.0)
+            })
+            .add_output(2)
+            .build()
+            .unwrap();
+
+        // The disabled edge would close a cycle, but is ignored.
+        assert_eq!(network.activate(&[1.0]), vec![6.0]);
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let linear = || node(0.0, ActivationFunction::linear());
+
+        assert_eq!(
+            Network::builder().inputs(1).add_output(1).build(),
+            Err(Error::UnknownNode { node: 1 })
+        );
+        assert_eq!(
+            Network::builder()
+                .inputs(1)
+                .add_node(linear())
+                .add_edge(Edge::new(0, 5, 1.0))
+                .build(),
+            Err(Error::UnknownNode { node: 5 })
+        );
+        assert_eq!(
+            Network::builder()
+                .inputs(2)
+                .add_edge(Edge::new(0, 1, 1.0))
+                .build(),
+            Err(Error::IntoInput { node: 1 })
+        );
+        assert_eq!(
+            Network::builder()
+                .inputs(1)
+                .add_node(linear())
+                .add_edge(Edge::new(1, 1, 1.0))
+                .build(),
+            Err(Error::Cycle)
+        );
+    }
+
+    #[test]
+    fn test_serde() {
+        let network = xor();
+
+        let json = serde_json::to_string(&network).unwrap();
+        assert!(!json.contains("plan"));
+        let parsed: Network = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, network);
+        assert_eq!(
+            parsed.activate(&[1.0, 0.0])[0].to_bits(),
+            network.activate(&[1.0, 0.0])[0].to_bits()
+        );
+
+        let cyclic = r#"{
+            "inputs": 0,
+            "nodes": [{"bias": 0.0, "activation": {"Linear": null}}],
+            "edges": [{"from": 0, "to": 0, "weight": 1.0}],
+            "outputs": [0]
+        }"#;
+        let error = serde_json::from_str::<Network>(cyclic).unwrap_err();
+        assert!(error.to_string().contains("cycle"), "{error}");
+    }
+}
@@ -0,0 +1,336 @@
+//! Importing dense networks trained with Keras.
+//!
+//! The architecture is the JSON written by `model.to_json()`. Keras keeps
+//! weights in HDF5, so they are read from JSON instead: one entry for each
+//! `Dense` layer, in order, holding the arrays that `layer.get_weights()`
+//! returns. In Python:
+//!
+//! ```python
+//! weights = [
+//!     [array.tolist() for array in layer.get_weights()]
+//!     for layer in model.layers
+//!     if layer.get_weights()
+//! ]
+//! json.dumps(weights)
+//! ```
+//!
+//! Only `Sequential` models of `Dense` layers can be imported. `InputLayer`,
+//! `Dropout` and `Flatten` layers change nothing at inference and are
+//! skipped, and an `Activation` layer sets the activation of the `Dense`
+//! layer before it.
+
+use crate::activation::LeakyRelu;
+use crate::{ActivationFunction, BasicNeuron, Layer, Network};
+use serde::Deserialize;
+
+/// An error importing a Keras model.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("{0} models are not supported, only Sequential models")]
+    UnsupportedModel(String),
+
+    #[error("layer {layer} is a {class} layer, which is not supported")]
+    UnsupportedLayer { layer: usize, class: String },
+
+    #[error("layer {layer} uses the {activation} activation, which is not supported")]
+    UnsupportedActivation { layer: usize, activation: String },
+
+    #[error("activation layer {layer} does not follow a dense layer with a linear activation")]
+    StrayActivation { layer: usize },
+
+    #[error("the model has {expected} dense layers, but {found} sets of weights were given")]
+    WeightsCount { expected: usize, found: usize },
+
+    #[error("the weights of layer {layer} do not fit its {units} units")]
+    Shape { layer: usize, units: usize },
+
+    #[error("the imported network is invalid: {0}")]
+    Network(#[from] crate::Error),
+}
+
+/// A model, as written by `model.to_json()`.
+#[derive(Deserialize)]
+struct Model {
+    class_name: String,
+    config: ModelConfig,
+}
+
+/// The part of a model's configuration that describes its layers.
+#[derive(Deserialize)]
+struct ModelConfig {
+    layers: Vec<KerasLayer>,
+}
+
+/// A layer of a model.
+#[derive(Deserialize)]
+struct KerasLayer {
+    class_name: String,
+
+    #[serde(default)]
+    config: LayerConfig,
+}
+
+/// The part of a layer's configuration that affects its outputs.
+#[derive(Default, Deserialize)]
+struct LayerConfig {
+    units: Option<usize>,
+    activation: Option<String>,
+    use_bias: Option<bool>,
+}
+
+/// The weights of a `Dense` layer, as returned by `layer.get_weights()`.
+///
+/// The kernel has one row per input and one column per unit.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DenseWeights {
+    KernelBias((Vec<Vec<f64>>, Vec<f64>)),
+    Kernel((Vec<Vec<f64>>,)),
+}
+
+/// Import a Keras model.
+///
+/// # Arguments
+///
+/// - `architecture` is the JSON written by `model.to_json()`.
+/// - `weights` is the JSON array of each `Dense` layer's weights.
+///
+/// # Returns
+///
+/// A network of basic neurons, one layer per `Dense` layer.
+///
+/// # Errors
+///
+/// If either input is not valid JSON of the expected shape, or the model
+/// uses layers or activations that have no equivalent here.
+pub fn from_json(architecture: &str, weights: &str) -> Result<Network, Error> {
+    let model: Model = serde_json::from_str(architecture)?;
+    if model.class_name != "Sequential" {
+        return Err(Error::UnsupportedModel(model.class_name));
+    }
+    let weights: Vec<DenseWeights> = serde_json::from_str(weights)?;
+    let expected = model
+        .config
+        .layers
+        .iter()
+        .filter(|layer| layer.class_name == "Dense")
+        .count();
+    if weights.len() != expected {
+        return Err(Error::WeightsCount {
+            expected,
+            found: weights.len(),
+        });
+    }
+
+    let mut weights = weights.into_iter();
+    let mut layers: Vec<Vec<BasicNeuron>> = vec![];
+    for (index, layer) in model.config.layers.into_iter().enumerate() {
+        match layer.class_name.as_str() {
+            "InputLayer" | "Dropout" | "Flatten" => {}
+            "Dense" => {
+                let activation = activation(index, layer.config.activation.as_deref())?;
+                let Some(weights) = weights.next() else {
+                    unreachable!("the weights were counted")
+                };
+                let neurons = dense(index, &layer.config, weights, &activation)?;
+                layers.push(neurons);
+            }
+            "Activation" => {
+                let activation = activation(index, layer.config.activation.as_deref())?;
+                let Some(neurons) = layers
+                    .last_mut()
+                    .filter(|neurons| neurons.iter().all(is_linear))
+                else {
+                    return Err(Error::StrayActivation { layer: index });
+                };
+                for neuron in neurons {
+                    *neuron = BasicNeuron::builder()
+                        .weights(neuron.weights().to_vec())
+                        .bias(neuron.bias())
+                        .activation(activation.clone())
+                        .build();
+                }
+            }
+            _ => {
+                return Err(Error::UnsupportedLayer {
+                    layer: index,
+                    class: layer.class_name,
+                })
+            }
+        }
+    }
+
+    let network = Network::builder()
+        .layers(
+            layers
+                .into_iter()
+                .map(|neurons| {
+                    Layer::builder()
+                        .neurons(neurons.into_iter().map(Into::into).collect())
+                        .build()
+                })
+                .collect(),
+        )
+        .build();
+    network.validate()?;
+    Ok(network)
+}
+
+/// Build the neurons of a `Dense` layer.
+///
+/// # Arguments
+///
+/// - `layer` is the index of the layer in the model.
+/// - `config` is the layer's configuration.
+/// - `weights` are the layer's kernel and bias.
+/// - `activation` is the activation function of every neuron.
+///
+/// # Returns
+///
+/// One neuron per unit.
+///
+/// # Errors
+///
+/// If the kernel or bias does not have one column per unit.
+fn dense(
+    layer: usize,
+    config: &LayerConfig,
+    weights: DenseWeights,
+    activation: &ActivationFunction,
+) -> Result<Vec<BasicNeuron>, Error> {
+    let (kernel, bias) = match weights {
+        DenseWeights::KernelBias((kernel, bias)) => (kernel, Some(bias)),
+        DenseWeights::Kernel((kernel,)) => (kernel, None),
+    };
+    let units = config
+        .units
+        .or_else(|| kernel.first().map(Vec::len))
+        .unwrap_or(0);
+    if kernel.iter().any(|row| row.len() != units)
+        || bias.as_ref().is_some_and(|bias| bias.len() != units)
+        || bias.is_some() != config.use_bias.unwrap_or(true)
+    {
+        return Err(Error::Shape { layer, units });
+    }
+
+    Ok((0..units)
+        .map(|unit| {
+            BasicNeuron::builder()
+                .weights(kernel.iter().map(|row| row[unit]).collect())
+                .bias(bias.as_ref().map_or(0.0, |bias| bias[unit]))
+                .activation(activation.clone())
+                .build()
+        })
+        .collect())
+}
+
+/// Find the activation function with a Keras name.
+///
+/// # Arguments
+///
+/// - `layer` is the index of the layer in the model.
+/// - `name` is the name; a missing name is linear, as in Keras.
+///
+/// # Returns
+///
+/// The activation function.
+///
+/// # Errors
+///
+/// If there is no equivalent activation function.
+fn activation(layer: usize, name: Option<&str>) -> Result<ActivationFunction, Error> {
+    Ok(match name {
+        None | Some("linear") => ActivationFunction::linear(),
+        Some("sigmoid") => ActivationFunction::sigmoid(),
+        Some("tanh") => ActivationFunction::tanh(),
+        Some("relu") => ActivationFunction::LeakyRelu(LeakyRelu::new(0.0)),
+        // The default slope of `keras.activations.leaky_relu`.
+        Some("leaky_relu") => ActivationFunction::LeakyRelu(LeakyRelu::new(0.2)),
+        Some(activation) => {
+            return Err(Error::UnsupportedActivation {
+                layer,
+                activation: activation.to_string(),
+            })
+        }
+    })
+}
+
+/// Check whether a neuron passes its sum through unchanged.
+fn is_linear(neuron: &BasicNeuron) -> bool {
+    matches!(neuron.activation(), ActivationFunction::Linear(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn architecture(layers: &str) -> String {
+        format!(r#"{{"class_name":"Sequential","config":{{"name":"model","layers":[{layers}]}}}}"#)
+    }
+
+    #[test]
+    fn test_from_json() {
+        let architecture = architecture(
+            r#"
+            {"class_name":"InputLayer","config":{"batch_input_shape":[null,2]}},
+            {"class_name":"Dense","config":{"units":3,"activation":"relu","use_bias":true}},
+            {"class_name":"Dropout","config":{"rate":0.5}},
+            {"class_name":"Dense","config":{"units":1,"use_bias":false}},
+            {"class_name":"Activation","config":{"activation":"sigmoid"}}
+            "#,
+        );
+        let weights = r"[
+            [[[1.0, -1.0, 0.5], [2.0, 0.0, 0.5]], [0.0, 0.5, -1.0]],
+            [[[1.0], [1.0], [2.0]]]
+        ]";
+
+        let network = from_json(&architecture, weights).unwrap();
+
+        assert_eq!(network.shape(), vec![3, 1]);
+        let first = &network.layers()[0].neurons()[1];
+        assert_eq!(first.weights(), &[-1.0, 0.0]);
+        assert!((first.bias() - 0.5).abs() < f64::EPSILON);
+
+        // The hidden layer gives [5.0, 0.0, 0.5], so the sum is 6.0.
+        let output = network.activate(&[1.0, 2.0])[0];
+        assert!((output - 1.0 / (1.0 + (-6.0f64).exp())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rejects_unsupported() {
+        let conv = architecture(r#"{"class_name":"Conv2D","config":{"filters":4}}"#);
+        assert!(matches!(
+            from_json(&conv, "[]"),
+            Err(Error::UnsupportedLayer { layer: 0, .. })
+        ));
+
+        let softmax =
+            architecture(r#"{"class_name":"Dense","config":{"units":1,"activation":"softmax"}}"#);
+        assert!(matches!(
+            from_json(&softmax, "[[[[1.0]], [0.0]]]"),
+            Err(Error::UnsupportedActivation { layer: 0, .. })
+        ));
+
+        let dense = architecture(r#"{"class_name":"Dense","config":{"units":2}}"#);
+        assert!(matches!(
+            from_json(&dense, "[]"),
+            Err(Error::WeightsCount {
+                expected: 1,
+                found: 0
+            })
+        ));
+        assert!(matches!(
+            from_json(&dense, "[[[[1.0]], [0.0]]]"),
+            Err(Error::Shape { layer: 0, units: 2 })
+        ));
+
+        let functional = r#"{"class_name":"Functional","config":{"layers":[]}}"#;
+        assert!(matches!(
+            from_json(functional, "[]"),
+            Err(Error::UnsupportedModel(_))
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Neuron, NeuronActivate};
+use crate::{Error, Neuron, NeuronActivate, Result};
 use serde::{Deserialize, Serialize};
 
 /// A layer of neurons.
@@ -49,6 +49,37 @@ impl Layer {
         self.neurons.iter().map(|n| n.activate(inputs)).collect()
     }
 
+    /// Activate the layer, writing the outputs into an existing buffer so its
+    /// allocation can be reused.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` to activate the layer with.
+    /// - `outputs` is cleared, then filled with the output of the layer.
+    pub fn activate_into(&self, inputs: &[f64], outputs: &mut Vec<f64>) {
+        outputs.clear();
+        outputs.extend(self.neurons.iter().map(|n| n.activate(inputs)));
+    }
+
+    /// Activate the layer as one step of a sequence, so recurrent neurons
+    /// remember their outputs for the next step.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` to activate the layer with.
+    ///
+    /// # Returns
+    ///
+    /// The output of the layer.
+    pub fn step(&mut self, inputs: &[f64]) -> Vec<f64> {
+        self.neurons.iter_mut().map(|n| n.step(inputs)).collect()
+    }
+
+    /// Forget the state of every recurrent neuron in the layer.
+    pub fn reset(&mut self) {
+        self.neurons.iter_mut().for_each(Neuron::reset);
+    }
+
     /// Get a reference to the set of neurons.
     ///
     /// # Returns
@@ -69,6 +100,100 @@ impl Layer {
     pub fn neurons(&self) -> &[Neuron] {
         &self.neurons
     }
+
+    /// Get a mutable reference to the set of neurons, e.g. to edit their
+    /// weights. Neurons can be changed but not added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the set of neurons.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{Layer, BasicNeuron};
+    ///
+    /// let neuron = BasicNeuron::builder().bias(1.0).build();
+    /// let mut layer = Layer::builder().add_neuron(neuron).build();
+    ///
+    /// layer.neurons_mut()[0].set_bias(2.0);
+    ///
+    /// assert_eq!(layer.neurons()[0].bias(), 2.0);
+    /// ```
+    pub fn neurons_mut(&mut self) -> &mut [Neuron] {
+        &mut self.neurons
+    }
+
+    /// Take the neurons out of the layer.
+    ///
+    /// # Returns
+    ///
+    /// The neurons.
+    pub(crate) fn into_neurons(self) -> Vec<Neuron> {
+        self.neurons
+    }
+
+    /// Check that every neuron can take the same inputs.
+    ///
+    /// # Arguments
+    ///
+    /// - `layer` is the index of the layer, for error messages.
+    /// - `inputs` is the number of inputs the layer receives, if known.
+    ///   Otherwise, every neuron must match the first one.
+    ///
+    /// # Returns
+    ///
+    /// The number of inputs the layer takes.
+    ///
+    /// # Errors
+    ///
+    /// If the layer has no neurons, a neuron has no weights or the wrong
+    /// number of them, or an LSTM gate has the wrong number of weights.
+    pub(crate) fn check(&self, layer: usize, inputs: Option<usize>) -> Result<usize> {
+        let first = self.neurons.first().ok_or(Error::EmptyLayer { layer })?;
+        let expected = inputs.unwrap_or_else(|| first.weights().len());
+        if expected == 0 {
+            return Err(Error::NoWeights);
+        }
+
+        for (neuron, found) in self.neurons.iter().map(|n| n.weights().len()).enumerate() {
+            if found != expected {
+                return Err(Error::WeightCount {
+                    layer,
+                    neuron,
+                    expected,
+                    found,
+                });
+            }
+        }
+        // A gate without weights ignores the inputs; any other gate must
+        // weigh every one of them.
+        for (neuron, lstm) in self
+            .neurons
+            .iter()
+            .enumerate()
+            .filter_map(|(index, neuron)| {
+                let Neuron::Lstm(lstm) = neuron else {
+                    return None;
+                };
+                Some((index, lstm))
+            })
+        {
+            let gates = [lstm.input_gate(), lstm.forget_gate(), lstm.output_gate()];
+            if let Some(gate) = gates
+                .iter()
+                .find(|gate| !gate.weights.is_empty() && gate.weights.len() != expected)
+            {
+                return Err(Error::GateWeightCount {
+                    layer,
+                    neuron,
+                    expected,
+                    found: gate.weights.len(),
+                });
+            }
+        }
+        Ok(expected)
+    }
 }
 
 /// A builder for `Layer`s.
@@ -183,6 +308,42 @@ impl Builder {
         let Self { neurons } = self;
         Layer { neurons }
     }
+
+    /// Build the layer, checking that its neurons can share the same inputs.
+    ///
+    /// # Returns
+    ///
+    /// The built layer.
+    ///
+    /// # Errors
+    ///
+    /// If the layer has no neurons, or its neurons have no weights or
+    /// different numbers of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, Error, Layer};
+    ///
+    /// let neuron = |weights| BasicNeuron::builder().weights(weights).build();
+    ///
+    /// assert_eq!(
+    ///     Layer::builder().try_build(),
+    ///     Err(Error::EmptyLayer { layer: 0 })
+    /// );
+    /// assert_eq!(
+    ///     Layer::builder()
+    ///         .add_neuron(neuron(vec![1.0, 1.0]))
+    ///         .add_neuron(neuron(vec![1.0]))
+    ///         .try_build(),
+    ///     Err(Error::WeightCount { layer: 0, neuron: 1, expected: 2, found: 1 })
+    /// );
+    /// ```
+    pub fn try_build(self) -> Result<Layer> {
+        let layer = self.build();
+        layer.check(0, None)?;
+        Ok(layer)
+    }
 }
 
 #[cfg(test)]
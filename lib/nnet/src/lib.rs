@@ -8,13 +8,28 @@
 )]
 
 pub mod activation;
+mod error;
+pub mod format;
+pub mod graph;
+pub mod keras;
 pub mod layer;
 pub mod network;
 pub mod neuron;
+pub mod noise;
+pub mod quant;
+pub mod stats;
+pub mod train;
 
 pub use crate::{
     activation::{Activate, Function as ActivationFunction},
+    error::{Error, Result},
     layer::Layer,
-    network::Network,
-    neuron::{Activate as NeuronActivate, Basic as BasicNeuron, Neuron},
+    network::{ActivationBuffers, InputSource, Network, NetworkDiff},
+    neuron::{
+        Activate as NeuronActivate, Basic as BasicNeuron, Lstm as LstmNeuron, Neuron,
+        Recurrent as RecurrentNeuron,
+    },
+    noise::{Mode as NoiseMode, Noise, Perturbation},
+    stats::{NetworkStats, WeightStats},
+    train::Trainer,
 };
@@ -0,0 +1,366 @@
+use super::Network;
+use crate::Neuron;
+
+/// A weight that two networks both have, but with different values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightChange {
+    /// The index of the layer.
+    pub layer: usize,
+
+    /// The index of the neuron in its layer.
+    pub neuron: usize,
+
+    /// The index of the weight.
+    pub weight: usize,
+
+    /// The weight in the first network.
+    pub from: f64,
+
+    /// The weight in the second network.
+    pub to: f64,
+}
+
+/// A bias that two networks both have, but with different values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BiasChange {
+    /// The index of the layer.
+    pub layer: usize,
+
+    /// The index of the neuron in its layer.
+    pub neuron: usize,
+
+    /// The bias in the first network.
+    pub from: f64,
+
+    /// The bias in the second network.
+    pub to: f64,
+}
+
+/// How one network differs from another.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NetworkDiff {
+    /// The weights that changed, among neurons whose structure did not.
+    pub weights: Vec<WeightChange>,
+
+    /// The biases that changed, among neurons whose structure did not.
+    pub biases: Vec<BiasChange>,
+
+    /// The neurons, as `(layer, neuron)`, that only one network has, or whose
+    /// kind, activation function, number of weights, feedback weight, or
+    /// gates changed.
+    pub neurons: Vec<(usize, usize)>,
+
+    /// The layers that only one network has, or whose inputs come from
+    /// different layers.
+    pub layers: Vec<usize>,
+}
+
+impl NetworkDiff {
+    /// Check whether the networks are the same.
+    ///
+    /// # Returns
+    ///
+    /// True if nothing changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+            && self.biases.is_empty()
+            && self.neurons.is_empty()
+            && self.layers.is_empty()
+    }
+
+    /// Measure how far the weights and biases moved.
+    ///
+    /// # Returns
+    ///
+    /// The sum of the magnitudes of the weight and bias changes. Structural
+    /// changes are not included.
+    #[must_use]
+    pub fn distance(&self) -> f64 {
+        let weights = self
+            .weights
+            .iter()
+            .map(|change| (change.to - change.from).abs());
+        let biases = self
+            .biases
+            .iter()
+            .map(|change| (change.to - change.from).abs());
+        weights.chain(biases).sum()
+    }
+}
+
+impl Network {
+    /// Check whether two networks are the same, allowing their parameters to
+    /// differ slightly, e.g. after a round trip through `f32`.
+    ///
+    /// # Arguments
+    ///
+    /// - `other` is the network to compare with.
+    /// - `epsilon` is how far apart two parameters may be.
+    ///
+    /// # Returns
+    ///
+    /// True if the networks have the same structure and activation functions,
+    /// and every weight, bias, feedback weight, and gate parameter is within
+    /// `epsilon` of its counterpart. Parameters that are not a number are
+    /// never equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, Layer, Network};
+    ///
+    /// let network = |weight| {
+    ///     let neuron = BasicNeuron::builder().weights(vec![weight]).build();
+    ///     Network::builder()
+    ///         .add_layer(Layer::builder().add_neuron(neuron).build())
+    ///         .build()
+    /// };
+    ///
+    /// assert!(network(0.5).approx_eq(&network(0.5 + 1e-9), 1e-6));
+    /// assert!(!network(0.5).approx_eq(&network(0.6), 1e-6));
+    /// ```
+    #[must_use]
+    pub fn approx_eq(&self, other: &Network, epsilon: f64) -> bool {
+        let close = |left: &[f64], right: &[f64]| {
+            left.len() == right.len()
+                && Iterator::zip(left.iter(), right)
+                    .all(|(left, right)| (left - right).abs() <= epsilon)
+        };
+        self.layers.len() == other.layers.len()
+            && (0..self.layers.len()).all(|layer| {
+                let (left, right) = (&self.layers[layer], &other.layers[layer]);
+                self.input_source(layer) == other.input_source(layer)
+                    && left.neurons().len() == right.neurons().len()
+                    && Iterator::zip(left.neurons().iter(), right.neurons()).all(|(left, right)| {
+                        same_kind(left, right)
+                            && close(left.weights(), right.weights())
+                            && close(&[left.bias()], &[right.bias()])
+                            && close(&extras(left), &extras(right))
+                    })
+            })
+    }
+
+    /// List how another network differs from this one, e.g. to see how far
+    /// an offspring moved from its parent.
+    ///
+    /// # Arguments
+    ///
+    /// - `other` is the network to compare with.
+    ///
+    /// # Returns
+    ///
+    /// The differences, in order of layer and neuron.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, Layer, Network};
+    ///
+    /// let network = |weights: Vec<f64>| {
+    ///     let neuron = BasicNeuron::builder().weights(weights).bias(0.5).build();
+    ///     Network::builder()
+    ///         .add_layer(Layer::builder().add_neuron(neuron).build())
+    ///         .build()
+    /// };
+    ///
+    /// let diff = network(vec![1.0, 2.0]).diff(&network(vec![1.0, 2.5]));
+    ///
+    /// assert_eq!(diff.weights.len(), 1);
+    /// assert_eq!((diff.weights[0].weight, diff.weights[0].to), (1, 2.5));
+    /// assert!(diff.biases.is_empty());
+    /// assert_eq!(diff.distance(), 0.5);
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &Network) -> NetworkDiff {
+        let mut diff = NetworkDiff::default();
+        for layer in 0..self.layers.len().max(other.layers.len()) {
+            let (Some(left), Some(right)) = (self.layers.get(layer), other.layers.get(layer))
+            else {
+                diff.layers.push(layer);
+                continue;
+            };
+            if self.input_source(layer) != other.input_source(layer) {
+                diff.layers.push(layer);
+            }
+
+            for neuron in 0..left.neurons().len().max(right.neurons().len()) {
+                let (Some(left), Some(right)) =
+                    (left.neurons().get(neuron), right.neurons().get(neuron))
+                else {
+                    diff.neurons.push((layer, neuron));
+                    continue;
+                };
+                let same_extras = Iterator::eq(
+                    extras(left).iter().map(|value| value.to_bits()),
+                    extras(right).iter().map(|value| value.to_bits()),
+                );
+                if !same_kind(left, right)
+                    || left.weights().len() != right.weights().len()
+                    || !same_extras
+                {
+                    diff.neurons.push((layer, neuron));
+                    continue;
+                }
+
+                for (weight, (from, to)) in
+                    Iterator::zip(left.weights().iter(), right.weights()).enumerate()
+                {
+                    if from.to_bits() != to.to_bits() {
+                        diff.weights.push(WeightChange {
+                            layer,
+                            neuron,
+                            weight,
+                            from: *from,
+                            to: *to,
+                        });
+                    }
+                }
+                if left.bias().to_bits() != right.bias().to_bits() {
+                    diff.biases.push(BiasChange {
+                        layer,
+                        neuron,
+                        from: left.bias(),
+                        to: right.bias(),
+                    });
+                }
+            }
+        }
+        diff
+    }
+}
+
+/// Check whether two neurons are of the same kind with the same activation
+/// function.
+///
+/// # Arguments
+///
+/// - `left` is the first neuron.
+/// - `right` is the second neuron.
+///
+/// # Returns
+///
+/// True if they match.
+fn same_kind(left: &Neuron, right: &Neuron) -> bool {
+    std::mem::discriminant(left) == std::mem::discriminant(right)
+        && left.activator() == right.activator()
+}
+
+/// Get a neuron's parameters other than its weights and bias.
+///
+/// # Arguments
+///
+/// - `neuron` is the neuron.
+///
+/// # Returns
+///
+/// The feedback weight, followed by the weights, recurrent weight, and bias
+/// of each gate; empty for a basic neuron.
+fn extras(neuron: &Neuron) -> Vec<f64> {
+    let mut extras = neuron.feedback().into_iter().collect::<Vec<_>>();
+    if let Neuron::Lstm(lstm) = neuron {
+        for gate in [lstm.input_gate(), lstm.forget_gate(), lstm.output_gate()] {
+            extras.extend(&gate.weights);
+            extras.extend([gate.recurrent, gate.bias]);
+        }
+    }
+    extras
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActivationFunction, BasicNeuron, InputSource, Layer, RecurrentNeuron};
+
+    fn basic(weights: &[f64], bias: f64) -> Neuron {
+        BasicNeuron::builder()
+            .weights(weights.to_vec())
+            .bias(bias)
+            .activation(ActivationFunction::linear())
+            .build()
+            .into()
+    }
+
+    fn network(layers: Vec<Vec<Neuron>>) -> Network {
+        Network::builder()
+            .layers(
+                layers
+                    .into_iter()
+                    .map(|neurons| Layer::builder().neurons(neurons).build())
+                    .collect(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let left = network(vec![vec![basic(&[1.0, 2.0], 0.5)]]);
+
+        assert!(left.approx_eq(&network(vec![vec![basic(&[1.0, 2.0], 0.5)]]), 0.0));
+        assert!(left.approx_eq(&network(vec![vec![basic(&[1.001, 2.0], 0.499)]]), 0.01));
+        assert!(!left.approx_eq(&network(vec![vec![basic(&[1.0, 2.0], 0.6)]]), 0.01));
+        assert!(!left.approx_eq(&network(vec![vec![basic(&[1.0], 0.5)]]), 0.01));
+        assert!(!left.approx_eq(&network(vec![vec![basic(&[f64::NAN, 2.0], 0.5)]]), 1.0));
+
+        let recurrent = |feedback| {
+            network(vec![vec![RecurrentNeuron::builder()
+                .weights(vec![1.0])
+                .feedback(feedback)
+                .build()
+                .into()]])
+        };
+        assert!(recurrent(0.5).approx_eq(&recurrent(0.501), 0.01));
+        assert!(!recurrent(0.5).approx_eq(&recurrent(0.6), 0.01));
+        assert!(!recurrent(0.5).approx_eq(&network(vec![vec![basic(&[1.0], 0.0)]]), 1.0));
+    }
+
+    #[test]
+    fn test_diff() {
+        let left = network(vec![
+            vec![basic(&[1.0], 0.0), basic(&[1.0], 0.0)],
+            vec![basic(&[1.0, 1.0], 0.0)],
+        ]);
+        let right = Network::builder()
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(basic(&[1.0], 0.25))
+                    .add_neuron(basic(&[-1.0], 0.0))
+                    .add_neuron(basic(&[1.0], 0.0))
+                    .build(),
+            )
+            .add_layer_with_input(
+                Layer::builder()
+                    .add_neuron(basic(&[1.0, 3.0, 1.0], 0.0))
+                    .build(),
+                InputSource::Layers(vec![0]),
+            )
+            .add_layer(Layer::builder().add_neuron(basic(&[1.0], 0.0)).build())
+            .build();
+
+        let diff = left.diff(&right);
+
+        assert_eq!(
+            diff.weights,
+            vec![WeightChange {
+                layer: 0,
+                neuron: 1,
+                weight: 0,
+                from: 1.0,
+                to: -1.0,
+            }]
+        );
+        assert_eq!(
+            diff.biases,
+            vec![BiasChange {
+                layer: 0,
+                neuron: 0,
+                from: 0.0,
+                to: 0.25,
+            }]
+        );
+        assert_eq!(diff.neurons, vec![(0, 2), (1, 0)]);
+        assert_eq!(diff.layers, vec![1, 2]);
+        assert!((diff.distance() - 2.25).abs() < f64::EPSILON);
+        assert!(left.diff(&left).is_empty());
+    }
+}
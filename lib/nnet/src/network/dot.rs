@@ -0,0 +1,148 @@
+use super::{gather, Network};
+use std::fmt::Write;
+
+impl Network {
+    /// Render the network as a Graphviz graph, to inspect its topology.
+    ///
+    /// Inputs are boxes, and each neuron is labeled with its activation
+    /// function and bias. Edges are labeled with their weight, drawn thicker
+    /// for larger weights, and colored red for negative weights. Recurrent
+    /// neurons get a dashed loop labeled with their feedback weight, and skip
+    /// connections are drawn from the layers they read.
+    ///
+    /// # Returns
+    ///
+    /// The graph in the DOT language.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{ActivationFunction, BasicNeuron, Layer, Network};
+    ///
+    /// let neuron = BasicNeuron::builder()
+    ///     .weights(vec![1.0, -2.0])
+    ///     .bias(0.5)
+    ///     .activation(ActivationFunction::sigmoid())
+    ///     .build();
+    /// let network = Network::builder()
+    ///     .add_layer(Layer::builder().add_neuron(neuron).build())
+    ///     .build();
+    ///
+    /// let dot = network.to_dot();
+    ///
+    /// assert!(dot.starts_with("digraph network {"));
+    /// assert!(dot.contains("l0n0 [label=\"sigmoid\\nbias 0.500\"];"));
+    /// assert!(dot.contains("i1 -> l0n0 [label=\"-2.000\", penwidth=2.500, color=red];"));
+    /// ```
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut dot =
+            String::from("digraph network {\n    rankdir=LR;\n    node [shape=circle];\n");
+        let inputs = self.input_size().unwrap_or(0);
+
+        // Writing to a `String` cannot fail.
+        let _ = writeln!(dot, "    subgraph inputs {{\n        rank=same;");
+        for index in 0..inputs {
+            let _ = writeln!(dot, "        i{index} [label=\"in {index}\", shape=box];");
+        }
+        let _ = writeln!(dot, "    }}");
+
+        let inputs = (0..inputs)
+            .map(|index| format!("i{index}"))
+            .collect::<Vec<_>>();
+        let mut outputs: Vec<Vec<String>> = Vec::with_capacity(self.layers.len());
+        for (l_index, layer) in self.layers.iter().enumerate() {
+            let _ = writeln!(dot, "    subgraph layer_{l_index} {{\n        rank=same;");
+            let targets = (0..layer.neurons().len())
+                .map(|n_index| format!("l{l_index}n{n_index}"))
+                .collect::<Vec<_>>();
+            for (target, neuron) in Iterator::zip(targets.iter(), layer.neurons()) {
+                let _ = writeln!(
+                    dot,
+                    "        {target} [label=\"{}\\nbias {:.3}\"];",
+                    neuron.activator(),
+                    neuron.bias()
+                );
+            }
+            let _ = writeln!(dot, "    }}");
+
+            let mut sources = vec![];
+            gather(self.input_source(l_index), &inputs, &outputs, &mut sources);
+            for (target, neuron) in Iterator::zip(targets.iter(), layer.neurons()) {
+                for (source, weight) in Iterator::zip(sources.iter(), neuron.weights()) {
+                    edge(&mut dot, source, target, *weight, "");
+                }
+                if let Some(feedback) = neuron.feedback() {
+                    edge(&mut dot, target, target, feedback, ", style=dashed");
+                }
+            }
+            outputs.push(targets);
+        }
+
+        dot.push('}');
+        dot
+    }
+}
+
+/// Write a weighted edge.
+///
+/// # Arguments
+///
+/// - `dot` is the graph to write to.
+/// - `source` is the node the edge starts at.
+/// - `target` is the node the edge ends at.
+/// - `weight` labels the edge and sets its width and color.
+/// - `attributes` are extra attributes, each starting with a comma.
+fn edge(dot: &mut String, source: &str, target: &str, weight: f64, attributes: &str) {
+    let color = if weight < 0.0 { "red" } else { "black" };
+    let _ = writeln!(
+        dot,
+        "    {source} -> {target} [label=\"{weight:.3}\", penwidth={:.3}, color={color}{attributes}];",
+        0.5 + weight.abs().min(4.0)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActivationFunction, BasicNeuron, InputSource, Layer, RecurrentNeuron};
+
+    #[test]
+    fn test_to_dot() {
+        let neuron = |weights: Vec<f64>| {
+            BasicNeuron::builder()
+                .weights(weights)
+                .activation(ActivationFunction::linear())
+                .build()
+        };
+        let network = Network::builder()
+            .add_layer(Layer::builder().add_neuron(neuron(vec![1.0])).build())
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(
+                        RecurrentNeuron::builder()
+                            .weights(vec![0.5])
+                            .feedback(-0.25)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .add_layer_with_input(
+                Layer::builder().add_neuron(neuron(vec![2.0, 3.0])).build(),
+                InputSource::Layers(vec![0, 1]),
+            )
+            .build();
+
+        let dot = network.to_dot();
+
+        assert!(dot.contains("i0 [label=\"in 0\", shape=box];"));
+        assert!(dot.contains("l0n0 [label=\"linear\\nbias 0.000\"];"));
+        assert!(dot.contains("i0 -> l0n0 [label=\"1.000\", penwidth=1.500, color=black];"));
+        assert!(dot.contains("l0n0 -> l1n0 [label=\"0.500\""));
+        assert!(dot
+            .contains("l1n0 -> l1n0 [label=\"-0.250\", penwidth=0.750, color=red, style=dashed];"));
+        assert!(dot.contains("l0n0 -> l2n0 [label=\"2.000\""));
+        assert!(dot.contains("l1n0 -> l2n0 [label=\"3.000\""));
+        assert!(dot.ends_with('}'));
+    }
+}
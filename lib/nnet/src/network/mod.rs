@@ -1,8 +1,22 @@
-use crate::Layer;
+mod diff;
+mod dot;
+mod prune;
+mod schema;
+
+pub use diff::{BiasChange, NetworkDiff, WeightChange};
+
+use crate::format::{self, Format};
+use crate::keras;
+use crate::{
+    ActivationFunction, BasicNeuron, Error, Layer, NetworkStats, Neuron, Result, WeightStats,
+};
 use serde::{Deserialize, Serialize};
 
 /// A neural network.
 ///
+/// The serialized form carries a schema version, so networks written by older
+/// versions of the crate keep loading; see [`Network::SCHEMA_VERSION`].
+///
 /// # Examples
 ///
 /// ```
@@ -17,9 +31,105 @@ use serde::{Deserialize, Serialize};
 ///
 /// assert_eq!(outputs.len(), 1);
 /// ```
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(try_from = "schema::Stored")]
 pub struct Network {
     layers: Vec<Layer>,
+
+    /// Where each layer takes its inputs from, by index. Layers past the end
+    /// take the output of the layer before them.
+    sources: Vec<InputSource>,
+}
+
+/// Where a layer of a [`Network`] takes its inputs from.
+///
+/// # Examples
+///
+/// ```
+/// use nnet::{ActivationFunction, BasicNeuron, InputSource, Layer, Network};
+///
+/// let layer = |weights: Vec<f64>| {
+///     let neuron = BasicNeuron::builder()
+///         .weights(weights)
+///         .activation(ActivationFunction::linear())
+///         .build();
+///     Layer::builder().add_neuron(neuron).build()
+/// };
+///
+/// // The last layer sees both the first and the second layer's outputs.
+/// let network = Network::builder()
+///     .add_layer(layer(vec![1.0]))
+///     .add_layer(layer(vec![2.0]))
+///     .add_layer_with_input(layer(vec![1.0, 1.0]), InputSource::Layers(vec![0, 1]))
+///     .build();
+///
+/// assert_eq!(network.activate(&[3.0]), vec![9.0]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputSource {
+    /// The output of the previous layer, or the network's inputs for the
+    /// first layer.
+    #[default]
+    Previous,
+
+    /// The outputs of earlier layers, by index, concatenated in the order
+    /// given. Listing the previous layer and an earlier one makes a skip, or
+    /// residual, connection.
+    Layers(Vec<usize>),
+}
+
+/// The source of layers that do not name one.
+static PREVIOUS: InputSource = InputSource::Previous;
+
+/// Collect the inputs of a layer.
+///
+/// # Arguments
+///
+/// - `source` is where the layer takes its inputs from.
+/// - `inputs` are the inputs of the network.
+/// - `outputs` are the outputs of the layers before this one.
+/// - `values` is filled with the inputs of the layer. Layers that do not
+///   exist contribute nothing.
+pub(crate) fn gather<T: Clone>(
+    source: &InputSource,
+    inputs: &[T],
+    outputs: &[Vec<T>],
+    values: &mut Vec<T>,
+) {
+    match source {
+        InputSource::Previous => {
+            values.extend_from_slice(outputs.last().map_or(inputs, Vec::as_slice));
+        }
+        InputSource::Layers(layers) => {
+            for output in layers.iter().filter_map(|layer| outputs.get(*layer)) {
+                values.extend_from_slice(output);
+            }
+        }
+    }
+}
+
+/// Make a layer that passes its inputs through unchanged.
+///
+/// # Arguments
+///
+/// - `width` is the number of inputs, and of neurons.
+///
+/// # Returns
+///
+/// A layer of linear neurons, each with a weight of one on its own input.
+fn identity(width: usize) -> Layer {
+    let neurons = (0..width)
+        .map(|index| {
+            let mut weights = vec![0.0; width];
+            weights[index] = 1.0;
+            BasicNeuron::builder()
+                .weights(weights)
+                .activation(ActivationFunction::linear())
+                .build()
+                .into()
+        })
+        .collect();
+    Layer::builder().neurons(neurons).build()
 }
 
 impl Network {
@@ -67,9 +177,180 @@ impl Network {
     /// ```
     #[must_use]
     pub fn activate(&self, inputs: &[f64]) -> Vec<f64> {
-        self.layers
+        if !self.has_skips() {
+            return self
+                .layers
+                .iter()
+                .fold(inputs.to_vec(), |values, layer| layer.activate(&values));
+        }
+
+        let mut outputs = Vec::with_capacity(self.layers.len());
+        for (index, layer) in self.layers.iter().enumerate() {
+            let mut values = vec![];
+            gather(self.input_source(index), inputs, &outputs, &mut values);
+            outputs.push(layer.activate(&values));
+        }
+        outputs.pop().unwrap_or_else(|| inputs.to_vec())
+    }
+
+    /// Activate the network without allocating, by reusing buffers across
+    /// calls. This is worthwhile when activating many times, e.g. once per
+    /// record while scoring a population.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` to activate the network with.
+    /// - `scratch` holds the values passed between layers. Its contents are
+    ///   overwritten; once it has grown to fit the widest layer, no further
+    ///   allocations are made.
+    ///
+    /// # Returns
+    ///
+    /// The output of the network, borrowed from `scratch`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{ActivationBuffers, BasicNeuron, Layer, Network};
+    ///
+    /// let neuron = BasicNeuron::builder().weights(vec![1.0, 1.0]).build();
+    /// let layer = Layer::builder().add_neuron(neuron).build();
+    /// let network = Network::builder().add_layer(layer).build();
+    /// let mut scratch = ActivationBuffers::default();
+    ///
+    /// for inputs in [[0.0, 0.0], [1.0, -1.0]] {
+    ///     let outputs = network.activate_into(&inputs, &mut scratch);
+    ///     assert_eq!(outputs, network.activate(&inputs).as_slice());
+    /// }
+    /// ```
+    pub fn activate_into<'a>(
+        &self,
+        inputs: &[f64],
+        scratch: &'a mut ActivationBuffers,
+    ) -> &'a [f64] {
+        let ActivationBuffers {
+            front,
+            back,
+            outputs,
+        } = scratch;
+        front.clear();
+        if !self.has_skips() {
+            front.extend_from_slice(inputs);
+            for layer in &self.layers {
+                layer.activate_into(front, back);
+                std::mem::swap(front, back);
+            }
+            return front;
+        }
+
+        // Skip connections need every layer's output, so keep them all.
+        outputs.resize_with(self.layers.len(), Vec::new);
+        for (index, layer) in self.layers.iter().enumerate() {
+            front.clear();
+            gather(self.input_source(index), inputs, &outputs[..index], front);
+            layer.activate_into(front, &mut outputs[index]);
+        }
+        &outputs[self.layers.len() - 1]
+    }
+
+    /// Activate the network, checking that it is well formed and that it was
+    /// given the right number of inputs.
+    ///
+    /// [`Network::activate`] pairs weights with inputs positionally, so extra
+    /// inputs are ignored and missing ones silently drop their weights. This
+    /// reports those mistakes instead.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` to activate the network with.
+    ///
+    /// # Returns
+    ///
+    /// The output of the network.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InputCount`] if the number of inputs does not match the
+    /// first layer's weights, or any error from [`Network::validate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, Error, Layer, Network};
+    ///
+    /// let neuron = BasicNeuron::builder().weights(vec![1.0, 1.0]).build();
+    /// let layer = Layer::builder().add_neuron(neuron).build();
+    /// let network = Network::builder().add_layer(layer).build();
+    ///
+    /// assert_eq!(network.try_activate(&[0.0, 0.0]), Ok(vec![0.5]));
+    /// assert_eq!(
+    ///     network.try_activate(&[0.0]),
+    ///     Err(Error::InputCount { expected: 2, found: 1 })
+    /// );
+    /// ```
+    pub fn try_activate(&self, inputs: &[f64]) -> Result<Vec<f64>> {
+        let expected = self.check()?;
+        if inputs.len() != expected {
+            return Err(Error::InputCount {
+                expected,
+                found: inputs.len(),
+            });
+        }
+        Ok(self.activate(inputs))
+    }
+
+    /// Activate the network on a sequence of inputs, such as a time series.
+    ///
+    /// Recurrent neurons feed each output into the next step, and keep their
+    /// state when the sequence ends, so a long series can be fed in parts.
+    /// Call [`Network::reset`] to start a new series. Networks without
+    /// recurrent neurons give the same outputs as [`Network::activate`].
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` are the inputs of each step, in order.
+    ///
+    /// # Returns
+    ///
+    /// The outputs of each step.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{ActivationFunction, Layer, Network, Neuron};
+    ///
+    /// let neuron = Neuron::recurrent()
+    ///     .weights(vec![1.0])
+    ///     .feedback(1.0)
+    ///     .activation(ActivationFunction::linear())
+    ///     .build();
+    /// let layer = Layer::builder().add_neuron(neuron).build();
+    /// let mut network = Network::builder().add_layer(layer).build();
+    ///
+    /// // A running total.
+    /// let outputs = network.activate_sequence(&[vec![1.0], vec![2.0], vec![3.0]]);
+    ///
+    /// assert_eq!(outputs, vec![vec![1.0], vec![3.0], vec![6.0]]);
+    /// ```
+    pub fn activate_sequence(&mut self, inputs: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        inputs
             .iter()
-            .fold(inputs.to_vec(), |values, layer| layer.activate(&values))
+            .map(|step| {
+                let mut outputs: Vec<Vec<f64>> = Vec::with_capacity(self.layers.len());
+                for (index, layer) in self.layers.iter_mut().enumerate() {
+                    let source = self.sources.get(index).unwrap_or(&PREVIOUS);
+                    let mut values = vec![];
+                    gather(source, step, &outputs, &mut values);
+                    outputs.push(layer.step(&values));
+                }
+                outputs.pop().unwrap_or_else(|| step.clone())
+            })
+            .collect()
+    }
+
+    /// Forget the state of every recurrent neuron, to start a new sequence.
+    pub fn reset(&mut self) {
+        self.layers.iter_mut().for_each(Layer::reset);
     }
 
     /// Get a reference to the set of layers.
@@ -94,6 +375,418 @@ impl Network {
         &self.layers
     }
 
+    /// Get where a layer takes its inputs from.
+    ///
+    /// # Arguments
+    ///
+    /// - `layer` is the index of the layer.
+    ///
+    /// # Returns
+    ///
+    /// The layer's source; [`InputSource::Previous`] unless the layer was
+    /// added with another one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{InputSource, Layer, Network};
+    ///
+    /// let network = Network::builder()
+    ///     .add_layer(Layer::builder().build())
+    ///     .add_layer_with_input(Layer::builder().build(), InputSource::Layers(vec![0]))
+    ///     .build();
+    ///
+    /// assert_eq!(network.input_source(0), &InputSource::Previous);
+    /// assert_eq!(network.input_source(1), &InputSource::Layers(vec![0]));
+    /// ```
+    #[must_use]
+    pub fn input_source(&self, layer: usize) -> &InputSource {
+        self.sources.get(layer).unwrap_or(&PREVIOUS)
+    }
+
+    /// Check whether any layer takes its inputs from somewhere other than the
+    /// layer before it.
+    ///
+    /// # Returns
+    ///
+    /// Whether the network has skip connections.
+    #[must_use]
+    pub fn has_skips(&self) -> bool {
+        self.sources
+            .iter()
+            .take(self.layers.len())
+            .any(|source| *source != InputSource::Previous)
+    }
+
+    /// Get a mutable reference to the set of layers, e.g. to fine-tune,
+    /// prune, or quantize the weights of a built network. Layers can be
+    /// changed but not added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the set of layers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{ActivationFunction, BasicNeuron, Layer, Network};
+    ///
+    /// let neuron = BasicNeuron::builder()
+    ///     .weights(vec![1.0, 1.0])
+    ///     .activation(ActivationFunction::linear())
+    ///     .build();
+    /// let layer = Layer::builder().add_neuron(neuron).build();
+    /// let mut network = Network::builder().add_layer(layer).build();
+    ///
+    /// network.layers_mut()[0].neurons_mut()[0].weights_mut()[1] = 3.0;
+    ///
+    /// assert_eq!(network.activate(&[1.0, 1.0]), vec![4.0]);
+    /// ```
+    pub fn layers_mut(&mut self) -> &mut [Layer] {
+        &mut self.layers
+    }
+
+    /// Check the whole topology: that every layer has neurons, and that each
+    /// layer's neurons have one weight per output of the layer before it.
+    ///
+    /// # Errors
+    ///
+    /// If the network has no layers, a layer has no neurons, or a neuron or
+    /// LSTM gate does not have one weight per input to its layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, Error, Layer, Network};
+    ///
+    /// let layer = |weights: Vec<f64>| {
+    ///     let neuron = BasicNeuron::builder().weights(weights).build();
+    ///     Layer::builder().add_neuron(neuron).build()
+    /// };
+    ///
+    /// assert_eq!(Network::builder().build().validate(), Err(Error::EmptyNetwork));
+    /// assert_eq!(
+    ///     Network::builder()
+    ///         .add_layer(layer(vec![1.0, 1.0]))
+    ///         .add_layer(layer(vec![1.0, 1.0]))
+    ///         .build()
+    ///         .validate(),
+    ///     Err(Error::WeightCount { layer: 1, neuron: 0, expected: 1, found: 2 })
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        self.check().map(|_| ())
+    }
+
+    /// Check that each layer can take the outputs of the one before it.
+    ///
+    /// # Returns
+    ///
+    /// The number of inputs the network takes.
+    ///
+    /// # Errors
+    ///
+    /// If the network has no layers, a layer has no neurons, or a neuron or
+    /// LSTM gate does not have one weight per input to its layer.
+    pub(crate) fn check(&self) -> Result<usize> {
+        if self.layers.is_empty() {
+            return Err(Error::EmptyNetwork);
+        }
+
+        let mut inputs = 0;
+        let mut sizes: Vec<usize> = Vec::with_capacity(self.layers.len());
+        for (index, layer) in self.layers.iter().enumerate() {
+            let expected = match self.input_source(index) {
+                InputSource::Previous => sizes.last().copied(),
+                InputSource::Layers(layers) => {
+                    let mut expected = 0;
+                    for source in layers {
+                        let size = sizes.get(*source).ok_or(Error::InvalidSource {
+                            layer: index,
+                            from: *source,
+                        })?;
+                        expected += size;
+                    }
+                    Some(expected)
+                }
+            };
+            let found = layer.check(index, expected)?;
+            if index == 0 {
+                inputs = found;
+            }
+            sizes.push(layer.neurons().len());
+        }
+        Ok(inputs)
+    }
+
+    /// Get the number of neurons in each layer.
+    ///
+    /// # Returns
+    ///
+    /// One size per layer, in order. The inputs are not included; see
+    /// [`Network::input_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, Layer, Network};
+    ///
+    /// let layer = |count, weights: usize| {
+    ///     let neurons = (0..count)
+    ///         .map(|_| BasicNeuron::builder().weights(vec![0.0; weights]).build().into())
+    ///         .collect();
+    ///     Layer::builder().neurons(neurons).build()
+    /// };
+    /// let network = Network::builder()
+    ///     .add_layer(layer(3, 4))
+    ///     .add_layer(layer(2, 3))
+    ///     .build();
+    ///
+    /// assert_eq!(network.shape(), vec![3, 2]);
+    /// assert_eq!(network.input_size(), Some(4));
+    /// assert_eq!(network.output_size(), Some(2));
+    /// ```
+    #[must_use]
+    pub fn shape(&self) -> Vec<usize> {
+        self.layers
+            .iter()
+            .map(|layer| layer.neurons().len())
+            .collect()
+    }
+
+    /// Get the number of inputs the network takes.
+    ///
+    /// # Returns
+    ///
+    /// The number of weights on the first neuron of the first layer, or
+    /// `None` if there is no such neuron.
+    #[must_use]
+    pub fn input_size(&self) -> Option<usize> {
+        self.layers
+            .first()
+            .and_then(|layer| layer.neurons().first())
+            .map(|neuron| neuron.weights().len())
+    }
+
+    /// Get the number of outputs the network gives.
+    ///
+    /// # Returns
+    ///
+    /// The number of neurons in the last layer, or `None` if the network has
+    /// no layers.
+    #[must_use]
+    pub fn output_size(&self) -> Option<usize> {
+        self.layers.last().map(|layer| layer.neurons().len())
+    }
+
+    /// Stack another network on top of this one, so its outputs become the
+    /// other network's inputs.
+    ///
+    /// # Arguments
+    ///
+    /// - `other` is the network to run after this one. Its skip connections
+    ///   are kept, pointing at its own layers.
+    ///
+    /// # Returns
+    ///
+    /// The combined network. It is not checked that this network's outputs
+    /// fit the other's inputs; use [`Network::validate`] for that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{ActivationFunction, BasicNeuron, Layer, Network};
+    ///
+    /// let scale = |weight| {
+    ///     let neuron = BasicNeuron::builder()
+    ///         .weights(vec![weight])
+    ///         .activation(ActivationFunction::linear())
+    ///         .build();
+    ///     Network::builder().add_layer(Layer::builder().add_neuron(neuron).build()).build()
+    /// };
+    ///
+    /// let network = scale(2.0).compose(scale(3.0));
+    ///
+    /// assert_eq!(network.layers().len(), 2);
+    /// assert_eq!(network.activate(&[1.0]), vec![6.0]);
+    /// ```
+    #[must_use]
+    pub fn compose(self, other: Network) -> Network {
+        let offset = self.layers.len();
+        let mut sources = (0..offset)
+            .map(|index| self.input_source(index).clone())
+            .collect::<Vec<_>>();
+        sources.extend(
+            (0..other.layers.len()).map(|index| match other.input_source(index) {
+                InputSource::Previous => InputSource::Previous,
+                InputSource::Layers(layers) => {
+                    InputSource::Layers(layers.iter().map(|layer| layer + offset).collect())
+                }
+            }),
+        );
+
+        let mut layers = self.layers;
+        layers.extend(other.layers);
+        Network::builder()
+            .layers(layers)
+            .input_sources(sources)
+            .build()
+    }
+
+    /// Run this network and another side by side on the same inputs, and
+    /// concatenate their outputs.
+    ///
+    /// The inputs are copied through an extra first layer so that both
+    /// networks can read them, and the outputs are joined by an extra last
+    /// layer. Both extra layers are linear, with a weight of one per value,
+    /// so they pass values through unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// - `other` is the network to run beside this one. It must take the
+    ///   same number of inputs.
+    ///
+    /// # Returns
+    ///
+    /// The combined network, whose outputs are this network's followed by
+    /// the other's.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{ActivationFunction, BasicNeuron, Layer, Network};
+    ///
+    /// let single = |weights: Vec<f64>| {
+    ///     let neuron = BasicNeuron::builder()
+    ///         .weights(weights)
+    ///         .activation(ActivationFunction::linear())
+    ///         .build();
+    ///     Network::builder().add_layer(Layer::builder().add_neuron(neuron).build()).build()
+    /// };
+    ///
+    /// let sum = single(vec![1.0, 1.0]);
+    /// let difference = single(vec![1.0, -1.0]);
+    /// let network = sum.parallel_merge(difference);
+    ///
+    /// assert_eq!(network.activate(&[5.0, 3.0]), vec![8.0, 2.0]);
+    /// ```
+    #[must_use]
+    pub fn parallel_merge(self, other: Network) -> Network {
+        let inputs = self
+            .input_size()
+            .or_else(|| other.input_size())
+            .unwrap_or_default();
+        let widths = [
+            self.output_size().unwrap_or(inputs),
+            other.output_size().unwrap_or(inputs),
+        ];
+
+        let mut layers = vec![identity(inputs)];
+        let mut sources = vec![InputSource::Previous];
+        let mut ends = vec![];
+        for network in [self, other] {
+            let start = layers.len();
+            if network.layers.is_empty() {
+                ends.push(0);
+                continue;
+            }
+            for index in 0..network.layers.len() {
+                sources.push(match network.input_source(index) {
+                    InputSource::Previous if index == 0 => InputSource::Layers(vec![0]),
+                    InputSource::Previous => InputSource::Layers(vec![start + index - 1]),
+                    InputSource::Layers(from) => {
+                        InputSource::Layers(from.iter().map(|layer| layer + start).collect())
+                    }
+                });
+            }
+            ends.push(start + network.layers.len() - 1);
+            layers.extend(network.layers);
+        }
+
+        layers.push(identity(widths[0] + widths[1]));
+        sources.push(InputSource::Layers(ends));
+        Network::builder()
+            .layers(layers)
+            .input_sources(sources)
+            .build()
+    }
+
+    /// Summarize the weights of each layer, e.g. to watch for weights
+    /// exploding or collapsing over generations. Biases are not included.
+    ///
+    /// # Returns
+    ///
+    /// One set of statistics per layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, Layer, Network};
+    ///
+    /// let neuron = BasicNeuron::builder().weights(vec![-1.0, 3.0]).build();
+    /// let layer = Layer::builder().add_neuron(neuron).build();
+    /// let network = Network::builder().add_layer(layer).build();
+    ///
+    /// let stats = network.weight_stats();
+    ///
+    /// assert_eq!(stats.len(), 1);
+    /// assert_eq!(stats[0].mean, 1.0);
+    /// assert_eq!(stats[0].std_dev, 2.0);
+    /// ```
+    #[must_use]
+    pub fn weight_stats(&self) -> Vec<WeightStats> {
+        self.layers
+            .iter()
+            .map(|layer| {
+                let weights = layer
+                    .neurons()
+                    .iter()
+                    .flat_map(|neuron| neuron.weights().iter().copied())
+                    .collect::<Vec<_>>();
+                WeightStats::new(&weights)
+            })
+            .collect()
+    }
+
+    /// Summarize the network, e.g. to watch for mutation making weights
+    /// explode or biases saturate over generations.
+    ///
+    /// # Returns
+    ///
+    /// The statistics of each layer's weights, as from
+    /// [`Network::weight_stats`], the number of saturated biases, and the
+    /// number of trainable parameters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, Layer, Network};
+    ///
+    /// let neuron = BasicNeuron::builder().weights(vec![-1.0, 3.0]).bias(-6.0).build();
+    /// let layer = Layer::builder().add_neuron(neuron).build();
+    /// let network = Network::builder().add_layer(layer).build();
+    ///
+    /// let stats = network.stats();
+    ///
+    /// assert_eq!(stats.layers[0].max, 3.0);
+    /// assert_eq!(stats.saturated_biases, 1);
+    /// assert_eq!(stats.parameters, 3);
+    /// ```
+    #[must_use]
+    pub fn stats(&self) -> NetworkStats {
+        let neurons = || self.layers.iter().flat_map(Layer::neurons);
+        NetworkStats {
+            layers: self.weight_stats(),
+            saturated_biases: neurons()
+                .filter(|neuron| {
+                    let bias = neuron.bias().abs();
+                    bias.is_nan() || bias >= NetworkStats::SATURATED_BIAS
+                })
+                .count(),
+            parameters: neurons().map(Neuron::parameter_count).sum(),
+        }
+    }
+
     /// Parse a JSON string into a network.
     ///
     /// # Arguments
@@ -123,7 +816,7 @@ impl Network {
     ///
     /// assert_eq!(network, parsed);
     /// ```
-    pub fn parse_json(json: &str) -> Result<Self, serde_json::Error> {
+    pub fn parse_json(json: &str) -> std::result::Result<Self, serde_json::Error> {
         serde_json::from_str(json)
     }
 
@@ -152,9 +845,205 @@ impl Network {
     ///
     /// assert_eq!(network, parsed);
     /// ```
-    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+    pub fn to_json(&self) -> std::result::Result<String, serde_json::Error> {
         serde_json::to_string(self)
     }
+
+    /// Write the network in an interchange format.
+    ///
+    /// # Arguments
+    ///
+    /// - `writer` receives the serialized network.
+    /// - `format` is the format to write in.
+    ///
+    /// # Errors
+    ///
+    /// If the network cannot be serialized or the writer fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::format::Format;
+    /// use nnet::{BasicNeuron, Layer, Network};
+    ///
+    /// let neuron = BasicNeuron::builder().weights(vec![0.5]).build();
+    /// let network = Network::builder()
+    ///     .add_layer(Layer::builder().add_neuron(neuron).build())
+    ///     .build();
+    ///
+    /// let mut bytes = vec![];
+    /// network.serialize_to(&mut bytes, Format::Json).unwrap();
+    ///
+    /// assert_eq!(Network::deserialize_from(bytes.as_slice(), Format::Json).unwrap(), network);
+    /// ```
+    pub fn serialize_to<W: std::io::Write>(
+        &self,
+        writer: W,
+        format: Format,
+    ) -> std::result::Result<(), format::Error> {
+        format::serialize_to(self, writer, format)
+    }
+
+    /// Read a network written in an interchange format.
+    ///
+    /// # Arguments
+    ///
+    /// - `reader` supplies the serialized network.
+    /// - `format` is the format to read.
+    ///
+    /// # Returns
+    ///
+    /// The network.
+    ///
+    /// # Errors
+    ///
+    /// If the input is not a network in the format, or the reader fails.
+    pub fn deserialize_from<R: std::io::Read>(
+        reader: R,
+        format: Format,
+    ) -> std::result::Result<Self, format::Error> {
+        format::deserialize_from(reader, format)
+    }
+
+    /// Import a dense network trained with Keras, as described in
+    /// [`keras`](crate::keras).
+    ///
+    /// # Arguments
+    ///
+    /// - `architecture` is the JSON written by `model.to_json()`.
+    /// - `weights` is the JSON array of each `Dense` layer's weights, as
+    ///   returned by `layer.get_weights()`.
+    ///
+    /// # Returns
+    ///
+    /// A network of basic neurons, one layer per `Dense` layer.
+    ///
+    /// # Errors
+    ///
+    /// If either input is not valid JSON of the expected shape, or the model
+    /// uses layers or activations that have no equivalent here.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::Network;
+    ///
+    /// let architecture = r#"{"class_name": "Sequential", "config": {"layers": [
+    ///     {"class_name": "Dense", "config": {"units": 1, "activation": "linear"}}
+    /// ]}}"#;
+    /// let weights = "[[[[2.0], [3.0]], [0.5]]]";
+    ///
+    /// let network = Network::from_keras_json(architecture, weights).unwrap();
+    ///
+    /// assert_eq!(network.activate(&[1.0, 1.0]), vec![5.5]);
+    /// ```
+    pub fn from_keras_json(
+        architecture: &str,
+        weights: &str,
+    ) -> std::result::Result<Self, keras::Error> {
+        keras::from_json(architecture, weights)
+    }
+}
+
+impl std::fmt::Display for Network {
+    /// Write a table with one row per layer: its index, its number of
+    /// neurons, the number of weights per neuron, and its activation
+    /// functions. Layers whose neurons differ show the range of weights and
+    /// every activation function used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{ActivationFunction, BasicNeuron, Layer, Network};
+    ///
+    /// let neuron = BasicNeuron::builder()
+    ///     .weights(vec![0.5, -0.5])
+    ///     .activation(ActivationFunction::linear())
+    ///     .build();
+    /// let network = Network::builder()
+    ///     .add_layer(Layer::builder().add_neuron(neuron).build())
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     network.to_string(),
+    ///     "layer  neurons  weights  activation\n\
+    ///      0      1        2        linear\n"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:<6} {:<8} {:<8} activation",
+            "layer", "neurons", "weights"
+        )?;
+        for (index, layer) in self.layers.iter().enumerate() {
+            let neurons = layer.neurons();
+            let min = neurons.iter().map(|n| n.weights().len()).min();
+            let max = neurons.iter().map(|n| n.weights().len()).max();
+            let weights = match (min, max) {
+                (Some(min), Some(max)) if min == max => min.to_string(),
+                (Some(min), Some(max)) => format!("{min}-{max}"),
+                _ => "-".to_string(),
+            };
+
+            let mut activations: Vec<String> = vec![];
+            for name in neurons.iter().map(|n| n.activator().to_string()) {
+                if !activations.contains(&name) {
+                    activations.push(name);
+                }
+            }
+            let activations = if activations.is_empty() {
+                "-".to_string()
+            } else {
+                activations.join(", ")
+            };
+
+            writeln!(
+                f,
+                "{index:<6} {:<8} {weights:<8} {activations}",
+                neurons.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Reusable buffers for [`Network::activate_into`].
+///
+/// Each layer reads from one buffer and writes to the other, and then they
+/// swap. One set of buffers can be shared by any number of networks, but not
+/// by two activations at the same time.
+#[derive(Clone, Debug, Default)]
+pub struct ActivationBuffers {
+    /// The values being read: the inputs, then the output of each layer.
+    front: Vec<f64>,
+
+    /// The values being written.
+    back: Vec<f64>,
+
+    /// The output of every layer, kept for networks with skip connections.
+    outputs: Vec<Vec<f64>>,
+}
+
+impl ActivationBuffers {
+    /// Create buffers that can hold a given number of values without
+    /// growing.
+    ///
+    /// # Arguments
+    ///
+    /// - `capacity` is the widest layer, or input, the buffers will see.
+    ///
+    /// # Returns
+    ///
+    /// The buffers.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            front: Vec::with_capacity(capacity),
+            back: Vec::with_capacity(capacity),
+            outputs: vec![],
+        }
+    }
 }
 
 /// A builder for [`Network`].
@@ -171,6 +1060,7 @@ impl Network {
 #[derive(Default)]
 pub struct Builder {
     layers: Vec<Layer>,
+    sources: Vec<InputSource>,
 }
 
 impl Builder {
@@ -201,6 +1091,60 @@ impl Builder {
         self
     }
 
+    /// Add a layer to the network that takes its inputs from a given source.
+    ///
+    /// # Arguments
+    ///
+    /// - `layer` to add to the network.
+    /// - `source` is where the layer takes its inputs from. Its neurons need
+    ///   one weight per value the source provides.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, InputSource, Layer, Network};
+    ///
+    /// let layer = |weights| {
+    ///     let neuron = BasicNeuron::builder().weights(vec![1.0; weights]).build();
+    ///     Layer::builder().add_neuron(neuron).build()
+    /// };
+    /// let network = Network::builder()
+    ///     .add_layer(layer(2))
+    ///     .add_layer(layer(1))
+    ///     .add_layer_with_input(layer(2), InputSource::Layers(vec![0, 1]))
+    ///     .try_build();
+    ///
+    /// assert!(network.is_ok());
+    /// ```
+    #[must_use]
+    pub fn add_layer_with_input(mut self, layer: Layer, source: InputSource) -> Self {
+        self.sources
+            .resize(self.layers.len(), InputSource::Previous);
+        self.sources.push(source);
+        self.layers.push(layer);
+        self
+    }
+
+    /// Set where each layer takes its inputs from.
+    ///
+    /// # Arguments
+    ///
+    /// - `sources` are the sources of each layer, by index. Layers past the
+    ///   end take the output of the layer before them.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn input_sources(mut self, sources: Vec<InputSource>) -> Self {
+        self.sources = sources;
+        self
+    }
+
     /// Add multiple layers to the network.
     ///
     /// # Arguments
@@ -272,9 +1216,62 @@ impl Builder {
     /// ```
     #[must_use]
     pub fn build(self) -> Network {
-        Network {
-            layers: self.layers,
+        let Self {
+            layers,
+            mut sources,
+        } = self;
+        sources.truncate(layers.len());
+        if sources
+            .iter()
+            .all(|source| *source == InputSource::Previous)
+        {
+            sources.clear();
         }
+        Network { layers, sources }
+    }
+
+    /// Build the network, checking that each layer can take the outputs of
+    /// the one before it.
+    ///
+    /// # Returns
+    ///
+    /// The network.
+    ///
+    /// # Errors
+    ///
+    /// If the network has no layers, a layer has no neurons, or a neuron or
+    /// LSTM gate does not have one weight per input to its layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, Error, Layer, Network};
+    ///
+    /// let layer = |count, weights: &[f64]| {
+    ///     let neurons = (0..count)
+    ///         .map(|_| BasicNeuron::builder().weights(weights.to_vec()).build().into())
+    ///         .collect();
+    ///     Layer::builder().neurons(neurons).build()
+    /// };
+    ///
+    /// assert_eq!(Network::builder().try_build(), Err(Error::EmptyNetwork));
+    /// assert!(Network::builder()
+    ///     .add_layer(layer(2, &[1.0, 1.0, 1.0]))
+    ///     .add_layer(layer(1, &[1.0, 1.0]))
+    ///     .try_build()
+    ///     .is_ok());
+    /// assert_eq!(
+    ///     Network::builder()
+    ///         .add_layer(layer(2, &[1.0, 1.0, 1.0]))
+    ///         .add_layer(layer(1, &[1.0, 1.0, 1.0]))
+    ///         .try_build(),
+    ///     Err(Error::WeightCount { layer: 1, neuron: 0, expected: 2, found: 3 })
+    /// );
+    /// ```
+    pub fn try_build(self) -> Result<Network> {
+        let network = self.build();
+        network.validate()?;
+        Ok(network)
     }
 }
 
@@ -284,13 +1281,20 @@ mod tests {
 
     #[test]
     fn test_create_network() {
-        assert_eq!(Network::builder().build(), Network { layers: vec![] });
+        assert_eq!(
+            Network::builder().build(),
+            Network {
+                layers: vec![],
+                sources: vec![]
+            }
+        );
         assert_eq!(
             Network::builder()
                 .add_layer(Layer::builder().build())
                 .build(),
             Network {
-                layers: vec![Layer::builder().build()]
+                layers: vec![Layer::builder().build()],
+                sources: vec![]
             }
         );
         assert_eq!(
@@ -299,8 +1303,320 @@ mod tests {
                 .add_layer(Layer::builder().build())
                 .build(),
             Network {
-                layers: vec![Layer::builder().build(), Layer::builder().build()]
+                layers: vec![Layer::builder().build(), Layer::builder().build()],
+                sources: vec![]
             }
         );
     }
+
+    #[test]
+    fn test_display() {
+        use crate::{ActivationFunction, BasicNeuron, Neuron};
+
+        let neuron = |weights: usize, activation| -> Neuron {
+            BasicNeuron::builder()
+                .weights(vec![0.0; weights])
+                .activation(activation)
+                .build()
+                .into()
+        };
+        let network = Network::builder()
+            .add_layer(
+                Layer::builder()
+                    .neurons(vec![
+                        neuron(3, ActivationFunction::sigmoid()),
+                        neuron(3, ActivationFunction::sigmoid()),
+                    ])
+                    .build(),
+            )
+            .add_layer(
+                Layer::builder()
+                    .neurons(vec![
+                        neuron(2, ActivationFunction::linear()),
+                        neuron(1, ActivationFunction::sigmoid()),
+                    ])
+                    .build(),
+            )
+            .add_layer(Layer::builder().build())
+            .build();
+
+        assert_eq!(
+            network.to_string(),
+            "layer  neurons  weights  activation\n\
+             0      2        3        sigmoid\n\
+             1      2        1-2      linear, sigmoid\n\
+             2      0        -        -\n"
+        );
+    }
+
+    #[test]
+    fn test_activate_into() {
+        use crate::{ActivationFunction, BasicNeuron};
+
+        let layer = |count, weights: usize| {
+            let neurons = (0..count)
+                .map(|index| {
+                    BasicNeuron::builder()
+                        .weights(vec![0.5; weights])
+                        .bias(f64::from(index))
+                        .activation(ActivationFunction::tanh())
+                        .build()
+                        .into()
+                })
+                .collect();
+            Layer::builder().neurons(neurons).build()
+        };
+        let network = Network::builder()
+            .add_layer(layer(5, 2))
+            .add_layer(layer(3, 5))
+            .add_layer(layer(1, 3))
+            .build();
+        let mut scratch = ActivationBuffers::with_capacity(5);
+        let (front, back) = (scratch.front.as_ptr(), scratch.back.as_ptr());
+
+        for inputs in [[0.0, 1.0], [-2.0, 0.5], [3.0, 3.0]] {
+            let expected = network.activate(&inputs);
+            let outputs = network.activate_into(&inputs, &mut scratch);
+            assert_eq!(
+                outputs.iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+                expected.iter().map(|x| x.to_bits()).collect::<Vec<_>>()
+            );
+        }
+
+        // Neither buffer had to grow.
+        let mut buffers = [scratch.front.as_ptr(), scratch.back.as_ptr()];
+        buffers.sort();
+        let mut original = [front, back];
+        original.sort();
+        assert_eq!(buffers, original);
+    }
+
+    #[test]
+    fn test_skip_connections() {
+        use crate::{ActivationFunction, BasicNeuron};
+
+        let layer = |count, weights: usize| {
+            let neurons = (0..count)
+                .map(|index| {
+                    BasicNeuron::builder()
+                        .weights(vec![0.5; weights])
+                        .bias(f64::from(index))
+                        .activation(ActivationFunction::linear())
+                        .build()
+                        .into()
+                })
+                .collect();
+            Layer::builder().neurons(neurons).build()
+        };
+        let network = Network::builder()
+            .add_layer(layer(2, 1))
+            .add_layer(layer(3, 2))
+            .add_layer_with_input(layer(1, 5), InputSource::Layers(vec![0, 1]))
+            .try_build()
+            .unwrap();
+
+        // Layer 0: [0.5, 1.5]; layer 1: [1.0, 2.0, 3.0]; layer 2 sees both.
+        assert_eq!(network.activate(&[1.0]), vec![4.0]);
+
+        let mut scratch = ActivationBuffers::default();
+        assert_eq!(network.activate_into(&[1.0], &mut scratch), &[4.0]);
+
+        let json = network.to_json().unwrap();
+        assert!(json.contains(r#""sources":["Previous","Previous",{"Layers":[0,1]}]"#));
+        assert_eq!(Network::parse_json(&json).unwrap(), network);
+
+        let mut copy = Network::parse_json(&json).unwrap();
+        assert_eq!(copy.activate_sequence(&[vec![1.0]]), vec![vec![4.0]]);
+
+        assert_eq!(
+            Network::builder()
+                .add_layer(layer(2, 1))
+                .add_layer_with_input(layer(1, 2), InputSource::Layers(vec![1]))
+                .try_build(),
+            Err(Error::InvalidSource { layer: 1, from: 1 })
+        );
+        assert_eq!(
+            Network::builder()
+                .add_layer(layer(2, 1))
+                .add_layer(layer(3, 2))
+                .add_layer_with_input(layer(1, 3), InputSource::Layers(vec![0, 1]))
+                .try_build(),
+            Err(Error::WeightCount {
+                layer: 2,
+                neuron: 0,
+                expected: 5,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_activate() {
+        use crate::{ActivationFunction, BasicNeuron};
+
+        let layer = |count, weights: usize| {
+            let neurons = (0..count)
+                .map(|_| {
+                    BasicNeuron::builder()
+                        .weights(vec![1.0; weights])
+                        .activation(ActivationFunction::linear())
+                        .build()
+                        .into()
+                })
+                .collect();
+            Layer::builder().neurons(neurons).build()
+        };
+        let network = Network::builder()
+            .add_layer(layer(2, 3))
+            .add_layer(layer(1, 2))
+            .build();
+
+        assert_eq!(network.try_activate(&[1.0, 2.0, 3.0]), Ok(vec![12.0]));
+        assert_eq!(
+            network.try_activate(&[1.0, 2.0, 3.0, 4.0]),
+            Err(Error::InputCount {
+                expected: 3,
+                found: 4
+            })
+        );
+
+        let network = Network::builder()
+            .add_layer(layer(2, 3))
+            .add_layer(layer(1, 3))
+            .build();
+        assert_eq!(
+            network.try_activate(&[1.0, 2.0, 3.0]),
+            Err(Error::WeightCount {
+                layer: 1,
+                neuron: 0,
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_build() {
+        use crate::neuron::Gate;
+        use crate::{BasicNeuron, LstmNeuron, Neuron};
+
+        let neuron = |weights: &[f64]| -> Neuron {
+            BasicNeuron::builder()
+                .weights(weights.to_vec())
+                .build()
+                .into()
+        };
+
+        assert_eq!(
+            Network::builder()
+                .add_layer(Layer::builder().neurons(vec![neuron(&[1.0])]).build())
+                .add_layer(Layer::builder().build())
+                .try_build(),
+            Err(Error::EmptyLayer { layer: 1 })
+        );
+        assert_eq!(
+            Network::builder()
+                .add_layer(Layer::builder().neurons(vec![neuron(&[])]).build())
+                .try_build(),
+            Err(Error::NoWeights)
+        );
+        assert_eq!(
+            Network::builder()
+                .add_layer(
+                    Layer::builder()
+                        .neurons(vec![neuron(&[1.0, 2.0]), neuron(&[1.0])])
+                        .build()
+                )
+                .try_build(),
+            Err(Error::WeightCount {
+                layer: 0,
+                neuron: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+
+        let lstm = |gate: Vec<f64>| -> Neuron {
+            LstmNeuron::builder()
+                .weights(vec![1.0])
+                .forget_gate(Gate {
+                    weights: gate,
+                    recurrent: 0.0,
+                    bias: 0.0,
+                })
+                .build()
+                .into()
+        };
+        let network = |gate| {
+            Network::builder()
+                .add_layer(Layer::builder().neurons(vec![neuron(&[1.0])]).build())
+                .add_layer(
+                    Layer::builder()
+                        .neurons(vec![neuron(&[1.0]), lstm(gate)])
+                        .build(),
+                )
+                .try_build()
+        };
+        assert!(network(vec![]).is_ok());
+        assert_eq!(
+            network(vec![1.0, 1.0]),
+            Err(Error::GateWeightCount {
+                layer: 1,
+                neuron: 1,
+                expected: 1,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_compose_and_merge() {
+        use crate::{ActivationFunction, BasicNeuron};
+
+        let layer = |count, weights: usize| {
+            let neurons = (0..count)
+                .map(|index| {
+                    BasicNeuron::builder()
+                        .weights(vec![0.5; weights])
+                        .bias(f64::from(index))
+                        .activation(ActivationFunction::linear())
+                        .build()
+                        .into()
+                })
+                .collect();
+            Layer::builder().neurons(neurons).build()
+        };
+        let skips = || {
+            Network::builder()
+                .add_layer(layer(2, 1))
+                .add_layer(layer(3, 2))
+                .add_layer_with_input(layer(1, 5), InputSource::Layers(vec![0, 1]))
+                .build()
+        };
+        let deep = skips();
+        let shallow = Network::builder().add_layer(layer(2, 1)).build();
+
+        let composed = Network::builder()
+            .add_layer(layer(1, 1))
+            .build()
+            .compose(skips());
+        composed.validate().unwrap();
+        assert_eq!(composed.input_source(3), &InputSource::Layers(vec![1, 2]));
+        assert_eq!(composed.activate(&[2.0]), deep.activate(&[1.0]));
+
+        let expected = [deep.activate(&[3.0]), shallow.activate(&[3.0])].concat();
+        let merged = skips().parallel_merge(shallow);
+        merged.validate().unwrap();
+        assert_eq!(merged.shape(), vec![1, 2, 3, 1, 2, 3]);
+        assert_eq!(merged.activate(&[3.0]), expected);
+
+        let mut scratch = ActivationBuffers::default();
+        assert_eq!(merged.activate_into(&[3.0], &mut scratch), expected);
+
+        let passthrough = Network::builder().build().parallel_merge(skips());
+        assert_eq!(
+            passthrough.activate(&[3.0]),
+            [vec![3.0], deep.activate(&[3.0])].concat()
+        );
+    }
 }
@@ -0,0 +1,265 @@
+use super::{InputSource, Network};
+use crate::{Layer, Neuron};
+use std::collections::BTreeMap;
+
+impl Network {
+    /// Remove the dead weight from a network to make it smaller.
+    ///
+    /// Every weight whose magnitude is at most `threshold` is set to zero.
+    /// Then, working back from the output layer, each neuron whose output is
+    /// given a weight of zero by every neuron that reads it is removed, along
+    /// with those weights. The output layer is kept whole, and every layer
+    /// keeps at least one neuron so the network stays valid.
+    ///
+    /// # Arguments
+    ///
+    /// - `threshold` is the largest magnitude of a weight to prune.
+    ///
+    /// # Returns
+    ///
+    /// The pruned network. Removing neurons does not change its outputs, so
+    /// they differ from the original's only by the pruned weights.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{ActivationFunction, BasicNeuron, Layer, Network};
+    ///
+    /// let neuron = |weights: Vec<f64>| {
+    ///     BasicNeuron::builder()
+    ///         .weights(weights)
+    ///         .activation(ActivationFunction::linear())
+    ///         .build()
+    /// };
+    /// let network = Network::builder()
+    ///     .add_layer(
+    ///         Layer::builder()
+    ///             .add_neuron(neuron(vec![1.0, 1.0]))
+    ///             .add_neuron(neuron(vec![2.0, 0.0]))
+    ///             .build(),
+    ///     )
+    ///     .add_layer(Layer::builder().add_neuron(neuron(vec![3.0, 1e-9])).build())
+    ///     .build();
+    ///
+    /// let pruned = network.prune(1e-6);
+    ///
+    /// assert_eq!(pruned.shape(), vec![1, 1]);
+    /// assert_eq!(pruned.activate(&[1.0, 2.0]), vec![9.0]);
+    /// ```
+    #[must_use]
+    pub fn prune(self, threshold: f64) -> Network {
+        let sources = (0..self.layers.len())
+            .map(|layer| self.input_source(layer).clone())
+            .collect::<Vec<_>>();
+        let mut layers = self
+            .layers
+            .into_iter()
+            .map(Layer::into_neurons)
+            .collect::<Vec<_>>();
+
+        for neuron in layers.iter_mut().flatten() {
+            for weights in neuron.input_weights_mut() {
+                for weight in weights
+                    .iter_mut()
+                    .filter(|weight| weight.abs() <= threshold)
+                {
+                    *weight = 0.0;
+                }
+            }
+        }
+
+        // Removing a neuron removes the weights it gave to the layers feeding
+        // it, which can leave their neurons dead in turn.
+        for layer in (0..layers.len().saturating_sub(1)).rev() {
+            let readers = readers(&sources, &layers, layer);
+            let mut dead = vec![true; layers[layer].len()];
+            for (reader, offset) in &readers {
+                for neuron in &mut layers[*reader] {
+                    for weights in neuron.input_weights_mut() {
+                        for (dead, weight) in dead.iter_mut().zip(weights.iter().skip(*offset)) {
+                            *dead &= *weight == 0.0;
+                        }
+                    }
+                }
+            }
+            if !dead.contains(&false) {
+                // Keep one neuron, so the layer is not left empty.
+                if let Some(first) = dead.first_mut() {
+                    *first = false;
+                }
+            }
+            if !dead.contains(&true) {
+                continue;
+            }
+
+            let dead = dead
+                .iter()
+                .enumerate()
+                .filter_map(|(neuron, dead)| dead.then_some(neuron))
+                .collect::<Vec<_>>();
+            let mut removed = BTreeMap::<usize, Vec<usize>>::new();
+            for (reader, offset) in readers {
+                let positions = removed.entry(reader).or_default();
+                positions.extend(dead.iter().map(|neuron| offset + neuron));
+            }
+            for (reader, positions) in removed {
+                for neuron in &mut layers[reader] {
+                    for weights in neuron.input_weights_mut() {
+                        *weights = remove(std::mem::take(weights), &positions);
+                    }
+                }
+            }
+            layers[layer] = remove(std::mem::take(&mut layers[layer]), &dead);
+        }
+
+        Network::builder()
+            .layers(
+                layers
+                    .into_iter()
+                    .map(|neurons| Layer::builder().neurons(neurons).build())
+                    .collect(),
+            )
+            .input_sources(sources)
+            .build()
+    }
+}
+
+/// Find the layers that read the outputs of a layer.
+///
+/// # Arguments
+///
+/// - `sources` are where each layer takes its inputs from.
+/// - `layers` are the neurons of each layer.
+/// - `layer` is the layer whose readers to find.
+///
+/// # Returns
+///
+/// Each reader, with where the layer's outputs start among its inputs. A
+/// layer that reads the outputs twice is listed twice.
+fn readers(sources: &[InputSource], layers: &[Vec<Neuron>], layer: usize) -> Vec<(usize, usize)> {
+    let mut readers = vec![];
+    for (reader, source) in sources.iter().enumerate().skip(layer + 1) {
+        match source {
+            InputSource::Previous if reader == layer + 1 => readers.push((reader, 0)),
+            InputSource::Previous => {}
+            InputSource::Layers(from) => {
+                let mut offset = 0;
+                for from in from.iter().filter(|from| **from < reader) {
+                    if *from == layer {
+                        readers.push((reader, offset));
+                    }
+                    offset += layers[*from].len();
+                }
+            }
+        }
+    }
+    readers
+}
+
+/// Remove entries from a list.
+///
+/// # Arguments
+///
+/// - `values` is the list.
+/// - `positions` are the indices to remove.
+///
+/// # Returns
+///
+/// The remaining entries, in order.
+fn remove<T>(values: Vec<T>, positions: &[usize]) -> Vec<T> {
+    values
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| !positions.contains(index))
+        .map(|(_, value)| value)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neuron::Gate;
+    use crate::{ActivationFunction, BasicNeuron, LstmNeuron};
+
+    fn neuron(weights: &[f64]) -> Neuron {
+        BasicNeuron::builder()
+            .weights(weights.to_vec())
+            .activation(ActivationFunction::linear())
+            .build()
+            .into()
+    }
+
+    fn layer(neurons: Vec<Neuron>) -> Layer {
+        Layer::builder().neurons(neurons).build()
+    }
+
+    #[test]
+    fn test_prune_cascades() {
+        // Neuron 1 of layer 0 only feeds neuron 1 of layer 1, which nothing
+        // reads, so both go.
+        let network = Network::builder()
+            .add_layer(layer(vec![neuron(&[1.0]), neuron(&[5.0])]))
+            .add_layer(layer(vec![neuron(&[2.0, 0.0]), neuron(&[0.0, 3.0])]))
+            .add_layer(layer(vec![neuron(&[1.0, 0.001])]))
+            .build();
+
+        let pruned = network.prune(0.01);
+
+        pruned.validate().unwrap();
+        assert_eq!(pruned.shape(), vec![1, 1, 1]);
+        assert_eq!(pruned.activate(&[3.0]), vec![6.0]);
+    }
+
+    #[test]
+    fn test_prune_skip_connections() {
+        let network = Network::builder()
+            .add_layer(layer(vec![neuron(&[1.0]), neuron(&[1.0])]))
+            .add_layer(layer(vec![neuron(&[0.0, 1.0]), neuron(&[0.0, 1.0])]))
+            .add_layer_with_input(
+                layer(vec![neuron(&[0.0, 0.0, 1.0, 0.0])]),
+                InputSource::Layers(vec![0, 1]),
+            )
+            .build();
+
+        let pruned = network.prune(0.0);
+
+        // Neuron 0 of layer 0 and neuron 1 of layer 1 are only given zero
+        // weights, including by the last layer's skip connection.
+        pruned.validate().unwrap();
+        assert_eq!(pruned.shape(), vec![1, 1, 1]);
+        assert_eq!(pruned.layers()[1].neurons()[0].weights(), &[1.0]);
+        assert_eq!(pruned.layers()[2].neurons()[0].weights(), &[0.0, 1.0]);
+        assert_eq!(pruned.input_source(2), &InputSource::Layers(vec![0, 1]));
+        assert_eq!(pruned.activate(&[2.0]), vec![2.0]);
+    }
+
+    #[test]
+    fn test_prune_keeps_gated_inputs() {
+        let gate = |weights: Vec<f64>| Gate {
+            weights,
+            recurrent: 0.0,
+            bias: 0.0,
+        };
+        let lstm = LstmNeuron::builder()
+            .weights(vec![1.0, 0.0, 0.0])
+            .input_gate(gate(vec![0.0, 1.0, 0.0]))
+            .forget_gate(gate(vec![0.0; 3]))
+            .output_gate(gate(vec![0.0; 3]))
+            .build();
+        let network = Network::builder()
+            .add_layer(layer(vec![neuron(&[1.0]), neuron(&[1.0]), neuron(&[1.0])]))
+            .add_layer(Layer::builder().add_neuron(lstm).build())
+            .build();
+
+        let pruned = network.prune(0.0);
+
+        // The second neuron only feeds the input gate, but is still read.
+        assert_eq!(pruned.shape(), vec![2, 1]);
+        let Neuron::Lstm(lstm) = &pruned.layers()[1].neurons()[0] else {
+            panic!("the LSTM neuron was replaced");
+        };
+        assert_eq!(lstm.weights(), &[1.0, 0.0]);
+        assert_eq!(lstm.input_gate().weights, vec![0.0, 1.0]);
+        assert_eq!(lstm.forget_gate().weights, vec![0.0, 0.0]);
+    }
+}
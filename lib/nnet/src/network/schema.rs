@@ -0,0 +1,124 @@
+//! The serialized form of a [`Network`], and migrations from older versions
+//! of it.
+//!
+//! Every network is written with the [`Network::SCHEMA_VERSION`] it was
+//! written with, and read back through [`Stored`], which accepts any older
+//! version and migrates it. Networks written before versions were added
+//! read as version 0.
+//!
+//! New neuron and activation variants are added at the end of their enums,
+//! so formats that store a variant's index still read old networks. A change
+//! that old data cannot be read into, such as renaming a variant or field,
+//! bumps the version and reads the old version into its own type here before
+//! converting it.
+
+use super::{InputSource, Network};
+use crate::{Error, Layer, Result};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize};
+
+impl Network {
+    /// The version of the serialized form written by this crate.
+    pub const SCHEMA_VERSION: u32 = 1;
+}
+
+impl Serialize for Network {
+    /// Write the network, starting with its schema version.
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        // The sources are always written, since binary formats such as
+        // bincode cannot skip fields.
+        let mut state = serializer.serialize_struct("Network", 3)?;
+        state.serialize_field("version", &Self::SCHEMA_VERSION)?;
+        state.serialize_field("layers", &self.layers)?;
+        state.serialize_field("sources", &self.sources)?;
+        state.end()
+    }
+}
+
+/// A network as written by any version of the crate.
+#[derive(Deserialize)]
+#[serde(rename = "Network")]
+pub(super) struct Stored {
+    /// The schema version; missing before versions were added.
+    #[serde(default)]
+    version: u32,
+
+    layers: Vec<Layer>,
+
+    /// Missing before skip connections were added.
+    #[serde(default)]
+    sources: Vec<InputSource>,
+}
+
+impl TryFrom<Stored> for Network {
+    type Error = Error;
+
+    /// Migrate a stored network to the current version.
+    ///
+    /// # Errors
+    ///
+    /// If the network was written by a newer version of the crate.
+    fn try_from(stored: Stored) -> Result<Self> {
+        let Stored {
+            version,
+            layers,
+            sources,
+        } = stored;
+        match version {
+            // Version 0 only lacks the version itself and, before skip
+            // connections, the sources, which default to empty.
+            0 | 1 => Ok(Self { layers, sources }),
+            found => Err(Error::UnsupportedVersion {
+                found,
+                supported: Self::SCHEMA_VERSION,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNeuron, Layer};
+
+    #[test]
+    fn test_writes_version() {
+        let neuron = BasicNeuron::builder().weights(vec![0.5]).build();
+        let network = Network::builder()
+            .add_layer(Layer::builder().add_neuron(neuron).build())
+            .build();
+
+        let json = network.to_json().unwrap();
+
+        assert!(json.starts_with(r#"{"version":1,"layers":"#), "{json}");
+        assert_eq!(Network::parse_json(&json).unwrap(), network);
+    }
+
+    #[test]
+    fn test_reads_unversioned() {
+        let json = r#"{"layers":[{"neurons":[{"Basic":{
+            "bias":0.5,"weights":[2.0],"activation":{"Linear":null}
+        }}]}]}"#;
+
+        let network = Network::parse_json(json).unwrap();
+
+        assert_eq!(network.activate(&[1.0]), vec![2.5]);
+        assert!(!network.has_skips());
+    }
+
+    #[test]
+    fn test_rejects_newer_version() {
+        let error = Network::parse_json(r#"{"version":99,"layers":[]}"#).unwrap_err();
+
+        assert!(
+            error.to_string().contains(&format!(
+                "version 99 is newer than the supported version {}",
+                Network::SCHEMA_VERSION
+            )),
+            "{error}"
+        );
+    }
+}
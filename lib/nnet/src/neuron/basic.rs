@@ -1,4 +1,4 @@
-use crate::{Activate, ActivationFunction, Neuron, NeuronActivate};
+use crate::{Activate, ActivationFunction, Error, Neuron, NeuronActivate, Result};
 use serde::{Deserialize, Serialize};
 
 /// A basic neuron.
@@ -108,6 +108,34 @@ impl Basic {
     pub fn weights(&self) -> &[f64] {
         &self.weights
     }
+
+    /// Get a mutable reference to the neuron's weights.
+    ///
+    /// # Returns
+    ///
+    /// The weights, which can be changed but not added or removed.
+    pub fn weights_mut(&mut self) -> &mut [f64] {
+        &mut self.weights
+    }
+
+    /// Set the neuron's bias.
+    ///
+    /// # Arguments
+    ///
+    /// - `bias` is the new bias.
+    pub fn set_bias(&mut self, bias: f64) {
+        self.bias = bias;
+    }
+
+    /// Get every set of weights the neuron applies to its inputs, for
+    /// editing the inputs a neuron takes.
+    ///
+    /// # Returns
+    ///
+    /// The sets of weights, each with one weight per input.
+    pub(crate) fn input_weights_mut(&mut self) -> Vec<&mut Vec<f64>> {
+        vec![&mut self.weights]
+    }
 }
 
 impl Neuron {
@@ -158,7 +186,7 @@ impl NeuronActivate for Basic {
 /// # Returns
 ///
 /// The sum of the products of the weights and inputs.
-fn sum(weights: &[f64], inputs: &[f64], bias: f64) -> f64 {
+pub(crate) fn sum(weights: &[f64], inputs: &[f64], bias: f64) -> f64 {
     let product = Iterator::zip(weights.iter(), inputs.iter())
         .map(|(weight, input)| weight * input)
         .sum::<f64>();
@@ -346,6 +374,32 @@ impl Builder {
             activation: self.activation.unwrap_or_else(ActivationFunction::sigmoid),
         }
     }
+
+    /// Build the neuron, checking that it can take inputs.
+    ///
+    /// # Returns
+    ///
+    /// The neuron.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::NoWeights`] if the neuron has no weights, since it would
+    /// ignore every input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, Error};
+    ///
+    /// assert!(BasicNeuron::builder().weights(vec![1.0]).try_build().is_ok());
+    /// assert_eq!(BasicNeuron::builder().try_build(), Err(Error::NoWeights));
+    /// ```
+    pub fn try_build(self) -> Result<Basic> {
+        if self.weights.is_empty() {
+            return Err(Error::NoWeights);
+        }
+        Ok(self.build())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,485 @@
+use super::basic::sum;
+use crate::activation::{Activate, Sigmoid};
+use crate::{ActivationFunction, Neuron, NeuronActivate};
+use serde::{Deserialize, Serialize};
+
+/// A gate of an [`Lstm`] neuron, which opens between 0.0 and 1.0 depending
+/// on the inputs and the neuron's previous output.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Gate {
+    /// The weights the gate applies to the inputs.
+    pub weights: Vec<f64>,
+
+    /// The weight the gate applies to the neuron's previous output.
+    pub recurrent: f64,
+
+    /// Shifts how far the gate opens.
+    pub bias: f64,
+}
+
+impl Gate {
+    /// Get how far the gate opens.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` are the inputs to the neuron.
+    /// - `hidden` is the neuron's previous output.
+    ///
+    /// # Returns
+    ///
+    /// A value between 0.0 and 1.0.
+    fn open(&self, inputs: &[f64], hidden: f64) -> f64 {
+        Sigmoid::default().activate(sum(&self.weights, inputs, self.bias) + self.recurrent * hidden)
+    }
+}
+
+/// A long short-term memory neuron.
+///
+/// The neuron keeps a cell value across the steps of a sequence. Each step,
+/// the forget gate decides how much of the cell to keep, the input gate how
+/// much of the candidate to add, and the output gate how much of the
+/// activated cell to output. The candidate is computed from the neuron's own
+/// weights, recurrent weight, bias, and activation function.
+///
+/// The state is not serialized, so a loaded network starts from a clean state.
+///
+/// # Examples
+///
+/// ```
+/// use nnet::neuron::Gate;
+/// use nnet::LstmNeuron;
+///
+/// let open = Gate { weights: vec![0.0], recurrent: 0.0, bias: 100.0 };
+/// let mut neuron = LstmNeuron::builder()
+///     .weights(vec![1.0])
+///     .input_gate(open.clone())
+///     .forget_gate(open.clone())
+///     .output_gate(open)
+///     .build();
+///
+/// let first = neuron.step(&[0.5]);
+/// let second = neuron.step(&[0.5]);
+///
+/// // With every gate open, the cell accumulates the candidate.
+/// let candidate = 0.5_f64.tanh();
+/// assert!((first - candidate.tanh()).abs() < 1e-9);
+/// assert!((second - (2.0 * candidate).tanh()).abs() < 1e-9);
+/// ```
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Lstm {
+    /// Shifts the candidate.
+    bias: f64,
+
+    /// The weights the candidate applies to the inputs.
+    weights: Vec<f64>,
+
+    /// The weight the candidate applies to the neuron's previous output.
+    recurrent: f64,
+
+    /// The activation function of the candidate and the cell.
+    activation: ActivationFunction,
+
+    /// Decides how much of the candidate is added to the cell.
+    input_gate: Gate,
+
+    /// Decides how much of the cell is kept.
+    forget_gate: Gate,
+
+    /// Decides how much of the activated cell is output.
+    output_gate: Gate,
+
+    /// The output of the last step.
+    #[serde(skip)]
+    hidden: f64,
+
+    /// The cell value after the last step.
+    #[serde(skip)]
+    cell: f64,
+}
+
+impl Lstm {
+    /// Create a new neuron builder.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Get the activation function of the candidate and the cell.
+    ///
+    /// # Returns
+    ///
+    /// The activation function.
+    #[must_use]
+    pub fn activation(&self) -> &ActivationFunction {
+        &self.activation
+    }
+
+    /// Get the candidate's bias.
+    ///
+    /// # Returns
+    ///
+    /// The bias.
+    #[must_use]
+    pub fn bias(&self) -> f64 {
+        self.bias
+    }
+
+    /// Get the weights the candidate applies to the inputs.
+    ///
+    /// # Returns
+    ///
+    /// The weights.
+    #[must_use]
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Get a mutable reference to the weights the candidate applies to the
+    /// inputs.
+    ///
+    /// # Returns
+    ///
+    /// The weights, which can be changed but not added or removed.
+    pub fn weights_mut(&mut self) -> &mut [f64] {
+        &mut self.weights
+    }
+
+    /// Set the candidate's bias.
+    ///
+    /// # Arguments
+    ///
+    /// - `bias` is the new bias.
+    pub fn set_bias(&mut self, bias: f64) {
+        self.bias = bias;
+    }
+
+    /// Get every set of weights the neuron applies to its inputs, for
+    /// editing the inputs a neuron takes.
+    ///
+    /// # Returns
+    ///
+    /// The weights of the candidate and of each gate, each with one weight
+    /// per input.
+    pub(crate) fn input_weights_mut(&mut self) -> Vec<&mut Vec<f64>> {
+        vec![
+            &mut self.weights,
+            &mut self.input_gate.weights,
+            &mut self.forget_gate.weights,
+            &mut self.output_gate.weights,
+        ]
+    }
+
+    /// Get the weight the candidate applies to the neuron's previous output.
+    ///
+    /// # Returns
+    ///
+    /// The recurrent weight.
+    #[must_use]
+    pub fn recurrent(&self) -> f64 {
+        self.recurrent
+    }
+
+    /// Get the input gate.
+    ///
+    /// # Returns
+    ///
+    /// The gate.
+    #[must_use]
+    pub fn input_gate(&self) -> &Gate {
+        &self.input_gate
+    }
+
+    /// Get the forget gate.
+    ///
+    /// # Returns
+    ///
+    /// The gate.
+    #[must_use]
+    pub fn forget_gate(&self) -> &Gate {
+        &self.forget_gate
+    }
+
+    /// Get the output gate.
+    ///
+    /// # Returns
+    ///
+    /// The gate.
+    #[must_use]
+    pub fn output_gate(&self) -> &Gate {
+        &self.output_gate
+    }
+
+    /// Activate the neuron and remember its output and cell for the next step.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` to activate the neuron with.
+    ///
+    /// # Returns
+    ///
+    /// The output of the neuron.
+    pub fn step(&mut self, inputs: &[f64]) -> f64 {
+        let (hidden, cell) = self.next(inputs);
+        self.hidden = hidden;
+        self.cell = cell;
+        hidden
+    }
+
+    /// Forget the output and cell of the last step.
+    pub fn reset(&mut self) {
+        self.hidden = 0.0;
+        self.cell = 0.0;
+    }
+
+    /// Compute the next output and cell from the current state.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` to activate the neuron with.
+    ///
+    /// # Returns
+    ///
+    /// The output and the cell.
+    fn next(&self, inputs: &[f64]) -> (f64, f64) {
+        let candidate = self
+            .activation
+            .activate(sum(&self.weights, inputs, self.bias) + self.recurrent * self.hidden);
+        let cell = self.forget_gate.open(inputs, self.hidden) * self.cell
+            + self.input_gate.open(inputs, self.hidden) * candidate;
+        let hidden = self.output_gate.open(inputs, self.hidden) * self.activation.activate(cell);
+        (hidden, cell)
+    }
+}
+
+impl Neuron {
+    /// Create a new LSTM neuron builder.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::Neuron;
+    ///
+    /// let neuron = Neuron::lstm().weights(vec![0.1, 0.2]).build();
+    /// ```
+    #[must_use]
+    pub fn lstm() -> Builder {
+        Builder::default()
+    }
+}
+
+impl From<Lstm> for Neuron {
+    fn from(lstm: Lstm) -> Neuron {
+        Neuron::Lstm(lstm)
+    }
+}
+
+impl NeuronActivate for Lstm {
+    /// Activate the neuron with its current state, without changing it.
+    fn activate(&self, inputs: &[f64]) -> f64 {
+        self.next(inputs).0
+    }
+}
+
+/// A builder for `Lstm` neurons.
+#[derive(Default)]
+pub struct Builder {
+    bias: f64,
+    weights: Vec<f64>,
+    recurrent: f64,
+    activation: Option<ActivationFunction>,
+    input_gate: Gate,
+    forget_gate: Gate,
+    output_gate: Gate,
+}
+
+impl Builder {
+    /// Set the candidate's bias.
+    ///
+    /// # Arguments
+    ///
+    /// - `bias` to set for the candidate.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn bias(mut self, bias: f64) -> Self {
+        self.bias = bias;
+        self
+    }
+
+    /// Set the weights the candidate applies to the inputs.
+    ///
+    /// # Arguments
+    ///
+    /// - `weights` to set for the candidate.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn weights(mut self, weights: Vec<f64>) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Set the weight the candidate applies to the neuron's previous output.
+    ///
+    /// # Arguments
+    ///
+    /// - `recurrent` is the recurrent weight.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn recurrent(mut self, recurrent: f64) -> Self {
+        self.recurrent = recurrent;
+        self
+    }
+
+    /// Set the activation function of the candidate and the cell. Defaults
+    /// to the hyperbolic tangent.
+    ///
+    /// # Arguments
+    ///
+    /// - `activation` to set for the neuron.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn activation(mut self, activation: ActivationFunction) -> Self {
+        self.activation = Some(activation);
+        self
+    }
+
+    /// Set the input gate.
+    ///
+    /// # Arguments
+    ///
+    /// - `gate` decides how much of the candidate is added to the cell.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn input_gate(mut self, gate: Gate) -> Self {
+        self.input_gate = gate;
+        self
+    }
+
+    /// Set the forget gate.
+    ///
+    /// # Arguments
+    ///
+    /// - `gate` decides how much of the cell is kept.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn forget_gate(mut self, gate: Gate) -> Self {
+        self.forget_gate = gate;
+        self
+    }
+
+    /// Set the output gate.
+    ///
+    /// # Arguments
+    ///
+    /// - `gate` decides how much of the activated cell is output.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn output_gate(mut self, gate: Gate) -> Self {
+        self.output_gate = gate;
+        self
+    }
+
+    /// Build the neuron, with a clean state.
+    ///
+    /// # Returns
+    ///
+    /// The neuron.
+    #[must_use]
+    pub fn build(self) -> Lstm {
+        Lstm {
+            bias: self.bias,
+            weights: self.weights,
+            recurrent: self.recurrent,
+            activation: self.activation.unwrap_or_else(ActivationFunction::tanh),
+            input_gate: self.input_gate,
+            forget_gate: self.forget_gate,
+            output_gate: self.output_gate,
+            hidden: 0.0,
+            cell: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gate(bias: f64) -> Gate {
+        Gate {
+            weights: vec![0.0],
+            recurrent: 0.0,
+            bias,
+        }
+    }
+
+    #[test]
+    fn test_gates() {
+        let neuron = |forget: f64| {
+            Builder::default()
+                .weights(vec![1.0])
+                .activation(ActivationFunction::linear())
+                .input_gate(gate(100.0))
+                .forget_gate(gate(forget))
+                .output_gate(gate(100.0))
+                .build()
+        };
+
+        let mut remembers = neuron(100.0);
+        let mut forgets = neuron(-100.0);
+        for _ in 0..3 {
+            let _ = remembers.step(&[1.0]);
+            let _ = forgets.step(&[1.0]);
+        }
+
+        assert!((remembers.step(&[0.0]) - 3.0).abs() < 1e-9);
+        assert!(forgets.step(&[0.0]).abs() < 1e-9);
+
+        remembers.reset();
+        assert!(remembers.activate(&[0.0]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_serialize_skips_state() {
+        let mut neuron = Builder::default()
+            .weights(vec![1.0])
+            .input_gate(gate(1.0))
+            .build();
+        let _ = neuron.step(&[1.0]);
+
+        let serialized = serde_json::to_string(&neuron).unwrap();
+        let mut deserialized: Lstm = serde_json::from_str(&serialized).unwrap();
+
+        assert_ne!(deserialized, neuron);
+        neuron.reset();
+        assert_eq!(deserialized, neuron);
+        assert!((deserialized.step(&[1.0]) - neuron.step(&[1.0])).abs() < f64::EPSILON);
+    }
+}
@@ -1,11 +1,18 @@
 mod basic;
+mod lstm;
+mod recurrent;
 
+pub(crate) use basic::sum;
 pub use basic::{Basic, Builder as BasicNeuronBuilder};
+pub use lstm::{Builder as LstmNeuronBuilder, Gate, Lstm};
+pub use recurrent::{Builder as RecurrentNeuronBuilder, Recurrent};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum Neuron {
     Basic(Basic),
+    Recurrent(Recurrent),
+    Lstm(Lstm),
 }
 
 impl Neuron {
@@ -32,6 +39,8 @@ impl Neuron {
     pub fn activator(&self) -> &crate::ActivationFunction {
         match self {
             Self::Basic(basic) => basic.activation(),
+            Self::Recurrent(recurrent) => recurrent.activation(),
+            Self::Lstm(lstm) => lstm.activation(),
         }
     }
 
@@ -58,6 +67,8 @@ impl Neuron {
     pub fn bias(&self) -> f64 {
         match self {
             Self::Basic(basic) => basic.bias(),
+            Self::Recurrent(recurrent) => recurrent.bias(),
+            Self::Lstm(lstm) => lstm.bias(),
         }
     }
 
@@ -84,6 +95,159 @@ impl Neuron {
     pub fn weights(&self) -> &[f64] {
         match self {
             Self::Basic(basic) => basic.weights(),
+            Self::Recurrent(recurrent) => recurrent.weights(),
+            Self::Lstm(lstm) => lstm.weights(),
+        }
+    }
+
+    /// Get a mutable reference to the neuron's weights, e.g. to fine-tune or
+    /// prune them. For an LSTM neuron, these are the candidate's weights.
+    ///
+    /// # Returns
+    ///
+    /// The weights, which can be changed but not added or removed, so the
+    /// neuron still fits its layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, Neuron};
+    ///
+    /// let mut neuron = Neuron::from(BasicNeuron::builder().weights(vec![0.1, 0.2]).build());
+    /// neuron.weights_mut()[0] = 0.0;
+    ///
+    /// assert_eq!(neuron.weights(), &[0.0, 0.2]);
+    /// ```
+    pub fn weights_mut(&mut self) -> &mut [f64] {
+        match self {
+            Self::Basic(basic) => basic.weights_mut(),
+            Self::Recurrent(recurrent) => recurrent.weights_mut(),
+            Self::Lstm(lstm) => lstm.weights_mut(),
+        }
+    }
+
+    /// Set the neuron's bias. For an LSTM neuron, this is the candidate's
+    /// bias.
+    ///
+    /// # Arguments
+    ///
+    /// - `bias` is the new bias.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{Neuron, RecurrentNeuron};
+    ///
+    /// let mut neuron = Neuron::from(RecurrentNeuron::builder().bias(1.0).build());
+    /// neuron.set_bias(-1.0);
+    ///
+    /// assert_eq!(neuron.bias(), -1.0);
+    /// ```
+    pub fn set_bias(&mut self, bias: f64) {
+        match self {
+            Self::Basic(basic) => basic.set_bias(bias),
+            Self::Recurrent(recurrent) => recurrent.set_bias(bias),
+            Self::Lstm(lstm) => lstm.set_bias(bias),
+        }
+    }
+
+    /// Get every set of weights the neuron applies to its inputs, including
+    /// those of an LSTM neuron's gates.
+    ///
+    /// # Returns
+    ///
+    /// The sets of weights, each with one weight per input.
+    pub(crate) fn input_weights_mut(&mut self) -> Vec<&mut Vec<f64>> {
+        match self {
+            Self::Basic(basic) => basic.input_weights_mut(),
+            Self::Recurrent(recurrent) => recurrent.input_weights_mut(),
+            Self::Lstm(lstm) => lstm.input_weights_mut(),
+        }
+    }
+
+    /// Get the weight the neuron applies to its previous output.
+    ///
+    /// # Returns
+    ///
+    /// The feedback weight, or `None` if the neuron is not recurrent. For an
+    /// LSTM neuron, this is the candidate's recurrent weight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, Neuron};
+    ///
+    /// assert_eq!(Neuron::from(BasicNeuron::builder().build()).feedback(), None);
+    /// assert_eq!(Neuron::from(Neuron::recurrent().feedback(0.5).build()).feedback(), Some(0.5));
+    /// ```
+    #[must_use]
+    pub fn feedback(&self) -> Option<f64> {
+        match self {
+            Self::Basic(_) => None,
+            Self::Recurrent(recurrent) => Some(recurrent.feedback()),
+            Self::Lstm(lstm) => Some(lstm.recurrent()),
+        }
+    }
+
+    /// Count the neuron's trainable parameters.
+    ///
+    /// # Returns
+    ///
+    /// The number of weights, biases, and feedback weights, including those
+    /// of an LSTM neuron's gates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{BasicNeuron, Neuron};
+    ///
+    /// let basic = Neuron::from(BasicNeuron::builder().weights(vec![1.0, 2.0]).build());
+    /// let recurrent = Neuron::from(Neuron::recurrent().weights(vec![1.0, 2.0]).build());
+    ///
+    /// assert_eq!(basic.parameter_count(), 3);
+    /// assert_eq!(recurrent.parameter_count(), 4);
+    /// ```
+    #[must_use]
+    pub fn parameter_count(&self) -> usize {
+        match self {
+            Self::Basic(basic) => basic.weights().len() + 1,
+            Self::Recurrent(recurrent) => recurrent.weights().len() + 2,
+            Self::Lstm(lstm) => {
+                let gates = [lstm.input_gate(), lstm.forget_gate(), lstm.output_gate()];
+                lstm.weights().len()
+                    + 2
+                    + gates
+                        .iter()
+                        .map(|gate| gate.weights.len() + 2)
+                        .sum::<usize>()
+            }
+        }
+    }
+
+    /// Activate the neuron as one step of a sequence, so recurrent neurons
+    /// remember their output for the next step.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` to activate the neuron with.
+    ///
+    /// # Returns
+    ///
+    /// The output of the neuron.
+    pub fn step(&mut self, inputs: &[f64]) -> f64 {
+        match self {
+            Self::Basic(basic) => basic.activate(inputs),
+            Self::Recurrent(recurrent) => recurrent.step(inputs),
+            Self::Lstm(lstm) => lstm.step(inputs),
+        }
+    }
+
+    /// Forget the state of a recurrent neuron. Other neurons have no state.
+    pub fn reset(&mut self) {
+        match self {
+            Self::Basic(_) => {}
+            Self::Recurrent(recurrent) => recurrent.reset(),
+            Self::Lstm(lstm) => lstm.reset(),
         }
     }
 }
@@ -99,6 +263,8 @@ impl Activate for Neuron {
     fn activate(&self, inputs: &[f64]) -> f64 {
         match self {
             Self::Basic(basic) => basic.activate(inputs),
+            Self::Recurrent(recurrent) => recurrent.activate(inputs),
+            Self::Lstm(lstm) => lstm.activate(inputs),
         }
     }
 }
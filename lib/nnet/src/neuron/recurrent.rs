@@ -0,0 +1,330 @@
+use super::basic::sum;
+use crate::{Activate, ActivationFunction, Neuron, NeuronActivate};
+use serde::{Deserialize, Serialize};
+
+/// A neuron that feeds its previous output back into itself.
+///
+/// Its state is the output of the last step of a sequence. It is not
+/// serialized, so a loaded network starts from a clean state.
+///
+/// # Examples
+///
+/// ```
+/// use nnet::{ActivationFunction, RecurrentNeuron};
+///
+/// let mut neuron = RecurrentNeuron::builder()
+///     .weights(vec![1.0])
+///     .feedback(0.5)
+///     .activation(ActivationFunction::linear())
+///     .build();
+///
+/// assert_eq!(neuron.step(&[2.0]), 2.0);
+/// assert_eq!(neuron.step(&[2.0]), 3.0);
+///
+/// neuron.reset();
+/// assert_eq!(neuron.step(&[2.0]), 2.0);
+/// ```
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Recurrent {
+    /// Shifts the neuron's overall sensitivity.
+    bias: f64,
+
+    /// The weights the neuron applies to its inputs.
+    weights: Vec<f64>,
+
+    /// The weight the neuron applies to its previous output.
+    feedback: f64,
+
+    /// The activation function to use.
+    activation: ActivationFunction,
+
+    /// The output of the last step.
+    #[serde(skip)]
+    state: f64,
+}
+
+impl Recurrent {
+    /// Create a new neuron builder.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Get the neuron's activation function.
+    ///
+    /// # Returns
+    ///
+    /// The activation function.
+    #[must_use]
+    pub fn activation(&self) -> &ActivationFunction {
+        &self.activation
+    }
+
+    /// Get the neuron's bias.
+    ///
+    /// # Returns
+    ///
+    /// The bias.
+    #[must_use]
+    pub fn bias(&self) -> f64 {
+        self.bias
+    }
+
+    /// Get the neuron's weights.
+    ///
+    /// # Returns
+    ///
+    /// The weights.
+    #[must_use]
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Get a mutable reference to the neuron's weights.
+    ///
+    /// # Returns
+    ///
+    /// The weights, which can be changed but not added or removed.
+    pub fn weights_mut(&mut self) -> &mut [f64] {
+        &mut self.weights
+    }
+
+    /// Set the neuron's bias.
+    ///
+    /// # Arguments
+    ///
+    /// - `bias` is the new bias.
+    pub fn set_bias(&mut self, bias: f64) {
+        self.bias = bias;
+    }
+
+    /// Get every set of weights the neuron applies to its inputs, for
+    /// editing the inputs a neuron takes.
+    ///
+    /// # Returns
+    ///
+    /// The sets of weights, each with one weight per input.
+    pub(crate) fn input_weights_mut(&mut self) -> Vec<&mut Vec<f64>> {
+        vec![&mut self.weights]
+    }
+
+    /// Get the weight the neuron applies to its previous output.
+    ///
+    /// # Returns
+    ///
+    /// The feedback weight.
+    #[must_use]
+    pub fn feedback(&self) -> f64 {
+        self.feedback
+    }
+
+    /// Get the output of the last step.
+    ///
+    /// # Returns
+    ///
+    /// The state, or 0.0 before the first step.
+    #[must_use]
+    pub fn state(&self) -> f64 {
+        self.state
+    }
+
+    /// Activate the neuron and remember its output for the next step.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` to activate the neuron with.
+    ///
+    /// # Returns
+    ///
+    /// The output of the neuron.
+    pub fn step(&mut self, inputs: &[f64]) -> f64 {
+        self.state = self.activate(inputs);
+        self.state
+    }
+
+    /// Forget the output of the last step.
+    pub fn reset(&mut self) {
+        self.state = 0.0;
+    }
+}
+
+impl Neuron {
+    /// Create a new recurrent neuron builder.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::Neuron;
+    ///
+    /// let neuron = Neuron::recurrent().weights(vec![0.1, 0.2]).feedback(0.5).build();
+    /// ```
+    #[must_use]
+    pub fn recurrent() -> Builder {
+        Builder::default()
+    }
+}
+
+impl From<Recurrent> for Neuron {
+    fn from(recurrent: Recurrent) -> Neuron {
+        Neuron::Recurrent(recurrent)
+    }
+}
+
+impl NeuronActivate for Recurrent {
+    /// Activate the neuron with its current state, without changing it.
+    fn activate(&self, inputs: &[f64]) -> f64 {
+        let sum = sum(&self.weights, inputs, self.bias) + self.feedback * self.state;
+        self.activation.activate(sum)
+    }
+}
+
+/// A builder for `Recurrent` neurons.
+#[derive(Default)]
+pub struct Builder {
+    bias: f64,
+    weights: Vec<f64>,
+    feedback: f64,
+    activation: Option<ActivationFunction>,
+}
+
+impl Builder {
+    /// Set the bias for the neuron.
+    ///
+    /// # Arguments
+    ///
+    /// - `bias` to set for the neuron.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn bias(mut self, bias: f64) -> Self {
+        self.bias = bias;
+        self
+    }
+
+    /// Set the weights for the neuron.
+    ///
+    /// # Arguments
+    ///
+    /// - `weights` to set for the neuron.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn weights(mut self, weights: Vec<f64>) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Set the weight the neuron applies to its previous output.
+    ///
+    /// # Arguments
+    ///
+    /// - `feedback` is the feedback weight.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn feedback(mut self, feedback: f64) -> Self {
+        self.feedback = feedback;
+        self
+    }
+
+    /// Set the activation function for the neuron.
+    ///
+    /// # Arguments
+    ///
+    /// - `activation` to set for the neuron.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn activation(mut self, activation: ActivationFunction) -> Self {
+        self.activation = Some(activation);
+        self
+    }
+
+    /// Build the neuron, with a clean state.
+    ///
+    /// # Returns
+    ///
+    /// The neuron.
+    #[must_use]
+    pub fn build(self) -> Recurrent {
+        Recurrent {
+            bias: self.bias,
+            weights: self.weights,
+            feedback: self.feedback,
+            activation: self.activation.unwrap_or_else(ActivationFunction::sigmoid),
+            state: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        let mut neuron = Builder::default()
+            .bias(1.0)
+            .weights(vec![0.5])
+            .feedback(2.0)
+            .activation(ActivationFunction::linear())
+            .build();
+
+        let outputs = [1.0, 1.0, 0.0].map(|input| neuron.step(&[input]));
+
+        assert_eq!(
+            outputs.map(f64::to_bits),
+            [1.5, 4.5, 10.0].map(f64::to_bits)
+        );
+        assert!((neuron.activate(&[0.0]) - 21.0).abs() < f64::EPSILON);
+        assert!((neuron.state() - 10.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_serialize_skips_state() {
+        let mut neuron = Builder::default().weights(vec![1.0]).feedback(0.5).build();
+        let _ = neuron.step(&[1.0]);
+
+        let serialized = serde_json::to_string(&neuron).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"bias":0.0,"weights":[1.0],"feedback":0.5,"activation":{"Sigmoid":null}}"#
+        );
+
+        neuron.reset();
+        assert_eq!(
+            serde_json::from_str::<Recurrent>(&serialized).unwrap(),
+            neuron
+        );
+    }
+
+    #[test]
+    fn test_serialize_binary() {
+        let mut neuron = Builder::default().weights(vec![1.0]).feedback(0.5).build();
+        let _ = neuron.step(&[1.0]);
+
+        let serialized = bincode::serialize(&neuron).unwrap();
+
+        neuron.reset();
+        assert_eq!(
+            bincode::deserialize::<Recurrent>(&serialized).unwrap(),
+            neuron
+        );
+    }
+}
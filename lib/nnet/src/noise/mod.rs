@@ -0,0 +1,319 @@
+use crate::network::gather;
+use crate::Network;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// What [`Noise`] does to an activation it picks.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Perturbation {
+    /// Set the activation to zero, as dropout does.
+    Zero,
+
+    /// Add a uniformly random amount between `-scale` and `scale`.
+    Jitter(f64),
+}
+
+/// Whether [`Noise`] is applied.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mode {
+    /// Perturb activations.
+    #[default]
+    Training,
+
+    /// Leave activations alone, so the network behaves as if there were no
+    /// noise.
+    Inference,
+}
+
+/// Perturbs a fraction of a network's hidden activations, so fitness can be
+/// measured on how robust a network is as well as how accurate.
+///
+/// The outputs of every layer but the last are perturbed before the layers
+/// that read them see them. The random numbers come from the caller, so a
+/// seeded RNG gives the same noise every time.
+///
+/// # Examples
+///
+/// ```
+/// use nnet::{ActivationFunction, BasicNeuron, Layer, Network, Noise, NoiseMode, Perturbation};
+/// use rand::{rngs::StdRng, SeedableRng};
+///
+/// let neuron = |weights: Vec<f64>| {
+///     BasicNeuron::builder()
+///         .weights(weights)
+///         .activation(ActivationFunction::linear())
+///         .build()
+/// };
+/// let network = Network::builder()
+///     .add_layer(Layer::builder().add_neuron(neuron(vec![1.0])).build())
+///     .add_layer(Layer::builder().add_neuron(neuron(vec![1.0])).build())
+///     .build();
+///
+/// // Drop every hidden activation.
+/// let noise = Noise::builder().rate(1.0).perturbation(Perturbation::Zero).build();
+/// let mut rng = StdRng::seed_from_u64(7);
+/// assert_eq!(noise.activate(&network, &[1.0], &mut rng), vec![0.0]);
+///
+/// let inference = Noise { mode: NoiseMode::Inference, ..noise };
+/// assert_eq!(inference.activate(&network, &[1.0], &mut rng), network.activate(&[1.0]));
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Noise {
+    /// The chance of each activation being perturbed, from 0.0 to 1.0.
+    pub rate: f64,
+
+    /// What happens to a perturbed activation.
+    pub perturbation: Perturbation,
+
+    /// Whether the noise is applied at all.
+    pub mode: Mode,
+}
+
+impl Noise {
+    /// The rate used unless one is set.
+    pub const DEFAULT_RATE: f64 = 0.1;
+
+    /// Create a new builder.
+    ///
+    /// # Returns
+    ///
+    /// A new builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{Noise, NoiseMode, Perturbation};
+    ///
+    /// let noise = Noise::builder().build();
+    ///
+    /// assert_eq!(noise.rate, Noise::DEFAULT_RATE);
+    /// assert_eq!(noise.perturbation, Perturbation::Zero);
+    /// assert_eq!(noise.mode, NoiseMode::Training);
+    /// ```
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Perturb a set of activations in place.
+    ///
+    /// # Arguments
+    ///
+    /// - `values` are the activations to perturb.
+    /// - `rng` decides which activations are perturbed, and by how much.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::{Noise, Perturbation};
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let noise = Noise::builder().rate(1.0).perturbation(Perturbation::Jitter(0.5)).build();
+    /// let mut values = vec![1.0, 2.0];
+    /// noise.apply(&mut values, &mut StdRng::seed_from_u64(1));
+    ///
+    /// assert!(values[0] != 1.0 && (values[0] - 1.0).abs() <= 0.5);
+    /// ```
+    pub fn apply<R: Rng + ?Sized>(&self, values: &mut [f64], rng: &mut R) {
+        if self.mode == Mode::Inference || self.rate <= 0.0 {
+            return;
+        }
+        for value in values {
+            if !rng.gen_bool(self.rate.min(1.0)) {
+                continue;
+            }
+            match self.perturbation {
+                Perturbation::Zero => *value = 0.0,
+                Perturbation::Jitter(scale) => *value += scale * rng.gen_range(-1.0..=1.0),
+            }
+        }
+    }
+
+    /// Activate a network, perturbing its hidden activations.
+    ///
+    /// # Arguments
+    ///
+    /// - `network` is the network to activate.
+    /// - `inputs` to activate the network with.
+    /// - `rng` decides which activations are perturbed, and by how much.
+    ///
+    /// # Returns
+    ///
+    /// The output of the network. In [`Mode::Inference`], this is the same
+    /// as [`Network::activate`].
+    pub fn activate<R: Rng + ?Sized>(
+        &self,
+        network: &Network,
+        inputs: &[f64],
+        rng: &mut R,
+    ) -> Vec<f64> {
+        if self.mode == Mode::Inference {
+            return network.activate(inputs);
+        }
+
+        let layers = network.layers();
+        let mut outputs: Vec<Vec<f64>> = Vec::with_capacity(layers.len());
+        for (index, layer) in layers.iter().enumerate() {
+            let mut values = vec![];
+            gather(network.input_source(index), inputs, &outputs, &mut values);
+            let mut output = layer.activate(&values);
+            if index + 1 < layers.len() {
+                self.apply(&mut output, rng);
+            }
+            outputs.push(output);
+        }
+        outputs.pop().unwrap_or_else(|| inputs.to_vec())
+    }
+}
+
+/// A builder for [`Noise`].
+pub struct Builder {
+    rate: f64,
+    perturbation: Perturbation,
+    mode: Mode,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            rate: Noise::DEFAULT_RATE,
+            perturbation: Perturbation::Zero,
+            mode: Mode::default(),
+        }
+    }
+}
+
+impl Builder {
+    /// Set the chance of each activation being perturbed.
+    ///
+    /// # Arguments
+    ///
+    /// - `rate` is the chance, from 0.0 to 1.0.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn rate(mut self, rate: f64) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    /// Set what happens to a perturbed activation.
+    ///
+    /// # Arguments
+    ///
+    /// - `perturbation` to apply.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn perturbation(mut self, perturbation: Perturbation) -> Self {
+        self.perturbation = perturbation;
+        self
+    }
+
+    /// Set whether the noise is applied.
+    ///
+    /// # Arguments
+    ///
+    /// - `mode` to use.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Build the noise.
+    ///
+    /// # Returns
+    ///
+    /// The noise.
+    #[must_use]
+    pub fn build(self) -> Noise {
+        Noise {
+            rate: self.rate,
+            perturbation: self.perturbation,
+            mode: self.mode,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNeuron, Layer};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn network() -> Network {
+        let layer = |inputs: usize, width: usize| {
+            let neurons = (0..width)
+                .map(|_| {
+                    BasicNeuron::builder()
+                        .weights(vec![0.5; inputs])
+                        .build()
+                        .into()
+                })
+                .collect();
+            Layer::builder().neurons(neurons).build()
+        };
+        Network::builder()
+            .add_layer(layer(2, 8))
+            .add_layer(layer(8, 8))
+            .add_layer(layer(8, 2))
+            .build()
+    }
+
+    fn bits(values: &[f64]) -> Vec<u64> {
+        values.iter().map(|value| value.to_bits()).collect()
+    }
+
+    #[test]
+    fn test_seeded_noise_is_deterministic() {
+        let network = network();
+        let noise = Noise::builder()
+            .rate(0.5)
+            .perturbation(Perturbation::Jitter(1.0))
+            .build();
+        let run = |seed| noise.activate(&network, &[1.0, -1.0], &mut StdRng::seed_from_u64(seed));
+
+        assert_eq!(bits(&run(3)), bits(&run(3)));
+        assert_ne!(bits(&run(3)), bits(&run(4)));
+        assert_ne!(bits(&run(3)), bits(&network.activate(&[1.0, -1.0])));
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let dropout = Noise::builder().rate(0.5).build();
+        let mut values = vec![1.0; 1000];
+        dropout.apply(&mut values, &mut rng);
+        let dropped = values
+            .iter()
+            .filter(|value| value.abs() < f64::EPSILON)
+            .count();
+        assert!((400..600).contains(&dropped), "{dropped}");
+        let kept = values
+            .iter()
+            .filter(|value| (*value - 1.0).abs() < f64::EPSILON)
+            .count();
+        assert_eq!(dropped + kept, 1000);
+
+        let mut values = vec![1.0; 10];
+        Noise::builder()
+            .rate(0.0)
+            .build()
+            .apply(&mut values, &mut rng);
+        Noise::builder()
+            .rate(1.0)
+            .mode(Mode::Inference)
+            .build()
+            .apply(&mut values, &mut rng);
+        assert_eq!(bits(&values), bits(&[1.0; 10]));
+    }
+}
@@ -0,0 +1,322 @@
+//! Networks with 8-bit integer weights, for targets such as microcontrollers
+//! where floating-point math is slow.
+//!
+//! Each neuron's weights are scaled so the largest fits `i8`, and each
+//! layer's inputs are scaled the same way as they arrive. The dot product of
+//! a neuron is then summed in `i32`, and only the result is scaled back to
+//! `f32` to add the bias and apply the activation function.
+
+use crate::network::gather;
+use crate::{Activate, ActivationFunction, Error, InputSource, Result};
+use serde::{Deserialize, Serialize};
+
+/// A neuron with quantized weights.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Neuron {
+    /// The weights, each a multiple of `scale`.
+    weights: Vec<i8>,
+
+    /// The value of a weight of one.
+    scale: f32,
+
+    /// Shifts the neuron's overall sensitivity.
+    bias: f32,
+
+    /// The activation function to use.
+    activation: ActivationFunction,
+}
+
+impl Neuron {
+    /// Get the quantized weights.
+    ///
+    /// # Returns
+    ///
+    /// The weights, each a multiple of [`Neuron::scale`].
+    #[must_use]
+    pub fn weights(&self) -> &[i8] {
+        &self.weights
+    }
+
+    /// Get the value of a quantized weight of one.
+    ///
+    /// # Returns
+    ///
+    /// The scale.
+    #[must_use]
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Get the bias.
+    ///
+    /// # Returns
+    ///
+    /// The bias.
+    #[must_use]
+    pub fn bias(&self) -> f32 {
+        self.bias
+    }
+
+    /// Get the activation function.
+    ///
+    /// # Returns
+    ///
+    /// The activation function.
+    #[must_use]
+    pub fn activation(&self) -> &ActivationFunction {
+        &self.activation
+    }
+
+    /// Activate the neuron.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` are the quantized inputs of the neuron's layer.
+    /// - `scale` is the value of a quantized input of one.
+    ///
+    /// # Returns
+    ///
+    /// The output of the neuron.
+    fn activate(&self, inputs: &[i8], scale: f32) -> f32 {
+        let sum = Iterator::zip(self.weights.iter(), inputs.iter())
+            .map(|(weight, input)| i32::from(*weight) * i32::from(*input))
+            .sum::<i32>();
+        #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+        let output = self
+            .activation
+            .activate(f64::from(sum as f32 * self.scale * scale + self.bias))
+            as f32;
+        output
+    }
+}
+
+/// A network with quantized weights.
+///
+/// # Examples
+///
+/// ```
+/// use nnet::{quant, ActivationFunction, BasicNeuron, Layer, Network};
+///
+/// let neuron = BasicNeuron::builder()
+///     .weights(vec![0.5, -0.25])
+///     .bias(0.1)
+///     .activation(ActivationFunction::linear())
+///     .build();
+/// let layer = Layer::builder().add_neuron(neuron).build();
+/// let network = Network::builder().add_layer(layer).build();
+///
+/// let quantized = quant::Network::quantize(&network).unwrap();
+///
+/// let expected = network.activate(&[1.0, 2.0])[0];
+/// let output = f64::from(quantized.activate(&[1.0, 2.0])[0]);
+/// assert!((output - expected).abs() < 0.01);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Network {
+    /// The neurons of each layer.
+    layers: Vec<Vec<Neuron>>,
+
+    /// Where each layer takes its inputs from; empty unless some layer has a
+    /// skip connection.
+    #[serde(default)]
+    sources: Vec<InputSource>,
+}
+
+impl Network {
+    /// Quantize a network.
+    ///
+    /// # Arguments
+    ///
+    /// - `network` is the network to quantize.
+    ///
+    /// # Returns
+    ///
+    /// The quantized network.
+    ///
+    /// # Errors
+    ///
+    /// If the network has recurrent or LSTM neurons, whose state cannot be
+    /// quantized.
+    pub fn quantize(network: &crate::Network) -> Result<Self> {
+        let layers = network
+            .layers()
+            .iter()
+            .enumerate()
+            .map(|(layer, neurons)| {
+                neurons
+                    .neurons()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, neuron)| {
+                        let crate::Neuron::Basic(basic) = neuron else {
+                            return Err(Error::Unquantizable {
+                                layer,
+                                neuron: index,
+                            });
+                        };
+                        let (weights, scale) = quantize(basic.weights());
+                        #[allow(clippy::cast_possible_truncation)]
+                        let bias = basic.bias() as f32;
+                        Ok(Neuron {
+                            weights,
+                            scale,
+                            bias,
+                            activation: basic.activation().clone(),
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<Result<_>>()?;
+        let sources = if network.has_skips() {
+            (0..network.layers().len())
+                .map(|layer| network.input_source(layer).clone())
+                .collect()
+        } else {
+            vec![]
+        };
+        Ok(Self { layers, sources })
+    }
+
+    /// Activate the network.
+    ///
+    /// # Arguments
+    ///
+    /// - `inputs` to activate the network with.
+    ///
+    /// # Returns
+    ///
+    /// The output of the network.
+    #[must_use]
+    pub fn activate(&self, inputs: &[f32]) -> Vec<f32> {
+        let mut outputs: Vec<Vec<f32>> = Vec::with_capacity(self.layers.len());
+        let mut values = vec![];
+        for (index, neurons) in self.layers.iter().enumerate() {
+            values.clear();
+            let source = self.sources.get(index).unwrap_or(&InputSource::Previous);
+            gather(source, inputs, &outputs, &mut values);
+            let (values, scale) = quantize(&values);
+            outputs.push(
+                neurons
+                    .iter()
+                    .map(|neuron| neuron.activate(&values, scale))
+                    .collect(),
+            );
+        }
+        outputs.pop().unwrap_or_else(|| inputs.to_vec())
+    }
+
+    /// Get the neurons of each layer.
+    ///
+    /// # Returns
+    ///
+    /// The layers.
+    #[must_use]
+    pub fn layers(&self) -> &[Vec<Neuron>] {
+        &self.layers
+    }
+}
+
+/// Scale a set of values to fit `i8`.
+///
+/// # Arguments
+///
+/// - `values` are the values to quantize.
+///
+/// # Returns
+///
+/// The quantized values, and the value of a quantized one. The largest
+/// magnitude becomes 127; if every value is zero, the scale is one.
+fn quantize<T: Copy + Into<f64>>(values: &[T]) -> (Vec<i8>, f32) {
+    let largest = values
+        .iter()
+        .map(|value| (*value).into().abs())
+        .filter(|value| value.is_finite())
+        .fold(0.0, f64::max);
+    let scale = if largest > 0.0 {
+        largest / f64::from(i8::MAX)
+    } else {
+        1.0
+    };
+    #[allow(clippy::cast_possible_truncation)]
+    let quantized = values
+        .iter()
+        .map(|value| ((*value).into() / scale).round().clamp(-127.0, 127.0) as i8)
+        .collect();
+    #[allow(clippy::cast_possible_truncation)]
+    let scale = scale as f32;
+    (quantized, scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNeuron, Layer, RecurrentNeuron};
+
+    fn layer(count: u32, weights: u32) -> Layer {
+        let neurons = (0..count)
+            .map(|index| {
+                let shift = f64::from(index);
+                BasicNeuron::builder()
+                    .weights(
+                        (0..weights)
+                            .map(|weight| 0.3 - 0.1 * shift + 0.05 * f64::from(weight))
+                            .collect(),
+                    )
+                    .bias(0.1 * shift)
+                    .build()
+                    .into()
+            })
+            .collect();
+        Layer::builder().neurons(neurons).build()
+    }
+
+    #[test]
+    fn test_activate_matches_network() {
+        let network = crate::Network::builder()
+            .add_layer(layer(4, 3))
+            .add_layer(layer(3, 4))
+            .add_layer_with_input(layer(2, 7), InputSource::Layers(vec![0, 1]))
+            .build();
+        let quantized = Network::quantize(&network).unwrap();
+
+        assert_eq!(quantized.layers().len(), 3);
+        for inputs in [[0.0, 0.0, 0.0], [1.0, -2.0, 0.5], [10.0, 3.0, -7.0]] {
+            let expected = network.activate(&inputs.map(f64::from));
+            let outputs = quantized.activate(&inputs);
+            assert_eq!(outputs.len(), expected.len());
+            for (output, expected) in outputs.iter().zip(expected) {
+                assert!(
+                    (f64::from(*output) - expected).abs() < 0.01,
+                    "{output} {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_quantize() {
+        let (values, scale) = quantize(&[0.5, -1.0, 0.0, 0.25]);
+        assert_eq!(values, vec![64, -127, 0, 32]);
+        assert!((scale - 1.0 / 127.0).abs() < f32::EPSILON);
+
+        let (values, scale) = quantize(&[0.0, 0.0]);
+        assert_eq!(values, vec![0, 0]);
+        assert!((scale - 1.0).abs() < f32::EPSILON);
+
+        let network = crate::Network::builder()
+            .add_layer(layer(1, 1))
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(RecurrentNeuron::builder().build())
+                    .build(),
+            )
+            .build();
+        assert_eq!(
+            Network::quantize(&network),
+            Err(Error::Unquantizable {
+                layer: 1,
+                neuron: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+
+/// Summary statistics of a set of weights, such as those of one layer.
+///
+/// Comparing them across generations shows weights exploding (a growing
+/// spread or extremes) or collapsing (a spread shrinking towards zero).
+///
+/// # Examples
+///
+/// ```
+/// use nnet::WeightStats;
+///
+/// let stats = WeightStats::new(&[-1.0, 0.0, 1.0, 2.0]);
+///
+/// assert_eq!(stats.count, 4);
+/// assert_eq!(stats.mean, 0.5);
+/// assert_eq!((stats.min, stats.max), (-1.0, 2.0));
+/// assert_eq!(stats.histogram.iter().sum::<usize>(), 4);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WeightStats {
+    /// The number of weights.
+    pub count: usize,
+
+    /// The mean weight.
+    pub mean: f64,
+
+    /// The population standard deviation of the weights.
+    pub std_dev: f64,
+
+    /// The smallest weight.
+    pub min: f64,
+
+    /// The largest weight.
+    pub max: f64,
+
+    /// The number of weights in each of [`WeightStats::BUCKETS`] equal-width
+    /// buckets from `min` to `max`.
+    pub histogram: Vec<usize>,
+}
+
+impl WeightStats {
+    /// The number of buckets in the histogram.
+    pub const BUCKETS: usize = 10;
+
+    /// Summarize a set of weights.
+    ///
+    /// # Arguments
+    ///
+    /// - `weights` are the weights to summarize.
+    ///
+    /// # Returns
+    ///
+    /// The statistics. With no weights, every statistic is 0.0 and every
+    /// bucket is empty. Non-finite weights make the mean and standard
+    /// deviation non-finite too, and are left out of the histogram.
+    #[must_use]
+    pub fn new(weights: &[f64]) -> Self {
+        if weights.is_empty() {
+            return Self {
+                count: 0,
+                mean: 0.0,
+                std_dev: 0.0,
+                min: 0.0,
+                max: 0.0,
+                histogram: vec![0; Self::BUCKETS],
+            };
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let count = weights.len() as f64;
+        let mean = weights.iter().sum::<f64>() / count;
+        let variance = weights
+            .iter()
+            .map(|weight| (weight - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        let min = weights.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = weights.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        let mut histogram = vec![0; Self::BUCKETS];
+        #[allow(clippy::cast_precision_loss)]
+        let width = (max - min) / Self::BUCKETS as f64;
+        for weight in weights.iter().filter(|weight| weight.is_finite()) {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let bucket = if width > 0.0 {
+                (((weight - min) / width) as usize).min(Self::BUCKETS - 1)
+            } else {
+                0
+            };
+            histogram[bucket] += 1;
+        }
+
+        Self {
+            count: weights.len(),
+            mean,
+            std_dev: variance.sqrt(),
+            min,
+            max,
+            histogram,
+        }
+    }
+}
+
+/// Summary statistics of a whole network.
+///
+/// # Examples
+///
+/// ```
+/// use nnet::{BasicNeuron, Layer, Network};
+///
+/// let neuron = |bias| BasicNeuron::builder().weights(vec![1.0, -1.0]).bias(bias).build();
+/// let layer = Layer::builder().add_neuron(neuron(0.5)).add_neuron(neuron(8.0)).build();
+/// let network = Network::builder().add_layer(layer).build();
+///
+/// let stats = network.stats();
+///
+/// assert_eq!(stats.layers[0].mean, 0.0);
+/// assert_eq!(stats.saturated_biases, 1);
+/// assert_eq!(stats.parameters, 6);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NetworkStats {
+    /// The statistics of each layer's weights.
+    pub layers: Vec<WeightStats>,
+
+    /// The number of biases whose magnitude is at least
+    /// [`NetworkStats::SATURATED_BIAS`], or which are not finite.
+    pub saturated_biases: usize,
+
+    /// The number of trainable parameters.
+    pub parameters: usize,
+}
+
+impl NetworkStats {
+    /// The magnitude at which a bias saturates: a sigmoid of it is within
+    /// 0.01 of its limit, and a tanh of it within 0.0001, so the neuron's
+    /// inputs barely move its output.
+    pub const SATURATED_BIAS: f64 = 5.0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNeuron, Layer, LstmNeuron, Network};
+
+    #[test]
+    fn test_new() {
+        let stats = WeightStats::new(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+
+        assert_eq!(stats.count, 8);
+        assert!((stats.mean - 5.0).abs() <= f64::EPSILON);
+        assert!((stats.std_dev - 2.0).abs() <= f64::EPSILON);
+        assert_eq!(stats.histogram, vec![1, 0, 3, 0, 2, 0, 0, 1, 0, 1]);
+
+        let same = WeightStats::new(&[1.5, 1.5]);
+        assert!(same.std_dev.abs() <= f64::EPSILON);
+        assert_eq!(same.histogram[0], 2);
+
+        let empty = WeightStats::new(&[]);
+        assert_eq!(empty.count, 0);
+        assert_eq!(empty.histogram.iter().sum::<usize>(), 0);
+    }
+
+    #[test]
+    fn test_network_stats() {
+        let basic = |bias| {
+            BasicNeuron::builder()
+                .weights(vec![2.0, 4.0])
+                .bias(bias)
+                .build()
+        };
+        let lstm = LstmNeuron::builder().weights(vec![1.0]).bias(-5.0).build();
+        let network = Network::builder()
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(basic(1.0))
+                    .add_neuron(basic(f64::NAN))
+                    .build(),
+            )
+            .add_layer(Layer::builder().add_neuron(lstm).build())
+            .build();
+
+        let stats = network.stats();
+
+        assert_eq!(stats.layers, network.weight_stats());
+        assert!((stats.layers[0].mean - 3.0).abs() <= f64::EPSILON);
+        assert_eq!(stats.saturated_biases, 2);
+        // Two basic neurons of 3, and an LSTM neuron of 3 plus three gates
+        // of 2, since they have no weights.
+        assert_eq!(stats.parameters, 15);
+    }
+}
@@ -0,0 +1,496 @@
+use crate::network::gather;
+use crate::{neuron::sum, Activate, Error, InputSource, Layer, Network, Neuron, Result};
+
+/// Trains a network by backpropagation with stochastic gradient descent, so
+/// a network found by evolution can be fine-tuned on labelled samples.
+///
+/// Each epoch presents every sample once, in order, and nudges the weights
+/// and biases against the gradient of the squared error after each sample.
+/// Only feed-forward networks can be trained, though they may have skip
+/// connections; recurrent and LSTM neurons are rejected.
+///
+/// # Examples
+///
+/// ```
+/// use nnet::{ActivationFunction, BasicNeuron, Layer, Network, Trainer};
+///
+/// let neuron = BasicNeuron::builder()
+///     .weights(vec![0.0])
+///     .activation(ActivationFunction::linear())
+///     .build();
+/// let layer = Layer::builder().add_neuron(neuron).build();
+/// let mut network = Network::builder().add_layer(layer).build();
+///
+/// // Learn y = 2x + 1.
+/// let samples = [(vec![0.0], vec![1.0]), (vec![1.0], vec![3.0])];
+/// let trainer = Trainer::builder().learning_rate(0.1).epochs(500).build();
+/// let losses = trainer.train(&mut network, &samples).unwrap();
+///
+/// assert_eq!(losses.len(), 500);
+/// assert!(losses[499] < 1e-6);
+/// assert!((network.activate(&[2.0])[0] - 5.0).abs() < 1e-2);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trainer {
+    /// How far to step against the gradient after each sample.
+    learning_rate: f64,
+
+    /// How many times to present every sample.
+    epochs: usize,
+}
+
+impl Trainer {
+    /// The learning rate used unless one is set.
+    pub const DEFAULT_LEARNING_RATE: f64 = 0.1;
+
+    /// The number of epochs used unless one is set.
+    pub const DEFAULT_EPOCHS: usize = 100;
+
+    /// Create a new builder.
+    ///
+    /// # Returns
+    ///
+    /// A new builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nnet::Trainer;
+    ///
+    /// let trainer = Trainer::builder().build();
+    ///
+    /// assert_eq!(trainer.epochs(), Trainer::DEFAULT_EPOCHS);
+    /// ```
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Get the learning rate.
+    ///
+    /// # Returns
+    ///
+    /// The learning rate.
+    #[must_use]
+    pub fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    /// Get the number of epochs.
+    ///
+    /// # Returns
+    ///
+    /// The number of epochs.
+    #[must_use]
+    pub fn epochs(&self) -> usize {
+        self.epochs
+    }
+
+    /// Train a network on a set of samples.
+    ///
+    /// # Arguments
+    ///
+    /// - `network` is the network to train, in place.
+    /// - `samples` are pairs of inputs and the outputs they should give.
+    ///
+    /// # Returns
+    ///
+    /// The mean squared error of each epoch, measured as the samples were
+    /// presented.
+    ///
+    /// # Errors
+    ///
+    /// If the network is not fully connected, has recurrent neurons, or a
+    /// sample has the wrong number of inputs or outputs. The network is left
+    /// untouched.
+    pub fn train(
+        &self,
+        network: &mut Network,
+        samples: &[(Vec<f64>, Vec<f64>)],
+    ) -> Result<Vec<f64>> {
+        check(network, samples)?;
+
+        #[allow(clippy::cast_precision_loss)]
+        let count = samples.len().max(1) as f64;
+        let losses = (0..self.epochs)
+            .map(|_| {
+                samples
+                    .iter()
+                    .map(|(inputs, targets)| self.fit(network, inputs, targets))
+                    .sum::<f64>()
+                    / count
+            })
+            .collect();
+        Ok(losses)
+    }
+
+    /// Take one gradient descent step on a single sample.
+    ///
+    /// # Arguments
+    ///
+    /// - `network` is the network to update.
+    /// - `inputs` are the inputs of the sample.
+    /// - `targets` are the outputs the sample should give.
+    ///
+    /// # Returns
+    ///
+    /// The mean squared error of the network on the sample, before the step.
+    fn fit(&self, network: &mut Network, inputs: &[f64], targets: &[f64]) -> f64 {
+        let Pass {
+            inputs: layer_inputs,
+            sums,
+            outputs,
+        } = forward(network, inputs);
+        let sources = (0..outputs.len())
+            .map(|index| network.input_source(index).clone())
+            .collect::<Vec<_>>();
+
+        let last = &outputs[outputs.len() - 1];
+        #[allow(clippy::cast_precision_loss)]
+        let loss = Iterator::zip(last.iter(), targets.iter())
+            .map(|(output, target)| (output - target).powi(2))
+            .sum::<f64>()
+            / last.len().max(1) as f64;
+
+        // The error of each layer's outputs, gathered from every layer that
+        // reads them.
+        let mut errors = outputs
+            .iter()
+            .map(|output| vec![0.0; output.len()])
+            .collect::<Vec<_>>();
+        errors[outputs.len() - 1] = Iterator::zip(last.iter(), targets.iter())
+            .map(|(output, target)| output - target)
+            .collect();
+        for (index, layer) in network.layers_mut().iter_mut().enumerate().rev() {
+            let neurons = layer.neurons_mut();
+            let deltas = neurons
+                .iter()
+                .zip(&sums[index])
+                .zip(&errors[index])
+                .map(|((neuron, sum), error)| error * neuron.activator().derivative(*sum))
+                .collect::<Vec<_>>();
+
+            let values = &layer_inputs[index];
+            let mut input_errors = vec![0.0; values.len()];
+            for (neuron, delta) in neurons.iter_mut().zip(&deltas) {
+                let Neuron::Basic(basic) = neuron else {
+                    unreachable!("the network was checked for recurrent neurons");
+                };
+                for ((weight, input), error) in basic
+                    .weights_mut()
+                    .iter_mut()
+                    .zip(values)
+                    .zip(&mut input_errors)
+                {
+                    *error += *weight * delta;
+                    *weight -= self.learning_rate * delta * input;
+                }
+                basic.set_bias(basic.bias() - self.learning_rate * delta);
+            }
+
+            let sources = match &sources[index] {
+                InputSource::Previous => index.checked_sub(1).into_iter().collect(),
+                InputSource::Layers(layers) => layers.clone(),
+            };
+            let mut input_errors = input_errors.into_iter();
+            for source in sources {
+                for (error, input_error) in errors[source].iter_mut().zip(&mut input_errors) {
+                    *error += input_error;
+                }
+            }
+        }
+        loss
+    }
+}
+
+/// What backpropagation needs from a forward pass.
+struct Pass {
+    /// The inputs of each layer.
+    inputs: Vec<Vec<f64>>,
+
+    /// The weighted sums of each layer, before activation.
+    sums: Vec<Vec<f64>>,
+
+    /// The outputs of each layer.
+    outputs: Vec<Vec<f64>>,
+}
+
+/// Run the network, keeping what backpropagation needs.
+///
+/// # Arguments
+///
+/// - `network` is the network to run.
+/// - `inputs` are the inputs of the network.
+///
+/// # Returns
+///
+/// The inputs, sums, and outputs of each layer.
+fn forward(network: &Network, inputs: &[f64]) -> Pass {
+    let mut pass = Pass {
+        inputs: vec![],
+        sums: vec![],
+        outputs: vec![],
+    };
+    for (index, layer) in network.layers().iter().enumerate() {
+        let mut values = vec![];
+        gather(
+            network.input_source(index),
+            inputs,
+            &pass.outputs,
+            &mut values,
+        );
+        let sums = layer
+            .neurons()
+            .iter()
+            .map(|neuron| sum(neuron.weights(), &values, neuron.bias()))
+            .collect::<Vec<_>>();
+        let outputs = Iterator::zip(layer.neurons().iter(), sums.iter())
+            .map(|(neuron, sum)| neuron.activator().activate(*sum))
+            .collect();
+        pass.inputs.push(values);
+        pass.sums.push(sums);
+        pass.outputs.push(outputs);
+    }
+    pass
+}
+
+/// Check that a network can be trained on a set of samples.
+///
+/// # Arguments
+///
+/// - `network` is the network to train.
+/// - `samples` are the samples to train it on.
+///
+/// # Errors
+///
+/// If the network is not fully connected, has recurrent neurons, or a sample
+/// has the wrong number of inputs or outputs.
+fn check(network: &Network, samples: &[(Vec<f64>, Vec<f64>)]) -> Result<()> {
+    let inputs = network.check()?;
+    for (layer, neurons) in network.layers().iter().map(Layer::neurons).enumerate() {
+        if let Some(neuron) = neurons.iter().position(|n| !matches!(n, Neuron::Basic(_))) {
+            return Err(Error::Untrainable { layer, neuron });
+        }
+    }
+
+    let outputs = network.layers().last().map_or(0, |l| l.neurons().len());
+    for (sample_inputs, targets) in samples {
+        if sample_inputs.len() != inputs {
+            return Err(Error::InputCount {
+                expected: inputs,
+                found: sample_inputs.len(),
+            });
+        }
+        if targets.len() != outputs {
+            return Err(Error::OutputCount {
+                expected: outputs,
+                found: targets.len(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// A builder for [`Trainer`].
+///
+/// # Examples
+///
+/// ```
+/// use nnet::Trainer;
+///
+/// let trainer = Trainer::builder().learning_rate(0.05).epochs(10).build();
+///
+/// assert_eq!(trainer.learning_rate(), 0.05);
+/// assert_eq!(trainer.epochs(), 10);
+/// ```
+#[derive(Default)]
+pub struct Builder {
+    learning_rate: Option<f64>,
+    epochs: Option<usize>,
+}
+
+impl Builder {
+    /// Set how far to step against the gradient after each sample.
+    ///
+    /// # Arguments
+    ///
+    /// - `learning_rate` is the step size.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = Some(learning_rate);
+        self
+    }
+
+    /// Set how many times to present every sample.
+    ///
+    /// # Arguments
+    ///
+    /// - `epochs` is the number of passes over the samples.
+    ///
+    /// # Returns
+    ///
+    /// The builder.
+    #[must_use]
+    pub fn epochs(mut self, epochs: usize) -> Self {
+        self.epochs = Some(epochs);
+        self
+    }
+
+    /// Build the trainer.
+    ///
+    /// # Returns
+    ///
+    /// The trainer.
+    #[must_use]
+    pub fn build(self) -> Trainer {
+        Trainer {
+            learning_rate: self.learning_rate.unwrap_or(Trainer::DEFAULT_LEARNING_RATE),
+            epochs: self.epochs.unwrap_or(Trainer::DEFAULT_EPOCHS),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActivationFunction, BasicNeuron, Layer};
+
+    fn network(activation: &ActivationFunction) -> Network {
+        let neuron = |weights: Vec<f64>| {
+            BasicNeuron::builder()
+                .weights(weights)
+                .bias(0.1)
+                .activation(activation.clone())
+                .build()
+        };
+        Network::builder()
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(neuron(vec![0.5, -0.4]))
+                    .add_neuron(neuron(vec![-0.3, 0.8]))
+                    .add_neuron(neuron(vec![0.2, 0.6]))
+                    .build(),
+            )
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(neuron(vec![0.7, -0.5, 0.3]))
+                    .build(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_xor() {
+        let samples = [
+            (vec![0.0, 0.0], vec![0.0]),
+            (vec![0.0, 1.0], vec![1.0]),
+            (vec![1.0, 0.0], vec![1.0]),
+            (vec![1.0, 1.0], vec![0.0]),
+        ];
+        let mut network = network(&ActivationFunction::tanh());
+        let trainer = Trainer::builder().learning_rate(0.2).epochs(2000).build();
+
+        let losses = trainer.train(&mut network, &samples).unwrap();
+
+        assert!(losses[losses.len() - 1] < losses[0]);
+        for (inputs, targets) in &samples {
+            let output = network.activate(inputs)[0];
+            assert!(
+                (output - targets[0]).abs() < 0.2,
+                "{inputs:?} gave {output}, expected {targets:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_skip_connections() {
+        // y = x1 + 2 * x2 through a layer that also sees the inputs' copy.
+        let linear = |weights: Vec<f64>| {
+            BasicNeuron::builder()
+                .weights(weights)
+                .activation(ActivationFunction::linear())
+                .build()
+        };
+        let mut network = Network::builder()
+            .add_layer(
+                Layer::builder()
+                    .add_neuron(linear(vec![0.1, 0.0]))
+                    .add_neuron(linear(vec![0.0, 0.1]))
+                    .build(),
+            )
+            .add_layer(Layer::builder().add_neuron(linear(vec![0.2, 0.2])).build())
+            .add_layer_with_input(
+                Layer::builder()
+                    .add_neuron(linear(vec![0.3, 0.3, 0.3]))
+                    .build(),
+                InputSource::Layers(vec![0, 1]),
+            )
+            .build();
+        let samples = [
+            (vec![1.0, 0.0], vec![1.0]),
+            (vec![0.0, 1.0], vec![2.0]),
+            (vec![1.0, 1.0], vec![3.0]),
+            (vec![-1.0, 0.5], vec![0.0]),
+        ];
+        let trainer = Trainer::builder().learning_rate(0.05).epochs(2000).build();
+
+        let losses = trainer.train(&mut network, &samples).unwrap();
+
+        assert!(losses[losses.len() - 1] < 1e-6, "{:?}", &losses[1990..]);
+    }
+
+    #[test]
+    fn test_gradient() {
+        // One step with a tiny learning rate should lower the loss.
+        for activation in [
+            ActivationFunction::sigmoid(),
+            ActivationFunction::leaky_relu(),
+            ActivationFunction::linear(),
+        ] {
+            let mut network = network(&activation);
+            let samples = [(vec![0.3, -0.7], vec![0.9])];
+            let trainer = Trainer::builder().learning_rate(1e-3).epochs(2).build();
+
+            let losses = trainer.train(&mut network, &samples).unwrap();
+
+            assert!(losses[1] < losses[0], "{activation}: {losses:?}");
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_samples() {
+        let mut network = network(&ActivationFunction::sigmoid());
+        let trainer = Trainer::builder().build();
+
+        assert_eq!(
+            trainer.train(&mut network, &[(vec![1.0], vec![1.0])]),
+            Err(Error::InputCount {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            trainer.train(&mut network, &[(vec![1.0, 1.0], vec![])]),
+            Err(Error::OutputCount {
+                expected: 1,
+                found: 0
+            })
+        );
+
+        let recurrent = Neuron::recurrent().weights(vec![1.0]).build();
+        let mut network = Network::builder()
+            .add_layer(Layer::builder().add_neuron(recurrent).build())
+            .build();
+        assert_eq!(
+            trainer.train(&mut network, &[]),
+            Err(Error::Untrainable {
+                layer: 0,
+                neuron: 0
+            })
+        );
+    }
+}